@@ -3,35 +3,49 @@
 //! Handles canvas interactions, zoom/pan logic and connects to the
 //! WebSocket stream providing market data.
 
-use futures::{channel::oneshot, lock::Mutex};
+use futures::channel::oneshot;
 use js_sys;
-use leptos::html::Canvas;
+use leptos::html::{Canvas, Div};
 use leptos::spawn_local_with_current_owner;
 use leptos::*;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
 
-use crate::event_utils::{EventOptions, wheel_event_options, window_event_listener_with_options};
+use crate::ecs::components::RenderTargetComponent;
+use crate::event_utils::{
+    EventOptions, client_to_ndc, device_pixel_ratio, element_client_to_ndc, touch_points,
+    wheel_event_options, window_event_listener_with_options,
+};
 use crate::global_signals;
-use crate::global_state::{ensure_chart, set_chart_in_ecs};
+use crate::global_state::{ensure_chart, render_ecs_frame, set_chart_in_ecs};
+use crate::settings::{ThemePreset, persist_settings};
 use crate::{
     domain::{
-        chart::Chart,
-        logging::{LogComponent, get_logger},
+        chart::{Chart, PriceChange, value_objects::ChartType},
+        logging::{LogComponent, filter_logs, get_logger, logs_revision, recent_logs},
         market_data::{
-            Candle, TimeInterval,
+            Candle, DEFAULT_DEPTH_LEVELS, HistoryDepth, OHLCV, OrderBook, Price, TimeInterval,
+            Timestamp, Volume, import_candles,
             value_objects::{Symbol, default_symbols},
         },
     },
     infrastructure::rendering::renderer::{
-        EDGE_GAP, LineVisibility, MAX_ELEMENT_WIDTH, MIN_ELEMENT_WIDTH, enqueue_render_task,
-        init_render_queue, set_global_renderer, spacing_ratio_for, with_global_renderer,
+        Canvas2dRenderer, ChartGeometryBuilder, EmptyChartState, FrameTiming, HorizontalLine,
+        IndicatorValue, LineVisibility, MSAA_SAMPLE_OPTIONS, MinBodyHeight, PRICE_GRID_LINES,
+        Renderer, VisibleSpan, XAxis, XAxisMode, candle_x_position, enqueue_render_task,
+        ensure_price_span, init_render_queue, nice_price_ticks, price_step_decimals,
+        session_high_low, set_fallback_renderer, set_global_renderer, time_grid_indices,
+        with_fallback_renderer, with_global_renderer,
+    },
+    infrastructure::{
+        rendering::{PanelBand, PanelId, PanelLayout, WebGpuRenderer},
+        websocket::{BinanceWebSocketClient, ConnectionState, Exchange, MarketDataSource},
     },
-    infrastructure::{rendering::WebGpuRenderer, websocket::BinanceWebSocketClient},
-    time_utils::format_time_label,
+    time_utils::{format_axis_time, time_zone_suffix},
 };
 
 /// Maximum number of candles visible at 1x zoom
@@ -40,15 +54,67 @@ const MAX_VISIBLE_CANDLES: f64 = 32.0;
 const MIN_VISIBLE_CANDLES: f64 = 1.0;
 
 /// Default canvas width
-const CHART_WIDTH: f64 = 800.0;
+pub const CHART_WIDTH: f64 = 800.0;
+
+/// Default canvas height
+pub const CHART_HEIGHT: f64 = 500.0;
+
+/// Horizontal room kept for the price axis left of the canvas and the
+/// price scale overhanging its right edge
+const CHART_GUTTER: f64 = 140.0;
+
+/// Narrowest canvas the chart shrinks to on small windows
+const MIN_CHART_WIDTH: f64 = 320.0;
+
+/// Quiet period (ms) after the last window resize before the canvas follows
+pub const RESIZE_DEBOUNCE_MS: u32 = 150;
+
+/// 📐 CSS size of the chart canvas given `available` horizontal pixels
+///
+/// The canvas never grows past the default size and always keeps its
+/// aspect ratio, so candles are not stretched when the window changes shape.
+pub fn fit_canvas_size(available: f64) -> (f64, f64) {
+    let width = available.clamp(MIN_CHART_WIDTH, CHART_WIDTH);
+    (width, width * CHART_HEIGHT / CHART_WIDTH)
+}
+
+/// 🖥️ Backing-store size in device pixels of a canvas shown at a CSS size
+pub fn backing_size(css_width: f64, css_height: f64, dpr: f64) -> (u32, u32) {
+    let scale = |css: f64| (css * dpr).round().max(1.0) as u32;
+    (scale(css_width), scale(css_height))
+}
 
 /// Base factor for converting mouse movement to candle offset
 pub const PAN_SENSITIVITY_BASE: f64 = MAX_VISIBLE_CANDLES / CHART_WIDTH;
 
+/// 🖱️ Pointer x in the chart pixels `apply_pan` takes, for NDC `ndc_x`
+///
+/// Drags measured in NDC pan the same number of candles at any canvas size
+/// or pixel ratio.
+pub fn pan_pointer_x(ndc_x: f64) -> f64 {
+    (ndc_x + 1.0) / 2.0 * CHART_WIDTH
+}
+
+/// Widest window shown when fully zoomed out, independent of retained history
+const MAX_ZOOMED_OUT_CANDLES: f64 = 300.0;
+
 /// Minimum allowed zoom level
-const MIN_ZOOM_LEVEL: f64 = MAX_VISIBLE_CANDLES / 300.0;
+const MIN_ZOOM_LEVEL: f64 = MAX_VISIBLE_CANDLES / MAX_ZOOMED_OUT_CANDLES;
 /// Maximum allowed zoom level
 const MAX_ZOOM_LEVEL: f64 = 32.0;
+/// Zoom level on startup and after `reset_view`
+pub const DEFAULT_ZOOM_LEVEL: f64 = 0.32;
+
+/// Zoom change per pixel of wheel delta
+const WHEEL_ZOOM_SENSITIVITY: f64 = 0.0015;
+
+/// Price scale zoom per pixel of wheel delta over the price axis
+const PRICE_WHEEL_ZOOM_SENSITIVITY: f64 = 0.002;
+
+/// 🔍 Price-axis zoom factor for a wheel `delta_y`; scrolling up (negative) zooms in
+pub fn price_zoom_factor(delta_y: f64) -> f64 {
+    (-delta_y * PRICE_WHEEL_ZOOM_SENSITIVITY).exp()
+}
 
 /// Pan offset required to trigger history loading
 pub const HISTORY_FETCH_THRESHOLD: f64 = -50.0;
@@ -56,12 +122,29 @@ pub const HISTORY_FETCH_THRESHOLD: f64 = -50.0;
 /// Number of candles kept in memory beyond the visible range
 const HISTORY_BUFFER_SIZE: usize = 150;
 
+/// Candles retained per interval unless changed with `set_history_limit`
+pub const DEFAULT_HISTORY_LIMIT: usize = 1000;
+
 /// Check if more historical data should be fetched
 pub fn should_fetch_history(pan: f64) -> bool {
     pan <= HISTORY_FETCH_THRESHOLD
 }
 
+/// 🔍 Clamp `level` to the allowed zoom range
+///
+/// Every zoom entry point goes through here so the limits live in one place.
+pub fn clamp_zoom(level: f64) -> f64 {
+    level.clamp(MIN_ZOOM_LEVEL, MAX_ZOOM_LEVEL)
+}
+
 /// Calculate visible range based on zoom level and pan offset
+///
+/// Zoom always renders from the base series of the current interval and only
+/// resizes this window, so there are no discrete LOD levels to pop between.
+/// The window grows or shrinks one whole candle at a time: candle width
+/// changes continuously while the outermost candle appears or leaves at
+/// once. Cross-fading pre-aggregated series would hide that step but costs a
+/// second geometry pass per frame and blurs candles mid-zoom.
 pub fn visible_range(len: usize, zoom: f64, pan: f64) -> (usize, usize) {
     let visible = ((MAX_VISIBLE_CANDLES / zoom).max(MIN_VISIBLE_CANDLES).min(len as f64)) as isize;
     let base_start = len as isize - visible;
@@ -72,12 +155,315 @@ pub fn visible_range(len: usize, zoom: f64, pan: f64) -> (usize, usize) {
     (start as usize, visible as usize)
 }
 
+/// 🔍 Zoom by a wheel `delta` keeping the candle under `cursor_ndc_x` in place
+///
+/// Zoom changes multiplicatively and is clamped to the allowed range; returns
+/// the new `(zoom, pan)`.
+pub fn zoom_at_cursor(zoom: f64, pan: f64, delta: f64, cursor_ndc_x: f32) -> (f64, f64) {
+    let new_zoom = clamp_zoom(zoom * (-delta * WHEEL_ZOOM_SENSITIVITY).exp());
+    // Share of the window to the right of the cursor
+    let right_share = (1.0 - cursor_ndc_x.clamp(-1.0, 1.0) as f64) / 2.0;
+    let old_visible = (MAX_VISIBLE_CANDLES / zoom).max(MIN_VISIBLE_CANDLES);
+    let new_visible = (MAX_VISIBLE_CANDLES / new_zoom).max(MIN_VISIBLE_CANDLES);
+    (new_zoom, pan + (new_visible - old_visible) * right_share)
+}
+
+/// Default minimum delay between redraws of the forming candle
+pub const FORMING_CANDLE_THROTTLE_MS: f64 = 100.0;
+
+/// ⏱️ Rate limiter for redraws of the forming (last) candle
+///
+/// Updates for the same candle are drawn at most once per `min_interval_ms`.
+/// A new open time always renders, so the closed candle shows its final OHLC.
+/// A skipped update gets a trailing redraw once the interval has passed.
+#[derive(Clone, Copy, Debug)]
+pub struct FormingCandleThrottle {
+    pub min_interval_ms: f64,
+    last_render_ms: Option<f64>,
+    last_timestamp: Option<u64>,
+    trailing_render_ms: Option<f64>,
+}
+
+impl FormingCandleThrottle {
+    pub fn new(min_interval_ms: f64) -> Self {
+        Self {
+            min_interval_ms,
+            last_render_ms: None,
+            last_timestamp: None,
+            trailing_render_ms: None,
+        }
+    }
+
+    /// Returns `true` if the update for `open_time` should be drawn at `now_ms`
+    pub fn should_render(&mut self, open_time: u64, now_ms: f64) -> bool {
+        let new_candle = self.last_timestamp != Some(open_time);
+        let due = self.last_render_ms.is_none_or(|last| now_ms - last >= self.min_interval_ms);
+        if !(new_candle || due) {
+            return false;
+        }
+        self.last_timestamp = Some(open_time);
+        self.last_render_ms = Some(now_ms);
+        true
+    }
+
+    /// Delay until the trailing redraw of an update skipped at `now_ms`
+    ///
+    /// Call after [`Self::should_render`] returned `false`. Returns `None`
+    /// while the trailing redraw of the current interval is already scheduled.
+    pub fn trailing_render_delay(&mut self, now_ms: f64) -> Option<f64> {
+        let due = self.last_render_ms? + self.min_interval_ms;
+        if self.trailing_render_ms == Some(due) {
+            return None;
+        }
+        self.trailing_render_ms = Some(due);
+        Some((due - now_ms).max(0.0))
+    }
+}
+
+/// Most candles buffered while streaming is paused; the oldest are dropped first
+pub const PAUSED_CANDLE_LIMIT: usize = 10_000;
+
+/// ⏸️ Live candles received while streaming is paused
+///
+/// Repeated updates of the forming candle collapse into its latest one, so
+/// resuming replays every candle missed, in order, with its final OHLCV.
+#[derive(Clone, Debug, Default)]
+pub struct PausedCandles {
+    symbol: Option<Symbol>,
+    candles: VecDeque<Candle>,
+}
+
+impl PausedCandles {
+    /// Buffer a live update of `symbol`, dropping candles of any other symbol
+    pub fn push(&mut self, symbol: &Symbol, candle: Candle) {
+        if self.symbol.as_ref() != Some(symbol) {
+            self.symbol = Some(symbol.clone());
+            self.candles.clear();
+        }
+        match self.candles.back_mut() {
+            Some(last) if last.timestamp.value() == candle.timestamp.value() => *last = candle,
+            _ => {
+                self.candles.push_back(candle);
+                if self.candles.len() > PAUSED_CANDLE_LIMIT {
+                    self.candles.pop_front();
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+
+    /// Empty the buffer; returns its candles if they belong to `symbol`
+    pub fn drain(&mut self, symbol: &Symbol) -> Vec<Candle> {
+        let candles = std::mem::take(&mut self.candles);
+        match self.symbol.take() {
+            Some(owner) if owner == *symbol => candles.into(),
+            _ => Vec::new(),
+        }
+    }
+}
+
 /// Check if the viewport is already at the latest candle
 pub fn should_auto_scroll(len: usize, zoom: f64, pan: f64) -> bool {
     let (start, visible) = visible_range(len, zoom, pan);
     start + visible >= len
 }
 
+/// Pan offset keeping the same candles in view after `added` new candles arrive
+///
+/// With auto-scroll the view stays anchored to the latest candle; otherwise the
+/// offset moves back so the studied history does not shift.
+pub fn pan_after_new_candles(pan: f64, added: usize, auto_scroll: bool) -> f64 {
+    if auto_scroll { pan } else { pan - added as f64 }
+}
+
+/// Fraction of the pan velocity kept after one 16 ms frame of inertia
+pub const PAN_INERTIA_FRICTION: f64 = 0.92;
+/// Velocity in px/ms below which inertial panning stops
+pub const PAN_INERTIA_MIN_VELOCITY: f64 = 0.02;
+/// Only pointer samples this recent contribute to the release velocity
+pub const PAN_VELOCITY_WINDOW_MS: f64 = 100.0;
+
+/// 🖐️ Pointer samples of the current drag, used to measure flick velocity
+#[derive(Clone, Debug, Default)]
+pub struct PanVelocityTracker {
+    samples: Vec<(f64, f64)>,
+}
+
+impl PanVelocityTracker {
+    /// Forget samples of the previous drag
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Record pointer `x` (px) at `time_ms`
+    pub fn record(&mut self, x: f64, time_ms: f64) {
+        self.samples.retain(|(t, _)| time_ms - t <= PAN_VELOCITY_WINDOW_MS);
+        self.samples.push((time_ms, x));
+    }
+
+    /// Pointer velocity in px/ms when released at `time_ms`; zero after a pause
+    pub fn release_velocity(&self, time_ms: f64) -> f64 {
+        let (Some(&(first_t, first_x)), Some(&(last_t, last_x))) =
+            (self.samples.first(), self.samples.last())
+        else {
+            return 0.0;
+        };
+        if time_ms - last_t > PAN_VELOCITY_WINDOW_MS || last_t <= first_t {
+            return 0.0;
+        }
+        (last_x - first_x) / (last_t - first_t)
+    }
+}
+
+/// Finger travel (px) up to which a touch still counts as a tap
+pub const TAP_SLOP_PX: f64 = 10.0;
+
+/// ✋ What a touch update asks the chart to do
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchAction {
+    /// Pan by `dx` pixels, like a mouse drag
+    Pan(f64),
+    /// Zoom by a wheel-equivalent `delta` around client x `center_x`
+    Zoom { delta: f64, center_x: f64 },
+    /// Show the tooltip at client (`x`, `y`)
+    Tap { x: f64, y: f64 },
+}
+
+/// ✋ One- and two-finger gesture over the chart, fed client touch points
+///
+/// A change in finger count re-anchors the gesture at the remaining fingers,
+/// so lifting one finger of a pinch continues as a pan without a jump.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TouchGesture {
+    #[default]
+    Idle,
+    Pan {
+        start: (f64, f64),
+        last_x: f64,
+        tap: bool,
+    },
+    Pinch {
+        distance: f64,
+    },
+}
+
+impl TouchGesture {
+    /// Fingers touched down, giving `points` on the screen
+    ///
+    /// Only a first finger can start a tap.
+    pub fn begin(&mut self, points: &[(f64, f64)]) {
+        *self = Self::anchor(points, *self == Self::Idle);
+    }
+
+    fn anchor(points: &[(f64, f64)], tap: bool) -> Self {
+        match points {
+            [] => Self::Idle,
+            [(x, y)] => Self::Pan { start: (*x, *y), last_x: *x, tap },
+            [a, b, ..] => Self::Pinch { distance: touch_distance(*a, *b) },
+        }
+    }
+
+    /// Fingers moved to `points`
+    pub fn update(&mut self, points: &[(f64, f64)]) -> Option<TouchAction> {
+        match (&mut *self, points) {
+            (Self::Pan { start, last_x, tap }, [(x, y)]) => {
+                let dx = x - *last_x;
+                *last_x = *x;
+                *tap &= (x - start.0).hypot(y - start.1) <= TAP_SLOP_PX;
+                Some(TouchAction::Pan(dx))
+            }
+            (Self::Pinch { distance }, [a, b, ..]) => {
+                let next = touch_distance(*a, *b);
+                let delta = pinch_zoom_delta(*distance, next);
+                *distance = next;
+                Some(TouchAction::Zoom { delta, center_x: (a.0 + b.0) / 2.0 })
+            }
+            _ => {
+                // Finger count changed without a start or end event
+                *self = Self::anchor(points, false);
+                None
+            }
+        }
+    }
+
+    /// Fingers lifted, leaving `remaining` on the screen
+    pub fn end(&mut self, remaining: &[(f64, f64)]) -> Option<TouchAction> {
+        let tap = match (&*self, remaining) {
+            (Self::Pan { start: (x, y), tap: true, .. }, []) => {
+                Some(TouchAction::Tap { x: *x, y: *y })
+            }
+            _ => None,
+        };
+        *self = Self::anchor(remaining, false);
+        tap
+    }
+}
+
+fn touch_distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// 🤏 Wheel delta zooming as much as spreading fingers from `from` to `to` px apart
+pub fn pinch_zoom_delta(from: f64, to: f64) -> f64 {
+    if from <= 0.0 || to <= 0.0 {
+        return 0.0;
+    }
+    -(to / from).ln() / WHEEL_ZOOM_SENSITIVITY
+}
+
+/// Advance inertia by `dt_ms`, returning the pointer delta (px) and the decayed velocity
+pub fn inertia_step(velocity: f64, dt_ms: f64) -> (f64, f64) {
+    if velocity.abs() < PAN_INERTIA_MIN_VELOCITY {
+        return (0.0, 0.0);
+    }
+    let next = velocity * PAN_INERTIA_FRICTION.powf(dt_ms / 16.0);
+    let next = if next.abs() < PAN_INERTIA_MIN_VELOCITY { 0.0 } else { next };
+    (velocity * dt_ms, next)
+}
+
+/// Fraction of the remaining distance kept after one 16 ms frame of the go-to-latest glide
+pub const SCROLL_TO_LATEST_EASING: f64 = 0.8;
+
+/// Next pan offset of the go-to-latest glide; snaps to zero once within half a candle
+pub fn ease_toward_latest(pan: f64, dt_ms: f64) -> f64 {
+    let next = pan * SCROLL_TO_LATEST_EASING.powf(dt_ms / 16.0);
+    if next.abs() < 0.5 { 0.0 } else { next }
+}
+
+/// Check if panning by pointer `delta_x` would move past the loaded data
+pub fn pan_at_boundary(len: usize, zoom: f64, pan: f64, delta_x: f64) -> bool {
+    let (start, visible) = visible_range(len, zoom, pan);
+    if delta_x > 0.0 { start == 0 } else { start + visible >= len }
+}
+
+/// 🗺️ Current view as `(left, width)` fractions of the loaded history
+pub fn minimap_window(len: usize, zoom: f64, pan: f64) -> (f64, f64) {
+    if len == 0 {
+        return (0.0, 1.0);
+    }
+    let (start, visible) = visible_range(len, zoom, pan);
+    (start as f64 / len as f64, visible as f64 / len as f64)
+}
+
+/// 🗺️ Main-chart pointer delta equivalent to dragging the minimap window by `delta_px`
+pub fn minimap_drag_delta(delta_px: f64, strip_width: f64, len: usize, zoom: f64) -> f64 {
+    let candles = delta_px / strip_width * len as f64;
+    -candles * zoom / PAN_SENSITIVITY_BASE
+}
+
+/// 🗺️ Zoom level that shows `width` (fraction of history) of `len` candles
+pub fn minimap_zoom_for_width(width: f64, len: usize) -> f64 {
+    let visible = (width * len as f64).max(MIN_VISIBLE_CANDLES);
+    clamp_zoom(MAX_VISIBLE_CANDLES / visible)
+}
+
 /// Determine visible range using timestamps from the viewport
 pub fn visible_range_by_time(
     candles: &[Candle],
@@ -102,21 +488,57 @@ pub fn visible_range_by_time(
     (start, visible)
 }
 
-/// Calculate price axis levels based on the viewport
+/// Round price axis levels of the viewport, highest first, matching the horizontal grid
 pub fn price_levels(viewport: &crate::domain::chart::value_objects::Viewport) -> Vec<f64> {
-    let step = (viewport.max_price - viewport.min_price) as f64 / 8.0;
-    (0..=8).rev().map(|i| viewport.min_price as f64 + i as f64 * step).collect()
+    let (min, max) = ensure_price_span(viewport.min_price, viewport.max_price);
+    let mut levels = nice_price_ticks(min as f64, max as f64, PRICE_GRID_LINES);
+    levels.reverse();
+    levels
+}
+
+/// Decimal places of the price axis labels for `levels`
+pub fn price_level_decimals(levels: &[f64]) -> usize {
+    match levels {
+        [a, b, ..] => price_step_decimals((a - b).abs()),
+        _ => 2,
+    }
+}
+
+/// 💲 `viewport` with the price range of the last rendered frame, once there is one
+///
+/// Auto-fit draws the visible candles' range rather than the chart viewport,
+/// so axis labels placed with this line up with the grid.
+pub fn displayed_price_viewport(
+    viewport: &crate::domain::chart::value_objects::Viewport,
+) -> crate::domain::chart::value_objects::Viewport {
+    let mut shown = viewport.clone();
+    if let Some((min_price, max_price)) = rendered_price_range().get() {
+        shown.min_price = min_price;
+        shown.max_price = max_price;
+    }
+    shown
+}
+
+/// Distance of `price` below the top of the viewport in percent of its height
+pub fn price_level_percent(
+    viewport: &crate::domain::chart::value_objects::Viewport,
+    price: f64,
+) -> f64 {
+    let (min, max) = ensure_price_span(viewport.min_price, viewport.max_price);
+    (max as f64 - price) / (max - min) as f64 * 100.0
 }
 
 // Helper aliases for global signals
 global_signals! {
     pub global_current_price => current_price: f64,
     global_candle_count => candle_count: usize,
-    global_is_streaming => is_streaming: bool,
+    pub connection_state => connection_state: ConnectionState,
     global_max_volume => max_volume: f64,
     loading_more => loading_more: bool,
+    pub load_failed => load_failed: bool,
     tooltip_data => tooltip_data: Option<TooltipData>,
     tooltip_visible => tooltip_visible: bool,
+    pub selected_candle => selected_candle: Option<usize>,
     zoom_level => zoom_level: f64,
     pan_offset => pan_offset: f64,
     is_dragging => is_dragging: bool,
@@ -126,738 +548,947 @@ global_signals! {
     pub global_charts => charts: HashMap<Symbol, RwSignal<Chart>>,
     pub stream_abort_handles => stream_abort_handles: HashMap<Symbol, futures::future::AbortHandle>,
     pub global_line_visibility => line_visibility: LineVisibility,
+    pub forming_candle_throttle_ms => forming_candle_throttle_ms: f64,
+    pub price_tick_size => price_tick_size: Option<f64>,
+    pub price_rounding => price_rounding: bool,
+    pub horizontal_lines => horizontal_lines: Vec<HorizontalLine>,
+    pub pan_inertia_enabled => pan_inertia_enabled: bool,
+    pub animate_transitions => animate_transitions: bool,
+    pub x_axis_mode => x_axis_mode: XAxisMode,
+    pub msaa_samples => msaa_samples: u32,
+    pub device_lost => device_lost: Option<(String, String)>,
+    pub stream_paused => stream_paused: bool,
+    pub auto_scroll => auto_scroll: bool,
+    pub panel_layout => panel_layout: PanelLayout,
+    pub time_zone_offset => time_zone_offset: Option<i32>,
+    pub frame_timing => frame_timing: FrameTiming,
+    pub exchange => exchange: Exchange,
+    pub theme_preset => theme_preset: ThemePreset,
+    pub min_body_height => min_body_height: MinBodyHeight,
+    pub canvas_size => canvas_size: (f64, f64),
+    pub history_limit => history_limit: usize,
+    pub history_depth => history_depth: HistoryDepth,
+    pub selected_price_line => selected_price_line: Option<u32>,
+    pub current_price_marker => current_price_marker: Option<(f32, [f32; 4])>,
+    pub indicator_values => indicator_values: Vec<IndicatorValue>,
+    pub rendered_price_range => rendered_price_range: Option<(f32, f32)>,
 }
 
-/// 📈 Fetch additional history and prepend it to the list
-fn fetch_more_history(set_status: WriteSignal<String>) {
-    if loading_more().get() {
-        return;
+/// 📏 Tick size the renderer should round to, if rounding is enabled
+pub fn effective_price_tick(rounding: bool, tick: Option<f64>) -> Option<f64> {
+    tick.filter(|_| rounding)
+}
+
+/// 📏 Push the price rounding setting to the renderer
+pub fn apply_price_precision() {
+    let tick =
+        effective_price_tick(price_rounding().get_untracked(), price_tick_size().get_untracked());
+    with_active_renderers(|r| r.geometry_mut().set_price_tick(tick));
+}
+
+/// Next id handed out by `add_horizontal_line`
+static NEXT_HORIZONTAL_LINE_ID: AtomicU32 = AtomicU32::new(1);
+
+/// 📌 Draw a labeled reference line at `price` and return its id
+pub fn add_horizontal_line(price: f64, color: [f32; 4], label: &str) -> u32 {
+    let id = NEXT_HORIZONTAL_LINE_ID.fetch_add(1, Ordering::Relaxed);
+    horizontal_lines().update(|lines| {
+        lines.push(HorizontalLine { id, price, color, label: label.to_string(), alert: false })
+    });
+    redraw_current_chart();
+    id
+}
+
+/// 📌 Remove a reference line; returns `false` if the id is unknown
+pub fn remove_horizontal_line(id: u32) -> bool {
+    let mut removed = false;
+    horizontal_lines().update(|lines| {
+        let before = lines.len();
+        lines.retain(|l| l.id != id);
+        removed = lines.len() != before;
+    });
+    if removed {
+        if selected_price_line().get_untracked() == Some(id) {
+            selected_price_line().set(None);
+        }
+        redraw_current_chart();
     }
+    removed
+}
 
-    let chart = ensure_chart(&current_symbol().get_untracked());
-    let oldest_ts = chart.with(|c| {
-        c.get_series(current_interval().get_untracked())
-            .and_then(|s| s.get_candles().front())
-            .map(|c| c.timestamp.value())
+/// Color of price lines placed from the chart
+const PRICE_LINE_COLOR: [f32; 4] = [0.95, 0.77, 0.06, 1.0];
+
+/// Label of price lines placed from the chart
+const PRICE_LINE_LABEL: &str = "📌";
+
+/// Distance in CSS pixels within which a click picks a price line
+pub const PRICE_LINE_HIT_PX: f64 = 5.0;
+
+/// 📌 Place a user price line at `price` and return its id
+pub fn add_price_line(price: f64) -> u32 {
+    add_horizontal_line(price, PRICE_LINE_COLOR, PRICE_LINE_LABEL)
+}
+
+/// 🔔 Arm or disarm the crossing alert of a price line; returns the new state
+///
+/// Arming asks for notification permission while the user gesture is live.
+pub fn toggle_price_alert(id: u32) -> Option<bool> {
+    let mut state = None;
+    horizontal_lines().update(|lines| {
+        if let Some(line) = lines.iter_mut().find(|l| l.id == id) {
+            line.alert = !line.alert;
+            state = Some(line.alert);
+        }
     });
-    let end_time = match oldest_ts {
-        Some(ts) if ts > 0 => ts - 1,
-        _ => return,
-    };
+    if state == Some(true) {
+        request_notification_permission();
+    }
+    // Alert lines are drawn dashed
+    if state.is_some() {
+        redraw_current_chart();
+    }
+    state
+}
 
-    loading_more().set(true);
+/// Fraction of the level the price must move away before a fired alert re-arms
+pub const ALERT_REARM_RATIO: f64 = 0.001;
 
-    let symbol = current_symbol().get_untracked();
-    let _ = spawn_local_with_current_owner(async move {
-        let interval = current_interval().get_untracked();
-        let client_arc =
-            Arc::new(Mutex::new(BinanceWebSocketClient::new(symbol.clone(), interval)));
-        let visible = chart.with(|c| {
-            let len = c.get_candle_count();
-            visible_range(len, zoom_level().get_untracked(), pan_offset().get_untracked()).1
-        });
-        let limit = (visible + HISTORY_BUFFER_SIZE) as u32;
-        let result = {
-            let client = client_arc.lock().await;
-            client.fetch_historical_data_before(end_time, limit).await
-        };
-        match result {
-            Ok(mut new_candles) => {
-                new_candles.sort_by(|a, b| a.timestamp.value().cmp(&b.timestamp.value()));
-                chart.update(|ch| {
-                    for candle in new_candles.iter() {
-                        ch.add_candle(candle.clone());
-                    }
-                });
-                chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-                chart.with_untracked(|c| {
-                    if c.get_candle_count() > 0
-                        && with_global_renderer(|r| {
-                            r.set_zoom_params(
-                                zoom_level().with_untracked(|z| *z),
-                                pan_offset().with_untracked(|p| *p),
-                            );
-                            let _ = r.render(c);
-                        })
-                        .is_none()
-                    {
-                        // renderer not available
-                    }
-                });
+#[derive(Clone, Copy, Debug)]
+struct AlertState {
+    last_price: f64,
+    armed: bool,
+}
 
-                let new_count = chart.with(|c| c.get_candle_count());
-                let max_volume = chart.with(|c| {
-                    c.get_series(current_interval().get_untracked())
-                        .unwrap()
-                        .get_candles()
-                        .iter()
-                        .map(|c| c.ohlcv.volume.value())
-                        .fold(0.0f64, |a, b| a.max(b))
-                });
-                global_candle_count().set(new_count);
-                global_max_volume().set(max_volume);
+/// 🔔 Crossing detector for the alert lines of one live stream
+///
+/// An alert only starts watching at the first live price it sees, so levels
+/// already passed by the historical data never fire. After firing it stays
+/// silent until the price moves `ALERT_REARM_RATIO` away from the level.
+#[derive(Clone, Debug, Default)]
+pub struct PriceAlertMonitor {
+    states: HashMap<u32, AlertState>,
+}
 
-                set_status.set(format!("📈 Loaded {} older candles", new_candles.len()));
+impl PriceAlertMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a live close price; returns the alert lines it crossed
+    pub fn check(&mut self, lines: &[HorizontalLine], price: f64) -> Vec<HorizontalLine> {
+        self.states.retain(|id, _| lines.iter().any(|l| l.alert && l.id == *id));
+        let mut fired = Vec::new();
+        for line in lines.iter().filter(|l| l.alert) {
+            let Some(state) = self.states.get_mut(&line.id) else {
+                self.states.insert(line.id, AlertState { last_price: price, armed: true });
+                continue;
+            };
+            let level = line.price;
+            let crossed = (state.last_price < level && price >= level)
+                || (state.last_price > level && price <= level);
+            if state.armed && crossed {
+                state.armed = false;
+                fired.push(line.clone());
+            } else if !state.armed && (price - level).abs() > level.abs() * ALERT_REARM_RATIO {
+                state.armed = true;
             }
-            Err(e) => set_status.set(format!("❌ Failed to load more data: {}", e)),
+            state.last_price = price;
         }
+        fired
+    }
+}
 
-        loading_more().set(false);
-    });
+fn request_notification_permission() {
+    if web_sys::Notification::permission() == web_sys::NotificationPermission::Default {
+        let _ = web_sys::Notification::request_permission();
+    }
 }
 
-/// 🎯 Data for the tooltip
-#[derive(Clone, Debug)]
-pub struct TooltipData {
-    pub candle: Candle,
-    pub x: f64,
-    pub y: f64,
-    pub formatted_text: String,
+/// 🔔 Log a crossed alert and show it as a browser notification
+fn notify_price_alert(symbol: &Symbol, line: &HorizontalLine, price: f64) {
+    let message = format!("{} crossed {:.2} (last {:.2})", symbol.value(), line.price, price);
+    get_logger().info(LogComponent::Presentation("PriceAlert"), &format!("🔔 {message}"));
+    match web_sys::Notification::permission() {
+        web_sys::NotificationPermission::Granted => {
+            let options = web_sys::NotificationOptions::new();
+            options.set_body(&message);
+            let _ = web_sys::Notification::new_with_options("🔔 Price alert", &options);
+        }
+        web_sys::NotificationPermission::Default => request_notification_permission(),
+        _ => {}
+    }
 }
 
-impl TooltipData {
-    pub fn new(candle: Candle, x: f64, y: f64) -> Self {
-        let change = candle.ohlcv.close.value() - candle.ohlcv.open.value();
-        let change_pct = (change / candle.ohlcv.open.value()) * 100.0;
-        let trend = if change >= 0.0 { "🟢" } else { "🔴" };
+/// 📌 Remove the reference line at `index`; returns `false` when out of range
+pub fn remove_price_line(index: usize) -> bool {
+    let id = horizontal_lines().with_untracked(|lines| lines.get(index).map(|l| l.id));
+    id.is_some_and(remove_horizontal_line)
+}
 
-        // Format time from the timestamp
-        let time_str = format!("Time: {}", candle.timestamp.value());
+/// 💲 Price at NDC height `y` inside a panel `band` showing `min..max`
+pub fn price_at_ndc(y: f32, band: PanelBand, min: f32, max: f32) -> f64 {
+    let t = (y - band.bottom) / band.height().max(f32::EPSILON);
+    (min + t * (max - min)) as f64
+}
 
-        let symbol = current_symbol().get_untracked();
-        let formatted_text = format!(
-            "{} {}\n📈 Open:   ${:.2}\n📊 High:   ${:.2}\n📉 Low:    ${:.2}\n💰 Close:  ${:.2}\n📈 Change: ${:.2} ({:.2}%)\n📊 Volume: {:.4}\n{}",
-            trend,
-            symbol.value(),
-            candle.ohlcv.open.value(),
-            candle.ohlcv.high.value(),
-            candle.ohlcv.low.value(),
-            candle.ohlcv.close.value(),
-            change,
-            change_pct,
-            candle.ohlcv.volume.value(),
-            time_str
-        );
+/// Height of the current price label in CSS pixels
+pub const CURRENT_PRICE_LABEL_HEIGHT_PX: f64 = 22.0;
 
-        Self { candle, x, y, formatted_text }
+/// 💰 CSS `top` of the current price label centered on NDC height `y`
+///
+/// Clamped so the whole label stays inside a canvas `height` pixels tall.
+pub fn current_price_label_top(y: f32, height: f64) -> f64 {
+    let half = CURRENT_PRICE_LABEL_HEIGHT_PX / 2.0;
+    let top = (1.0 - y as f64) / 2.0 * height;
+    top.clamp(half, (height - half).max(half))
+}
+
+/// 📣 Publish what the HTML overlays need from the frame `geometry` just built for `chart`
+///
+/// Renderers call this while they are borrowed, so the overlays read these
+/// signals instead of borrowing the renderer themselves.
+pub fn publish_frame_overlays(geometry: &ChartGeometryBuilder, chart: &Chart) {
+    let marker = geometry.current_price_y_ndc().map(|y| (y, geometry.theme().current_price));
+    if current_price_marker().get_untracked() != marker {
+        current_price_marker().set(marker);
+    }
+    let [_, _, min_price, max_price] = geometry.cached_uniforms().viewport;
+    let range = (max_price > min_price).then_some((min_price, max_price));
+    if rendered_price_range().get_untracked() != range {
+        rendered_price_range().set(range);
+    }
+    let values = geometry.compute_indicator_values(chart);
+    if indicator_values().with_untracked(|current| *current != values) {
+        indicator_values().set(values);
     }
 }
 
-/// 🦀 Main Crypto Chart component built with Leptos
-#[component]
-pub fn app() -> impl IntoView {
-    // 🚀 Initialize the global logger on application start
-    use crate::domain::logging::get_logger;
+/// 🎨 `#rrggbb` for a normalized RGBA color
+pub fn css_hex(color: [f32; 4]) -> String {
+    let [r, g, b, _] = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
 
-    // Extra console.log for diagnostics
-    web_sys::console::log_1(&"🚀 Starting Crypto Chart App".into());
+/// 📌 Index of the line closest to `price`, if it lies within `tolerance`
+pub fn price_line_near(lines: &[HorizontalLine], price: f64, tolerance: f64) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (i, (l.price - price).abs()))
+        .filter(|(_, distance)| *distance <= tolerance)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
 
-    get_logger().info(LogComponent::Presentation("App"), "🚀 Starting Crypto Chart App");
+/// 💲 Price under the pointer and the price span of one CSS pixel
+///
+/// Uses the range of the last rendered frame, so it matches what is drawn
+/// even while auto-fit keeps the viewport prices stale.
+fn pointer_price(event: &web_sys::MouseEvent) -> Option<(f64, f64)> {
+    let (_, ndc_y) = client_to_ndc(event)?;
+    let [_, _, min_price, max_price] =
+        with_global_renderer(|r| r.geometry().cached_uniforms().viewport)?;
+    if max_price <= min_price {
+        return None;
+    }
+    let band = panel_layout().with_untracked(|l| l.main_band());
+    let height = canvas_size().get_untracked().1.max(1.0);
+    let per_px = 2.0 * (max_price - min_price) as f64 / (band.height() as f64 * height);
+    Some((price_at_ndc(ndc_y, band, min_price, max_price), per_px))
+}
 
-    web_sys::console::log_1(&"📦 Creating view...".into());
+/// 📌 Id of the line within `PRICE_LINE_HIT_PX` of `price`
+fn price_line_at(price: f64, per_px: f64) -> Option<u32> {
+    horizontal_lines().with_untracked(|lines| {
+        price_line_near(lines, price, PRICE_LINE_HIT_PX * per_px).map(|i| lines[i].id)
+    })
+}
 
-    view! {
-        <style>
-            {r#"
-            .bitcoin-chart-app {
-                font-family: 'SF Pro Display', -apple-system, BlinkMacSystemFont, sans-serif;
-                background: linear-gradient(135deg, #1e3c72 0%, #2a5298 100%);
-                min-height: 100vh;
-                padding: 20px;
-                color: white;
-            }
-            
-            .header {
-                text-align: center;
-                margin-bottom: 20px;
-                background: rgba(255, 255, 255, 0.1);
-                backdrop-filter: blur(10px);
-                padding: 20px;
-                border-radius: 15px;
-                border: 1px solid rgba(255, 255, 255, 0.2);
-            }
-            
-            .price-info {
-                display: flex;
-                justify-content: center;
-                gap: 40px;
-                margin-top: 15px;
-            }
-            
-            .price-item {
-                text-align: center;
-            }
-            
-            .price-value {
-                font-size: 24px;
-                font-weight: 700;
-                color: #74c787;
-                text-shadow: 0 0 10px rgba(114, 198, 133, 0.3);
-            }
-            
-            .price-label {
-                font-size: 12px;
-                color: #a0a0a0;
-                margin-top: 5px;
-            }
-            
-            .chart-container {
-                position: relative;
-                display: flex;
-                flex-direction: column;
-                align-items: center;
-                gap: 10px;
-                margin-bottom: 20px;
-            }
-            
-            .chart-wrapper {
-                position: relative;
-                display: inline-block;
-            }
-            
-            .price-scale {
-                position: absolute;
-                right: -60px;
-                top: 0;
-                height: 100%;
-                width: 80px;
-                pointer-events: none;
-            }
-            
-            .current-price-label {
-                position: absolute;
-                right: 0;
-                transform: translateY(-50%);
-                background: #f39c12;
-                color: white;
-                padding: 4px 8px;
-                border-radius: 4px;
-                font-size: 12px;
-                font-weight: bold;
-                white-space: nowrap;
-                box-shadow: 0 2px 4px rgba(0,0,0,0.3);
-            }
-            
-            .price-value {
-                font-family: 'Courier New', monospace;
+/// localStorage key holding the serialized panel layout
+const PANEL_LAYOUT_STORAGE_KEY: &str = "price-chart.panel-layout";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// 🧱 Load the panel layout saved by a previous session, if any
+pub fn restore_panel_layout() {
+    let saved = local_storage().and_then(|s| s.get_item(PANEL_LAYOUT_STORAGE_KEY).ok().flatten());
+    if let Some(layout) = saved.and_then(|json| serde_json::from_str::<PanelLayout>(&json).ok()) {
+        panel_layout().set(layout.validated());
+    }
+}
+
+/// localStorage key holding the reference lines of each symbol
+const PRICE_LINES_STORAGE_KEY: &str = "price-chart.price-lines";
+
+fn stored_price_lines() -> HashMap<String, Vec<HorizontalLine>> {
+    local_storage()
+        .and_then(|s| s.get_item(PRICE_LINES_STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// 💾 Keep the reference lines of each symbol in localStorage
+///
+/// Switching symbols swaps in the lines saved for the new one. Their ids
+/// are handed out afresh so they never clash with lines added later.
+pub fn persist_price_lines() {
+    create_effect(move |previous: Option<Symbol>| {
+        let symbol = current_symbol().get();
+        if previous.as_ref() != Some(&symbol) {
+            let mut lines = stored_price_lines().remove(symbol.value()).unwrap_or_default();
+            for line in &mut lines {
+                line.id = NEXT_HORIZONTAL_LINE_ID.fetch_add(1, Ordering::Relaxed);
             }
-            
-            .tooltip {
-                position: absolute;
-                background: rgba(0, 0, 0, 0.9);
-                color: white;
-                padding: 8px 12px;
-                border-radius: 6px;
-                font-size: 12px;
-                font-family: 'Courier New', monospace;
-                white-space: pre-line;
-                pointer-events: none;
-                z-index: 1000;
-                border: 1px solid #4a5d73;
-                box-shadow: 0 4px 12px rgba(0, 0, 0, 0.5);
-                backdrop-filter: blur(5px);
-                line-height: 1.4;
-                transform: translate(10px, -100%);
+            selected_price_line().set(None);
+            horizontal_lines().set(lines);
+            redraw_current_chart();
+        } else if let Some(storage) = local_storage() {
+            let mut stored = stored_price_lines();
+            let lines = horizontal_lines().get_untracked();
+            if lines.is_empty() {
+                stored.remove(symbol.value());
+            } else {
+                stored.insert(symbol.value().to_string(), lines);
             }
-            
-            .status {
-                color: #74c787;
-                font-size: 14px;
-                text-align: center;
+            if let Ok(json) = serde_json::to_string(&stored) {
+                let _ = storage.set_item(PRICE_LINES_STORAGE_KEY, &json);
             }
-            
-
-            "#}
-        </style>
-        <div class="bitcoin-chart-app">
-            <Header />
-            <ChartContainer />
-        </div>
-    }
+        }
+        horizontal_lines().track();
+        symbol
+    });
 }
 
-/// 📊 Price header with real data
-#[component]
-fn header() -> impl IntoView {
-    // Use global signals for real data
-    let current_price = global_current_price();
-    let candle_count = global_candle_count();
-    let is_streaming = global_is_streaming();
-    let max_volume = global_max_volume();
-    let zoom_level = zoom_level();
+/// localStorage key naming the exchange to load market data from
+const EXCHANGE_STORAGE_KEY: &str = "price-chart.exchange";
 
-    view! {
-        <div class="header">
-            <h1>{move || format!("🌐 {} WebSocket Chart", current_symbol().get().value())}</h1>
-            <p>{move || format!("{} • Real-time Leptos + WebGPU", current_symbol().get().value())}</p>
+/// 🌐 Select the exchange saved under `price-chart.exchange`, Binance otherwise
+pub fn restore_exchange() {
+    let saved = local_storage().and_then(|s| s.get_item(EXCHANGE_STORAGE_KEY).ok().flatten());
+    let venue = saved.and_then(|name| name.parse::<Exchange>().ok()).unwrap_or_default();
+    exchange().set(venue);
+    // A restored interval the venue cannot stream would fail right away
+    current_interval().update(|interval| *interval = venue.supported_interval(*interval));
+}
 
-            <div class="price-info">
-                <div class="price-item">
-                    <div class="price-value">
-                        {move || format!("${:.2}", current_price.get())}
-                    </div>
-                    <div class="price-label">"Current Price"</div>
-                </div>
-                <div class="price-item">
-                    <div class="price-value">
-                        {move || candle_count.get().to_string()}
-                    </div>
-                    <div class="price-label">"Candles"</div>
-                </div>
-                <div class="price-item">
-                    <div class="price-value">
-                        {move || if is_streaming.get() { "🟢 LIVE" } else { "🔴 OFF" }}
-                    </div>
-                    <div class="price-label">"WebSocket"</div>
-                </div>
-                <div class="price-item">
-                    <div class="price-value">
-                        {move || format!("{:.2}", max_volume.get())}
-                    </div>
-                    <div class="price-label">"Max Volume"</div>
-                </div>
-                <div class="price-item">
-                    <div class="price-value">
-                        {move || format!("{:.1}x", zoom_level.get())}
-                    </div>
-                    <div class="price-label">"🔍 Zoom"</div>
-                </div>
-            </div>
-        </div>
+/// Apply a layout change, persist it and redraw
+fn update_panel_layout<R>(f: impl FnOnce(&mut PanelLayout) -> R) -> R {
+    let mut result = None;
+    panel_layout().update(|layout| result = Some(f(layout)));
+    if let (Some(storage), Ok(json)) =
+        (local_storage(), panel_layout().with_untracked(serde_json::to_string))
+    {
+        let _ = storage.set_item(PANEL_LAYOUT_STORAGE_KEY, &json);
     }
+    // The layout is not part of the geometry cache key
+    rebuild_current_chart();
+    result.expect("panel layout update closure not run")
 }
 
-#[component]
-fn PriceAxisLeft(chart: RwSignal<Chart>) -> impl IntoView {
-    let labels = move || {
-        let vp = chart.with(|c| c.viewport.clone());
-        price_levels(&vp)
-    };
+/// 🧱 Add an indicator sub-panel with relative height `weight` (main panel is 1.0)
+pub fn add_panel(weight: f32) -> PanelId {
+    update_panel_layout(|layout| layout.add_panel(weight))
+}
 
-    view! {
-        <div style="width: 60px; height: 500px; background: #222; display: flex; flex-direction: column; justify-content: space-between; align-items: flex-end; margin-right: 8px;">
-            <For
-                each=labels
-                key=|v| (*v * 100.0) as i64
-                children=|v| view! {
-                    <div style="font-size: 12px; color: #fff;">{format!("{:.2}", v)}</div>
-                }
-            />
-        </div>
-    }
+/// 🧱 Remove a sub-panel; its indicators return to the main panel
+pub fn remove_panel(id: PanelId) -> bool {
+    update_panel_layout(|layout| layout.remove_panel(id))
 }
 
-/// ⏰ Time scale below the chart
-#[component]
-fn TimeScale(chart: RwSignal<Chart>) -> impl IntoView {
-    let time_labels = move || {
-        let zoom = zoom_level().get_untracked();
-        let interval = current_interval().get_untracked();
-        let candles = chart.with(|c| c.get_series(interval).unwrap().get_candles().clone());
+/// 🧱 Change the relative height of a panel
+pub fn set_panel_weight(id: PanelId, weight: f32) -> bool {
+    update_panel_layout(|layout| layout.set_weight(id, weight))
+}
 
-        if candles.is_empty() {
-            return vec![];
-        }
+/// 🧱 Draw `indicator` in panel `id`
+pub fn assign_indicator_panel(indicator: &str, id: PanelId) -> bool {
+    update_panel_layout(|layout| layout.assign(indicator, id))
+}
 
-        let (start_idx, visible) = visible_range(candles.len(), zoom, pan_offset().get_untracked());
+/// 🧩 Run `f` on the WebGPU renderer and the canvas 2D fallback, whichever exist
+///
+/// Display settings go through this so they also reach the fallback; returns
+/// whether any renderer was available.
+fn with_active_renderers(mut f: impl FnMut(&mut dyn Renderer)) -> bool {
+    let gpu = with_global_renderer(|r| f(r)).is_some();
+    let fallback = with_fallback_renderer(|r| f(r)).is_some();
+    gpu || fallback
+}
 
-        // Show 5 time labels
-        let num_labels = 5;
-        let mut labels = Vec::new();
+/// Queue a full geometry refresh of the current symbol's chart
+fn redraw_current_chart() {
+    render_current_chart(false);
+}
 
-        for i in 0..num_labels {
-            let index = (i * visible) / (num_labels - 1);
-            if let Some(candle) =
-                candles.iter().skip(start_idx).nth(index.min(visible.saturating_sub(1)))
-            {
-                let timestamp = candle.timestamp.value();
-                let time_str = format_time_label(timestamp, zoom);
-                let position_percent = (i as f64 / (num_labels as f64 - 1.0)) * 100.0;
-                labels.push((time_str, position_percent));
-            }
-        }
+/// Queue a redraw that rebuilds the geometry even though the chart data is unchanged
+fn rebuild_current_chart() {
+    render_current_chart(true);
+}
 
-        labels
+fn render_current_chart(rebuild: bool) {
+    let render = move |r: &mut dyn Renderer| {
+        if rebuild {
+            r.geometry_mut().invalidate();
+        }
+        let target = RenderTargetComponent {
+            interval: current_interval().get_untracked(),
+            zoom_level: zoom_level().get_untracked(),
+            pan_offset: pan_offset().get_untracked(),
+        };
+        let _ = render_ecs_frame(&current_symbol().get_untracked(), target, r);
     };
-
-    view! {
-        <div style="width: 800px; height: 30px; background: #222; display: flex; align-items: center; justify-content: space-between; padding: 0 10px; margin-top: 5px; border-radius: 5px;">
-            <For
-                each=time_labels
-                key=|(time, _pos)| time.clone()
-                children=|(time, _position)| view! {
-                    <div style="font-size: 11px; color: #888;">
-                        {time}
-                    </div>
-                }
-            />
-        </div>
+    // The canvas 2D fallback draws synchronously, without the render queue
+    if with_fallback_renderer(|r| render(r)).is_none() {
+        enqueue_render_task(Box::new(move |r| render(r)));
     }
 }
 
-/// 🎨 Container for the WebGPU chart
-#[component]
-fn ChartContainer() -> impl IntoView {
-    ensure_chart(&current_symbol().get_untracked());
-    create_effect(move |_| {
-        let sym = current_symbol().get();
-        ensure_chart(&sym);
-    });
-    let chart_memo = create_memo(move |_| {
-        let sym = current_symbol().get();
-        global_charts().with(|m| m.get(&sym).copied().unwrap())
-    });
-    let chart = move || chart_memo.get();
-    let (_renderer, set_renderer) = create_signal::<Option<Rc<RefCell<WebGpuRenderer>>>>(None);
-    let (status, set_status) = create_signal("Initializing...".to_string());
+/// 💥 Replace the global renderer after its GPU device was lost
+///
+/// The new renderer takes over the old geometry builder, so zoom, pan, theme
+/// and chart data survive; the lost one no-ops until it is dropped.
+async fn recover_renderer(canvas_id: String, set_status: WriteSignal<String>) {
+    let (css_width, css_height) = canvas_size().get_untracked();
+    let (width, height) = backing_size(css_width, css_height, device_pixel_ratio());
+    match WebGpuRenderer::new(&canvas_id, width, height).await {
+        Ok(mut renderer) => {
+            with_global_renderer(|lost| renderer.adopt_geometry(lost));
+            msaa_samples().set(renderer.set_msaa_sample_count(msaa_samples().get_untracked()));
+            set_global_renderer(Rc::new(RefCell::new(renderer)));
+            redraw_current_chart();
+            get_logger().info(
+                LogComponent::Infrastructure("WebGPU"),
+                "✅ Renderer rebuilt after device loss",
+            );
+            set_status.set("✅ WebGPU renderer recovered".to_string());
+        }
+        Err(e) => {
+            get_logger().error(
+                LogComponent::Infrastructure("WebGPU"),
+                &format!("❌ Renderer recovery failed: {e}"),
+            );
+            set_status.set(format!("❌ WebGPU recovery failed: {e}"));
+        }
+    }
+}
 
-    // Reference to the canvas element
-    let canvas_ref = create_node_ref::<Canvas>();
-    let (initialized, set_initialized) = create_signal(false);
+/// Keep the restored settings instead of the renderer defaults
+fn apply_restored_settings(geometry: &mut ChartGeometryBuilder, dpr: f64) {
+    geometry.set_pixel_ratio(dpr as f32);
+    geometry.set_line_visibility(global_line_visibility().get_untracked());
+    geometry.set_theme(theme_preset().get_untracked().palette());
+    geometry.set_min_body_height(min_body_height().get_untracked());
+    geometry.set_empty_state(empty_chart_state(
+        &connection_state().get_untracked(),
+        load_failed().get_untracked(),
+    ));
+    geometry.set_animate_transitions(animate_transitions().get_untracked());
+    geometry.set_x_axis_mode(x_axis_mode().get_untracked());
+}
 
-    // Initialize WebGPU once the canvas is available
-    create_effect(move |_| {
-        if initialized.get() {
-            return;
-        }
+/// 🎞️ Coalesce redraw requests into the next animation frame
+///
+/// Bursts of wheel events or WebSocket ticks between two frames produce a
+/// single render, so redraws stay at the display rate (~60fps).
+pub fn schedule_frame_render() {
+    if FRAME_RENDER_PENDING.with(|p| p.replace(true)) {
+        return;
+    }
+    let render = || {
+        FRAME_RENDER_PENDING.with(|p| p.set(false));
+        redraw_current_chart();
+    };
+    let Some(window) = web_sys::window() else {
+        render();
+        return;
+    };
+    let callback = Closure::once_into_js(render);
+    if window.request_animation_frame(callback.unchecked_ref()).is_err() {
+        FRAME_RENDER_PENDING.with(|p| p.set(false));
+        redraw_current_chart();
+    }
+}
 
-        if let Some(canvas) = canvas_ref.get() {
-            let canvas_id = std::ops::Deref::deref(&canvas).id();
-            set_initialized.set(true);
-            let _ = spawn_local_with_current_owner(async move {
-                web_sys::console::log_1(&"🔍 Canvas found, starting WebGPU init...".into());
-                set_status.set("🚀 Initializing WebGPU renderer...".to_string());
+thread_local! {
+    static FRAME_RENDER_PENDING: Cell<bool> = const { Cell::new(false) };
+    static PAN_VELOCITY: RefCell<PanVelocityTracker> = RefCell::new(PanVelocityTracker::default());
+    static PAUSED_CANDLES: RefCell<PausedCandles> = RefCell::new(PausedCandles::default());
+    static REPORTED_CONNECTION: RefCell<ConnectionState> = const { RefCell::new(ConnectionState::Offline) };
+    static MINIMAP_RENDERER: RefCell<Option<WebGpuRenderer>> = const { RefCell::new(None) };
+    static DEPTH_STREAM: RefCell<Option<futures::future::AbortHandle>> = const { RefCell::new(None) };
+}
 
-                // Detailed WebGPU diagnostics
-                web_sys::console::log_1(&"🏗️ Creating WebGPU renderer...".into());
-                get_logger().info(
-                    LogComponent::Infrastructure("WebGPU"),
-                    "🔍 Starting WebGPU initialization...",
+/// 📚 Stream the order book of `symbol` into the depth histogram, replacing any running one
+///
+/// Only Binance publishes depth here; other exchanges leave the histogram empty.
+pub fn start_depth_stream(symbol: Symbol) {
+    stop_depth_stream();
+    if exchange().get_untracked() != Exchange::Binance {
+        return;
+    }
+    let (handle, registration) = futures::future::AbortHandle::new_pair();
+    DEPTH_STREAM.with(|s| *s.borrow_mut() = Some(handle));
+    let stream = futures::future::Abortable::new(
+        async move {
+            let mut client = BinanceWebSocketClient::new(symbol, TimeInterval::OneMinute);
+            let handler = |book: &OrderBook| {
+                with_active_renderers(|r| r.geometry_mut().set_order_book(Some(book.clone())));
+                schedule_frame_render();
+            };
+            if let Err(e) = client.start_depth_stream(DEFAULT_DEPTH_LEVELS, handler).await {
+                get_logger().error(
+                    LogComponent::Presentation("DepthStream"),
+                    &format!("❌ Depth stream stopped: {e}"),
                 );
+            }
+        },
+        registration,
+    );
+    let _ = spawn_local_with_current_owner(async move {
+        let _ = stream.await;
+    });
+}
 
-                web_sys::console::log_1(&"⚡ About to call WebGpuRenderer::new...".into());
+/// Abort the depth stream and clear the histogram
+pub fn stop_depth_stream() {
+    if let Some(handle) = DEPTH_STREAM.with(|s| s.borrow_mut().take()) {
+        handle.abort();
+    }
+    with_active_renderers(|r| r.geometry_mut().set_order_book(None));
+}
 
-                match WebGpuRenderer::new(canvas_id.as_str(), 800, 500).await {
-                    Ok(webgpu_renderer) => {
-                        get_logger().info(
-                            LogComponent::Infrastructure("WebGPU"),
-                            "✅ WebGPU renderer created successfully",
-                        );
+/// Canvas element of the overview strip
+const MINIMAP_CANVAS_ID: &str = "minimap-canvas";
+/// Height of the overview strip in pixels
+const MINIMAP_HEIGHT: f64 = 40.0;
 
-                        let renderer_rc = Rc::new(RefCell::new(webgpu_renderer));
-                        set_renderer.set(Some(renderer_rc.clone()));
-                        set_global_renderer(renderer_rc.clone());
-                        init_render_queue();
-                        let _ = renderer_rc.borrow().log_gpu_memory_usage();
-                        set_status.set("✅ WebGPU renderer ready".to_string());
+/// Number of candles loaded for the current symbol and interval
+fn current_candle_len() -> usize {
+    ensure_chart(&current_symbol().get_untracked()).with_untracked(|ch| {
+        ch.get_series(current_interval().get_untracked()).map_or(0, |s| s.get_candles().len())
+    })
+}
 
-                        // Start WebSocket after the renderer is initialized
-                        get_logger().info(
-                            LogComponent::Infrastructure("WebSocket"),
-                            "🌐 Starting WebSocket stream...",
-                        );
-                        start_websocket_stream(set_status).await;
-                    }
-                    Err(e) => {
-                        let msg = e.as_string().unwrap_or_else(|| format!("{e:?}"));
-                        web_sys::console::error_1(
-                            &format!("❌ WebGPU initialization error: {msg}").into(),
-                        );
-                        get_logger().error(
-                            LogComponent::Infrastructure("WebGPU"),
-                            &format!("❌ WebGPU initialization failed: {msg}"),
-                        );
-                        set_status.set(format!(
-                            "❌ WebGPU failed: {msg}\n💡 Try Chrome Canary with --enable-unsafe-webgpu flag",
-                        ));
+/// 🗺️ Redraw the overview strip with the close line of the whole loaded range
+fn redraw_minimap() {
+    let interval = current_interval().get_untracked();
+    let closes: Vec<f64> = ensure_chart(&current_symbol().get_untracked()).with_untracked(|ch| {
+        ch.get_series(interval)
+            .map(|s| s.get_candles().iter().map(|c| c.ohlcv.close.value()).collect())
+            .unwrap_or_default()
+    });
+    MINIMAP_RENDERER.with(|cell| {
+        if let Some(r) = cell.borrow_mut().as_mut() {
+            let _ = r.render(&crate::sparkline_chart(MINIMAP_CANVAS_ID, &closes, interval));
+        }
+    });
+}
 
-                        // Fallback: show data even without the chart
-                        get_logger().info(
-                            LogComponent::Infrastructure("Fallback"),
-                            "🔄 Starting fallback mode without WebGPU...",
-                        );
+/// Bumped on every interaction; a running pan animation stops when it changes
+static PAN_ANIMATION_GENERATION: AtomicU32 = AtomicU32::new(0);
 
-                        // Generate sample data for demo purposes
-                        let mut test_candles = Vec::new();
-                        let base_price = 90000.0;
-                        let base_time = js_sys::Date::now() as u64;
+/// 🛑 Stop any running pan animation (inertia or scroll to latest)
+fn cancel_pan_animation() {
+    PAN_ANIMATION_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
 
-                        for i in 0..50 {
-                            let price_variation = (i as f64 * 0.1).sin() * 1000.0;
-                            let open = base_price + price_variation;
-                            let close = open + (i as f64 % 3.0 - 1.0) * 200.0;
-                            let high = open.max(close) + 100.0;
-                            let low = open.min(close) - 100.0;
-                            let volume = 100.0 + (i as f64 * 0.2).cos() * 50.0;
+/// 🔍 Zoom `chart` by a wheel-equivalent `delta` keeping the candle under `cursor_x` (NDC) in place
+fn zoom_view_at(
+    chart: RwSignal<Chart>,
+    delta: f64,
+    cursor_x: f32,
+    set_status: WriteSignal<String>,
+) {
+    let old_zoom = zoom_level().get_untracked();
+    let old_pan = pan_offset().get_untracked();
+    let (new_zoom, new_pan) = with_global_renderer(|r| {
+        r.geometry_mut().set_zoom_params(old_zoom, old_pan);
+        r.geometry_mut().zoom_at(delta, cursor_x)
+    })
+    .unwrap_or_else(|| zoom_at_cursor(old_zoom, old_pan, delta, cursor_x));
+    zoom_level().set(new_zoom);
+    pan_offset().set(new_pan);
+
+    let applied_factor = (new_zoom / old_zoom) as f32;
+    let center_x = (cursor_x + 1.0) / 2.0;
+    chart.update(|ch| {
+        ch.zoom(applied_factor, center_x);
+        ch.pan(center_x - 0.5, 0.0);
+    });
+    let symbol = current_symbol().get_untracked();
+    chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
+    update_auto_scroll();
+    web_sys::console::log_1(&format!("🔍 Zoom: {:.2}x -> {:.2}x", old_zoom, new_zoom).into());
+
+    // Rapid wheel and pinch events share one queued redraw
+    schedule_frame_render();
+    get_logger().info(
+        LogComponent::Presentation("ChartZoom"),
+        &format!("🔍 Zoom level: {:.2}x", zoom_level().with_untracked(|z_val| *z_val)),
+    );
+    let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
+    if need_history {
+        fetch_more_history(set_status);
+    }
+}
 
-                            let candle = Candle::new(
-                                crate::domain::market_data::Timestamp::from(base_time + i * 60000),
-                                crate::domain::market_data::OHLCV::new(
-                                    crate::domain::market_data::Price::from(open),
-                                    crate::domain::market_data::Price::from(high),
-                                    crate::domain::market_data::Price::from(low),
-                                    crate::domain::market_data::Price::from(close),
-                                    crate::domain::market_data::Volume::from(volume),
-                                ),
-                            );
-                            test_candles.push(candle);
-                        }
+/// 🎯 Show the tooltip at (`x`, `y`) for the candle under `ndc_x`, or hide it
+fn show_tooltip_at(chart: RwSignal<Chart>, ndc_x: f64, x: f64, y: f64) {
+    chart.with_untracked(|ch| {
+        let interval = current_interval().get_untracked();
+        let candles = ch.get_series(interval).map(|s| s.get_candles());
+        let Some(candles) = candles.filter(|c| !c.is_empty()) else {
+            tooltip_visible().set(false);
+            return;
+        };
+        let (start_idx, visible_count) = visible_range(
+            candles.len(),
+            zoom_level().get_untracked(),
+            pan_offset().get_untracked(),
+        );
 
-                        chart().update(|ch| ch.set_historical_data(test_candles));
-                        let symbol = current_symbol().get_untracked();
-                        chart().with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-                        set_status.set(format!(
-                            "🎯 Demo mode: Using test data (WebSocket disabled)\nReason: {msg}",
-                        ));
-                    }
-                }
-            });
+        // Same window and x axis as the renderer, clamped at the edges
+        let visible: Vec<Candle> =
+            candles.iter().skip(start_idx).take(visible_count).cloned().collect();
+        let x_axis = XAxis::new(x_axis_mode().get_untracked(), &visible, interval);
+        match x_axis.index_at(ndc_x).and_then(|idx| visible.get(idx)) {
+            Some(candle) => {
+                tooltip_data().set(Some(TooltipData::new(candle.clone(), x, y)));
+                tooltip_visible().set(true);
+            }
+            None => tooltip_visible().set(false),
         }
     });
+}
 
-    // 🎯 Mouse events for the tooltip
-    let handle_mouse_move = {
-        let chart_signal = chart;
-        let status_clone = set_status;
-        move |event: web_sys::MouseEvent| {
-            let mouse_x = event.offset_x() as f64;
-            let mouse_y = event.offset_y() as f64;
+/// 🖐️ Pan the current chart by a pointer movement of `delta_x` pixels
+fn apply_pan(delta_x: f64, set_status: WriteSignal<String>) {
+    shift_view(delta_x);
 
-            // 🔍 Handle panning
-            let dragging = is_dragging().get_untracked();
-            if dragging {
-                let last_x = last_mouse_x().get_untracked();
-                let delta_x = mouse_x - last_x;
-                pan_offset().update(|o| {
-                    let zoom = zoom_level().with_untracked(|val| *val);
-                    let pan_sensitivity = PAN_SENSITIVITY_BASE / zoom;
-                    *o -= delta_x * pan_sensitivity;
-                });
-                chart_signal().update(|ch| {
-                    let factor_x = -(delta_x as f32) / ch.viewport.width as f32;
-                    ch.pan(factor_x, 0.0);
-                });
-                let symbol = current_symbol().get_untracked();
-                chart_signal().with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-                last_mouse_x().set(mouse_x);
+    let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
+    if need_history {
+        fetch_more_history(set_status);
+    }
 
-                let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
-                if need_history {
-                    fetch_more_history(status_clone);
-                }
+    redraw_current_chart();
+}
 
-                enqueue_render_task(Box::new(|r| {
-                    let chart_signal = ensure_chart(&current_symbol().get_untracked());
-                    chart_signal.with_untracked(|ch| {
-                        if ch.get_candle_count() > 0 {
-                            r.set_zoom_params(
-                                zoom_level().with_untracked(|val| *val),
-                                pan_offset().with_untracked(|val| *val),
-                            );
-                            let _ = r.render(ch);
-                        }
-                    });
-                }));
-            } else {
-                // Convert to NDC coordinates (assuming an 800x500 canvas)
-                let canvas_width = 800.0;
-                let canvas_height = 500.0;
-                let ndc_x = (mouse_x / canvas_width) * 2.0 - 1.0;
-                let _ndc_y = 1.0 - (mouse_y / canvas_height) * 2.0;
+/// Move the pan offset and chart viewport by `delta_x` pixels without redrawing
+fn shift_view(delta_x: f64) {
+    let chart_signal = ensure_chart(&current_symbol().get_untracked());
+    pan_offset().update(|o| {
+        let zoom = zoom_level().with_untracked(|val| *val);
+        let pan_sensitivity = PAN_SENSITIVITY_BASE / zoom;
+        *o -= delta_x * pan_sensitivity;
+    });
+    chart_signal.update(|ch| {
+        let factor_x = -(delta_x as f32) / ch.viewport.width as f32;
+        ch.pan(factor_x, 0.0);
+    });
+    let symbol = current_symbol().get_untracked();
+    chart_signal.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
 
-                chart_signal().with_untracked(|ch| {
-                    let interval = current_interval().get_untracked();
-                    let candles = ch.get_series(interval).unwrap().get_candles();
-                    if !candles.is_empty() {
-                        let (start_idx, visible_count) = visible_range(
-                            candles.len(),
-                            zoom_level().get_untracked(),
-                            pan_offset().get_untracked(),
-                        );
-                        let visible: Vec<_> =
-                            candles.iter().skip(start_idx).take(visible_count).collect();
-
-                        // Use the same logic as in candle_x_position
-                        let step_size = 2.0 / visible.len() as f64;
-                        let spacing = spacing_ratio_for(visible.len()) as f64;
-                        let width = (step_size * (1.0 - spacing))
-                            .clamp(MIN_ELEMENT_WIDTH as f64, MAX_ELEMENT_WIDTH as f64);
-                        let half_width = width / 2.0;
-                        // Inverse formula matching candle_x_position
-                        // index = visible_len - 1 - (1.0 - EDGE_GAP as f64 - half_width - ndc_x) / step_size
-                        let index_float = visible.len() as f64
-                            - 1.0
-                            - (1.0 - EDGE_GAP as f64 - half_width - ndc_x) / step_size;
-                        let candle_idx = index_float.round() as i32;
-
-                        if candle_idx >= 0 && (candle_idx as usize) < visible.len() {
-                            let candle = visible[candle_idx as usize];
-                            let data = TooltipData::new(candle.clone(), mouse_x, mouse_y);
-
-                            tooltip_data().set(Some(data));
-                            tooltip_visible().set(true);
-                        } else {
-                            tooltip_visible().set(false);
-                        }
-                    } else {
-                        tooltip_visible().set(false);
-                    }
-                });
-            }
-        }
-    };
+    update_auto_scroll();
+}
 
-    let handle_mouse_leave = move |_event: web_sys::MouseEvent| {
-        tooltip_visible().set(false);
-        is_dragging().set(false);
-    };
+/// 📌 Enable auto-scroll only while the view touches the latest candle
+fn update_auto_scroll() {
+    let at_edge = should_auto_scroll(
+        current_candle_len(),
+        zoom_level().get_untracked(),
+        pan_offset().get_untracked(),
+    );
+    if auto_scroll().get_untracked() != at_edge {
+        auto_scroll().set(at_edge);
+    }
+}
 
-    // 🔍 Mouse wheel zoom - simplified without effects
-    let handle_wheel = {
-        let chart_signal = chart;
-        let status_clone = set_status;
-        move |event: web_sys::WheelEvent| {
-            if chart_signal().try_get_untracked().is_none() {
-                return;
-            }
-            web_sys::console::log_1(&format!("🖱️ Wheel event: delta_y={}", event.delta_y()).into());
-            event.prevent_default();
+/// ⏭️ Jump back to the newest candle and resume auto-scroll
+pub fn scroll_to_latest() {
+    cancel_pan_animation();
+    pan_offset().set(0.0);
+    auto_scroll().set(true);
+    let symbol = current_symbol().get_untracked();
+    let chart_signal = ensure_chart(&symbol);
+    chart_signal.update(|ch| ch.recompute_viewport());
+    chart_signal.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
+    redraw_current_chart();
+}
 
-            let delta_y = event.delta_y();
-            let delta_zoom = if delta_y < 0.0 { 0.2 } else { -0.2 }; // constant step
-
-            let old_zoom = zoom_level().with_untracked(|z| *z);
-            let new_zoom = (old_zoom + delta_zoom).clamp(MIN_ZOOM_LEVEL, MAX_ZOOM_LEVEL);
-            zoom_level().set(new_zoom);
-            let applied_factor = (new_zoom / old_zoom) as f32;
-            let center_x = event.offset_x() as f32 / 800.0;
-            let pan_diff = center_x - 0.5;
-            chart_signal().update(|ch| {
-                ch.zoom(applied_factor, center_x);
-                ch.pan(pan_diff, 0.0);
-            });
-            let symbol = current_symbol().get_untracked();
-            chart_signal().with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-            pan_offset().update(|o| {
-                let zoom = zoom_level().with_untracked(|val| *val);
-                let pan_sensitivity = PAN_SENSITIVITY_BASE / zoom;
-                *o -= pan_diff as f64 * CHART_WIDTH * pan_sensitivity;
-            });
-            web_sys::console::log_1(
-                &format!("🔍 Zoom: {:.2}x -> {:.2}x", old_zoom, new_zoom).into(),
-            );
+/// 🎛️ Check that the renderer exists, warning that `action` is skipped otherwise
+fn view_controls_ready(action: &str) -> bool {
+    let ready = with_global_renderer(|_| ()).is_some();
+    if !ready {
+        get_logger().warn(
+            LogComponent::Presentation("ViewControls"),
+            &format!("⚠️ {action} ignored: renderer not initialized"),
+        );
+    }
+    ready
+}
 
-            // Apply zoom immediately without effects
-            chart_signal().with_untracked(|ch| {
-                if ch.get_candle_count() > 0
-                    && with_global_renderer(|r| {
-                        r.set_zoom_params(new_zoom, pan_offset().with_untracked(|val| *val));
-                        let _ = r.render(ch);
-                        get_logger().info(
-                            LogComponent::Infrastructure("ZoomWheel"),
-                            &format!("✅ Applied zoom {:.2}x to WebGPU renderer", new_zoom),
-                        );
-                    })
-                    .is_none()
-                {
-                    // renderer not available
-                }
-            });
-            get_logger().info(
-                LogComponent::Presentation("ChartZoom"),
-                &format!("🔍 Zoom level: {:.2}x", zoom_level().with_untracked(|z_val| *z_val)),
-            );
-            let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
-            if need_history {
-                fetch_more_history(status_clone);
-            }
+/// 🔍 Set the zoom level (clamped to the allowed range) and redraw
+///
+/// Does nothing but log a warning before the renderer is initialized.
+pub fn set_zoom(level: f64) {
+    if !view_controls_ready("set_zoom") {
+        return;
+    }
+    cancel_pan_animation();
+    zoom_level().set(clamp_zoom(level));
+    update_auto_scroll();
+    redraw_current_chart();
+}
+
+/// 🖐️ Pan as if the pointer moved `delta_px` pixels (positive reveals older candles)
+///
+/// Does nothing but log a warning before the renderer is initialized.
+pub fn pan(delta_px: f64) {
+    if !view_controls_ready("pan") {
+        return;
+    }
+    cancel_pan_animation();
+    if !current_pan_at_boundary(delta_px) {
+        shift_view(delta_px);
+        redraw_current_chart();
+    }
+}
+
+/// ⏮️ Restore the default zoom, jump back to the newest candle and refit prices
+///
+/// Also hides the tooltip. Does nothing but log a warning before the
+/// renderer is initialized.
+pub fn reset_view() {
+    if !view_controls_ready("reset_view") {
+        return;
+    }
+    with_global_renderer(|r| r.reset_view());
+    tooltip_visible().set(false);
+    zoom_level().set(DEFAULT_ZOOM_LEVEL);
+    scroll_to_latest();
+}
+
+/// 🗄️ Retain up to `limit` candles per interval in every chart
+///
+/// Raise it to scroll further back than the default; see
+/// `Chart::set_max_candles` for the memory cost. Lowering it drops the
+/// oldest candles right away.
+pub fn set_history_limit(limit: usize) {
+    let limit = limit.max(MAX_ZOOMED_OUT_CANDLES as usize);
+    history_limit().set(limit);
+    global_charts().with_untracked(|charts| {
+        for (symbol, chart) in charts {
+            chart.update(|c| c.set_max_candles(limit));
+            chart.with_untracked(|c| set_chart_in_ecs(symbol, c.clone()));
         }
-    };
+    });
+    let count =
+        ensure_chart(&current_symbol().get_untracked()).with_untracked(|c| c.get_candle_count());
+    global_candle_count().set(count);
+    redraw_current_chart();
+}
 
-    // 🖱️ Start panning
-    let handle_mouse_down = move |event: web_sys::MouseEvent| {
-        if event.button() == 0 {
-            // Left mouse button
-            web_sys::console::log_1(&"🖱️ Mouse down".into());
-            is_dragging().set(true);
-            last_mouse_x().set(event.offset_x() as f64);
+/// 📜 Candles the first history load requests for `depth` at `interval`
+///
+/// Never more than the chart retains (`set_history_limit`).
+pub fn history_load_count(depth: HistoryDepth, interval: TimeInterval) -> u32 {
+    let retained = u32::try_from(history_limit().get_untracked()).unwrap_or(u32::MAX);
+    depth.candle_count(interval).min(retained)
+}
 
-            // Give the canvas focus for keyboard events
-            if let Some(target) = event.target() {
-                if let Ok(canvas) = target.dyn_into::<web_sys::HtmlCanvasElement>() {
-                    let _ = canvas.focus();
-                }
-            }
+/// 📜 Load `depth` of history when a chart opens; applies from the next load
+///
+/// The choice is saved with the settings.
+pub fn set_history_depth(depth: HistoryDepth) {
+    history_depth().set(depth);
+}
+
+/// ↕️ Switch the price scale to manual, starting from the range on screen
+///
+/// While auto-fit is on the viewport prices may be stale, so they are
+/// replaced by the range of the last frame before the user adjusts them.
+fn take_manual_price_control(chart: RwSignal<Chart>) {
+    with_global_renderer(|r| {
+        let geometry = r.geometry_mut();
+        if !geometry.auto_fit_y() {
+            return;
         }
-    };
+        let [_, _, min_price, max_price] = geometry.cached_uniforms().viewport;
+        geometry.set_auto_fit_y(false);
+        if max_price > min_price {
+            chart.update(|c| {
+                c.viewport.min_price = min_price;
+                c.viewport.max_price = max_price;
+            });
+        }
+    });
+}
 
-    // 🖱️ End panning
-    let handle_mouse_up = move |_event: web_sys::MouseEvent| {
-        web_sys::console::log_1(&"🖱️ Mouse up".into());
-        is_dragging().set(false);
+/// Apply `adjust` to the current chart's viewport under manual price control and redraw
+fn adjust_price_view(adjust: impl FnOnce(&mut Chart)) {
+    let symbol = current_symbol().get_untracked();
+    let chart = ensure_chart(&symbol);
+    take_manual_price_control(chart);
+    chart.update(adjust);
+    chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
+    redraw_current_chart();
+}
+
+/// ↕️ Move the price scale as if dragged down by `delta_px` pixels
+///
+/// Turns price auto-fit off until `reset_price_view`. Does nothing but log
+/// a warning before the renderer is initialized.
+pub fn pan_price(delta_px: f64) {
+    if !view_controls_ready("pan_price") {
+        return;
+    }
+    let height = canvas_size().get_untracked().1.max(1.0);
+    adjust_price_view(|c| c.viewport.pan(0.0, (delta_px / height) as f32));
+}
+
+/// 🔍 Stretch the price scale by `factor` around `center_y` (0 top, 1 bottom)
+///
+/// Turns price auto-fit off until `reset_price_view`. Does nothing but log
+/// a warning before the renderer is initialized.
+pub fn zoom_price(factor: f64, center_y: f64) {
+    if !(view_controls_ready("zoom_price") && factor.is_finite() && factor > 0.0) {
+        return;
+    }
+    adjust_price_view(|c| c.zoom_price(factor as f32, center_y.clamp(0.0, 1.0) as f32));
+}
+
+/// 📐 Fit the price scale to the visible candles again
+///
+/// Does nothing but log a warning before the renderer is initialized.
+pub fn reset_price_view() {
+    if !view_controls_ready("reset_price_view") {
+        return;
+    }
+    with_active_renderers(|r| r.geometry_mut().set_auto_fit_y(true));
+    redraw_current_chart();
+}
+
+/// Check if the current chart cannot pan any further by `delta_x`
+fn current_pan_at_boundary(delta_x: f64) -> bool {
+    let len = current_candle_len();
+    pan_at_boundary(len, zoom_level().get_untracked(), pan_offset().get_untracked(), delta_x)
+}
+
+/// Self-rescheduling `requestAnimationFrame` callback
+type FrameCallback = Closure<dyn FnMut(f64)>;
+
+/// 🎞️ Call `step` with the frame time delta (ms) every animation frame
+///
+/// The animation ends when `step` returns `false` or any interaction calls
+/// `cancel_pan_animation`; starting a new one cancels the previous.
+fn run_pan_animation(mut step: impl FnMut(f64) -> bool + 'static) {
+    let generation = PAN_ANIMATION_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let Some(window) = web_sys::window() else {
+        return;
     };
 
-    // ⌨️ Zoom keys (+/- and PageUp/PageDown)
-    let handle_keydown = {
-        let chart_signal = chart;
-        let status_clone = set_status;
-        move |event: web_sys::KeyboardEvent| {
-            let key = event.key();
-            let mut zoom_changed = false;
+    let frame: Rc<RefCell<Option<FrameCallback>>> = Rc::new(RefCell::new(None));
+    let handle = frame.clone();
+    let mut last_ts: Option<f64> = None;
+    *handle.borrow_mut() = Some(Closure::new(move |ts: f64| {
+        if PAN_ANIMATION_GENERATION.load(Ordering::Relaxed) != generation {
+            let _ = frame.borrow_mut().take();
+            return;
+        }
+        let dt = last_ts.map_or(16.0, |last| ts - last);
+        last_ts = Some(ts);
+        if !step(dt) {
+            let _ = frame.borrow_mut().take();
+            return;
+        }
+        if let (Some(window), Some(cb)) = (web_sys::window(), frame.borrow().as_ref()) {
+            let _ = window.request_animation_frame(cb.as_ref().unchecked_ref());
+        }
+    }));
 
-            match key.as_str() {
-                "+" | "=" => {
-                    event.prevent_default();
-                    zoom_level().update(|z| {
-                        *z *= 1.2;
-                        *z = z.min(MAX_ZOOM_LEVEL);
-                    });
-                    zoom_changed = true;
-                }
-                "-" | "_" => {
-                    event.prevent_default();
-                    zoom_level().update(|z| {
-                        *z *= 0.8;
-                        *z = z.max(MIN_ZOOM_LEVEL);
-                    });
-                    zoom_changed = true;
-                }
-                "PageUp" => {
-                    event.prevent_default();
-                    zoom_level().update(|z| {
-                        *z *= 1.5;
-                        *z = z.min(MAX_ZOOM_LEVEL);
-                    });
-                    zoom_changed = true;
-                }
-                "PageDown" => {
-                    event.prevent_default();
-                    zoom_level().update(|z| {
-                        *z *= 0.67;
-                        *z = z.max(MIN_ZOOM_LEVEL);
-                    });
-                    zoom_changed = true;
-                }
-                _ => {}
-            }
+    if let Some(cb) = handle.borrow().as_ref() {
+        let _ = window.request_animation_frame(cb.as_ref().unchecked_ref());
+    }
+}
 
-            if zoom_changed {
-                let new_zoom = zoom_level().with_untracked(|z_val| *z_val);
-                web_sys::console::log_1(&format!("⌨️ Keyboard zoom: {:.2}x", new_zoom).into());
+/// 🌀 Keep panning after a flick with velocity decaying every animation frame
+fn start_pan_inertia(velocity: f64, set_status: WriteSignal<String>) {
+    let mut velocity = velocity;
+    run_pan_animation(move |dt| {
+        let (delta_x, next) = inertia_step(velocity, dt);
+        velocity = next;
+        if delta_x == 0.0 || current_pan_at_boundary(delta_x) {
+            return false;
+        }
+        apply_pan(delta_x, set_status);
+        true
+    });
+}
 
-                // Apply zoom to the renderer for keyboard commands
-                chart_signal().with_untracked(|ch| {
-                    if ch.get_candle_count() > 0
+/// ⏭️ Glide back to the newest candle, then resume auto-scroll
+fn animate_to_latest(set_status: WriteSignal<String>) {
+    run_pan_animation(move |dt| {
+        let pan = pan_offset().get_untracked();
+        let next = ease_toward_latest(pan, dt);
+        if next == 0.0 {
+            scroll_to_latest();
+            return false;
+        }
+        let zoom = zoom_level().get_untracked();
+        apply_pan(-(next - pan) * zoom / PAN_SENSITIVITY_BASE, set_status);
+        true
+    });
+}
+
+/// 📈 Fetch additional history and prepend it to the list
+fn fetch_more_history(set_status: WriteSignal<String>) {
+    if loading_more().get() {
+        return;
+    }
+
+    let chart = ensure_chart(&current_symbol().get_untracked());
+    let oldest_ts = chart.with(|c| {
+        c.get_series(current_interval().get_untracked())
+            .and_then(|s| s.get_candles().front())
+            .map(|c| c.timestamp.value())
+    });
+    let end_time = match oldest_ts {
+        Some(ts) if ts > 0 => ts - 1,
+        _ => return,
+    };
+
+    loading_more().set(true);
+
+    let symbol = current_symbol().get_untracked();
+    let _ = spawn_local_with_current_owner(async move {
+        let interval = current_interval().get_untracked();
+        let source = exchange().get_untracked().data_source(&symbol, interval);
+        let visible = chart.with(|c| {
+            let len = c.get_candle_count();
+            visible_range(len, zoom_level().get_untracked(), pan_offset().get_untracked()).1
+        });
+        let limit = (visible + HISTORY_BUFFER_SIZE) as u32;
+        let result = source.fetch_historical_before(&symbol, interval, end_time, limit).await;
+        match result {
+            Ok(mut new_candles) => {
+                new_candles.sort_by(|a, b| a.timestamp.value().cmp(&b.timestamp.value()));
+                chart.update(|ch| {
+                    for candle in new_candles.iter() {
+                        ch.add_candle(candle.clone());
+                    }
+                });
+                chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
+                chart.with_untracked(|c| {
+                    if c.get_candle_count() > 0
                         && with_global_renderer(|r| {
-                            r.set_zoom_params(new_zoom, pan_offset().with_untracked(|val| *val));
-                            let _ = r.render(ch);
-                            get_logger().info(
-                                LogComponent::Infrastructure("KeyboardZoom"),
-                                &format!(
-                                    "⌨️ Applied keyboard zoom {:.2}x to WebGPU renderer",
-                                    new_zoom
-                                ),
+                            r.geometry_mut().set_zoom_params(
+                                zoom_level().with_untracked(|z| *z),
+                                pan_offset().with_untracked(|p| *p),
                             );
+                            let _ = r.render(c);
                         })
                         .is_none()
                     {
@@ -865,206 +1496,1835 @@ fn ChartContainer() -> impl IntoView {
                     }
                 });
 
-                get_logger().info(
-                    LogComponent::Presentation("KeyboardZoom"),
-                    &format!("⌨️ Zoom level: {:.2}x", new_zoom),
-                );
-                let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
-                if need_history {
-                    fetch_more_history(status_clone);
+                let new_count = chart.with(|c| c.get_candle_count());
+                let max_volume = chart.with(|c| {
+                    c.get_series(current_interval().get_untracked())
+                        .unwrap()
+                        .get_candles()
+                        .iter()
+                        .map(|c| c.ohlcv.volume.value())
+                        .fold(0.0f64, |a, b| a.max(b))
+                });
+                global_candle_count().set(new_count);
+                global_max_volume().set(max_volume);
+
+                set_status.set(format!("📈 Loaded {} older candles", new_candles.len()));
+            }
+            Err(e) => set_status.set(format!("❌ Failed to load more data: {}", e)),
+        }
+
+        loading_more().set(false);
+    });
+}
+
+/// 🎯 CSS pixel position of a visible candle's close
+///
+/// `viewport` is the price range actually drawn (see `displayed_price_viewport`)
+/// and `backing` the canvas size in device pixels at `dpr`, so the point lands
+/// on the rendered candle at any zoom and screen density.
+pub fn candle_screen_position(
+    index: usize,
+    visible: &[Candle],
+    x_axis: &XAxis,
+    viewport: &crate::domain::chart::value_objects::Viewport,
+    backing: (u32, u32),
+    dpr: f64,
+) -> (f64, f64) {
+    let ndc_x = x_axis.x(index) as f64;
+    let (min, max) = ensure_price_span(viewport.min_price, viewport.max_price);
+    let ndc_y = (visible[index].ohlcv.close.value() - min as f64) / (max - min) as f64 * 2.0 - 1.0;
+
+    let dpr = dpr.max(f64::EPSILON);
+    let (width, height) = (backing.0 as f64 / dpr, backing.1 as f64 / dpr);
+    ((ndc_x + 1.0) / 2.0 * width, (1.0 - ndc_y) / 2.0 * height)
+}
+
+/// 🎯 Index of the visible candle under NDC `ndc_x`, clamped to the chart edges
+///
+/// Inverse of `candle_x_position`; `None` only when nothing is visible.
+pub fn candle_index_at_ndc(ndc_x: f64, visible_len: usize) -> Option<usize> {
+    XAxis::ordinal(visible_len).index_at(ndc_x)
+}
+
+/// ⌨️ Move the keyboard selection by `step`, starting from the latest candle
+pub fn step_selection(current: Option<usize>, step: isize, visible_len: usize) -> Option<usize> {
+    let last = visible_len.checked_sub(1)?;
+    match current {
+        None => Some(last),
+        Some(idx) => Some((idx.min(last) as isize + step).clamp(0, last as isize) as usize),
+    }
+}
+
+/// Pointer distance (px) one arrow-key press pans the chart
+pub const KEYBOARD_PAN_PX: f64 = 80.0;
+
+/// ⌨️ Chart action bound to a key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyboardShortcut {
+    /// Multiply the zoom level by the factor
+    Zoom(f64),
+    /// Pan as if the pointer moved by this many pixels
+    Pan(f64),
+    /// Move the candle inspector selection by this many candles
+    Inspect(isize),
+    /// Glide back to the newest candle
+    JumpToLatest,
+    /// Toggle the named indicator line (see `LineVisibility::HOTKEYS`)
+    ToggleLine(&'static str),
+    /// Hide the inspector tooltip and clear the selection
+    ClearSelection,
+    /// Remove the selected price line
+    DeletePriceLine,
+    /// Arm or disarm the alert of the selected price line
+    TogglePriceAlert,
+}
+
+/// ⌨️ Shortcut for `key`; Shift turns arrow-key panning into candle inspection
+pub fn keyboard_shortcut(key: &str, shift: bool) -> Option<KeyboardShortcut> {
+    let shortcut = match key {
+        "+" | "=" => KeyboardShortcut::Zoom(1.2),
+        "-" | "_" => KeyboardShortcut::Zoom(0.8),
+        "PageUp" => KeyboardShortcut::Zoom(1.5),
+        "PageDown" => KeyboardShortcut::Zoom(0.67),
+        "ArrowLeft" if shift => KeyboardShortcut::Inspect(-1),
+        "ArrowRight" if shift => KeyboardShortcut::Inspect(1),
+        "ArrowLeft" => KeyboardShortcut::Pan(KEYBOARD_PAN_PX),
+        "ArrowRight" => KeyboardShortcut::Pan(-KEYBOARD_PAN_PX),
+        "Home" => KeyboardShortcut::JumpToLatest,
+        "Escape" => KeyboardShortcut::ClearSelection,
+        "Delete" | "Backspace" => KeyboardShortcut::DeletePriceLine,
+        "n" | "N" => KeyboardShortcut::TogglePriceAlert,
+        _ => KeyboardShortcut::ToggleLine(LineVisibility::line_for_key(key)?),
+    };
+    Some(shortcut)
+}
+
+/// Check if keystrokes on `element` belong to a text field rather than the chart
+pub fn is_text_entry(element: &web_sys::Element) -> bool {
+    let editable =
+        element.dyn_ref::<web_sys::HtmlElement>().is_some_and(|el| el.is_content_editable());
+    editable
+        || matches!(
+            element.tag_name().to_ascii_lowercase().as_str(),
+            "input" | "textarea" | "select"
+        )
+}
+
+/// 🎯 Data for the tooltip
+#[derive(Clone, Debug)]
+pub struct TooltipData {
+    pub candle: Candle,
+    pub x: f64,
+    pub y: f64,
+    pub formatted_text: String,
+}
+
+impl TooltipData {
+    pub fn new(candle: Candle, x: f64, y: f64) -> Self {
+        let change = candle.ohlcv.close.value() - candle.ohlcv.open.value();
+        let change_pct = candle.ohlcv.open.pct_change(candle.ohlcv.close);
+        let trend = if change >= 0.0 { "🟢" } else { "🔴" };
+
+        // Format time from the timestamp
+        let time_str = format!("Time: {}", candle.timestamp.value());
+
+        let symbol = current_symbol().get_untracked();
+        let formatted_text = format!(
+            "{} {}\n📈 Open:   ${:.2}\n📊 High:   ${:.2}\n📉 Low:    ${:.2}\n💰 Close:  ${:.2}\n📈 Change: ${:.2} ({:.2}%)\n📊 Volume: {:.4}\n{}",
+            trend,
+            symbol.value(),
+            candle.ohlcv.open.value(),
+            candle.ohlcv.high.value(),
+            candle.ohlcv.low.value(),
+            candle.ohlcv.close.value(),
+            change,
+            change_pct,
+            candle.ohlcv.volume.value(),
+            time_str
+        );
+
+        Self { candle, x, y, formatted_text }
+    }
+}
+
+/// 🦀 Main Crypto Chart component built with Leptos
+#[component]
+pub fn app() -> impl IntoView {
+    // 🚀 Initialize the global logger on application start
+    use crate::domain::logging::get_logger;
+
+    // Extra console.log for diagnostics
+    web_sys::console::log_1(&"🚀 Starting Crypto Chart App".into());
+
+    get_logger().info(LogComponent::Presentation("App"), "🚀 Starting Crypto Chart App");
+
+    web_sys::console::log_1(&"📦 Creating view...".into());
+    let (status, set_status) = create_signal("Initializing...".to_string());
+
+    view! {
+        <style>
+            {r#"
+            .bitcoin-chart-app {
+                font-family: 'SF Pro Display', -apple-system, BlinkMacSystemFont, sans-serif;
+                background: linear-gradient(135deg, #1e3c72 0%, #2a5298 100%);
+                min-height: 100vh;
+                padding: 20px;
+                color: white;
+            }
+            
+            .header {
+                text-align: center;
+                margin-bottom: 20px;
+                background: rgba(255, 255, 255, 0.1);
+                backdrop-filter: blur(10px);
+                padding: 20px;
+                border-radius: 15px;
+                border: 1px solid rgba(255, 255, 255, 0.2);
+            }
+            
+            .price-info {
+                display: flex;
+                justify-content: center;
+                gap: 40px;
+                margin-top: 15px;
+            }
+            
+            .price-item {
+                text-align: center;
+            }
+            
+            .price-value {
+                font-size: 24px;
+                font-weight: 700;
+                color: #74c787;
+                text-shadow: 0 0 10px rgba(114, 198, 133, 0.3);
+            }
+            
+            .price-label {
+                font-size: 12px;
+                color: #a0a0a0;
+                margin-top: 5px;
+            }
+            
+            .chart-container {
+                position: relative;
+                display: flex;
+                flex-direction: column;
+                align-items: center;
+                gap: 10px;
+                margin-bottom: 20px;
+            }
+            
+            .chart-wrapper {
+                position: relative;
+                display: inline-block;
+            }
+            
+            .price-scale {
+                position: absolute;
+                right: -60px;
+                top: 0;
+                height: 100%;
+                width: 80px;
+                pointer-events: none;
+            }
+            
+            .current-price-label {
+                position: absolute;
+                right: 0;
+                transform: translateY(-50%);
+                background: #f39c12;
+                color: #111;
+                height: 22px;
+                line-height: 14px;
+                box-sizing: border-box;
+                padding: 4px 8px;
+                border-radius: 4px;
+                font-size: 12px;
+                font-weight: bold;
+                white-space: nowrap;
+                box-shadow: 0 2px 4px rgba(0,0,0,0.3);
+            }
+            
+            .price-value {
+                font-family: 'Courier New', monospace;
+            }
+            
+            .tooltip {
+                position: absolute;
+                background: rgba(0, 0, 0, 0.9);
+                color: white;
+                padding: 8px 12px;
+                border-radius: 6px;
+                font-size: 12px;
+                font-family: 'Courier New', monospace;
+                white-space: pre-line;
+                pointer-events: none;
+                z-index: 1000;
+                border: 1px solid #4a5d73;
+                box-shadow: 0 4px 12px rgba(0, 0, 0, 0.5);
+                backdrop-filter: blur(5px);
+                line-height: 1.4;
+                transform: translate(10px, -100%);
+            }
+            
+            .status {
+                color: #74c787;
+                font-size: 14px;
+                text-align: center;
+            }
+
+            .indicator-legend {
+                position: absolute;
+                top: 8px;
+                left: 10px;
+                display: flex;
+                flex-direction: column;
+                gap: 2px;
+                font-size: 11px;
+                color: #ddd;
+                pointer-events: none;
+            }
+
+            .indicator-legend-entry {
+                display: flex;
+                align-items: center;
+                gap: 6px;
+                padding: 1px 6px;
+                background: rgba(0, 0, 0, 0.45);
+                border-radius: 3px;
+                pointer-events: auto;
+            }
+
+            .indicator-legend-swatch {
+                width: 10px;
+                height: 10px;
+                border-radius: 2px;
+            }
+
+            .no-data-overlay {
+                position: absolute;
+                inset: 0;
+                display: flex;
+                flex-direction: column;
+                align-items: center;
+                justify-content: center;
+                gap: 12px;
+                border-radius: 10px;
+                font-size: 16px;
+                color: #a0a0a0;
+            }
+
+            .no-data-overlay button {
+                padding: 6px 16px;
+                border: none;
+                border-radius: 4px;
+                background: #74c787;
+                color: black;
+                cursor: pointer;
+            }
+            
+
+            "#}
+        </style>
+        <div class="bitcoin-chart-app">
+            <Header set_status=set_status />
+            <ChartContainer status=status set_status=set_status />
+        </div>
+    }
+}
+
+/// ⏱️ Header text for the renderer's frame rate and frame time
+pub fn format_frame_timing(timing: FrameTiming) -> String {
+    format!("{:.0} fps • {:.1} ms", timing.fps, timing.frame_ms)
+}
+
+/// Window of the header price change
+pub const HEADER_CHANGE_WINDOW: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// 📈 Header value and label for a price change over `HEADER_CHANGE_WINDOW`
+///
+/// A change measured over a shorter history is labelled as such.
+pub fn format_price_change(change: Option<PriceChange>) -> (String, &'static str) {
+    match change {
+        None => ("—".to_string(), "24h Change"),
+        Some(PriceChange { pct, full_window }) => {
+            let label = if full_window { "24h Change" } else { "Change (loaded history)" };
+            (format!("{pct:+.2}%"), label)
+        }
+    }
+}
+
+/// 📊 Price header with real data
+#[component]
+fn header(set_status: WriteSignal<String>) -> impl IntoView {
+    // Use global signals for real data
+    let current_price = global_current_price();
+    let candle_count = global_candle_count();
+    let max_volume = global_max_volume();
+    let zoom_level = zoom_level();
+    // Follows new candles through the current symbol's chart signal
+    let price_change = move || {
+        let symbol = current_symbol().get();
+        let chart = global_charts().with(|m| m.get(&symbol).copied())?;
+        chart.with(|c| c.price_change_pct(current_interval().get(), HEADER_CHANGE_WINDOW))
+    };
+    let change_color = move || match price_change() {
+        Some(change) if change.pct < 0.0 => "#e74c3c",
+        Some(_) => "#74c787",
+        None => "inherit",
+    };
+
+    view! {
+        <div class="header">
+            <h1>{move || format!("🌐 {} WebSocket Chart", current_symbol().get().value())}</h1>
+            <p>{move || format!("{} • Real-time Leptos + WebGPU", current_symbol().get().value())}</p>
+            <SymbolSelect set_status=set_status />
+            <ThemeSelect />
+            <MsaaSelect />
+            <PauseButton />
+            <ImportButton set_status=set_status />
+
+            <div class="price-info">
+                <div class="price-item">
+                    <div class="price-value">
+                        {move || format!("${:.2}", current_price.get())}
+                    </div>
+                    <div class="price-label">"Current Price"</div>
+                </div>
+                <div class="price-item">
+                    <div class="price-value" style:color=change_color>
+                        {move || format_price_change(price_change()).0}
+                    </div>
+                    <div class="price-label">{move || format_price_change(price_change()).1}</div>
+                </div>
+                <div class="price-item">
+                    <div class="price-value">
+                        {move || candle_count.get().to_string()}
+                    </div>
+                    <div class="price-label">"Candles"</div>
+                </div>
+                <div class="price-item">
+                    <div
+                        class="price-value"
+                        title=move || match connection_state().get() {
+                            ConnectionState::Error(reason) => reason,
+                            _ => String::new(),
+                        }
+                    >
+                        {move || connection_state().get().label()}
+                    </div>
+                    <div class="price-label">"WebSocket"</div>
+                </div>
+                <div class="price-item">
+                    <div class="price-value">
+                        {move || format!("{:.2}", max_volume.get())}
+                    </div>
+                    <div class="price-label">"Max Volume"</div>
+                </div>
+                <div class="price-item">
+                    <div class="price-value">
+                        {move || format!("{:.1}x", zoom_level.get())}
+                    </div>
+                    <div class="price-label">"🔍 Zoom"</div>
+                </div>
+                <div class="price-item">
+                    <div class="price-value" id="perf-readout">
+                        {move || format_frame_timing(frame_timing().get())}
+                    </div>
+                    <div class="price-label">"⏱️ Render"</div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// 💲 Price labels left of the chart; drag to pan, scroll to zoom, double-click to refit
+#[component]
+fn PriceAxisLeft(chart: RwSignal<Chart>) -> impl IntoView {
+    let labels = move || {
+        let vp = chart.with(|c| displayed_price_viewport(&c.viewport));
+        let levels = price_levels(&vp);
+        let decimals = price_level_decimals(&levels);
+        let band = panel_layout().with(|l| l.main_band());
+        levels
+            .into_iter()
+            .map(|price| {
+                (format!("{price:.decimals$}"), band.css_percent(price_level_percent(&vp, price)))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Pointer y of the last drag step while the axis is held
+    let drag_y = create_rw_signal::<Option<f64>>(None);
+    let handle_mouse_move = move |event: web_sys::MouseEvent| {
+        let Some(last_y) = drag_y.get_untracked() else {
+            return;
+        };
+        let y = event.client_y() as f64;
+        drag_y.set(Some(y));
+        pan_price(y - last_y);
+    };
+
+    view! {
+        // Wheel zoom is dispatched by the chart's window wheel listener
+        <div
+            class="price-axis"
+            style:height=move || format!("{}px", canvas_size().get().1)
+            style="position: relative; width: 60px; background: #222; margin-right: 8px; cursor: ns-resize; user-select: none;"
+            on:mousedown=move |e: web_sys::MouseEvent| drag_y.set(Some(e.client_y() as f64))
+            on:mousemove=handle_mouse_move
+            on:mouseup=move |_| drag_y.set(None)
+            on:mouseleave=move |_| drag_y.set(None)
+            on:dblclick=move |_| reset_price_view()
+        >
+            <For
+                each=labels
+                key=|(text, pos)| (text.clone(), (*pos * 100.0) as i64)
+                children=|(text, position)| view! {
+                    <div style=format!("position: absolute; top: {position}%; right: 4px; transform: translateY(-50%); font-size: 12px; color: #fff;")>
+                        {text}
+                    </div>
+                }
+            />
+        </div>
+    }
+}
+
+/// ⏰ Time scale below the chart
+#[component]
+fn TimeScale(chart: RwSignal<Chart>) -> impl IntoView {
+    // Labels sit under the renderer's vertical time grid lines
+    let time_labels = move || {
+        global_candle_count().track();
+        zoom_level().track();
+        pan_offset().track();
+        let interval = current_interval().get();
+        let offset = time_zone_offset().get().unwrap_or(0);
+        let span = chart.with(|c| {
+            with_global_renderer(|r| r.geometry().visible_time_span(c)).flatten().or_else(|| {
+                let candles = c.get_series(interval)?.get_candles();
+                let (start, count) = visible_range(
+                    candles.len(),
+                    zoom_level().get_untracked(),
+                    pan_offset().get_untracked(),
+                );
+                Some(VisibleSpan {
+                    first: candles.get(start)?.timestamp.value(),
+                    last: candles.get(start + count.checked_sub(1)?)?.timestamp.value(),
+                    count,
+                })
+            })
+        });
+        let Some(span) = span else {
+            return Vec::new();
+        };
+        time_grid_indices(span.count)
+            .into_iter()
+            .map(|idx| {
+                let x = candle_x_position(idx, span.count) as f64;
+                let label = format_axis_time(span.timestamp_at(idx), interval, offset);
+                (label, (x + 1.0) * 50.0)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div style="position: relative; width: 800px; height: 30px; background: #222; margin-top: 5px; border-radius: 5px;">
+            <For
+                each=time_labels
+                key=|(time, pos)| (time.clone(), (*pos * 100.0) as i64)
+                children=|(time, position)| view! {
+                    <div style=format!("position: absolute; left: {}%; top: 50%; transform: translate(-50%, -50%); font-size: 11px; color: #888; white-space: nowrap;", position)>
+                        {time}
+                    </div>
+                }
+            />
+            <div style="position: absolute; right: 4px; bottom: 2px; font-size: 9px; color: #666;">
+                {move || time_zone_suffix(time_zone_offset().get())}
+            </div>
+        </div>
+    }
+}
+
+/// 🎨 Container for the WebGPU chart
+#[component]
+fn ChartContainer(status: ReadSignal<String>, set_status: WriteSignal<String>) -> impl IntoView {
+    ensure_chart(&current_symbol().get_untracked());
+    restore_panel_layout();
+    persist_price_lines();
+    restore_exchange();
+    persist_settings();
+    // 📚 Follow the depth toggle and the charted symbol
+    let depth_visible = create_memo(move |_| global_line_visibility().with(|v| v.depth));
+    create_effect(move |_| {
+        let symbol = current_symbol().get();
+        if depth_visible.get() {
+            start_depth_stream(symbol);
+        } else {
+            stop_depth_stream();
+        }
+    });
+    create_effect(move |_| {
+        let sym = current_symbol().get();
+        ensure_chart(&sym);
+    });
+    // 🕳️ Placeholder on the canvas while the chart has no candles
+    create_effect(move |_| {
+        let state = connection_state().with(|c| empty_chart_state(c, load_failed().get()));
+        with_active_renderers(|r| r.geometry_mut().set_empty_state(state));
+        if global_candle_count().get_untracked() == 0 {
+            redraw_current_chart();
+        }
+    });
+    let chart_memo = create_memo(move |_| {
+        let sym = current_symbol().get();
+        global_charts().with(|m| m.get(&sym).copied().unwrap())
+    });
+    let chart = move || chart_memo.get();
+    let (_renderer, set_renderer) = create_signal::<Option<Rc<RefCell<WebGpuRenderer>>>>(None);
+
+    // Reference to the canvas element
+    let canvas_ref = create_node_ref::<Canvas>();
+    let container_ref = create_node_ref::<Div>();
+    let (initialized, set_initialized) = create_signal(false);
+
+    // 📐 Fit the canvas to its container and rebuild the GPU surface to match
+    let resize_canvas = move || {
+        let (Some(container), Some(canvas)) =
+            (container_ref.get_untracked(), canvas_ref.get_untracked())
+        else {
+            return;
+        };
+        let (css_width, css_height) =
+            fit_canvas_size(container.client_width() as f64 - CHART_GUTTER);
+        // Re-read the ratio: the window may have moved to another monitor
+        let dpr = device_pixel_ratio();
+        let (width, height) = backing_size(css_width, css_height, dpr);
+        canvas_size().set((css_width, css_height));
+        canvas.set_width(width);
+        canvas.set_height(height);
+        chart_memo.get_untracked().with_untracked(|ch| {
+            let resize = |r: &mut dyn Renderer| {
+                r.geometry_mut().set_pixel_ratio(dpr as f32);
+                r.resize(width, height);
+                let _ = r.render(ch);
+            };
+            with_active_renderers(resize);
+        });
+    };
+
+    // Replacing the pending timeout cancels it, so only the last resize lands
+    let pending_resize = Rc::new(RefCell::new(None::<gloo_timers::callback::Timeout>));
+    let resize_listener =
+        window_event_listener_with_options(ev::resize, &EventOptions::default(), move |_| {
+            let timeout = gloo_timers::callback::Timeout::new(RESIZE_DEBOUNCE_MS, resize_canvas);
+            pending_resize.borrow_mut().replace(timeout);
+        });
+    on_cleanup(move || resize_listener.remove());
+
+    // 💥 Rebuild the renderer when its GPU device is lost
+    create_effect(move |_| {
+        let Some((lost_canvas, message)) = device_lost().get() else {
+            return;
+        };
+        let main_canvas = with_global_renderer(|r| r.canvas_id().to_string());
+        if main_canvas.as_deref() != Some(lost_canvas.as_str()) {
+            return;
+        }
+        device_lost().set(None);
+        set_status.set(format!("⚠️ GPU device lost ({message}), reinitializing..."));
+        let _ = spawn_local_with_current_owner(recover_renderer(lost_canvas, set_status));
+    });
+
+    // Initialize WebGPU once the canvas is available
+    create_effect(move |_| {
+        if initialized.get() {
+            return;
+        }
+
+        if let Some(canvas) = canvas_ref.get() {
+            let canvas_id = std::ops::Deref::deref(&canvas).id();
+            set_initialized.set(true);
+            let _ = spawn_local_with_current_owner(async move {
+                web_sys::console::log_1(&"🔍 Canvas found, starting WebGPU init...".into());
+                set_status.set("🚀 Initializing WebGPU renderer...".to_string());
+
+                // Detailed WebGPU diagnostics
+                web_sys::console::log_1(&"🏗️ Creating WebGPU renderer...".into());
+                get_logger().info(
+                    LogComponent::Infrastructure("WebGPU"),
+                    "🔍 Starting WebGPU initialization...",
+                );
+
+                web_sys::console::log_1(&"⚡ About to call WebGpuRenderer::new...".into());
+
+                // The backing store holds device pixels, the CSS size stays put
+                let dpr = device_pixel_ratio();
+                let (css_width, css_height) = canvas_size().get_untracked();
+                let (width, height) = backing_size(css_width, css_height, dpr);
+
+                // 🖌️ Browsers without WebGPU get the slower canvas 2D renderer
+                if !WebGpuRenderer::is_webgpu_supported().await {
+                    get_logger().warn(
+                        LogComponent::Infrastructure("WebGPU"),
+                        "⚠️ WebGPU is not supported, falling back to Canvas 2D",
+                    );
+                    match Canvas2dRenderer::new(canvas_id.as_str(), width, height) {
+                        Ok(mut renderer) => {
+                            apply_restored_settings(renderer.geometry_mut(), dpr);
+                            set_fallback_renderer(Rc::new(RefCell::new(renderer)));
+                            resize_canvas();
+                            set_status.set("⚠️ WebGPU unavailable, using Canvas 2D".to_string());
+                            start_websocket_stream(set_status).await;
+                        }
+                        Err(e) => set_status.set(format!("❌ No renderer available: {e}")),
+                    }
+                    return;
+                }
+
+                match WebGpuRenderer::new(canvas_id.as_str(), width, height).await {
+                    Ok(mut webgpu_renderer) => {
+                        get_logger().info(
+                            LogComponent::Infrastructure("WebGPU"),
+                            "✅ WebGPU renderer created successfully",
+                        );
+                        apply_restored_settings(webgpu_renderer.geometry_mut(), dpr);
+                        msaa_samples().set(
+                            webgpu_renderer.set_msaa_sample_count(msaa_samples().get_untracked()),
+                        );
+
+                        let renderer_rc = Rc::new(RefCell::new(webgpu_renderer));
+                        set_renderer.set(Some(renderer_rc.clone()));
+                        set_global_renderer(renderer_rc.clone());
+                        init_render_queue();
+                        resize_canvas();
+                        let _ = renderer_rc.borrow().log_gpu_memory_usage();
+                        set_status.set("✅ WebGPU renderer ready".to_string());
+
+                        // Start WebSocket after the renderer is initialized
+                        get_logger().info(
+                            LogComponent::Infrastructure("WebSocket"),
+                            "🌐 Starting WebSocket stream...",
+                        );
+                        start_websocket_stream(set_status).await;
+                    }
+                    Err(e) => {
+                        let msg = e.to_string();
+                        web_sys::console::error_1(
+                            &format!("❌ WebGPU initialization error: {msg}").into(),
+                        );
+                        get_logger().error(
+                            LogComponent::Infrastructure("WebGPU"),
+                            &format!("❌ WebGPU initialization failed: {msg}"),
+                        );
+                        set_status.set(format!(
+                            "❌ WebGPU failed: {msg}\n💡 Try Chrome Canary with --enable-unsafe-webgpu flag",
+                        ));
+
+                        // Fallback: show data even without the chart
+                        get_logger().info(
+                            LogComponent::Infrastructure("Fallback"),
+                            "🔄 Starting fallback mode without WebGPU...",
+                        );
+
+                        // Generate sample data for demo purposes
+                        let mut test_candles = Vec::new();
+                        let base_price = 90000.0;
+                        let base_time = js_sys::Date::now() as u64;
+
+                        for i in 0..50 {
+                            let price_variation = (i as f64 * 0.1).sin() * 1000.0;
+                            let open = base_price + price_variation;
+                            let close = open + (i as f64 % 3.0 - 1.0) * 200.0;
+                            let high = open.max(close) + 100.0;
+                            let low = open.min(close) - 100.0;
+                            let volume = 100.0 + (i as f64 * 0.2).cos() * 50.0;
+
+                            test_candles.push(Candle::new(
+                                Timestamp::from_millis(base_time + i * 60000),
+                                OHLCV::new(
+                                    Price::from(open),
+                                    Price::from(high),
+                                    Price::from(low),
+                                    Price::from(close),
+                                    Volume::from(volume),
+                                ),
+                            ));
+                        }
+
+                        chart().update(|ch| ch.set_historical_data(test_candles));
+                        let symbol = current_symbol().get_untracked();
+                        chart().with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
+                        set_status.set(format!(
+                            "🎯 Demo mode: Using test data (WebSocket disabled)\nReason: {msg}",
+                        ));
+                    }
+                }
+            });
+        }
+    });
+
+    // 🎯 Mouse events for the tooltip
+    let handle_mouse_move = {
+        let chart_signal = chart;
+        let status_clone = set_status;
+        move |event: web_sys::MouseEvent| {
+            // Convert to NDC using the canvas rect and device pixel ratio
+            let Some((ndc_x, _ndc_y)) = client_to_ndc(&event) else {
+                return;
+            };
+
+            // 🔍 Handle panning
+            let dragging = is_dragging().get_untracked();
+            if dragging {
+                let pointer_x = pan_pointer_x(ndc_x as f64);
+                let delta_x = pointer_x - last_mouse_x().get_untracked();
+                last_mouse_x().set(pointer_x);
+                PAN_VELOCITY.with(|t| t.borrow_mut().record(pointer_x, event.time_stamp()));
+                apply_pan(delta_x, status_clone);
+            } else if let Some(canvas) =
+                event.current_target().and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+            {
+                let rect = canvas.get_bounding_client_rect();
+                let mouse_x = event.client_x() as f64 - rect.left();
+                let mouse_y = event.client_y() as f64 - rect.top();
+                show_tooltip_at(chart_signal(), ndc_x as f64, mouse_x, mouse_y);
+            }
+        }
+    };
+
+    let handle_mouse_leave = move |_event: web_sys::MouseEvent| {
+        tooltip_visible().set(false);
+        is_dragging().set(false);
+    };
+
+    // 🔍 Mouse wheel zoom - simplified without effects
+    let handle_wheel = {
+        let chart_signal = chart;
+        let status_clone = set_status;
+        move |event: web_sys::WheelEvent| {
+            if chart_signal().try_get_untracked().is_none() {
+                return;
+            }
+            web_sys::console::log_1(&format!("🖱️ Wheel event: delta_y={}", event.delta_y()).into());
+            event.prevent_default();
+
+            // Over the price axis the wheel stretches prices instead of time
+            let price_axis = event
+                .target()
+                .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                .and_then(|el| el.closest(".price-axis").ok().flatten());
+            if let Some(axis) = price_axis {
+                let rect = axis.get_bounding_client_rect();
+                let center_y = (event.client_y() as f64 - rect.top()) / rect.height().max(1.0);
+                zoom_price(price_zoom_factor(event.delta_y()), center_y);
+                return;
+            }
+            cancel_pan_animation();
+
+            let cursor_x = client_to_ndc(&event).map_or(0.0, |(x, _)| x);
+            zoom_view_at(chart_signal(), event.delta_y(), cursor_x, status_clone);
+        }
+    };
+
+    // 🖱️ Start panning
+    let handle_mouse_down = move |event: web_sys::MouseEvent| {
+        if event.button() == 0 {
+            // Left mouse button
+            web_sys::console::log_1(&"🖱️ Mouse down".into());
+            cancel_pan_animation();
+            is_dragging().set(true);
+            let pointer_x = client_to_ndc(&event).map_or(0.0, |(x, _)| pan_pointer_x(x as f64));
+            last_mouse_x().set(pointer_x);
+            PAN_VELOCITY.with(|t| {
+                let mut tracker = t.borrow_mut();
+                tracker.reset();
+                tracker.record(pointer_x, event.time_stamp());
+            });
+
+            // Give the canvas focus for keyboard events
+            if let Some(target) = event.target() {
+                if let Ok(canvas) = target.dyn_into::<web_sys::HtmlCanvasElement>() {
+                    let _ = canvas.focus();
+                }
+            }
+        }
+    };
+
+    // 🖱️ End panning, continuing with inertia after a flick
+    let handle_mouse_up = move |event: web_sys::MouseEvent| {
+        web_sys::console::log_1(&"🖱️ Mouse up".into());
+        let was_dragging = is_dragging().get_untracked();
+        is_dragging().set(false);
+        if was_dragging && pan_inertia_enabled().get_untracked() {
+            let velocity = PAN_VELOCITY.with(|t| t.borrow().release_velocity(event.time_stamp()));
+            if velocity.abs() >= PAN_INERTIA_MIN_VELOCITY {
+                start_pan_inertia(velocity, set_status);
+            }
+        }
+    };
+
+    // ✋ One finger pans, two fingers pinch-zoom and a tap shows the tooltip
+    let touch_gesture = store_value(TouchGesture::default());
+    let touch_canvas = |event: &web_sys::TouchEvent| {
+        event.current_target().and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+    };
+    let handle_touch_start = move |event: web_sys::TouchEvent| {
+        event.prevent_default();
+        cancel_pan_animation();
+        let points = touch_points(&event.touches());
+        touch_gesture.update_value(|g| g.begin(&points));
+    };
+    let handle_touch_move = move |event: web_sys::TouchEvent| {
+        event.prevent_default();
+        let points = touch_points(&event.touches());
+        match touch_gesture.try_update_value(|g| g.update(&points)).flatten() {
+            Some(TouchAction::Pan(dx)) => {
+                tooltip_visible().set(false);
+                apply_pan(dx, set_status);
+            }
+            Some(TouchAction::Zoom { delta, center_x }) => {
+                let Some(canvas) = touch_canvas(&event) else {
+                    return;
+                };
+                let (cursor_x, _) = element_client_to_ndc(&canvas, center_x, 0.0);
+                zoom_view_at(chart(), delta, cursor_x, set_status);
+            }
+            _ => {}
+        }
+    };
+    let handle_touch_end = move |event: web_sys::TouchEvent| {
+        event.prevent_default();
+        let points = touch_points(&event.touches());
+        let tap = touch_gesture.try_update_value(|g| g.end(&points)).flatten();
+        if let (Some(TouchAction::Tap { x, y }), Some(canvas)) = (tap, touch_canvas(&event)) {
+            let (ndc_x, _) = element_client_to_ndc(&canvas, x, y);
+            let rect = canvas.get_bounding_client_rect();
+            show_tooltip_at(chart(), ndc_x as f64, x - rect.left(), y - rect.top());
+        }
+    };
+
+    // 📌 Click a price line to select it for deletion, Shift + double-click to place one;
+    // a plain double-click resets the view
+    let handle_click = move |event: web_sys::MouseEvent| {
+        if let Some((price, per_px)) = pointer_price(&event) {
+            selected_price_line().set(price_line_at(price, per_px));
+        }
+    };
+    let handle_dblclick = move |event: web_sys::MouseEvent| {
+        if !event.shift_key() {
+            reset_view();
+            return;
+        }
+        let Some((price, per_px)) = pointer_price(&event) else {
+            return;
+        };
+        if price_line_at(price, per_px).is_none() {
+            selected_price_line().set(Some(add_price_line(price)));
+        }
+    };
+
+    // ⌨️ Keyboard navigation: zoom, pan, jump to latest, inspector and line toggles
+    let handle_keydown = {
+        let chart_signal = chart;
+        let status_clone = set_status;
+        move |event: web_sys::KeyboardEvent| {
+            if event.ctrl_key() || event.meta_key() || event.alt_key() {
+                return;
+            }
+            let typing = event
+                .target()
+                .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                .is_some_and(|el| is_text_entry(&el));
+            if typing {
+                return;
+            }
+            let Some(shortcut) = keyboard_shortcut(&event.key(), event.shift_key()) else {
+                return;
+            };
+            event.prevent_default();
+            cancel_pan_animation();
+            let mut zoom_changed = false;
+
+            match shortcut {
+                KeyboardShortcut::Zoom(factor) => {
+                    zoom_level().update(|z| {
+                        *z = clamp_zoom(*z * factor);
+                    });
+                    zoom_changed = true;
+                }
+                KeyboardShortcut::Pan(delta_x) => {
+                    if !current_pan_at_boundary(delta_x) {
+                        apply_pan(delta_x, status_clone);
+                    }
+                }
+                KeyboardShortcut::JumpToLatest => animate_to_latest(status_clone),
+                KeyboardShortcut::ToggleLine(name) => {
+                    chart_signal().with_untracked(|ch| {
+                        if !with_active_renderers(|r| {
+                            r.geometry_mut().toggle_line_visibility(name);
+                            let _ = r.render(ch);
+                        }) {
+                            // renderer not available
+                        }
+                    });
+                }
+                KeyboardShortcut::Inspect(step) => {
+                    chart_signal().with_untracked(|ch| {
+                        let interval = current_interval().get_untracked();
+                        let Some(series) = ch.get_series(interval) else {
+                            return;
+                        };
+                        let candles = series.get_candles();
+                        let (start_idx, visible_count) = visible_range(
+                            candles.len(),
+                            zoom_level().get_untracked(),
+                            pan_offset().get_untracked(),
+                        );
+                        let visible: Vec<Candle> =
+                            candles.iter().skip(start_idx).take(visible_count).cloned().collect();
+                        let selected =
+                            step_selection(selected_candle().get_untracked(), step, visible.len());
+                        selected_candle().set(selected);
+                        if let Some(idx) = selected {
+                            let (x, y) = {
+                                let (css_width, css_height) = canvas_size().get_untracked();
+                                let dpr = device_pixel_ratio();
+                                let backing = backing_size(css_width, css_height, dpr);
+                                let x_axis =
+                                    XAxis::new(x_axis_mode().get_untracked(), &visible, interval);
+                                let viewport = displayed_price_viewport(&ch.viewport);
+                                candle_screen_position(
+                                    idx, &visible, &x_axis, &viewport, backing, dpr,
+                                )
+                            };
+                            tooltip_data().set(Some(TooltipData::new(visible[idx].clone(), x, y)));
+                            tooltip_visible().set(true);
+                        }
+                    });
+                }
+                KeyboardShortcut::ClearSelection => {
+                    tooltip_visible().set(false);
+                    selected_candle().set(None);
+                    selected_price_line().set(None);
+                }
+                KeyboardShortcut::DeletePriceLine => {
+                    if let Some(id) = selected_price_line().get_untracked() {
+                        remove_horizontal_line(id);
+                    }
+                }
+                KeyboardShortcut::TogglePriceAlert => {
+                    if let Some(id) = selected_price_line().get_untracked() {
+                        toggle_price_alert(id);
+                    }
+                }
+            }
+
+            if zoom_changed {
+                let new_zoom = zoom_level().with_untracked(|z_val| *z_val);
+                web_sys::console::log_1(&format!("⌨️ Keyboard zoom: {:.2}x", new_zoom).into());
+
+                // Apply zoom to the renderer for keyboard commands
+                chart_signal().with_untracked(|ch| {
+                    if ch.get_candle_count() > 0
+                        && with_global_renderer(|r| {
+                            r.geometry_mut()
+                                .set_zoom_params(new_zoom, pan_offset().with_untracked(|val| *val));
+                            let _ = r.render(ch);
+                            get_logger().info(
+                                LogComponent::Infrastructure("KeyboardZoom"),
+                                &format!(
+                                    "⌨️ Applied keyboard zoom {:.2}x to WebGPU renderer",
+                                    new_zoom
+                                ),
+                            );
+                        })
+                        .is_none()
+                    {
+                        // renderer not available
+                    }
+                });
+
+                get_logger().info(
+                    LogComponent::Presentation("KeyboardZoom"),
+                    &format!("⌨️ Zoom level: {:.2}x", new_zoom),
+                );
+                let need_history = pan_offset().with_untracked(|val| should_fetch_history(*val));
+                if need_history {
+                    fetch_more_history(status_clone);
+                }
+            }
+        }
+    };
+
+    // Attach wheel event listener to the window
+    let wheel_listener = window_event_listener_with_options(
+        ev::wheel,
+        &EventOptions { passive: false, capture: false, once: false },
+        handle_wheel,
+    );
+    on_cleanup(move || wheel_listener.remove());
+
+    // Reset dragging state when the mouse is released anywhere
+    let mouseup_listener =
+        window_event_listener_with_options(ev::mouseup, &EventOptions::default(), move |_| {
+            is_dragging().set(false)
+        });
+    on_cleanup(move || mouseup_listener.remove());
+
+    // Shortcuts work wherever focus is, except inside text fields
+    let keydown_listener =
+        window_event_listener_with_options(ev::keydown, &EventOptions::default(), handle_keydown);
+    on_cleanup(move || keydown_listener.remove());
+
+    // Zoom effect removed - handled directly in the wheel handler
+
+    view! {
+        <div class="chart-container" node_ref=container_ref>
+            <div style="display:flex;justify-content:space-between;margin-bottom:8px;width:800px;">
+                <AssetSelector set_status=set_status />
+                <div style="display:flex;gap:6px;">
+                    <TimeframeSelector chart=chart() set_status=set_status />
+                    <ChartTypeSelector chart=chart() />
+                    <CsvExportButtons />
+                </div>
+            </div>
+
+            <div style="display: flex; flex-direction: row; align-items: flex-start;">
+                <PriceAxisLeft chart=chart() />
+                <div style="position: relative;">
+                    <canvas
+                        id="chart-canvas"
+                        node_ref=canvas_ref
+                        use:wheel_event_options=&EventOptions { passive: false, capture: false, once: false }
+                        width="800"
+                        height="500"
+                        style:width=move || format!("{}px", canvas_size().get().0)
+                        style:height=move || format!("{}px", canvas_size().get().1)
+                        tabindex="0"
+                        style="border: 2px solid #4a5d73; border-radius: 10px; background: #253242; cursor: crosshair; outline: none; touch-action: none;"
+                        on:mousemove=handle_mouse_move
+                        on:mouseleave=handle_mouse_leave
+                        on:mousedown=handle_mouse_down
+                        on:mouseup=handle_mouse_up
+                        on:click=handle_click
+                        on:dblclick=handle_dblclick
+                        on:touchstart=handle_touch_start
+                        on:touchmove=handle_touch_move
+                        on:touchend=handle_touch_end
+                        on:touchcancel=handle_touch_end
+                    />
+                    <PriceScale chart=chart() />
+                    <IndicatorLegend chart=chart() />
+                    <ChartTooltip />
+                    <NoDataOverlay set_status=set_status />
+                    <GoToLatestButton set_status=set_status />
+                </div>
+            </div>
+
+            <Minimap set_status=set_status />
+
+            <Legend chart=chart() />
+
+            // Time scale below the chart
+            <div style="display: flex; justify-content: center; margin-top: 10px;">
+                <TimeScale chart=chart() />
+            </div>
+
+            <div class="status">
+                {move || status.get()}
+            </div>
+
+            <DebugConsole />
+
+            // Control hints
+            <div style="text-align: center; margin-top: 10px; font-size: 12px; color: #888;">
+                "🔍 Zoom: Mouse wheel, +/- keys, PageUp/PageDown | 🖱️ Pan: Left click + drag, double-click to reset | 🎯 Tooltip: Mouse hover, ←/→ keys (Esc to hide) | 📌 Price line: Shift + double-click to add, click + Delete to remove, click + N for an alert"
+            </div>
+        </div>
+    }
+}
+
+/// 💰 Price scale on the right side of the chart
+#[component]
+fn PriceScale(chart: RwSignal<Chart>) -> impl IntoView {
+    let current_price = global_current_price();
+
+    // Labels follow the main panel when sub-panels take part of the height
+    let main_band = move || panel_layout().with(|l| l.main_band());
+
+    // Calculate price levels for display (same as in the grid)
+    let price_levels = move || {
+        let vp = chart.with(|c| displayed_price_viewport(&c.viewport));
+        let levels = price_levels(&vp);
+        let decimals = price_level_decimals(&levels);
+        let band = main_band();
+        levels
+            .into_iter()
+            .map(|price| {
+                let text = format!("{price:.decimals$}");
+                (text, band.css_percent(price_level_percent(&vp, price)))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Session levels follow live updates and the legend toggle
+    let session_labels = move || {
+        if !global_line_visibility().with(|v| v.session_levels) {
+            return Vec::new();
+        }
+        global_candle_count().track();
+        chart.with(|c| {
+            let vp = &displayed_price_viewport(&c.viewport);
+            let range = (vp.max_price - vp.min_price) as f64;
+            c.get_series(current_interval().get())
+                .and_then(|s| {
+                    let candles: Vec<Candle> = s.get_candles().iter().cloned().collect();
+                    session_high_low(&candles)
+                })
+                .filter(|_| range > 0.0)
+                .map(|(low, high)| {
+                    let pos = |p: f64| (vp.max_price as f64 - p) / range * 100.0;
+                    vec![("S.High", high, pos(high)), ("S.Low", low, pos(low))]
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, _, pos)| (0.0..=100.0).contains(pos))
+                .map(|(name, price, pos)| (name, price, main_band().css_percent(pos)))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Published by the renderer after each rebuilt frame, so zoom, pan and ticks move it
+    let current_price_label = move || {
+        let height = canvas_size().get().1;
+        current_price_marker()
+            .get()
+            .map(|(y, color)| (current_price_label_top(y, height), css_hex(color)))
+    };
+
+    // Reference lines within the visible price range
+    let reference_labels = move || {
+        let vp = chart.with(|c| displayed_price_viewport(&c.viewport));
+        let range = (vp.max_price - vp.min_price) as f64;
+        horizontal_lines().with(|lines| {
+            lines
+                .iter()
+                .filter(|_| range > 0.0)
+                .map(|l| {
+                    let pos = (vp.max_price as f64 - l.price) / range * 100.0;
+                    let bell = if l.alert { "🔔 " } else { "" };
+                    let text = format!("{bell}{} {:.2}", l.label, l.price);
+                    let selected = selected_price_line().get() == Some(l.id);
+                    let key = (l.id, selected, l.alert);
+                    (key, selected, text, pos, css_hex(l.color))
+                })
+                .filter(|(_, _, _, pos, _)| (0.0..=100.0).contains(pos))
+                .map(|(key, selected, text, pos, color)| {
+                    (key, selected, text, main_band().css_percent(pos), color)
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="price-scale">
+            // Display price levels
+            <For
+                each=price_levels
+                key=|(text, pos)| (text.clone(), (*pos * 100.0) as i64)
+                children=|(text, position)| view! {
+                    <div
+                        class="price-level"
+                        style=format!("position: absolute; top: {}%; right: 5px; transform: translateY(-50%); font-size: 11px; color: #888; background: rgba(0,0,0,0.7); padding: 2px 4px; border-radius: 2px;", position)
+                    >
+                        {text}
+                    </div>
+                }
+            />
+
+            // Session high/low labels for the current UTC day
+            <For
+                each=session_labels
+                key=|(name, _price, _pos)| *name
+                children=|(name, price, position)| view! {
+                    <div
+                        class="session-level"
+                        style=format!("position: absolute; top: {}%; left: 5px; transform: translateY(-50%); font-size: 11px; color: #99b3e6; background: rgba(0,0,0,0.7); padding: 2px 4px; border-radius: 2px;", position)
+                    >
+                        {format!("{} {:.2}", name, price)}
+                    </div>
+                }
+            />
+
+            // Reference line labels at the right edge
+            <For
+                each=reference_labels
+                key=|(key, _selected, _label, _pos, _color)| *key
+                children=|(_key, selected, label, position, color)| view! {
+                    <div
+                        class="reference-line-label"
+                        style=format!("position: absolute; top: {}%; right: 5px; transform: translateY(-50%); font-size: 11px; color: {}; background: rgba(0,0,0,0.7); padding: 2px 4px; border-radius: 2px; outline: {};", position, color, if selected { "1px solid white" } else { "none" })
+                    >
+                        {label}
+                    </div>
+                }
+            />
+
+            // Current price at the right end of its line, in the line's color
+            <Show when=move || current_price_label().is_some()>
+                <div
+                    class="current-price-label"
+                    style:top=move || current_price_label().map(|(top, _)| format!("{top}px"))
+                    style:background=move || current_price_label().map(|(_, color)| color)
+                >
+                    {move || format!("${:.2}", current_price.get())}
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// 🎯 Chart Tooltip component inside the chart wrapper
+#[component]
+fn ChartTooltip() -> impl IntoView {
+    let tooltip_visible = tooltip_visible();
+    let tooltip_data = tooltip_data();
+
+    view! {
+        <div
+            class="tooltip"
+            style:display=move || if tooltip_visible.get() { "block" } else { "none" }
+            style:left=move || {
+                tooltip_data.with(|data| {
+                    if let Some(tooltip) = data {
+                        format!("{}px", tooltip.x)
+                    } else {
+                        "0px".to_string()
+                    }
+                })
+            }
+            style:top=move || {
+                tooltip_data.with(|data| {
+                    if let Some(tooltip) = data {
+                        format!("{}px", tooltip.y)
+                    } else {
+                        "0px".to_string()
+                    }
+                })
+            }
+        >
+            {move || {
+                tooltip_data.with(|data| {
+                    if let Some(tooltip) = data {
+                        tooltip.formatted_text.clone()
+                    } else {
+                        String::new()
+                    }
+                })
+            }}
+        </div>
+    }
+}
+
+/// 🚫 Caption over the canvas placeholder while the chart has no candles
+///
+/// Says it is loading until the stream is live or loading failed, then
+/// offers a retry.
+#[component]
+fn NoDataOverlay(set_status: WriteSignal<String>) -> impl IntoView {
+    let visible = move || global_candle_count().get() == 0;
+    let state = move || connection_state().with(|c| empty_chart_state(c, load_failed().get()));
+
+    view! {
+        <Show when=visible>
+            <div class="no-data-overlay">
+                <Show
+                    when=move || state() == EmptyChartState::NoData
+                    fallback=|| view! { <div>"⏳ Loading…"</div> }
+                >
+                    <div>"No data"</div>
+                    <button on:click=move |_| retry_data_load(set_status)>"Retry"</button>
+                </Show>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn TimeframeSelector(chart: RwSignal<Chart>, set_status: WriteSignal<String>) -> impl IntoView {
+    let options = vec![
+        TimeInterval::TwoSeconds,
+        TimeInterval::OneMinute,
+        TimeInterval::FiveMinutes,
+        TimeInterval::FifteenMinutes,
+        TimeInterval::OneHour,
+    ];
+
+    view! {
+        <div style="display:flex;gap:6px;margin-top:8px;">
+            // Only intervals the selected exchange can stream
+            <For
+                each=move || {
+                    let venue = exchange().get();
+                    options.iter().copied().filter(|i| venue.supports_interval(*i)).collect::<Vec<_>>()
+                }
+                key=|i| i.as_ref().to_string()
+                children=move |interval| {
+                    let label = interval.as_ref().to_string();
+                    let chart_signal = chart;
+                    view! {
+                        <button
+                            style="padding:4px 6px;border:none;border-radius:4px;background:#74c787;color:black;"
+                            on:click=move |_| switch_interval(interval, chart_signal, set_status)
+                        >
+                            {label}
+                        </button>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn LegendIndicatorToggle(name: &'static str, chart: RwSignal<Chart>) -> impl IntoView {
+    let id = name;
+    let label = name.to_uppercase();
+    let checked = move || {
+        global_line_visibility().with(|v| match name {
+            "sma20" => v.sma_20,
+            "sma50" => v.sma_50,
+            "sma200" => v.sma_200,
+            "ema12" => v.ema_12,
+            "ema26" => v.ema_26,
+            "session" => v.session_levels,
+            "rsi" => v.rsi_14,
+            "macd" => v.macd,
+            "bollinger" => v.bollinger,
+            "vwap" => v.vwap,
+            "depth" => v.depth,
+            "stochastic" => v.stochastic,
+            "atr" => v.atr,
+            "keltner" => v.keltner,
+            "volume_ma" => v.volume_ma,
+            "gaps" => v.gaps,
+            _ => true,
+        })
+    };
+    view! {
+        <label style="display:flex;align-items:center;gap:4px;">
+            <input
+                type="checkbox"
+                id=id
+                prop:checked=checked
+                on:change=move |_| toggle_indicator(name, chart)
+            />
+            {label}
+        </label>
+    }
+}
+
+/// Show or hide the `toggle_line_visibility` line `name` and redraw `chart`
+fn toggle_indicator(name: &str, chart: RwSignal<Chart>) {
+    chart.with_untracked(|c| {
+        with_active_renderers(|r| {
+            r.geometry_mut().toggle_line_visibility(name);
+            let _ = r.render(c);
+        });
+    });
+}
+
+/// 🏷️ Legend value with thousands separators, e.g. `43,210.50`
+pub fn format_indicator_value(value: f64) -> String {
+    let decimals = if value.abs() >= 1.0 { 2 } else { 5 };
+    let text = format!("{value:.decimals$}");
+    let (sign, digits) = text.split_at(usize::from(text.starts_with('-')));
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let mut grouped = String::from(sign);
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{grouped}.{fraction}")
+}
+
+/// 🏷️ Legend line such as `SMA 20: 43,210.50`; bands list all their lines
+pub fn indicator_legend_text(value: &IndicatorValue) -> String {
+    let values: Vec<String> = value.values.iter().map(|v| format_indicator_value(*v)).collect();
+    format!("{}: {}", value.label, values.join(" / "))
+}
+
+/// 🏷️ Visible indicators with their latest values over the chart's top-left corner
+///
+/// Click an entry to hide its line.
+#[component]
+fn IndicatorLegend(chart: RwSignal<Chart>) -> impl IntoView {
+    // Published by the renderer after each rebuilt frame, so ticks, toggles and theme changes show up
+    let entries = move || {
+        indicator_values().with(|values| {
+            values
+                .iter()
+                .map(|v| (v.toggle, indicator_legend_text(v), css_hex(v.color)))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <div class="indicator-legend">
+            <For
+                each=entries
+                key=|entry| entry.clone()
+                children=move |(toggle, text, color)| view! {
+                    <div
+                        class="indicator-legend-entry"
+                        style:cursor=if toggle.is_some() { "pointer" } else { "default" }
+                        on:click=move |_| {
+                            if let Some(name) = toggle {
+                                toggle_indicator(name, chart);
+                            }
+                        }
+                    >
+                        <span class="indicator-legend-swatch" style:background=color></span>
+                        {text}
+                    </div>
+                }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn PriceRoundingToggle(chart: RwSignal<Chart>) -> impl IntoView {
+    view! {
+        <label style="display:flex;align-items:center;gap:4px;">
+            <input
+                type="checkbox"
+                id="price-rounding"
+                prop:checked=move || price_rounding().get()
+                on:change=move |_| {
+                    price_rounding().update(|r| *r = !*r);
+                    apply_price_precision();
+                    chart.with_untracked(|c| {
+                        with_active_renderers(|r| {
+                            let _ = r.render(c);
+                        });
+                    });
+                }
+            />
+            "ROUND TO TICK"
+        </label>
+    }
+}
+
+#[component]
+fn PanInertiaToggle() -> impl IntoView {
+    view! {
+        <label style="display:flex;align-items:center;gap:4px;">
+            <input
+                type="checkbox"
+                id="pan-inertia"
+                prop:checked=move || pan_inertia_enabled().get()
+                on:change=move |_| {
+                    pan_inertia_enabled().update(|enabled| *enabled = !*enabled);
+                    cancel_pan_animation();
+                }
+            />
+            "INERTIA"
+        </label>
+    }
+}
+
+/// 🎞️ Ease candle updates; off restores instant updates for low-power devices
+#[component]
+fn AnimationToggle() -> impl IntoView {
+    view! {
+        <label style="display:flex;align-items:center;gap:4px;">
+            <input
+                type="checkbox"
+                id="animate-transitions"
+                prop:checked=move || animate_transitions().get()
+                on:change=move |_| {
+                    animate_transitions().update(|enabled| *enabled = !*enabled);
+                    let enabled = animate_transitions().get_untracked();
+                    with_active_renderers(|r| r.geometry_mut().set_animate_transitions(enabled));
+                    redraw_current_chart();
+                }
+            />
+            "ANIMATE"
+        </label>
+    }
+}
+
+/// ↔️ Space candles by open time so missing candles leave blank space
+#[component]
+fn TimeAxisToggle() -> impl IntoView {
+    view! {
+        <label style="display:flex;align-items:center;gap:4px;">
+            <input
+                type="checkbox"
+                id="time-x-axis"
+                prop:checked=move || x_axis_mode().get() == XAxisMode::Time
+                on:change=move |_| {
+                    x_axis_mode().update(|mode| {
+                        *mode = match mode {
+                            XAxisMode::Ordinal => XAxisMode::Time,
+                            XAxisMode::Time => XAxisMode::Ordinal,
+                        }
+                    });
+                    let mode = x_axis_mode().get_untracked();
+                    with_active_renderers(|r| r.geometry_mut().set_x_axis_mode(mode));
+                    redraw_current_chart();
+                }
+            />
+            "TIME X-AXIS"
+        </label>
+    }
+}
+
+/// ⏭️ Floating button shown while the view is away from the newest candle
+#[component]
+fn GoToLatestButton(set_status: WriteSignal<String>) -> impl IntoView {
+    let off_edge = move || {
+        global_candle_count().track();
+        current_symbol().track();
+        current_interval().track();
+        let len = current_candle_len();
+        len > 0 && !should_auto_scroll(len, zoom_level().get(), pan_offset().get())
+    };
+
+    view! {
+        <Show when=off_edge>
+            <button
+                class="go-to-latest"
+                style="position:absolute;right:12px;bottom:12px;padding:4px 10px;border:none;border-radius:12px;background:#2a5298;color:white;cursor:pointer;opacity:0.9;"
+                on:click=move |_| animate_to_latest(set_status)
+            >
+                "Go to latest ›"
+            </button>
+        </Show>
+    }
+}
+
+/// Dragging the minimap window either pans or resizes the view
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MinimapDrag {
+    Move,
+    Resize,
+}
+
+#[component]
+fn Minimap(set_status: WriteSignal<String>) -> impl IntoView {
+    let canvas_ref = create_node_ref::<Canvas>();
+    let drag = create_rw_signal::<Option<(MinimapDrag, f64)>>(None);
+
+    // Create the strip's own lightweight renderer once the canvas exists
+    create_effect(move |initialized: Option<bool>| {
+        if initialized == Some(true) {
+            return true;
+        }
+        let Some(canvas) = canvas_ref.get() else {
+            return false;
+        };
+        let canvas_id = std::ops::Deref::deref(&canvas).id();
+        let _ = spawn_local_with_current_owner(async move {
+            match WebGpuRenderer::new(&canvas_id, CHART_WIDTH as u32, MINIMAP_HEIGHT as u32).await {
+                Ok(r) => {
+                    MINIMAP_RENDERER.with(|cell| *cell.borrow_mut() = Some(r));
+                    redraw_minimap();
                 }
+                Err(e) => get_logger().error(
+                    LogComponent::Infrastructure("Minimap"),
+                    &format!("❌ Minimap renderer failed: {e}"),
+                ),
             }
+        });
+        true
+    });
+
+    // Follow history loads, new candles and symbol/interval switches
+    create_effect(move |_| {
+        global_candle_count().track();
+        current_symbol().track();
+        current_interval().track();
+        redraw_minimap();
+    });
+
+    let window_style = move || {
+        global_candle_count().track();
+        current_symbol().track();
+        let (left, width) =
+            minimap_window(current_candle_len(), zoom_level().get(), pan_offset().get());
+        format!(
+            "position:absolute;top:0;left:{:.3}%;width:{:.3}%;height:100%;box-sizing:border-box;\
+             border:1px solid #8ab4f8;background:rgba(138,180,248,0.15);cursor:grab;",
+            left * 100.0,
+            width * 100.0
+        )
+    };
+
+    let start_drag = move |mode: MinimapDrag, event: web_sys::MouseEvent| {
+        if event.button() != 0 {
+            return;
         }
+        event.prevent_default();
+        event.stop_propagation();
+        cancel_pan_animation();
+        drag.set(Some((mode, event.client_x() as f64)));
     };
 
-    // Attach wheel event listener to the window
-    let wheel_listener = window_event_listener_with_options(
-        ev::wheel,
-        &EventOptions { passive: false, capture: false, once: false },
-        handle_wheel,
-    );
-    on_cleanup(move || wheel_listener.remove());
+    let move_listener =
+        window_event_listener_with_options(ev::mousemove, &EventOptions::default(), move |event| {
+            let Some((mode, last_x)) = drag.get_untracked() else {
+                return;
+            };
+            let x = event.client_x() as f64;
+            let delta_px = x - last_x;
+            drag.set(Some((mode, x)));
+            let len = current_candle_len();
+            if len == 0 || delta_px == 0.0 {
+                return;
+            }
+            match mode {
+                MinimapDrag::Move => {
+                    let zoom = zoom_level().get_untracked();
+                    apply_pan(minimap_drag_delta(delta_px, CHART_WIDTH, len, zoom), set_status);
+                }
+                MinimapDrag::Resize => {
+                    // The right edge stays put; moving the left handle changes the width
+                    let (_, width) = minimap_window(
+                        len,
+                        zoom_level().get_untracked(),
+                        pan_offset().get_untracked(),
+                    );
+                    let width = width - delta_px / CHART_WIDTH;
+                    zoom_level().set(minimap_zoom_for_width(width, len));
+                    redraw_current_chart();
+                }
+            }
+        });
+    on_cleanup(move || move_listener.remove());
 
-    // Reset dragging state when the mouse is released anywhere
-    let mouseup_listener =
+    let up_listener =
         window_event_listener_with_options(ev::mouseup, &EventOptions::default(), move |_| {
-            is_dragging().set(false)
+            drag.set(None)
         });
-    on_cleanup(move || mouseup_listener.remove());
-
-    // Zoom effect removed - handled directly in the wheel handler
+    on_cleanup(move || up_listener.remove());
 
     view! {
-        <div class="chart-container">
-            <div style="display:flex;justify-content:space-between;margin-bottom:8px;width:800px;">
-                <AssetSelector set_status=set_status />
-                <div style="display:flex;gap:6px;">
-                    <TimeframeSelector chart=chart() />
-                </div>
-            </div>
-
-            <div style="display: flex; flex-direction: row; align-items: flex-start;">
-                <PriceAxisLeft chart=chart() />
-                <div style="position: relative;">
-                    <canvas
-                        id="chart-canvas"
-                        node_ref=canvas_ref
-                        use:wheel_event_options=&EventOptions { passive: false, capture: false, once: false }
-                        width="800"
-                        height="500"
-                        tabindex="0"
-                        style="border: 2px solid #4a5d73; border-radius: 10px; background: #253242; cursor: crosshair; outline: none;"
-                        on:mousemove=handle_mouse_move
-                        on:mouseleave=handle_mouse_leave
-                        on:mousedown=handle_mouse_down
-                        on:mouseup=handle_mouse_up
-                        on:keydown=handle_keydown
-                    />
-                    <PriceScale chart=chart() />
-                    <ChartTooltip />
-                </div>
-            </div>
-
-            <Legend chart=chart() />
-
-            // Time scale below the chart
-            <div style="display: flex; justify-content: center; margin-top: 10px;">
-                <TimeScale chart=chart() />
-            </div>
-
-            <div class="status">
-                {move || status.get()}
-            </div>
-
-            // Control hints
-            <div style="text-align: center; margin-top: 10px; font-size: 12px; color: #888;">
-                "🔍 Zoom: Mouse wheel, +/- keys, PageUp/PageDown | 🖱️ Pan: Left click + drag | 🎯 Tooltip: Mouse hover"
+        <div style="position:relative;width:800px;height:40px;margin-top:6px;">
+            <canvas
+                id=MINIMAP_CANVAS_ID
+                node_ref=canvas_ref
+                width="800"
+                height="40"
+                style="display:block;border-radius:4px;background:#1e2a38;"
+            />
+            <div
+                class="minimap-window"
+                style=window_style
+                on:mousedown=move |e| start_drag(MinimapDrag::Move, e)
+            >
+                <div
+                    class="minimap-handle"
+                    style="position:absolute;left:-3px;top:0;width:6px;height:100%;cursor:ew-resize;"
+                    on:mousedown=move |e| start_drag(MinimapDrag::Resize, e)
+                />
             </div>
         </div>
     }
 }
 
-/// 💰 Price scale on the right side of the chart
 #[component]
-fn PriceScale(chart: RwSignal<Chart>) -> impl IntoView {
-    let current_price = global_current_price();
-
-    // Calculate price levels for display (same as in the grid)
-    let price_levels = move || {
-        let vp = chart.with(|c| c.viewport.clone());
-        let levels = price_levels(&vp);
-        let step = 100.0 / 8.0;
-        levels
-            .into_iter()
-            .enumerate()
-            .map(|(i, level_price)| (level_price, i as f64 * step))
-            .collect::<Vec<_>>()
-    };
-
+fn Legend(chart: RwSignal<Chart>) -> impl IntoView {
+    let names = vec![
+        "sma20",
+        "sma50",
+        "sma200",
+        "ema12",
+        "ema26",
+        "session",
+        "rsi",
+        "macd",
+        "bollinger",
+        "vwap",
+        "depth",
+        "stochastic",
+        "atr",
+        "keltner",
+        "volume_ma",
+        "gaps",
+    ];
     view! {
-        <div class="price-scale">
-            // Display price levels
+        <div style="display:flex;gap:6px;margin-top:8px;">
             <For
-                each=price_levels
-                key=|(_price, pos)| (*pos * 100.0) as i64
-                children=|(price, position)| view! {
-                    <div
-                        class="price-level"
-                        style=format!("position: absolute; top: {}%; right: 5px; transform: translateY(-50%); font-size: 11px; color: #888; background: rgba(0,0,0,0.7); padding: 2px 4px; border-radius: 2px;", position)
-                    >
-                        {format!("{:.2}", price)}
-                    </div>
-                }
+                each=move || names.clone()
+                key=|name| name.to_string()
+                children=move |name| view! { <LegendIndicatorToggle name=name chart=chart /> }
             />
-
-            // Display the current price (highlighted)
-            <div class="current-price-label" style=format!("top: 50%")>
-                <span class="price-value">{move || format!("${:.2}", current_price.get())}</span>
-            </div>
+            <PriceRoundingToggle chart=chart />
+            <PanInertiaToggle />
+            <AnimationToggle />
+            <TimeAxisToggle />
         </div>
     }
 }
 
-/// 🎯 Chart Tooltip component inside the chart wrapper
 #[component]
-fn ChartTooltip() -> impl IntoView {
-    let tooltip_visible = tooltip_visible();
-    let tooltip_data = tooltip_data();
+fn AssetSelector(set_status: WriteSignal<String>) -> impl IntoView {
+    let options = default_symbols();
 
     view! {
-        <div
-            class="tooltip"
-            style:display=move || if tooltip_visible.get() { "block" } else { "none" }
-            style:left=move || {
-                tooltip_data.with(|data| {
-                    if let Some(tooltip) = data {
-                        format!("{}px", tooltip.x)
-                    } else {
-                        "0px".to_string()
-                    }
-                })
-            }
-            style:top=move || {
-                tooltip_data.with(|data| {
-                    if let Some(tooltip) = data {
-                        format!("{}px", tooltip.y)
-                    } else {
-                        "0px".to_string()
-                    }
-                })
-            }
-        >
-            {move || {
-                tooltip_data.with(|data| {
-                    if let Some(tooltip) = data {
-                        tooltip.formatted_text.clone()
-                    } else {
-                        String::new()
+        <div style="display:flex;gap:6px;margin-top:8px;">
+            <For
+                each=move || options.clone()
+                key=|s: &Symbol| s.value().to_string()
+                children=move |sym: Symbol| {
+                    let label = sym.value().to_string();
+                    let status_cloned = set_status;
+                    view! {
+                        <button
+                            style="padding:4px 6px;border:none;border-radius:4px;background:#2a5298;color:white;"
+                            on:click=move |_| switch_symbol(sym.clone(), status_cloned)
+                        >
+                            {label}
+                        </button>
                     }
-                })
-            }}
+                }
+            />
         </div>
     }
 }
 
+/// 📈 Switch between candle, Heikin-Ashi, line and area rendering
 #[component]
-fn TimeframeSelector(chart: RwSignal<Chart>) -> impl IntoView {
+fn ChartTypeSelector(chart: RwSignal<Chart>) -> impl IntoView {
     let options = vec![
-        TimeInterval::TwoSeconds,
-        TimeInterval::OneMinute,
-        TimeInterval::FiveMinutes,
-        TimeInterval::FifteenMinutes,
-        TimeInterval::OneHour,
+        ChartType::Candlestick,
+        ChartType::HeikinAshi,
+        ChartType::OhlcBar,
+        ChartType::Line,
+        ChartType::Area,
     ];
 
     view! {
         <div style="display:flex;gap:6px;margin-top:8px;">
             <For
                 each=move || options.clone()
-                key=|i| i.as_ref().to_string()
-                children=move |interval| {
-                    let label = interval.as_ref().to_string();
-                    let chart_signal = chart;
+                key=|t| t.as_ref().to_string()
+                children=move |chart_type| {
                     view! {
                         <button
                             style="padding:4px 6px;border:none;border-radius:4px;background:#74c787;color:black;"
                             on:click=move |_| {
-                                current_interval().set(interval);
-                                chart_signal.update(|c| c.update_viewport_for_data());
-                                chart_signal.with_untracked(|c| {
-                                    if c.get_candle_count() > 0 && with_global_renderer(|r| {
-                                            r.set_zoom_params(
-                                                zoom_level().with_untracked(|z| *z),
-                                                pan_offset().with_untracked(|p| *p),
-                                            );
-                                            let _ = r.render(c);
-                                        }).is_none() {
-                                        // renderer not available
-                                    }
-                                });
+                                chart.update(|c| c.chart_type = chart_type);
+                                redraw_current_chart();
                             }
                         >
-                            {label}
+                            {chart_type.to_string()}
                         </button>
                     }
                 }
@@ -1073,85 +3333,318 @@ fn TimeframeSelector(chart: RwSignal<Chart>) -> impl IntoView {
     }
 }
 
+/// 📄 Download the current series as CSV, either the visible window or all loaded candles
 #[component]
-fn LegendIndicatorToggle(name: &'static str, chart: RwSignal<Chart>) -> impl IntoView {
-    let id = name;
-    let label = name.to_uppercase();
-    let checked = move || {
-        global_line_visibility().with(|v| match name {
-            "sma20" => v.sma_20,
-            "sma50" => v.sma_50,
-            "sma200" => v.sma_200,
-            "ema12" => v.ema_12,
-            "ema26" => v.ema_26,
-            _ => true,
-        })
+fn CsvExportButtons() -> impl IntoView {
+    view! {
+        <div style="display:flex;gap:6px;margin-top:8px;">
+            <button
+                style="padding:4px 6px;border:none;border-radius:4px;background:#74c787;color:black;"
+                on:click=move |_| download_csv(true)
+            >
+                "CSV VIEW"
+            </button>
+            <button
+                style="padding:4px 6px;border:none;border-radius:4px;background:#74c787;color:black;"
+                on:click=move |_| download_csv(false)
+            >
+                "CSV ALL"
+            </button>
+        </div>
+    }
+}
+
+/// Save the current symbol's candles at the current interval as a CSV file
+fn download_csv(visible_only: bool) {
+    let symbol = current_symbol().get_untracked();
+    let interval = current_interval().get_untracked();
+    let window = visible_only.then(|| {
+        visible_range(
+            current_candle_len(),
+            zoom_level().get_untracked(),
+            pan_offset().get_untracked(),
+        )
+    });
+    let csv = ensure_chart(&symbol).with_untracked(|c| c.to_csv(interval, window));
+    let file_name = format!("{}-{}.csv", symbol.value(), interval.as_ref());
+    if let Err(e) = save_text_file(&file_name, &csv, "text/csv") {
+        get_logger().error(
+            LogComponent::Presentation("CsvExport"),
+            &format!("❌ CSV export failed: {e:?}"),
+        );
+    }
+}
+
+/// Offer `contents` as a browser download named `file_name`
+fn save_text_file(
+    file_name: &str,
+    contents: &str,
+    mime: &str,
+) -> Result<(), wasm_bindgen::JsValue> {
+    let parts = js_sys::Array::of1(&contents.into());
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let anchor = document().create_element("a")?.dyn_into::<web_sys::HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// Quiet period (ms) after the last keystroke before the log search applies
+pub const LOG_SEARCH_DEBOUNCE_MS: u32 = 250;
+
+/// How often (ms) the debug console checks for new log lines
+const LOG_POLL_MS: u32 = 500;
+
+/// 🐞 Recent log lines with a case-insensitive search and a copy button
+#[component]
+fn DebugConsole() -> impl IntoView {
+    let logs = create_rw_signal(recent_logs());
+    let (query, set_query) = create_signal(String::new());
+    let filtered = create_memo(move |_| logs.with(|lines| filter_logs(lines, &query.get())));
+
+    // The logger is not reactive, so pick up new lines by polling its revision
+    let seen = Cell::new(logs_revision());
+    let poll = gloo_timers::callback::Interval::new(LOG_POLL_MS, move || {
+        let revision = logs_revision();
+        if revision != seen.replace(revision) {
+            logs.set(recent_logs());
+        }
+    });
+    on_cleanup(move || drop(poll));
+
+    // Replacing the pending timeout cancels it, so only the last edit lands
+    let pending_search = Rc::new(RefCell::new(None::<gloo_timers::callback::Timeout>));
+    let handle_search = move |ev: web_sys::Event| {
+        let text = event_target_value(&ev);
+        let timeout = gloo_timers::callback::Timeout::new(LOG_SEARCH_DEBOUNCE_MS, move || {
+            set_query.set(text);
+        });
+        pending_search.borrow_mut().replace(timeout);
     };
+
     view! {
-        <label style="display:flex;align-items:center;gap:4px;">
-            <input
-                type="checkbox"
-                id=id
-                prop:checked=checked
-                on:change=move |_| {
-                    chart.with_untracked(|c| {
-                        if with_global_renderer(|r| {
-                            r.toggle_line_visibility(name);
-                            let _ = r.render(c);
-                        }).is_none() {
-                            // renderer not available
-                        }
-                    });
+        <div style="width:800px;margin-top:10px;font-size:11px;">
+            <div style="display:flex;gap:6px;margin-bottom:4px;">
+                <input
+                    type="search"
+                    placeholder="Filter logs"
+                    style="flex:1;padding:4px 6px;border-radius:4px;border:1px solid #4a5d73;background:#253242;color:white;"
+                    on:input=handle_search
+                />
+                <button
+                    style="padding:4px 6px;border:none;border-radius:4px;background:#74c787;color:black;"
+                    on:click=move |_| copy_logs(filtered.get_untracked().join("\n"))
+                >
+                    "Copy logs"
+                </button>
+            </div>
+            <pre style="max-height:160px;overflow-y:auto;margin:0;padding:6px;background:rgba(0,0,0,0.4);border-radius:4px;text-align:left;white-space:pre-wrap;">
+                {move || filtered.get().join("\n")}
+            </pre>
+        </div>
+    }
+}
+
+/// Write `text` to the clipboard, logging a warning if the browser refuses
+fn copy_logs(text: String) {
+    let navigator = window().navigator();
+    // `navigator.clipboard` is missing outside secure contexts
+    let available = js_sys::Reflect::has(&navigator, &"clipboard".into()).unwrap_or(false);
+    if !available {
+        get_logger().warn(
+            LogComponent::Presentation("DebugConsole"),
+            "⚠️ Clipboard API unavailable, logs not copied",
+        );
+        return;
+    }
+    let promise = navigator.clipboard().write_text(&text);
+    spawn_local(async move {
+        if let Err(e) = wasm_bindgen_futures::JsFuture::from(promise).await {
+            get_logger().warn(
+                LogComponent::Presentation("DebugConsole"),
+                &format!("⚠️ Clipboard write rejected: {e:?}"),
+            );
+        }
+    });
+}
+
+/// Kline interval streamed from Binance while viewing `interval`
+///
+/// `TwoSeconds` has no Binance kline and is served from the one-minute feed.
+pub fn stream_interval(interval: TimeInterval) -> TimeInterval {
+    if interval.is_binance_kline() { interval } else { TimeInterval::OneMinute }
+}
+
+/// ⏱️ Chart `interval` candles
+///
+/// A new kline interval reconnects the stream and reloads history at that
+/// interval; otherwise only the displayed series of the running stream changes.
+pub fn switch_interval(
+    interval: TimeInterval,
+    chart: RwSignal<Chart>,
+    set_status: WriteSignal<String>,
+) {
+    let previous = current_interval().get_untracked();
+    if previous == interval || !exchange().get_untracked().supports_interval(interval) {
+        return;
+    }
+    current_interval().set(interval);
+    if stream_interval(interval) == stream_interval(previous) {
+        chart.update(|c| c.recompute_viewport());
+        redraw_current_chart();
+        return;
+    }
+
+    let symbol = current_symbol().get_untracked();
+    stream_abort_handles().update(|m| {
+        if let Some(handle) = m.remove(&symbol) {
+            handle.abort();
+        }
+    });
+    chart.update(|ch| ch.set_historical_data(Vec::new()));
+    global_candle_count().set(0);
+    tooltip_visible().set(false);
+    selected_candle().set(None);
+    let _ = spawn_local_with_current_owner(async move {
+        start_websocket_stream(set_status).await;
+    });
+}
+
+/// 🎨 Switch the color scheme; the choice is saved with the settings
+pub fn set_theme_preset(preset: ThemePreset) {
+    theme_preset().set(preset);
+    with_active_renderers(|r| r.geometry_mut().set_theme(preset.palette()));
+    redraw_current_chart();
+}
+
+/// ⬛ Stretch short candle bodies to `min_height`, or draw dojis as thin lines
+///
+/// The choice is saved with the settings. Heights outside
+/// `0..=MAX_MIN_BODY_HEIGHT` are ignored.
+pub fn set_min_body_height(min_height: MinBodyHeight) {
+    if !min_height.is_valid() {
+        get_logger().warn(
+            LogComponent::Presentation("Settings"),
+            &format!("⚠️ Ignored minimum body height {min_height:?}"),
+        );
+        return;
+    }
+    min_body_height().set(min_height);
+    with_active_renderers(|r| r.geometry_mut().set_min_body_height(min_height));
+    redraw_current_chart();
+}
+
+/// 🎨 Dropdown in the header choosing the color scheme
+#[component]
+fn ThemeSelect() -> impl IntoView {
+    let label = |preset: ThemePreset| match preset {
+        ThemePreset::Dark => "🌙 Dark",
+        ThemePreset::Light => "☀️ Light",
+        ThemePreset::Colorblind => "👁️ Colorblind",
+    };
+
+    view! {
+        <select
+            id="theme-select"
+            style="padding:4px 6px;margin-left:6px;border:none;border-radius:4px;background:#2a5298;color:white;"
+            prop:value=move || theme_preset().get().name()
+            on:change=move |ev| {
+                if let Some(preset) = ThemePreset::from_name(&event_target_value(&ev)) {
+                    set_theme_preset(preset);
                 }
-            />
-            {label}
-        </label>
+            }
+        >
+            {ThemePreset::ALL
+                .into_iter()
+                .map(|preset| view! { <option value=preset.name()>{label(preset)}</option> })
+                .collect_view()}
+        </select>
     }
 }
 
+/// 🔬 Request `count` MSAA samples; the GPU may settle on fewer
+///
+/// The switch runs on the render queue between two frames and
+/// [`msaa_samples`] then holds the count actually in use. Ignored by the
+/// canvas 2D fallback.
+pub fn set_msaa_samples(count: u32) {
+    enqueue_render_task(Box::new(move |r| {
+        msaa_samples().set(r.set_msaa_sample_count(count));
+        redraw_current_chart();
+    }));
+}
+
+/// 🔬 Dropdown in the header choosing the antialiasing quality
 #[component]
-fn Legend(chart: RwSignal<Chart>) -> impl IntoView {
-    let names = vec!["sma20", "sma50", "sma200", "ema12", "ema26"];
+fn MsaaSelect() -> impl IntoView {
+    let label =
+        |count: u32| if count == 1 { "MSAA off".to_string() } else { format!("MSAA {count}x") };
+
     view! {
-        <div style="display:flex;gap:6px;margin-top:8px;">
-            <For
-                each=move || names.clone()
-                key=|name| name.to_string()
-                children=move |name| view! { <LegendIndicatorToggle name=name chart=chart /> }
-            />
-        </div>
+        <select
+            id="msaa-select"
+            style="padding:4px 6px;margin-left:6px;border:none;border-radius:4px;background:#2a5298;color:white;"
+            prop:value=move || msaa_samples().get().to_string()
+            on:change=move |ev| {
+                if let Ok(count) = event_target_value(&ev).parse() {
+                    set_msaa_samples(count);
+                }
+            }
+        >
+            {MSAA_SAMPLE_OPTIONS
+                .into_iter()
+                .map(|count| view! { <option value=count.to_string()>{label(count)}</option> })
+                .collect_view()}
+        </select>
     }
 }
 
+/// 🔀 Dropdown in the header switching the charted symbol
 #[component]
-fn AssetSelector(set_status: WriteSignal<String>) -> impl IntoView {
+fn SymbolSelect(set_status: WriteSignal<String>) -> impl IntoView {
     let options = default_symbols();
 
     view! {
-        <div style="display:flex;gap:6px;margin-top:8px;">
-            <For
-                each=move || options.clone()
-                key=|s: &Symbol| s.value().to_string()
-                children=move |sym: Symbol| {
-                    let label = sym.value().to_string();
-                    let status_cloned = set_status;
-                    view! {
-                        <button
-                            style="padding:4px 6px;border:none;border-radius:4px;background:#2a5298;color:white;"
-                            on:click=move |_| {
-                                current_symbol().set(sym.clone());
-                                let _ = spawn_local_with_current_owner(async move {
-                                    start_websocket_stream(status_cloned).await;
-                                });
-                            }
-                        >
-                            {label}
-                        </button>
-                    }
-                }
-            />
-        </div>
+        <select
+            id="symbol-select"
+            style="padding:4px 6px;border:none;border-radius:4px;background:#2a5298;color:white;"
+            prop:value=move || current_symbol().get().value().to_string()
+            on:change=move |ev| {
+                switch_symbol(Symbol::from(event_target_value(&ev).as_str()), set_status)
+            }
+        >
+            {options
+                .into_iter()
+                .map(|sym| {
+                    let value = sym.value().to_string();
+                    view! { <option value=value.clone()>{value}</option> }
+                })
+                .collect_view()}
+        </select>
+    }
+}
+
+/// 🔀 Chart `symbol` instead of the current one and restart the live stream
+///
+/// The old stream is aborted first, so its handler drops any candle still in
+/// flight before history for the new symbol is fetched into a cleared chart.
+pub fn switch_symbol(symbol: Symbol, set_status: WriteSignal<String>) {
+    if current_symbol().get_untracked() == symbol {
+        return;
     }
+    abort_other_streams(&symbol);
+    ensure_chart(&symbol).update(|ch| ch.set_historical_data(Vec::new()));
+    global_candle_count().set(0);
+    tooltip_visible().set(false);
+    selected_candle().set(None);
+    current_symbol().set(symbol);
+    let _ = spawn_local_with_current_owner(async move {
+        start_websocket_stream(set_status).await;
+    });
 }
 
 /// Abort all active streams except the one for `symbol`.
@@ -1168,6 +3661,258 @@ pub fn abort_other_streams(symbol: &Symbol) {
     });
 }
 
+/// 🔁 Drop the current stream and run the load sequence again
+pub fn retry_data_load(set_status: WriteSignal<String>) {
+    let symbol = current_symbol().get_untracked();
+    stream_abort_handles().update(|m| {
+        if let Some(handle) = m.remove(&symbol) {
+            handle.abort();
+        }
+    });
+    load_failed().set(false);
+    let _ = spawn_local_with_current_owner(async move {
+        start_websocket_stream(set_status).await;
+    });
+}
+
+/// 📜 Replace the candles of `chart` with `candles`, draw them and refresh the header signals
+fn apply_history(symbol: &Symbol, chart: RwSignal<Chart>, candles: Vec<Candle>) {
+    // Update global signals using the historical data
+    if let Some(last_candle) = candles.last() {
+        global_current_price().set(last_candle.ohlcv.close.value());
+    }
+    // Compute the maximum volume from history
+    let max_vol = candles.iter().map(|c| c.ohlcv.volume.value()).fold(0.0f64, |a, b| a.max(b));
+    global_max_volume().set(max_vol);
+
+    chart.update(|ch| ch.set_historical_data(candles));
+    chart.with_untracked(|c| set_chart_in_ecs(symbol, c.clone()));
+    chart.with_untracked(|c| {
+        if c.get_candle_count() > 0
+            && with_global_renderer(|r| {
+                r.geometry_mut().set_zoom_params(
+                    zoom_level().with_untracked(|z| *z),
+                    pan_offset().with_untracked(|p| *p),
+                );
+                let _ = r.render(c);
+            })
+            .is_none()
+        {
+            // renderer not available
+        }
+    });
+
+    let cnt = chart.with(|c| c.get_candle_count());
+    global_candle_count().set(cnt);
+    load_failed().set(false);
+}
+
+/// 📥 Stop the exchange stream of the current symbol so only embedder candles are shown
+fn detach_exchange_stream(symbol: &Symbol) {
+    stream_abort_handles().update(|m| {
+        if let Some(handle) = m.remove(symbol) {
+            handle.abort();
+        }
+    });
+    report_connection_state(ConnectionState::Offline);
+}
+
+/// 🕳️ Whether a chart without candles is still loading or has nothing to show
+pub fn empty_chart_state(connection: &ConnectionState, load_failed: bool) -> EmptyChartState {
+    match connection {
+        _ if load_failed => EmptyChartState::NoData,
+        ConnectionState::Live | ConnectionState::Paused | ConnectionState::Error(_) => {
+            EmptyChartState::NoData
+        }
+        _ => EmptyChartState::Loading,
+    }
+}
+
+/// 🚦 Record the live stream's state; the header keeps showing `Paused` while paused
+pub fn report_connection_state(state: ConnectionState) {
+    REPORTED_CONNECTION.with(|s| *s.borrow_mut() = state.clone());
+    if !stream_paused().get_untracked() {
+        connection_state().set(state);
+    }
+}
+
+/// 📥 Add one candle from an embedder's own feed to the current chart
+///
+/// The exchange stream is stopped first. A candle with the open time of the
+/// last one replaces it, like a forming-candle update; a later one appends.
+/// While paused it is buffered like a live candle.
+pub fn push_candle(candle: Candle) {
+    let symbol = current_symbol().get_untracked();
+    detach_exchange_stream(&symbol);
+    if stream_paused().get_untracked() {
+        PAUSED_CANDLES.with(|b| b.borrow_mut().push(&symbol, candle));
+        return;
+    }
+    global_current_price().set(candle.ohlcv.close.value());
+    apply_live_candle(&symbol, ensure_chart(&symbol), candle);
+    schedule_frame_render();
+}
+
+/// 📥 Replace the current chart's candles with an embedder's data set
+pub fn set_candles(candles: Vec<Candle>) {
+    let symbol = current_symbol().get_untracked();
+    detach_exchange_stream(&symbol);
+    apply_history(&symbol, ensure_chart(&symbol), candles);
+}
+
+/// 📈 Merge a live `candle` into `chart` and refresh the candle count and max volume
+fn apply_live_candle(symbol: &Symbol, chart: RwSignal<Chart>, candle: Candle) {
+    // The displayed interval may be an aggregate of the streamed one
+    let shown = current_interval().get_untracked();
+    chart.update(|ch| {
+        let series_len = |ch: &Chart| ch.get_series(shown).map_or(0, |s| s.get_candles().len());
+        let before = series_len(ch);
+        ch.add_realtime_candle(candle.clone());
+        let added = series_len(ch).saturating_sub(before);
+        let scrolling = auto_scroll().get_untracked();
+        if scrolling {
+            ch.recompute_viewport();
+        } else if added > 0 {
+            // Keep the studied range in place instead of yanking to the latest
+            pan_offset().update(|p| *p = pan_after_new_candles(*p, added, scrolling));
+        }
+    });
+    crate::global_state::push_realtime_candle(symbol, candle);
+
+    let count = chart.with(|c| c.get_candle_count());
+    global_candle_count().set(count);
+
+    let max_vol = chart.with(|c| {
+        c.get_series(shown).map_or(0.0, |s| {
+            s.get_candles().iter().map(|c| c.ohlcv.volume.value()).fold(0.0f64, |a, b| a.max(b))
+        })
+    });
+    global_max_volume().set(max_vol);
+}
+
+/// ⏸️ Freeze the chart for inspection; live candles are buffered meanwhile
+///
+/// Price alerts still fire while paused. Tooltip and crosshair keep working
+/// on the frozen candles.
+pub fn pause_streaming() {
+    if stream_paused().get_untracked() {
+        return;
+    }
+    stream_paused().set(true);
+    connection_state().set(ConnectionState::Paused);
+}
+
+/// ▶️ Replay every candle missed while paused, then continue live
+///
+/// The replay goes through the same path as live updates, so the view jumps
+/// to the latest candle when auto-scroll is on and stays put otherwise.
+pub fn resume_streaming() {
+    if !stream_paused().get_untracked() {
+        return;
+    }
+    stream_paused().set(false);
+    let symbol = current_symbol().get_untracked();
+    let missed = PAUSED_CANDLES.with(|b| b.borrow_mut().drain(&symbol));
+    if let Some(last) = missed.last() {
+        let chart = ensure_chart(&symbol);
+        let close = last.ohlcv.close.value();
+        batch(|| {
+            for candle in missed {
+                apply_live_candle(&symbol, chart, candle);
+            }
+            global_current_price().set(close);
+        });
+    }
+    // Whatever the stream reported while paused
+    connection_state().set(REPORTED_CONNECTION.with(|s| s.borrow().clone()));
+    redraw_current_chart();
+}
+
+/// ⏸️ Header button pausing and resuming live updates
+#[component]
+fn PauseButton() -> impl IntoView {
+    view! {
+        <button
+            id="pause-stream"
+            style="padding:4px 10px;margin-left:6px;border:none;border-radius:4px;background:#2a5298;color:white;cursor:pointer;"
+            on:click=move |_| {
+                if stream_paused().get_untracked() { resume_streaming() } else { pause_streaming() }
+            }
+        >
+            {move || if stream_paused().get() { "▶️ Resume" } else { "⏸️ Pause" }}
+        </button>
+    }
+}
+
+/// 📂 File picker that replaces the chart's candles with a local JSON or CSV file
+#[component]
+fn ImportButton(set_status: WriteSignal<String>) -> impl IntoView {
+    let on_change = move |ev: web_sys::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Let the same file be picked again after editing it
+        input.set_value("");
+        spawn_local(async move {
+            let name = file.name();
+            let text = match wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                Ok(text) => text.as_string().unwrap_or_default(),
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Presentation("Import"),
+                        &format!("❌ Could not read {name}: {e:?}"),
+                    );
+                    return;
+                }
+            };
+            set_status.set(import_file(&name, &text));
+        });
+    };
+
+    view! {
+        <label
+            style="padding:4px 10px;margin-left:6px;border-radius:4px;background:#2a5298;color:white;cursor:pointer;"
+        >
+            "📂 Import"
+            <input type="file" id="import-candles" accept=".json,.csv" style="display:none;" on:change=on_change />
+        </label>
+    }
+}
+
+/// 📂 Load the candles in `text` into the chart and return a status line
+pub fn import_file(name: &str, text: &str) -> String {
+    let report = match import_candles(text) {
+        Ok(report) => report,
+        Err(e) => {
+            get_logger().error(
+                LogComponent::Presentation("Import"),
+                &format!("❌ Failed to import {name}: {e}"),
+            );
+            return format!("❌ Import failed: {e}");
+        }
+    };
+    let skipped = report.errors.len();
+    if report.candles.is_empty() {
+        get_logger().error(
+            LogComponent::Presentation("Import"),
+            &format!("❌ {name} has no valid candles ({skipped} rows skipped)"),
+        );
+        return format!("❌ No valid candles in {name}");
+    }
+    let count = report.candles.len();
+    set_candles(report.candles);
+    get_logger().info(
+        LogComponent::Presentation("Import"),
+        &format!("📂 Imported {count} candles from {name}, {skipped} rows skipped"),
+    );
+    if skipped == 0 {
+        format!("📂 Imported {count} candles")
+    } else {
+        format!("📂 Imported {count} candles ({skipped} rows skipped)")
+    }
+}
+
 /// 🌐 Start WebSocket stream in Leptos and update global signals
 pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
     let symbol = current_symbol().get_untracked();
@@ -1180,59 +3925,57 @@ pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
         return;
     }
 
-    let interval = current_interval().get_untracked();
+    // 🔌 Registered before the first await, so switching away aborts the load too
+    let (abort_handle, abort_reg) = futures::future::AbortHandle::new_pair();
+    let (done_tx, done_rx) = oneshot::channel::<()>();
+    stream_abort_handles().update(|m| {
+        m.insert(symbol.clone(), abort_handle.clone());
+    });
+    on_cleanup({
+        let symbol = symbol.clone();
+        let handle = abort_handle.clone();
+        let done_rx = done_rx;
+        move || {
+            handle.abort();
+            let _ = spawn_local_with_current_owner(async move {
+                let _ = done_rx.await;
+                stream_abort_handles().update(|m| {
+                    m.remove(&symbol);
+                });
+            });
+        }
+    });
 
-    let rest_client_arc =
-        Arc::new(Mutex::new(BinanceWebSocketClient::new(symbol.clone(), interval)));
+    let interval = stream_interval(current_interval().get_untracked());
 
-    // Set the streaming status
-    global_is_streaming().set(false);
+    let source = exchange().get_untracked().data_source(&symbol, interval);
+
+    report_connection_state(ConnectionState::Connecting);
 
     // 📈 First load historical data
     set_status.set("📈 Loading historical data...".to_string());
 
-    let hist_res = {
-        let client = rest_client_arc.lock().await;
-        client.fetch_historical_data(500).await
-    };
+    let count = history_load_count(history_depth().get_untracked(), interval);
+    let hist_res = source.fetch_history(&symbol, interval, count).await;
+    if abort_handle.is_aborted() {
+        return;
+    }
     match hist_res {
         Ok(historical_candles) => {
             get_logger().info(
                 LogComponent::Presentation("WebSocketStream"),
                 &format!("✅ Loaded {} historical candles", historical_candles.len()),
             );
-
-            chart.update(|ch| ch.set_historical_data(historical_candles.clone()));
-            chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-            chart.with_untracked(|c| {
-                if c.get_candle_count() > 0
-                    && with_global_renderer(|r| {
-                        r.set_zoom_params(
-                            zoom_level().with_untracked(|z| *z),
-                            pan_offset().with_untracked(|p| *p),
-                        );
-                        let _ = r.render(c);
-                    })
-                    .is_none()
-                {
-                    // renderer not available
-                }
-            });
-
-            // Update global signals using the historical data
-            let cnt = chart.with(|c| c.get_candle_count());
-            global_candle_count().set(cnt);
-
-            if let Some(last_candle) = historical_candles.last() {
-                global_current_price().set(last_candle.ohlcv.close.value());
+            let timestamps: Vec<u64> =
+                historical_candles.iter().map(|c| c.timestamp.value()).collect();
+            if !interval.matches_spacing(&timestamps) {
+                get_logger().warn(
+                    LogComponent::Presentation("WebSocketStream"),
+                    &format!("⚠️ History is not spaced by {} candles", interval.as_ref()),
+                );
             }
 
-            // Compute the maximum volume from history
-            let max_vol = historical_candles
-                .iter()
-                .map(|c| c.ohlcv.volume.value())
-                .fold(0.0f64, |a, b| a.max(b));
-            global_max_volume().set(max_vol);
+            apply_history(&symbol, chart, historical_candles);
 
             set_status.set("✅ Historical data loaded. Starting real-time stream...".to_string());
         }
@@ -1241,83 +3984,67 @@ pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
                 LogComponent::Presentation("WebSocketStream"),
                 &format!("❌ Failed to load historical data: {}", e),
             );
+            load_failed().set(true);
             set_status.set("⚠️ Historical data failed. Starting real-time only...".to_string());
         }
     }
 
+    // 📏 Default tick size for price rounding from exchangeInfo
+    let tick_res = source.fetch_tick_size(&symbol).await;
+    if abort_handle.is_aborted() {
+        return;
+    }
+    match tick_res {
+        Ok(tick) => price_tick_size().set(Some(tick)),
+        Err(e) => {
+            get_logger().warn(
+                LogComponent::Presentation("WebSocketStream"),
+                &format!("⚠️ Tick size unavailable: {}", e),
+            );
+            price_tick_size().set(None);
+        }
+    }
+    apply_price_precision();
+
     // 🔌 Start the WebSocket for real-time updates
     set_status.set("🔌 Starting WebSocket stream...".to_string());
-    global_is_streaming().set(true);
 
-    let stream_client_arc =
-        Arc::new(Mutex::new(BinanceWebSocketClient::new(symbol.clone(), interval)));
-    let (abort_handle, abort_reg) = futures::future::AbortHandle::new_pair();
-    let (done_tx, done_rx) = oneshot::channel::<()>();
-    stream_abort_handles().update(|m| {
-        m.insert(symbol.clone(), abort_handle.clone());
-    });
-    on_cleanup({
-        let symbol = symbol.clone();
-        let handle = abort_handle.clone();
-        let done_rx = done_rx;
-        move || {
-            handle.abort();
-            let _ = spawn_local_with_current_owner(async move {
-                let _ = done_rx.await;
-                stream_abort_handles().update(|m| {
-                    m.remove(&symbol);
-                });
-            });
-        }
-    });
     let handle_check = abort_handle.clone();
+    let stream_symbol = symbol.clone();
     let fut = futures::future::Abortable::new(
         async move {
             let handler_handle = handle_check.clone();
+            let mut throttle =
+                FormingCandleThrottle::new(forming_candle_throttle_ms().get_untracked());
+            let mut alerts = PriceAlertMonitor::new();
             let handler = move |candle: Candle| {
                 if handler_handle.is_aborted() {
                     return;
                 }
-                global_current_price().set(candle.ohlcv.close.value());
-
-                chart.update(|ch| {
-                    ch.add_realtime_candle(candle.clone());
-                    let zoom = zoom_level().get_untracked();
-                    let pan = pan_offset().get_untracked();
-                    let len = ch.get_candle_count();
-                    if should_auto_scroll(len, zoom, pan) {
-                        ch.update_viewport_for_data();
-                    }
-                });
-                chart.with_untracked(|c| set_chart_in_ecs(&symbol, c.clone()));
-                crate::global_state::push_realtime_candle(candle.clone());
-
-                let count = chart.with(|c| c.get_candle_count());
-                global_candle_count().set(count);
+                let close = candle.ohlcv.close.value();
+                // Alerts keep watching while the chart is paused
+                let fired = horizontal_lines().with_untracked(|lines| alerts.check(lines, close));
+                for line in &fired {
+                    notify_price_alert(&symbol, line, close);
+                }
+                if stream_paused().get_untracked() {
+                    PAUSED_CANDLES.with(|b| b.borrow_mut().push(&symbol, candle));
+                    return;
+                }
 
-                let max_vol = chart.with(|c| {
-                    c.get_series(interval)
-                        .unwrap()
-                        .get_candles()
-                        .iter()
-                        .map(|c| c.ohlcv.volume.value())
-                        .fold(0.0f64, |a, b| a.max(b))
-                });
-                global_max_volume().set(max_vol);
-
-                let sym_for_queue = symbol.clone();
-                enqueue_render_task(Box::new(move |r| {
-                    let chart_signal = ensure_chart(&sym_for_queue);
-                    chart_signal.with_untracked(|ch| {
-                        if ch.get_candle_count() > 0 {
-                            r.set_zoom_params(
-                                zoom_level().with_untracked(|z| *z),
-                                pan_offset().with_untracked(|p| *p),
-                            );
-                            let _ = r.render(ch);
-                        }
-                    });
-                }));
+                global_current_price().set(close);
+                apply_live_candle(&symbol, chart, candle.clone());
+
+                // ⏱️ Skip redraws of a rapidly changing forming candle
+                throttle.min_interval_ms = forming_candle_throttle_ms().get_untracked();
+                let now = js_sys::Date::now();
+                if throttle.should_render(candle.timestamp.value(), now) {
+                    schedule_frame_render();
+                } else if let Some(delay) = throttle.trailing_render_delay(now) {
+                    // The skipped update is drawn once the interval has passed
+                    gloo_timers::callback::Timeout::new(delay.ceil() as u32, schedule_frame_render)
+                        .forget();
+                }
 
                 if handler_handle.is_aborted() {
                     return;
@@ -1325,10 +4052,14 @@ pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
                 set_status.set("🌐 WebSocket LIVE • Real-time updates".to_string());
             };
 
-            let result = {
-                let mut client = stream_client_arc.lock().await;
-                client.start_stream(handler).await
+            let status_handle = handle_check.clone();
+            let on_status = move |state: ConnectionState| {
+                if !status_handle.is_aborted() {
+                    report_connection_state(state);
+                }
             };
+            let result =
+                source.stream_with_status(&stream_symbol, interval, handler, on_status).await;
             if handle_check.is_aborted() {
                 return;
             }
@@ -1337,7 +4068,7 @@ pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
                     return;
                 }
                 set_status.set(format!("❌ WebSocket error: {}", e));
-                global_is_streaming().set(false);
+                report_connection_state(ConnectionState::Error(e.to_string()));
             }
         },
         abort_reg,
@@ -1352,8 +4083,8 @@ pub async fn start_websocket_stream(set_status: WriteSignal<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::chart::value_objects::ChartType;
     use crate::domain::market_data::value_objects::Symbol;
+    use crate::infrastructure::rendering::ChartGeometryBuilder;
     use wasm_bindgen::JsCast;
     use wasm_bindgen_test::*;
 
@@ -1402,7 +4133,11 @@ mod tests {
     fn timeframe_buttons_update_interval() {
         let container = setup_container();
         let chart = create_rw_signal(Chart::new("test".to_string(), ChartType::Candlestick, 100));
-        leptos::mount_to(container.clone(), move || view! { <TimeframeSelector chart=chart /> });
+        let (_status, set_status) = create_signal(String::new());
+        leptos::mount_to(
+            container.clone(),
+            move || view! { <TimeframeSelector chart=chart set_status=set_status /> },
+        );
 
         let two_sec = find_button(&container, "2s").expect("2s button not found");
         two_sec.click();
@@ -1422,43 +4157,35 @@ mod tests {
     }
 
     #[wasm_bindgen_test]
-    fn legend_checkbox_toggles_visibility() {
-        use crate::infrastructure::rendering::renderer::{dummy_renderer, set_global_renderer};
-        use std::cell::RefCell;
-        use std::rc::Rc;
-
+    fn legend_checkbox_updates_on_visibility_change() {
         let container = setup_container();
         let chart = create_rw_signal(Chart::new("test".to_string(), ChartType::Candlestick, 10));
-        let renderer = Rc::new(RefCell::new(dummy_renderer()));
-
-        set_global_renderer(renderer.clone());
+        global_line_visibility().set(LineVisibility::default());
         leptos::mount_to(container.clone(), move || view! { <Legend chart=chart /> });
 
         let cb = find_checkbox(&container, "sma20").expect("sma20 checkbox not found");
-        cb.click();
+        assert!(cb.checked());
 
-        assert!(!renderer.borrow().line_visibility().sma_20);
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        builder.toggle_line_visibility("sma20");
+
+        assert!(!cb.checked());
     }
 
     #[wasm_bindgen_test]
-    fn legend_checkbox_updates_on_renderer_change() {
-        use crate::infrastructure::rendering::renderer::{dummy_renderer, set_global_renderer};
-        use std::cell::RefCell;
-        use std::rc::Rc;
-
+    fn no_data_overlay_follows_load_state() {
         let container = setup_container();
-        let chart = create_rw_signal(Chart::new("test".to_string(), ChartType::Candlestick, 10));
-        let renderer = Rc::new(RefCell::new(dummy_renderer()));
-
-        set_global_renderer(renderer.clone());
-        leptos::mount_to(container.clone(), move || view! { <Legend chart=chart /> });
-
-        let cb = find_checkbox(&container, "sma20").expect("sma20 checkbox not found");
-        assert!(cb.checked());
-
-        renderer.borrow_mut().toggle_line_visibility("sma20");
+        let (_status, set_status) = create_signal(String::new());
+        global_candle_count().set(0);
+        load_failed().set(false);
+        leptos::mount_to(
+            container.clone(),
+            move || view! { <NoDataOverlay set_status=set_status /> },
+        );
 
-        assert!(!cb.checked());
+        assert!(find_button(&container, "Retry").is_err());
+        load_failed().set(true);
+        assert!(find_button(&container, "Retry").is_ok());
     }
 
     #[test]
@@ -1488,6 +4215,30 @@ mod tests {
         assert_eq!(current_symbol().get(), Symbol::from("BTCUSDT"));
     }
 
+    #[wasm_bindgen_test]
+    fn symbol_dropdown_switches_symbol() {
+        let container = setup_container();
+        let (_status, set_status) = create_signal(String::new());
+        current_symbol().set(Symbol::from("BTCUSDT"));
+        leptos::mount_to(
+            container.clone(),
+            move || view! { <SymbolSelect set_status=set_status /> },
+        );
+
+        let select = container
+            .query_selector("#symbol-select")
+            .unwrap()
+            .expect("symbol select not found")
+            .dyn_into::<web_sys::HtmlSelectElement>()
+            .unwrap();
+        assert_eq!(select.length(), 3);
+
+        select.set_value("ETHUSDT");
+        select.dispatch_event(&web_sys::Event::new("change").unwrap()).unwrap();
+        assert_eq!(current_symbol().get(), Symbol::from("ETHUSDT"));
+        assert_eq!(global_candle_count().get(), 0);
+    }
+
     #[wasm_bindgen_test]
     fn zoom_persists_across_symbol_switch() {
         let container = setup_container();
@@ -1,7 +1,33 @@
 use super::value_objects::{ChartType, Viewport};
+use crate::domain::errors::ChartError;
 use crate::domain::market_data::services::{Aggregator, IchimokuData};
-use crate::domain::market_data::{Candle, CandleSeries, TimeInterval, Volume};
+use crate::domain::market_data::{Candle, CandleSeries, OHLCV, TimeInterval, Timestamp, Volume};
+use crate::time_utils::format_iso8601;
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Header row of `Chart::to_csv`
+pub const CSV_HEADER: &str = "timestamp,time,open,high,low,close,volume";
+
+/// Fewest missing candles between two neighbours that count as a gap
+///
+/// Exchanges occasionally skip a single bar; only longer outages are flagged.
+pub const GAP_MIN_MISSING_CANDLES: u64 = 2;
+
+/// Number of `interval` candles missing between open times `prev` and `next`
+pub fn missing_candles(prev: Timestamp, next: Timestamp, interval: TimeInterval) -> u64 {
+    let step = interval.duration_ms();
+    (next.value().saturating_sub(prev.value()) / step).saturating_sub(1)
+}
+
+/// Price change over a time window, see `Chart::price_change_pct`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceChange {
+    pub pct: f64,
+    /// `false` when the history is shorter than the window and the change
+    /// is measured from the oldest loaded candle instead
+    pub full_window: bool,
+}
 
 /// Domain entity - Chart
 #[derive(Debug, Clone)]
@@ -36,11 +62,34 @@ impl Chart {
         }
     }
 
+    /// Maximum number of candles retained per interval
+    pub fn max_candles(&self) -> usize {
+        self.series.get(&TimeInterval::TwoSeconds).map_or(0, |s| s.capacity())
+    }
+
+    /// 🗄️ Retain up to `limit` candles per interval, trimming the oldest
+    ///
+    /// Every interval keeps its own series of 48-byte candles, so the memory
+    /// held by a chart grows linearly: 5 000 candles take roughly 2 MB
+    /// across all intervals. Only the visible window is turned into
+    /// geometry, so a larger limit does not slow down rendering.
+    pub fn set_max_candles(&mut self, limit: usize) {
+        for series in self.series.values_mut() {
+            series.set_max_size(limit);
+        }
+    }
+
     pub fn add_candle(&mut self, candle: Candle) {
+        let is_empty = self.get_candle_count() == 0;
+
         if let Some(base) = self.series.get_mut(&TimeInterval::TwoSeconds) {
             base.add_candle(candle.clone());
         }
         self.update_aggregates(candle);
+
+        if is_empty || self.viewport.is_empty() {
+            self.recompute_viewport();
+        }
     }
 
     /// Add historical data, replacing existing values
@@ -66,7 +115,7 @@ impl Chart {
         }
 
         // Update the viewport
-        self.update_viewport_for_data();
+        self.recompute_viewport();
     }
     /// Add a new candle in real time
     pub fn add_realtime_candle(&mut self, candle: Candle) {
@@ -77,8 +126,8 @@ impl Chart {
         }
         self.update_aggregates(candle);
 
-        if is_empty {
-            self.update_viewport_for_data();
+        if is_empty || self.viewport.is_empty() {
+            self.recompute_viewport();
         }
     }
 
@@ -100,8 +149,11 @@ impl Chart {
         self.indicators.retain(|ind| ind.id != indicator_id);
     }
 
-    /// Update the viewport based on candle data
-    pub fn update_viewport_for_data(&mut self) {
+    /// 📐 Fit the viewport's price and time range to the candle data
+    ///
+    /// Runs when data is replaced and when the first candle arrives, so the
+    /// viewport is the price scale used once auto-fit is off.
+    pub fn recompute_viewport(&mut self) {
         if let Some(base) = self.series.get(&TimeInterval::TwoSeconds) {
             if let Some((min_price, max_price)) = base.price_range() {
                 // Add padding for better visualization (5% top and bottom)
@@ -152,6 +204,90 @@ impl Chart {
         self.series.get(&interval)
     }
 
+    /// 🧮 The collected candles rebuilt as `target` candles, without refetching
+    ///
+    /// Works from the base series, so 1m candles collected live can be shown
+    /// as 5m right away; the trailing bucket may be incomplete.
+    pub fn resample(&self, target: TimeInterval) -> Vec<Candle> {
+        let Some(base) = self.get_series(TimeInterval::TwoSeconds) else {
+            return Vec::new();
+        };
+        let candles: Vec<Candle> = base.get_candles().iter().cloned().collect();
+        Aggregator::resample(&candles, target)
+    }
+
+    /// 📄 `interval` candles as CSV, oldest first
+    ///
+    /// `window` limits the rows to `(start, count)` as returned by
+    /// `visible_range`; `None` exports the whole series. Each row carries the
+    /// open time as epoch milliseconds and as ISO-8601.
+    pub fn to_csv(&self, interval: TimeInterval, window: Option<(usize, usize)>) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+        let Some(series) = self.get_series(interval) else {
+            return csv;
+        };
+        let (start, count) = window.unwrap_or((0, series.count()));
+        for candle in series.get_candles().iter().skip(start).take(count) {
+            let ts = candle.timestamp.value();
+            let o = &candle.ohlcv;
+            csv.push_str(&format!(
+                "{ts},{},{},{},{},{},{}\n",
+                format_iso8601(ts),
+                o.open.value(),
+                o.high.value(),
+                o.low.value(),
+                o.close.value(),
+                o.volume.value()
+            ));
+        }
+        csv
+    }
+
+    /// 🕳️ Outages in the `interval` series as the open times of the candles on either side
+    ///
+    /// Gaps of fewer than `GAP_MIN_MISSING_CANDLES` missing candles are ignored.
+    pub fn find_gaps(&self, interval: TimeInterval) -> Vec<(Timestamp, Timestamp)> {
+        self.find_gaps_with(interval, GAP_MIN_MISSING_CANDLES)
+    }
+
+    /// Like `find_gaps` with a custom `min_missing` threshold
+    pub fn find_gaps_with(
+        &self,
+        interval: TimeInterval,
+        min_missing: u64,
+    ) -> Vec<(Timestamp, Timestamp)> {
+        let Some(series) = self.get_series(interval) else {
+            return Vec::new();
+        };
+        let candles = series.get_candles();
+        candles
+            .iter()
+            .zip(candles.iter().skip(1))
+            .map(|(prev, next)| (prev.timestamp, next.timestamp))
+            .filter(|&(prev, next)| missing_candles(prev, next, interval) >= min_missing.max(1))
+            .collect()
+    }
+
+    /// 📈 Percent change of the latest `interval` close over `window`
+    ///
+    /// The window ends at the close time of the newest candle and starts at
+    /// the open of the first candle inside it. Without enough history the
+    /// oldest candle is used and `full_window` is `false`.
+    pub fn price_change_pct(
+        &self,
+        interval: TimeInterval,
+        window: Duration,
+    ) -> Option<PriceChange> {
+        let candles = self.get_series(interval)?.get_candles();
+        let last = candles.back()?;
+        let end = last.timestamp.value() + interval.duration_ms();
+        let start = end.checked_sub(window.as_millis() as u64);
+        let first = start.map_or(0, |s| candles.partition_point(|c| c.timestamp.value() < s));
+        let full_window = first > 0 || start == Some(candles[0].timestamp.value());
+        let base = candles[first.min(candles.len() - 1)].ohlcv.open;
+        Some(PriceChange { pct: base.pct_change(last.ohlcv.close), full_window })
+    }
+
     fn update_aggregates(&mut self, candle: Candle) {
         let intervals = [
             TimeInterval::OneMinute,
@@ -191,6 +327,33 @@ impl Chart {
     }
 }
 
+/// Candles from CSV written by `Chart::to_csv`
+///
+/// The ISO-8601 column is informational; times come from the epoch column.
+pub fn candles_from_csv(data: &str) -> Result<Vec<Candle>, ChartError> {
+    let mut lines = data.lines();
+    if lines.next() != Some(CSV_HEADER) {
+        return Err(ChartError::Parse("Missing CSV header".to_string()));
+    }
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [ts, _, open, high, low, close, volume] = fields[..] else {
+                return Err(ChartError::Parse(format!("Expected 7 columns: {line}")));
+            };
+            let ohlcv = OHLCV::validated(
+                open.parse()?,
+                high.parse()?,
+                low.parse()?,
+                close.parse()?,
+                volume.parse()?,
+            )?;
+            Ok(Candle::new(Timestamp::new(ts.parse()?), ohlcv))
+        })
+        .collect()
+}
+
 /// Simplified Indicator entity - only essential fields
 #[derive(Debug, Clone)]
 pub struct Indicator {
@@ -2,7 +2,7 @@ use derive_more::Display;
 use strum::{AsRefStr, EnumIter, EnumString};
 
 /// Value Object - Chart type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter, EnumString, AsRefStr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, AsRefStr)]
 pub enum ChartType {
     #[display(fmt = "Candlestick")]
     #[strum(serialize = "candlestick")]
@@ -13,18 +13,24 @@ pub enum ChartType {
     #[display(fmt = "Area")]
     #[strum(serialize = "area")]
     Area,
+    /// Traditional bars with open and close ticks instead of filled bodies
     #[display(fmt = "OHLC")]
     #[strum(serialize = "ohlc")]
-    OHLC,
-    #[display(fmt = "Heikin")]
-    #[strum(serialize = "heikin")]
-    Heikin,
+    OhlcBar,
+    /// Smoothed candles averaging each bar with the previous one
+    #[display(fmt = "Heikin-Ashi")]
+    #[strum(serialize = "heikin-ashi")]
+    HeikinAshi,
     #[display(fmt = "Renko")]
     #[strum(serialize = "renko")]
     Renko,
     #[display(fmt = "Point and Figure")]
     #[strum(serialize = "point-and-figure")]
     PointAndFigure,
+    /// Bare close-price line for small embedded canvases
+    #[display(fmt = "Sparkline")]
+    #[strum(serialize = "sparkline")]
+    Sparkline,
 }
 
 /// Value Object - Viewport
@@ -60,6 +66,11 @@ impl Viewport {
         self.end_time - self.start_time
     }
 
+    /// No usable price range, e.g. before any data arrived
+    pub fn is_empty(&self) -> bool {
+        !(self.max_price - self.min_price).is_finite() || self.max_price <= self.min_price
+    }
+
     pub fn price_range(&self) -> f32 {
         self.max_price - self.min_price
     }
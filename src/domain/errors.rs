@@ -1,23 +1,78 @@
-/// Simplified error system - no over-engineering!
-#[derive(Debug, Clone)]
-pub enum AppError {
-    NetworkError(String),
-    RenderingError(String),
-    ValidationError(String),
+use wasm_bindgen::JsValue;
+
+/// Crate-wide error, grouped by what went wrong
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartError {
+    /// A request or connection failed
+    Network(String),
+    /// A payload did not have the expected format
+    Parse(String),
+    /// WebGPU setup or rendering failed
+    Gpu(String),
+    /// A required resource (canvas, renderer, socket) is not ready
+    NotInitialized(String),
+    /// Values violate a domain rule
+    InvalidData(String),
+    /// The venue or configuration does not support the request
+    Unsupported(String),
 }
 
-impl std::fmt::Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ChartError {
+    /// Short category name for the UI
+    pub fn category(&self) -> &'static str {
+        match self {
+            ChartError::Network(_) => "Network",
+            ChartError::Parse(_) => "Parse",
+            ChartError::Gpu(_) => "GPU",
+            ChartError::NotInitialized(_) => "Not initialized",
+            ChartError::InvalidData(_) => "Invalid data",
+            ChartError::Unsupported(_) => "Unsupported",
+        }
+    }
+
+    pub fn message(&self) -> &str {
         match self {
-            AppError::NetworkError(msg) => write!(f, "Network Error: {}", msg),
-            AppError::RenderingError(msg) => write!(f, "Rendering Error: {}", msg),
-            AppError::ValidationError(msg) => write!(f, "Validation Error: {}", msg),
+            ChartError::Network(msg)
+            | ChartError::Parse(msg)
+            | ChartError::Gpu(msg)
+            | ChartError::NotInitialized(msg)
+            | ChartError::InvalidData(msg)
+            | ChartError::Unsupported(msg) => msg,
         }
     }
 }
 
-impl std::error::Error for AppError {}
+impl std::fmt::Display for ChartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} error: {}", self.category(), self.message())
+    }
+}
+
+impl std::error::Error for ChartError {}
+
+impl From<serde_json::Error> for ChartError {
+    fn from(e: serde_json::Error) -> Self {
+        ChartError::Parse(e.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for ChartError {
+    fn from(e: std::num::ParseFloatError) -> Self {
+        ChartError::Parse(e.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for ChartError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        ChartError::Parse(e.to_string())
+    }
+}
+
+/// WASM boundary: errors reach JavaScript as their display string
+impl From<ChartError> for JsValue {
+    fn from(e: ChartError) -> Self {
+        JsValue::from_str(&e.to_string())
+    }
+}
 
-// Simple convenience type aliases
-pub type NetworkResult<T> = Result<T, AppError>;
-pub type RenderingResult<T> = Result<T, AppError>;
+pub type ChartResult<T> = Result<T, ChartError>;
@@ -109,7 +109,9 @@ impl LogEntry {
 }
 
 /// Global services using thread-safe statics
-use std::sync::OnceLock;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
 static GLOBAL_LOGGER: OnceLock<Box<dyn Logger + Sync + Send>> = OnceLock::new();
 static GLOBAL_TIME_PROVIDER: OnceLock<Box<dyn TimeProvider + Sync + Send>> = OnceLock::new();
 
@@ -133,6 +135,41 @@ pub fn get_time_provider() -> &'static dyn TimeProvider {
     GLOBAL_TIME_PROVIDER.get().map(|provider| provider.as_ref()).unwrap_or(&BasicTimeProvider)
 }
 
+/// Most recent formatted log lines kept for the debug console
+pub const MAX_LOG_LINES: usize = 500;
+
+/// Ring buffer of formatted log lines shown in the debug console
+static GLOBAL_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+/// Bumped on every recorded line so the console can poll for changes cheaply
+static LOG_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// Append a formatted line to `GLOBAL_LOGS`, dropping the oldest past `MAX_LOG_LINES`
+pub fn record_log_line(line: String) {
+    if let Ok(mut logs) = GLOBAL_LOGS.lock() {
+        if logs.len() == MAX_LOG_LINES {
+            logs.pop_front();
+        }
+        logs.push_back(line);
+        LOG_REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Snapshot of the recorded log lines, oldest first
+pub fn recent_logs() -> Vec<String> {
+    GLOBAL_LOGS.lock().map(|logs| logs.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Counter that changes whenever a line is recorded
+pub fn logs_revision() -> u64 {
+    LOG_REVISION.load(Ordering::Relaxed)
+}
+
+/// Lines containing `query`, ignoring case; an empty query keeps everything
+pub fn filter_logs(lines: &[String], query: &str) -> Vec<String> {
+    let needle = query.trim().to_lowercase();
+    lines.iter().filter(|line| line.to_lowercase().contains(&needle)).cloned().collect()
+}
+
 /// No-op logger fallback
 struct NoOpLogger;
 impl Logger for NoOpLogger {
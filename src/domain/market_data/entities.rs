@@ -1,4 +1,5 @@
 pub use super::value_objects::{OHLCV, Price, Timestamp, Volume};
+use crate::domain::errors::ChartError;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -23,15 +24,68 @@ impl Candle {
     }
 
     pub fn body_size(&self) -> Price {
-        Price::from((self.ohlcv.close.value() - self.ohlcv.open.value()).abs())
+        self.ohlcv.close.abs_diff(self.ohlcv.open)
     }
 
     pub fn wick_high(&self) -> Price {
-        Price::from(self.ohlcv.high.value() - self.ohlcv.close.value().max(self.ohlcv.open.value()))
+        self.ohlcv.high - self.ohlcv.close.max(self.ohlcv.open)
     }
 
     pub fn wick_low(&self) -> Price {
-        Price::from(self.ohlcv.close.value().min(self.ohlcv.open.value()) - self.ohlcv.low.value())
+        self.ohlcv.close.min(self.ohlcv.open) - self.ohlcv.low
+    }
+
+    /// Candle from plain numbers; `timestamp_ms` is the open time in Unix milliseconds
+    ///
+    /// Each value goes through its value-object validator and the high/low
+    /// must enclose the open and close.
+    pub fn from_raw(
+        timestamp_ms: f64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Result<Self, ChartError> {
+        let timestamp = Timestamp::validate(timestamp_ms)?;
+        let ohlcv = OHLCV::validated(open, high, low, close, volume)?;
+        if !ohlcv.is_valid() {
+            return Err(ChartError::InvalidData(format!(
+                "Inconsistent OHLC at {}: high {} / low {} do not enclose open {} and close {}",
+                timestamp_ms, high, low, open, close
+            )));
+        }
+        Ok(Self::new(timestamp, ohlcv))
+    }
+}
+
+/// 📥 Candle as an embedder supplies it: Unix-millisecond open time and plain numbers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RawCandle {
+    pub timestamp: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    #[serde(default)]
+    pub volume: f64,
+}
+
+impl RawCandle {
+    pub fn to_candle(&self) -> Result<Candle, ChartError> {
+        Candle::from_raw(self.timestamp, self.open, self.high, self.low, self.close, self.volume)
+    }
+
+    /// Candles from a JSON array of `RawCandle` objects; fails on the first invalid one
+    pub fn parse_list(json: &str) -> Result<Vec<Candle>, ChartError> {
+        let raw: Vec<RawCandle> = serde_json::from_str(json)?;
+        raw.iter()
+            .enumerate()
+            .map(|(i, r)| {
+                r.to_candle()
+                    .map_err(|e| ChartError::InvalidData(format!("Candle {}: {}", i, e.message())))
+            })
+            .collect()
     }
 }
 
@@ -120,6 +174,13 @@ impl CandleSeries {
         self.max_size
     }
 
+    /// Change the capacity, dropping the oldest candles that no longer fit
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size.max(1);
+        let excess = self.candles.len().saturating_sub(self.max_size);
+        self.candles.drain(..excess);
+    }
+
     /// Get the last closing price
     pub fn get_latest_price(&self) -> Option<&Price> {
         self.candles.back().map(|candle| &candle.ohlcv.close)
@@ -0,0 +1,172 @@
+//! 📂 Candles from a local OHLCV file.
+//!
+//! Accepts JSON (Binance klines arrays or one object per candle) and CSV with
+//! an optional header. Invalid rows are logged with their row number and
+//! skipped so one bad line does not discard the rest of the file.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{Candle, RawCandle};
+use crate::domain::errors::ChartError;
+use crate::domain::logging::{LogComponent, get_logger};
+
+/// 📂 A row that could not be turned into a candle
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// 1-based row: the array entry for JSON, the line for CSV
+    pub row: usize,
+    pub message: String,
+}
+
+/// 📂 Result of an import: the valid candles in time order plus skipped rows
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub candles: Vec<Candle>,
+    pub errors: Vec<RowError>,
+}
+
+/// Parse a JSON or CSV file; only an unreadable file as a whole is an error
+pub fn import_candles(text: &str) -> Result<ImportReport, ChartError> {
+    let text = text.trim_start_matches('\u{feff}').trim();
+    let rows = if text.starts_with(['[', '{']) { json_rows(text)? } else { csv_rows(text)? };
+
+    let mut report = ImportReport::default();
+    for (row, parsed) in rows {
+        match parsed {
+            Ok(candle) => report.candles.push(candle),
+            Err(e) => {
+                get_logger().warn(
+                    LogComponent::Domain("Import"),
+                    &format!("⚠️ Skipped row {row}: {}", e.message()),
+                );
+                report.errors.push(RowError { row, message: e.message().to_string() });
+            }
+        }
+    }
+    report.candles.sort_by_key(|c| c.timestamp.value());
+    Ok(report)
+}
+
+type Row = (usize, Result<Candle, ChartError>);
+
+/// Entries of a JSON array, each a klines array or a candle object
+fn json_rows(text: &str) -> Result<Vec<Row>, ChartError> {
+    let entries: Vec<Value> = serde_json::from_str(text)?;
+    Ok(entries.iter().enumerate().map(|(i, entry)| (i + 1, json_candle(entry))).collect())
+}
+
+fn json_candle(entry: &Value) -> Result<Candle, ChartError> {
+    match entry {
+        // Binance klines: [openTime, open, high, low, close, volume, ...]
+        Value::Array(fields) => {
+            if fields.len() < 5 {
+                return Err(ChartError::Parse(format!(
+                    "Expected at least 5 fields, got {}",
+                    fields.len()
+                )));
+            }
+            let field = |i: usize, what: &str| match &fields[i] {
+                Value::Number(n) => n.as_f64().ok_or_else(|| invalid(what)),
+                Value::String(s) => s.trim().parse::<f64>().map_err(|_| invalid(what)),
+                _ => Err(invalid(what)),
+            };
+            let volume = if fields.len() > 5 { field(5, "volume")? } else { 0.0 };
+            Candle::from_raw(
+                field(0, "timestamp")?,
+                field(1, "open price")?,
+                field(2, "high price")?,
+                field(3, "low price")?,
+                field(4, "close price")?,
+                volume,
+            )
+        }
+        Value::Object(_) => RawCandle::deserialize(entry)?.to_candle(),
+        _ => Err(ChartError::Parse("Expected an array or an object".to_string())),
+    }
+}
+
+fn invalid(what: &str) -> ChartError {
+    ChartError::Parse(format!("Invalid {what}"))
+}
+
+/// Column positions of timestamp, open, high, low, close and (optionally) volume
+struct CsvColumns {
+    ohlc: [usize; 5],
+    volume: Option<usize>,
+}
+
+impl CsvColumns {
+    /// Headerless files list the fields in OHLCV order
+    const POSITIONAL: Self = Self { ohlc: [0, 1, 2, 3, 4], volume: Some(5) };
+
+    fn from_header(header: &[&str]) -> Result<Self, ChartError> {
+        let find = |names: &[&str]| {
+            header.iter().position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+        };
+        let required = |names: &[&str]| {
+            find(names)
+                .ok_or_else(|| ChartError::Parse(format!("CSV header has no {} column", names[0])))
+        };
+        Ok(Self {
+            ohlc: [
+                required(&["timestamp", "time", "open_time", "date"])?,
+                required(&["open"])?,
+                required(&["high"])?,
+                required(&["low"])?,
+                required(&["close"])?,
+            ],
+            volume: find(&["volume", "vol"]),
+        })
+    }
+}
+
+/// Data lines of a CSV file numbered as in an editor
+fn csv_rows(text: &str) -> Result<Vec<Row>, ChartError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, csv_fields(line)))
+        .filter(|(_, fields)| fields.iter().any(|f| !f.is_empty()))
+        .peekable();
+
+    let Some((_, first)) = lines.peek() else {
+        return Ok(Vec::new());
+    };
+    let columns = if first[0].parse::<f64>().is_err() {
+        let columns = CsvColumns::from_header(first)?;
+        lines.next();
+        columns
+    } else {
+        CsvColumns::POSITIONAL
+    };
+
+    Ok(lines.map(|(row, fields)| (row, csv_candle(&fields, &columns))).collect())
+}
+
+fn csv_fields(line: &str) -> Vec<&str> {
+    line.split(',').map(|f| f.trim().trim_matches('"')).collect()
+}
+
+fn csv_candle(fields: &[&str], columns: &CsvColumns) -> Result<Candle, ChartError> {
+    let field = |i: usize, what: &str| {
+        fields
+            .get(i)
+            .ok_or_else(|| ChartError::Parse(format!("Missing {what}")))?
+            .parse::<f64>()
+            .map_err(|_| invalid(what))
+    };
+    let [ts, open, high, low, close] = columns.ohlc;
+    let volume = match columns.volume {
+        Some(i) if i < fields.len() && !fields[i].is_empty() => field(i, "volume")?,
+        _ => 0.0,
+    };
+    Candle::from_raw(
+        field(ts, "timestamp")?,
+        field(open, "open price")?,
+        field(high, "high price")?,
+        field(low, "low price")?,
+        field(close, "close price")?,
+        volume,
+    )
+}
@@ -1,8 +1,12 @@
 //! Market data aggregate containing entities, services and value objects.
 
 pub mod entities;
+pub mod import;
+pub mod order_book;
 pub mod services;
 pub mod value_objects;
 
 pub use entities::*;
+pub use import::*;
+pub use order_book::*;
 pub use value_objects::*;
@@ -0,0 +1,175 @@
+//! Bounded limit order book kept consistent with snapshot + diff depth updates.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Levels kept per side unless configured otherwise
+pub const DEFAULT_DEPTH_LEVELS: usize = 100;
+
+/// Price with a total order so levels can key a `BTreeMap`
+#[derive(Debug, Clone, Copy)]
+pub struct LevelPrice(pub f64);
+
+impl PartialEq for LevelPrice {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LevelPrice {}
+
+impl PartialOrd for LevelPrice {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LevelPrice {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Diff of bid/ask levels covering update ids `first_update_id..=final_update_id`
+///
+/// A zero quantity removes the level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthUpdate {
+    pub first_update_id: u64,
+    pub final_update_id: u64,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// Outcome of applying a `DepthUpdate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthSync {
+    /// The book now reflects the update
+    Applied,
+    /// Already covered by the book; ignored
+    Stale,
+    /// Updates are missing; the book needs a new snapshot
+    Gap,
+}
+
+/// 📚 Order book with sorted price → quantity maps, bounded to the top levels
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBook {
+    bids: BTreeMap<LevelPrice, f64>,
+    asks: BTreeMap<LevelPrice, f64>,
+    last_update_id: u64,
+    max_levels: usize,
+}
+
+impl OrderBook {
+    pub fn new(max_levels: usize) -> Self {
+        Self {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update_id: 0,
+            max_levels: max_levels.max(1),
+        }
+    }
+
+    /// Book from a REST snapshot taken at `last_update_id`
+    pub fn from_snapshot(
+        last_update_id: u64,
+        bids: &[(f64, f64)],
+        asks: &[(f64, f64)],
+        max_levels: usize,
+    ) -> Self {
+        let mut book = Self::new(max_levels);
+        book.last_update_id = last_update_id;
+        Self::set_levels(&mut book.bids, bids);
+        Self::set_levels(&mut book.asks, asks);
+        book.trim();
+        book
+    }
+
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+
+    pub fn max_levels(&self) -> usize {
+        self.max_levels
+    }
+
+    /// Apply `update` if it continues the book's update id sequence
+    ///
+    /// The first update after a snapshot may start before it, but must
+    /// reach past it; later ones follow without gaps.
+    pub fn apply(&mut self, update: &DepthUpdate) -> DepthSync {
+        if update.final_update_id <= self.last_update_id {
+            return DepthSync::Stale;
+        }
+        if update.first_update_id > self.last_update_id + 1 {
+            return DepthSync::Gap;
+        }
+        Self::set_levels(&mut self.bids, &update.bids);
+        Self::set_levels(&mut self.asks, &update.asks);
+        self.last_update_id = update.final_update_id;
+        self.trim();
+        DepthSync::Applied
+    }
+
+    fn set_levels(side: &mut BTreeMap<LevelPrice, f64>, levels: &[(f64, f64)]) {
+        for &(price, quantity) in levels {
+            if quantity > 0.0 {
+                side.insert(LevelPrice(price), quantity);
+            } else {
+                side.remove(&LevelPrice(price));
+            }
+        }
+    }
+
+    /// Drop levels furthest from the spread beyond `max_levels`
+    fn trim(&mut self) {
+        while self.bids.len() > self.max_levels {
+            self.bids.pop_first();
+        }
+        while self.asks.len() > self.max_levels {
+            self.asks.pop_last();
+        }
+    }
+
+    /// Bid levels from the best (highest) price down
+    pub fn bids(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.bids.iter().rev().map(|(p, q)| (p.0, *q))
+    }
+
+    /// Ask levels from the best (lowest) price up
+    pub fn asks(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.asks.iter().map(|(p, q)| (p.0, *q))
+    }
+
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.last_key_value().map(|(p, _)| p.0)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first_key_value().map(|(p, _)| p.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// Bids as `(price, cumulative quantity)` from the best price down
+    pub fn cumulative_bids(&self) -> Vec<(f64, f64)> {
+        cumulate(self.bids())
+    }
+
+    /// Asks as `(price, cumulative quantity)` from the best price up
+    pub fn cumulative_asks(&self) -> Vec<(f64, f64)> {
+        cumulate(self.asks())
+    }
+}
+
+fn cumulate(levels: impl Iterator<Item = (f64, f64)>) -> Vec<(f64, f64)> {
+    levels
+        .scan(0.0, |total, (price, quantity)| {
+            *total += quantity;
+            Some((price, *total))
+        })
+        .collect()
+}
@@ -1,5 +1,12 @@
 use crate::domain::market_data::{Candle, OHLCV, Price, TimeInterval, Timestamp, Volume};
 
+/// Simple or exponential moving average
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaKind {
+    Sma,
+    Ema,
+}
+
 /// Data structure for moving averages
 #[derive(Debug, Clone)]
 pub struct MovingAveragesData {
@@ -10,6 +17,42 @@ pub struct MovingAveragesData {
     pub ema_26: Vec<Price>,
 }
 
+impl MovingAveragesData {
+    /// Lines in this set as `(kind, period)`
+    pub const LINES: [(MaKind, usize); 5] = [
+        (MaKind::Sma, 20),
+        (MaKind::Sma, 50),
+        (MaKind::Sma, 200),
+        (MaKind::Ema, 12),
+        (MaKind::Ema, 26),
+    ];
+
+    /// Set with the values `values` returns for each of [`Self::LINES`]
+    pub fn from_lines<'a>(values: impl Fn(MaKind, usize) -> Option<&'a [Price]>) -> Self {
+        let line = |kind, period| values(kind, period).map(<[Price]>::to_vec).unwrap_or_default();
+        Self {
+            sma_20: line(MaKind::Sma, 20),
+            sma_50: line(MaKind::Sma, 50),
+            sma_200: line(MaKind::Sma, 200),
+            ema_12: line(MaKind::Ema, 12),
+            ema_26: line(MaKind::Ema, 26),
+        }
+    }
+
+    /// Precomputed values of the `kind` average over `period`, if this set has them
+    pub fn get(&self, kind: MaKind, period: usize) -> Option<&[Price]> {
+        let values = match (kind, period) {
+            (MaKind::Sma, 20) => &self.sma_20,
+            (MaKind::Sma, 50) => &self.sma_50,
+            (MaKind::Sma, 200) => &self.sma_200,
+            (MaKind::Ema, 12) => &self.ema_12,
+            (MaKind::Ema, 26) => &self.ema_26,
+            _ => return None,
+        };
+        Some(values)
+    }
+}
+
 /// Ichimoku indicator components
 #[derive(Debug, Clone, Default)]
 pub struct IchimokuData {
@@ -67,44 +110,51 @@ impl MarketAnalysisService {
             return Vec::new();
         }
 
-        let mut sma_values = Vec::new();
-
-        for i in (period - 1)..candles.len() {
-            let sum: f64 =
-                candles[i - period + 1..=i].iter().map(|candle| candle.ohlcv.close.value()).sum();
-
-            sma_values.push(Price::from(sum / period as f64));
-        }
-
-        sma_values
+        candles
+            .windows(period)
+            .map(|window| window.iter().map(|c| c.ohlcv.close).sum::<Price>() / period as f64)
+            .collect()
     }
 
     /// Calculate the Exponential Moving Average (EMA)
     pub fn calculate_ema(&self, candles: &[Candle], period: usize) -> Vec<Price> {
-        if candles.len() < period {
+        let closes: Vec<f64> = candles.iter().map(|candle| candle.ohlcv.close.value()).collect();
+        self.calculate_ema_values(&closes, period).into_iter().map(Price::from).collect()
+    }
+
+    /// Calculate the EMA of an arbitrary series (e.g. the MACD signal line)
+    pub fn calculate_ema_values(&self, values: &[f64], period: usize) -> Vec<f64> {
+        if period == 0 || values.len() < period {
             return Vec::new();
         }
 
-        let mut ema_values = Vec::new();
-        let alpha = 2.0 / (period as f64 + 1.0); // Smoothing factor
-
-        // First EMA value is the simple average over the first period candles
-        let first_sma: f64 =
-            candles[0..period].iter().map(|candle| candle.ohlcv.close.value()).sum::<f64>()
-                / period as f64;
+        // First EMA value is the simple average over the first period values
+        let first_sma: f64 = values[0..period].iter().sum::<f64>() / period as f64;
 
-        ema_values.push(Price::from(first_sma));
+        let mut ema_values = vec![first_sma];
+        ema_values.extend(self.extend_ema_values(first_sma, &values[period..], period));
+        ema_values
+    }
 
-        // Compute the remaining EMA values
-        for candle in candles.iter().skip(period) {
-            let current_price = candle.ohlcv.close.value();
-            let prev_ema = ema_values.last().unwrap().value();
-            let new_ema = alpha * current_price + (1.0 - alpha) * prev_ema;
+    /// EMA values of `values` continuing from the previous EMA value `prev_ema`
+    pub fn extend_ema_values(&self, mut prev_ema: f64, values: &[f64], period: usize) -> Vec<f64> {
+        let alpha = 2.0 / (period as f64 + 1.0); // Smoothing factor
+        values
+            .iter()
+            .map(|value| {
+                prev_ema = alpha * value + (1.0 - alpha) * prev_ema;
+                prev_ema
+            })
+            .collect()
+    }
 
-            ema_values.push(Price::from(new_ema));
+    /// Calculate a moving average of the given kind
+    pub fn calculate_ma(&self, candles: &[Candle], kind: MaKind, period: usize) -> Vec<Price> {
+        match kind {
+            MaKind::Sma if period > 0 => self.calculate_sma(candles, period),
+            MaKind::Sma => Vec::new(),
+            MaKind::Ema => self.calculate_ema(candles, period),
         }
-
-        ema_values
     }
 
     /// Calculate multiple moving averages at once
@@ -197,9 +247,9 @@ impl MarketAnalysisService {
         let mut result = Vec::new();
         for i in (period - 1)..candles.len() {
             let slice = &candles[i + 1 - period..=i];
-            let high = slice.iter().map(|c| c.ohlcv.high.value()).fold(f64::NEG_INFINITY, f64::max);
-            let low = slice.iter().map(|c| c.ohlcv.low.value()).fold(f64::INFINITY, f64::min);
-            result.push(Price::from((high + low) / 2.0));
+            let high = slice.iter().map(|c| c.ohlcv.high).reduce(Price::max).unwrap();
+            let low = slice.iter().map(|c| c.ohlcv.low).reduce(Price::min).unwrap();
+            result.push(Price::midpoint(high, low));
         }
 
         result
@@ -221,7 +271,7 @@ impl MarketAnalysisService {
         let tenkan = self.calculate_tenkan_sen(candles, tenkan_period);
         let kijun = self.calculate_kijun_sen(candles, kijun_period);
         let len = tenkan.len().min(kijun.len());
-        (0..len).map(|i| Price::from((tenkan[i].value() + kijun[i].value()) / 2.0)).collect()
+        (0..len).map(|i| Price::midpoint(tenkan[i], kijun[i])).collect()
     }
 
     /// Calculate Senkou Span B
@@ -278,6 +328,19 @@ impl Aggregator {
             OHLCV::new(open, Price::from(high), Price::from(low), close, Volume::from(volume_sum)),
         ))
     }
+
+    /// 🧮 Time-ordered `candles` merged into `interval` buckets
+    ///
+    /// Buckets start at multiples of `interval.duration_ms()`. A bucket that is
+    /// still filling, such as the in-progress period, is emitted from the
+    /// candles it has so far.
+    pub fn resample(candles: &[Candle], interval: TimeInterval) -> Vec<Candle> {
+        let duration = interval.duration_ms().max(1);
+        candles
+            .chunk_by(|a, b| a.timestamp.value() / duration == b.timestamp.value() / duration)
+            .filter_map(|bucket| Self::aggregate(bucket, interval))
+            .collect()
+    }
 }
 
 // DataValidationService removed - validation is handled in MarketAnalysisService.validate_candle()
@@ -1,6 +1,9 @@
+use crate::domain::errors::ChartError;
 use derive_more::{Constructor, Deref, DerefMut, Display, From, Into};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Sub};
 use strum::{AsRefStr, Display as StrumDisplay, EnumIter, EnumString};
 
 /// Value Object - Price with generated helpers
@@ -14,13 +17,75 @@ impl Price {
         self.0
     }
 
-    pub fn validate(value: f64) -> Result<Self, String> {
+    pub fn validate(value: f64) -> Result<Self, ChartError> {
         if value.is_finite() && value >= 0.0 {
             Ok(Self(value))
         } else {
-            Err(format!("Invalid price: {}", value))
+            Err(ChartError::InvalidData(format!("Invalid price: {}", value)))
         }
     }
+
+    /// Price halfway between `a` and `b`
+    pub fn midpoint(a: Price, b: Price) -> Price {
+        Self((a.0 + b.0) / 2.0)
+    }
+
+    /// Percent change from `self` to `other`; zero when `self` is zero
+    pub fn pct_change(&self, other: Price) -> f64 {
+        if self.0 == 0.0 { 0.0 } else { (other.0 - self.0) / self.0 * 100.0 }
+    }
+
+    /// Distance between two prices
+    pub fn abs_diff(self, other: Price) -> Price {
+        Self((self.0 - other.0).abs())
+    }
+
+    pub fn max(self, other: Price) -> Price {
+        Self(self.0.max(other.0))
+    }
+
+    pub fn min(self, other: Price) -> Price {
+        Self(self.0.min(other.0))
+    }
+}
+
+impl Add for Price {
+    type Output = Price;
+
+    fn add(self, rhs: Price) -> Price {
+        Self(self.0 + rhs.0)
+    }
+}
+
+/// Saturates at zero: a price never goes negative, use `abs_diff` for spreads
+impl Sub for Price {
+    type Output = Price;
+
+    fn sub(self, rhs: Price) -> Price {
+        Self((self.0 - rhs.0).max(0.0))
+    }
+}
+
+impl Mul<f64> for Price {
+    type Output = Price;
+
+    fn mul(self, rhs: f64) -> Price {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for Price {
+    type Output = Price;
+
+    fn div(self, rhs: f64) -> Price {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Sum for Price {
+    fn sum<I: Iterator<Item = Price>>(iter: I) -> Price {
+        Self(iter.map(|p| p.0).sum())
+    }
 }
 
 impl PartialOrd for Price {
@@ -40,15 +105,54 @@ impl Volume {
         self.0
     }
 
-    pub fn validate(value: f64) -> Result<Self, String> {
+    pub fn validate(value: f64) -> Result<Self, ChartError> {
         if value.is_finite() && value >= 0.0 {
             Ok(Self(value))
         } else {
-            Err(format!("Invalid volume: {}", value))
+            Err(ChartError::InvalidData(format!("Invalid volume: {}", value)))
         }
     }
 }
 
+impl Add for Volume {
+    type Output = Volume;
+
+    fn add(self, rhs: Volume) -> Volume {
+        Self(self.0 + rhs.0)
+    }
+}
+
+/// Saturates at zero like [`Price`]
+impl Sub for Volume {
+    type Output = Volume;
+
+    fn sub(self, rhs: Volume) -> Volume {
+        Self((self.0 - rhs.0).max(0.0))
+    }
+}
+
+impl Mul<f64> for Volume {
+    type Output = Volume;
+
+    fn mul(self, rhs: f64) -> Volume {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for Volume {
+    type Output = Volume;
+
+    fn div(self, rhs: f64) -> Volume {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Sum for Volume {
+    fn sum<I: Iterator<Item = Volume>>(iter: I) -> Volume {
+        Self(iter.map(|v| v.0).sum())
+    }
+}
+
 /// Value Object - Timestamp with generated helpers
 #[derive(
     Debug,
@@ -80,9 +184,24 @@ impl Timestamp {
         Self(value)
     }
 
-    pub fn from_milliseconds(millis: u64) -> Result<Self, String> {
+    pub fn from_milliseconds(millis: u64) -> Result<Self, ChartError> {
         Ok(Self(millis))
     }
+
+    /// Timestamp from Unix milliseconds given as a JavaScript number
+    ///
+    /// Rejects NaN, infinite, negative and fractional values.
+    pub fn validate(millis: f64) -> Result<Self, ChartError> {
+        if millis.is_finite() && millis >= 0.0 && millis.fract() == 0.0 && millis <= u64::MAX as f64
+        {
+            Ok(Self(millis as u64))
+        } else {
+            Err(ChartError::InvalidData(format!(
+                "Invalid timestamp: {} (expected Unix ms)",
+                millis
+            )))
+        }
+    }
 }
 
 /// Value Object - OHLCV data with generated helpers
@@ -105,6 +224,31 @@ impl OHLCV {
             && self.low <= self.close
             && self.volume.value() >= 0.0
     }
+
+    /// OHLCV from raw values, rejecting NaN, infinite or negative ones
+    pub fn validated(
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Result<Self, ChartError> {
+        Ok(Self::new(
+            Price::validate(open)?,
+            Price::validate(high)?,
+            Price::validate(low)?,
+            Price::validate(close)?,
+            Volume::validate(volume)?,
+        ))
+    }
+
+    /// Copy with high and low widened to cover every price
+    pub fn repaired(self) -> Self {
+        let prices = [self.open.value(), self.high.value(), self.low.value(), self.close.value()];
+        let high = prices.iter().copied().fold(f64::MIN, f64::max);
+        let low = prices.iter().copied().fold(f64::MAX, f64::min);
+        Self { high: Price::new(high), low: Price::new(low), ..self }
+    }
 }
 
 /// Value Object - Trading symbol with generated helpers
@@ -113,9 +257,9 @@ impl OHLCV {
 pub struct Symbol(String);
 
 impl Symbol {
-    pub fn new(symbol: String) -> Result<Self, String> {
+    pub fn new(symbol: String) -> Result<Self, ChartError> {
         if symbol.is_empty() {
-            return Err("Symbol cannot be empty".to_string());
+            return Err(ChartError::InvalidData("Symbol cannot be empty".to_string()));
         }
         Ok(Self(symbol.to_uppercase()))
     }
@@ -194,6 +338,20 @@ impl TimeInterval {
         self.as_ref()
     }
 
+    /// Whether Binance serves klines at this interval; `TwoSeconds` is aggregated client-side
+    pub fn is_binance_kline(&self) -> bool {
+        !matches!(self, Self::TwoSeconds)
+    }
+
+    /// Whether consecutive candle open `timestamps` are whole intervals apart
+    pub fn matches_spacing(&self, timestamps: &[u64]) -> bool {
+        let step = self.duration_ms();
+        timestamps.windows(2).all(|pair| {
+            let gap = pair[1].saturating_sub(pair[0]);
+            gap > 0 && gap % step == 0
+        })
+    }
+
     pub fn duration_ms(&self) -> u64 {
         match self {
             Self::TwoSeconds => 2 * 1000,
@@ -207,4 +365,58 @@ impl TimeInterval {
             Self::OneMonth => 30 * 24 * 60 * 60 * 1000,
         }
     }
+
+    /// 📜 Candles loaded when a chart opens at this interval
+    ///
+    /// Intraday charts get more bars than daily and longer ones.
+    pub fn default_history_candles(&self) -> u32 {
+        match self {
+            Self::TwoSeconds | Self::OneMinute | Self::FiveMinutes => 1000,
+            Self::FifteenMinutes => 800,
+            Self::OneHour | Self::FourHours => 500,
+            Self::OneDay => 365,
+            Self::OneWeek => 156,
+            Self::OneMonth => 60,
+        }
+    }
+}
+
+/// Most candles the initial history load requests, whatever the setting
+pub const MAX_HISTORY_CANDLES: u32 = 5000;
+
+/// 📜 How much history a chart loads when it opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryDepth {
+    /// The interval's `default_history_candles`
+    #[default]
+    Auto,
+    /// A fixed number of candles
+    Candles(u32),
+    /// Enough candles to cover this many milliseconds back from the latest one
+    Lookback(u64),
+}
+
+impl HistoryDepth {
+    /// Lookback covering the last `days` days
+    pub fn days(days: u64) -> Self {
+        Self::Lookback(days.saturating_mul(TimeInterval::OneDay.duration_ms()))
+    }
+
+    /// 🔢 Candles to load at `interval`, at most `MAX_HISTORY_CANDLES`
+    ///
+    /// A zero count or lookback falls back to the interval's default.
+    pub fn candle_count(self, interval: TimeInterval) -> u32 {
+        let count = match self {
+            Self::Auto => 0,
+            Self::Candles(count) => count,
+            Self::Lookback(ms) => {
+                u32::try_from(ms.div_ceil(interval.duration_ms())).unwrap_or(u32::MAX)
+            }
+        };
+        match count {
+            0 => interval.default_history_candles(),
+            count => count.min(MAX_HISTORY_CANDLES),
+        }
+    }
 }
@@ -1,14 +1,97 @@
 use crate::domain::chart::{Chart, value_objects::Viewport};
-use crate::domain::market_data::Candle;
+use crate::domain::market_data::{Candle, Symbol, TimeInterval, services::MovingAveragesData};
+use crate::infrastructure::rendering::renderer::{
+    MaCacheUpdate, MaUpdateTiming, MovingAverageCache,
+};
 
 /// ECS component containing a complete trading chart.
 #[derive(Debug, Clone)]
 pub struct ChartComponent(pub Chart);
 
-/// ECS component storing a single candle.
+/// ECS component queuing a streamed candle for the chart of `symbol`.
+///
+/// `apply_candles` drains these entities on the next run.
 #[derive(Debug, Clone)]
-pub struct CandleComponent(pub Candle);
+pub struct PendingCandle {
+    pub symbol: Symbol,
+    pub candle: Candle,
+}
 
 /// ECS component for viewport state.
 #[derive(Debug, Clone)]
 pub struct ViewportComponent(pub Viewport);
+
+/// Marks the chart entity shown on screen with the zoom, pan and interval to draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderTargetComponent {
+    pub interval: TimeInterval,
+    pub zoom_level: f64,
+    pub pan_offset: f64,
+}
+
+/// Identifies the candle series indicators were computed from.
+///
+/// The last close is included so updates to the forming candle count as new data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndicatorSource {
+    pub len: usize,
+    pub last_timestamp: u64,
+    pub last_close_bits: u64,
+}
+
+impl IndicatorSource {
+    /// Fingerprint of `candles`.
+    pub fn of<'a, I>(candles: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Candle>,
+        I::IntoIter: ExactSizeIterator + DoubleEndedIterator,
+    {
+        let mut candles = candles.into_iter();
+        let len = candles.len();
+        let last = candles.next_back();
+        Self {
+            len,
+            last_timestamp: last.map_or(0, |c| c.timestamp.value()),
+            last_close_bits: last.map_or(0, |c| c.ohlcv.close.value().to_bits()),
+        }
+    }
+}
+
+/// ECS component with indicator values computed by the indicator system.
+///
+/// The averages live in an incremental cache, so a tick that only changes the
+/// forming candle recomputes the tail of each line instead of the whole series.
+#[derive(Debug, Clone)]
+pub struct IndicatorComponent {
+    pub interval: TimeInterval,
+    pub source: IndicatorSource,
+    pub moving_averages: MovingAveragesData,
+    pub cache: MovingAverageCache,
+    /// How the latest update refreshed the averages
+    pub last_update: MaCacheUpdate,
+    pub timing: MaUpdateTiming,
+}
+
+impl IndicatorComponent {
+    /// Empty indicators for `interval`, filled by the first [`Self::update`].
+    pub fn new(interval: TimeInterval) -> Self {
+        Self {
+            interval,
+            source: IndicatorSource::of(&[]),
+            moving_averages: MovingAveragesData::from_lines(|_, _| None),
+            cache: MovingAverageCache::default(),
+            last_update: MaCacheUpdate::Unchanged,
+            timing: MaUpdateTiming::default(),
+        }
+    }
+
+    /// Bring the averages up to date with `candles`.
+    pub fn update(&mut self, candles: &[Candle]) -> MaCacheUpdate {
+        let update = self.cache.update(candles, MovingAveragesData::LINES);
+        self.last_update = update;
+        self.source = IndicatorSource::of(candles);
+        self.moving_averages =
+            MovingAveragesData::from_lines(|kind, period| self.cache.get(kind, period));
+        update
+    }
+}
@@ -3,27 +3,59 @@ use hecs::World;
 pub mod components;
 pub mod systems;
 
+use components::{ChartComponent, RenderTargetComponent};
+use systems::Schedule;
+
 /// Shared ECS world for the application.
-/// Currently only manages entities and components without systems.
+/// Systems in `schedule` run once per rendered frame.
 #[derive(Default)]
 pub struct EcsWorld {
     pub world: World,
+    pub schedule: Schedule,
 }
 
 impl EcsWorld {
-    /// Create a new empty ECS world.
+    /// Create a new empty ECS world with the default systems.
     pub fn new() -> Self {
-        Self { world: World::new() }
+        Self::default()
     }
 
     /// Spawn a new chart entity with its component.
     pub fn spawn_chart(&mut self, chart: crate::domain::chart::Chart) -> hecs::Entity {
-        use crate::ecs::components::ChartComponent;
         self.world.spawn((ChartComponent(chart),))
     }
 
-    /// Apply all pending candle components to charts.
+    /// Apply all `PendingCandle` entities to their charts.
     pub fn run_candle_system(&mut self) {
         crate::ecs::systems::apply_candles(&mut self.world);
     }
+
+    /// Run every scheduled system for one frame.
+    pub fn run_frame(&mut self) {
+        self.schedule.run(&mut self.world);
+    }
+
+    /// Render the chart with id `chart_id` using `target`; other charts stop rendering.
+    ///
+    /// Returns `false` if no chart entity has that id.
+    pub fn set_render_target(&mut self, chart_id: &str, target: RenderTargetComponent) -> bool {
+        let mut chosen = None;
+        let mut stale = Vec::new();
+        for (entity, (chart, current)) in
+            self.world.query::<(&ChartComponent, Option<&RenderTargetComponent>)>().iter()
+        {
+            if chosen.is_none() && chart.0.id == chart_id {
+                chosen = Some(entity);
+            } else if current.is_some() {
+                stale.push(entity);
+            }
+        }
+        for entity in stale {
+            let _ = self.world.remove_one::<RenderTargetComponent>(entity);
+        }
+        match chosen {
+            Some(entity) => self.world.insert_one(entity, target).is_ok(),
+            None => false,
+        }
+    }
 }
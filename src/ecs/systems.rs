@@ -1,25 +1,149 @@
 use hecs::World;
 
-use super::components::{CandleComponent, ChartComponent};
+use super::components::{
+    ChartComponent, IndicatorComponent, IndicatorSource, PendingCandle, RenderTargetComponent,
+};
+use crate::domain::errors::ChartError;
+use crate::domain::logging::LogComponent;
+use crate::domain::market_data::Candle;
+use crate::infrastructure::rendering::renderer::Renderer;
+use crate::log_info;
 
-/// Apply new candles to all charts and remove processed candle entities.
+/// A system that updates the world once per frame.
+pub type System = fn(&mut World);
+
+/// Ordered list of systems executed on every frame.
+pub struct Schedule {
+    systems: Vec<System>,
+}
+
+impl Default for Schedule {
+    /// Candle ingestion followed by indicator computation.
+    fn default() -> Self {
+        Self::new().with_system(apply_candles).with_system(compute_indicators)
+    }
+}
+
+impl Schedule {
+    /// Create a schedule without systems.
+    pub fn new() -> Self {
+        Self { systems: Vec::new() }
+    }
+
+    /// Append a system that runs after the already registered ones.
+    pub fn with_system(mut self, system: System) -> Self {
+        self.systems.push(system);
+        self
+    }
+
+    /// Run every system in registration order.
+    pub fn run(&self, world: &mut World) {
+        for system in &self.systems {
+            system(world);
+        }
+    }
+}
+
+/// Append pending candles to the chart of their symbol and despawn them.
+///
+/// Candles are applied in spawn order through `Chart::add_realtime_candle`, so a
+/// candle with the timestamp of the last one replaces it and each series keeps
+/// its chart's capacity. Candles for symbols without a chart are dropped.
 pub fn apply_candles(world: &mut World) {
-    let mut candle_entities = Vec::new();
-    let candles: Vec<(hecs::Entity, CandleComponent)> =
-        world.query::<&CandleComponent>().iter().map(|(e, c)| (e, c.clone())).collect();
+    let pending: Vec<(hecs::Entity, PendingCandle)> =
+        world.query::<&PendingCandle>().iter().map(|(e, p)| (e, p.clone())).collect();
 
-    if candles.is_empty() {
+    if pending.is_empty() {
         return;
     }
 
-    for (_, candle) in &candles {
-        for (_, chart) in world.query::<&mut ChartComponent>().iter() {
-            chart.0.add_realtime_candle(candle.0.clone());
+    for (_, chart) in world.query::<&mut ChartComponent>().iter() {
+        let chart = &mut chart.0;
+        for (_, p) in &pending {
+            if p.symbol.value() == chart.id {
+                chart.add_realtime_candle(p.candle.clone());
+            }
         }
     }
 
-    candle_entities.extend(candles.into_iter().map(|(e, _)| e));
-    for e in candle_entities {
-        let _ = world.despawn(e);
+    for (entity, _) in pending {
+        let _ = world.despawn(entity);
+    }
+}
+
+/// Update moving averages of rendered charts whose candles changed.
+///
+/// Each chart keeps its averages in an incremental cache, so a live tick only
+/// recomputes the values from the changed candle on; a new interval starts over.
+pub fn compute_indicators(world: &mut World) {
+    let mut inserts = Vec::new();
+
+    for (entity, (chart, target, current)) in world.query_mut::<(
+        &ChartComponent,
+        &RenderTargetComponent,
+        Option<&mut IndicatorComponent>,
+    )>() {
+        let Some(series) = chart.0.get_series(target.interval) else {
+            continue;
+        };
+        let source = IndicatorSource::of(series.get_candles());
+        let indicators = match current {
+            Some(c) if c.interval == target.interval && c.source == source => continue,
+            Some(c) if c.interval == target.interval => c,
+            Some(c) => {
+                *c = IndicatorComponent::new(target.interval);
+                c
+            }
+            None => {
+                inserts.push((entity, IndicatorComponent::new(target.interval)));
+                &mut inserts.last_mut().expect("just pushed").1
+            }
+        };
+        let candles: Vec<Candle> = series.get_candles().iter().cloned().collect();
+        let started = now_ms();
+        let update = indicators.update(&candles);
+        if let Some(elapsed) = started.zip(now_ms()).map(|(start, end)| end - start)
+            && let Some((incremental_ms, rebuild_ms)) =
+                indicators.timing.record(update, elapsed, TIMING_REPORT_EVERY)
+        {
+            log_info!(
+                LogComponent::Infrastructure("ECS"),
+                "⏱️ moving averages: {:.3}ms incremental vs {:.3}ms rebuild ({:.1}x)",
+                incremental_ms,
+                rebuild_ms,
+                rebuild_ms / incremental_ms.max(f64::EPSILON)
+            );
+        }
+    }
+
+    for (entity, indicators) in inserts {
+        let _ = world.insert_one(entity, indicators);
+    }
+}
+
+/// Incremental indicator updates between two timing reports
+const TIMING_REPORT_EVERY: u32 = 100;
+
+/// Current time in milliseconds; native builds (headless tests) have no clock
+fn now_ms() -> Option<f64> {
+    #[cfg(target_arch = "wasm32")]
+    return web_sys::window().and_then(|w| w.performance()).map(|p| p.now());
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+/// Hand every rendered chart and its precomputed indicators to the renderer.
+///
+/// Charts without candles are passed on too so the renderer can draw their placeholder.
+pub fn render_charts(world: &World, renderer: &mut dyn Renderer) -> Result<(), ChartError> {
+    for (_, (chart, target, indicators)) in world
+        .query::<(&ChartComponent, &RenderTargetComponent, Option<&IndicatorComponent>)>()
+        .iter()
+    {
+        let geometry = renderer.geometry_mut();
+        geometry.set_zoom_params(target.zoom_level, target.pan_offset);
+        geometry.set_moving_averages(indicators.map(|i| (i.source, i.moving_averages.clone())));
+        renderer.render(&chart.0)?;
     }
+    Ok(())
 }
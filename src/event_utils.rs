@@ -65,4 +65,61 @@ where
 }
 use leptos::{HtmlElement, html::AnyElement};
 
+/// Canvas bounding rect in CSS pixels, as returned by `getBoundingClientRect`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasRect {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Map client (viewport) coordinates to backing-store pixels of the canvas.
+///
+/// `clientX/Y` and the bounding rect are both viewport-relative, so page
+/// scroll cancels out; only the rect origin and `devicePixelRatio` matter.
+pub fn client_to_canvas_px(client_x: f64, client_y: f64, rect: CanvasRect, dpr: f64) -> (f64, f64) {
+    ((client_x - rect.left) * dpr, (client_y - rect.top) * dpr)
+}
+
+/// Map client coordinates to NDC (`-1..1`, y up) of a canvas with `rect` and `dpr`
+pub fn client_to_ndc_in(client_x: f64, client_y: f64, rect: CanvasRect, dpr: f64) -> (f32, f32) {
+    let (px, py) = client_to_canvas_px(client_x, client_y, rect, dpr);
+    let (width, height) = ((rect.width * dpr).max(1.0), (rect.height * dpr).max(1.0));
+    ((px / width * 2.0 - 1.0) as f32, (1.0 - py / height * 2.0) as f32)
+}
+
+/// 🖥️ Device pixels per CSS pixel of the current screen
+///
+/// Changes when the window moves to a monitor with a different density, so
+/// read it again on every resize instead of caching it.
+pub fn device_pixel_ratio() -> f64 {
+    web_sys::window().map(|w| w.device_pixel_ratio()).filter(|r| *r > 0.0).unwrap_or(1.0)
+}
+
+/// 🎯 NDC position of a pointer event over the element it is attached to
+pub fn client_to_ndc(event: &web_sys::MouseEvent) -> Option<(f32, f32)> {
+    let target: web_sys::Element = event.current_target()?.dyn_into().ok()?;
+    Some(element_client_to_ndc(&target, event.client_x() as f64, event.client_y() as f64))
+}
+
+/// NDC position of client (`client_x`, `client_y`) over `element`
+pub fn element_client_to_ndc(
+    element: &web_sys::Element,
+    client_x: f64,
+    client_y: f64,
+) -> (f32, f32) {
+    let r = element.get_bounding_client_rect();
+    let rect = CanvasRect { left: r.left(), top: r.top(), width: r.width(), height: r.height() };
+    client_to_ndc_in(client_x, client_y, rect, device_pixel_ratio())
+}
+
+/// ✋ Client positions of the fingers in `touches`
+pub fn touch_points(touches: &web_sys::TouchList) -> Vec<(f64, f64)> {
+    (0..touches.length())
+        .filter_map(|i| touches.get(i))
+        .map(|t| (t.client_x() as f64, t.client_y() as f64))
+        .collect()
+}
+
 pub fn wheel_event_options(_el: HtmlElement<AnyElement>, _opts: &EventOptions) {}
@@ -19,11 +19,14 @@ use std::sync::Mutex;
 pub struct Globals {
     pub current_price: RwSignal<f64>,
     pub candle_count: RwSignal<usize>,
-    pub is_streaming: RwSignal<bool>,
+    /// State of the live stream as shown in the header
+    pub connection_state: RwSignal<crate::infrastructure::websocket::ConnectionState>,
     pub max_volume: RwSignal<f64>,
     pub loading_more: RwSignal<bool>,
+    pub load_failed: RwSignal<bool>,
     pub tooltip_data: RwSignal<Option<TooltipData>>,
     pub tooltip_visible: RwSignal<bool>,
+    pub selected_candle: RwSignal<Option<usize>>,
     pub zoom_level: RwSignal<f64>,
     pub pan_offset: RwSignal<f64>,
     pub is_dragging: RwSignal<bool>,
@@ -33,6 +36,41 @@ pub struct Globals {
     pub charts: RwSignal<HashMap<Symbol, RwSignal<Chart>>>,
     pub stream_abort_handles: RwSignal<HashMap<Symbol, AbortHandle>>,
     pub line_visibility: RwSignal<crate::infrastructure::rendering::renderer::LineVisibility>,
+    pub forming_candle_throttle_ms: RwSignal<f64>,
+    pub price_tick_size: RwSignal<Option<f64>>,
+    pub price_rounding: RwSignal<bool>,
+    pub horizontal_lines: RwSignal<Vec<crate::infrastructure::rendering::renderer::HorizontalLine>>,
+    pub pan_inertia_enabled: RwSignal<bool>,
+    pub animate_transitions: RwSignal<bool>,
+    pub x_axis_mode: RwSignal<crate::infrastructure::rendering::renderer::XAxisMode>,
+    /// MSAA samples per pixel in use by the WebGPU renderer
+    pub msaa_samples: RwSignal<u32>,
+    pub device_lost: RwSignal<Option<(String, String)>>,
+    pub stream_paused: RwSignal<bool>,
+    pub auto_scroll: RwSignal<bool>,
+    pub panel_layout: RwSignal<crate::infrastructure::rendering::PanelLayout>,
+    /// Time axis offset from UTC in minutes; `None` shows UTC
+    pub time_zone_offset: RwSignal<Option<i32>>,
+    pub frame_timing: RwSignal<crate::infrastructure::rendering::renderer::FrameTiming>,
+    /// Venue market data is loaded from
+    pub exchange: RwSignal<crate::infrastructure::websocket::Exchange>,
+    pub theme_preset: RwSignal<crate::settings::ThemePreset>,
+    /// Floor on the drawn candle body height
+    pub min_body_height: RwSignal<crate::infrastructure::rendering::renderer::MinBodyHeight>,
+    /// CSS size of the main chart canvas
+    pub canvas_size: RwSignal<(f64, f64)>,
+    /// Candles retained per interval by newly created charts
+    pub history_limit: RwSignal<usize>,
+    /// History loaded when a chart opens
+    pub history_depth: RwSignal<crate::domain::market_data::HistoryDepth>,
+    /// Id of the reference line picked for deletion
+    pub selected_price_line: RwSignal<Option<u32>>,
+    /// NDC height and color of the current price line in the last built frame
+    pub current_price_marker: RwSignal<Option<(f32, [f32; 4])>>,
+    /// Legend entries of the last built frame
+    pub indicator_values: RwSignal<Vec<crate::infrastructure::rendering::renderer::IndicatorValue>>,
+    /// Main panel price range of the last built frame
+    pub rendered_price_range: RwSignal<Option<(f32, f32)>>,
 }
 
 // The `OnceCell` ensures this state is created at most once on demand.
@@ -43,12 +81,14 @@ pub fn globals() -> &'static Globals {
     GLOBALS.get_or_init(|| Globals {
         current_price: create_rw_signal(0.0),
         candle_count: create_rw_signal(0),
-        is_streaming: create_rw_signal(false),
+        connection_state: create_rw_signal(Default::default()),
         max_volume: create_rw_signal(0.0),
         loading_more: create_rw_signal(false),
+        load_failed: create_rw_signal(false),
         tooltip_data: create_rw_signal(None),
         tooltip_visible: create_rw_signal(false),
-        zoom_level: create_rw_signal(0.32),
+        selected_candle: create_rw_signal(None),
+        zoom_level: create_rw_signal(crate::app::DEFAULT_ZOOM_LEVEL),
         pan_offset: create_rw_signal(0.0),
         is_dragging: create_rw_signal(false),
         last_mouse_x: create_rw_signal(0.0),
@@ -59,6 +99,32 @@ pub fn globals() -> &'static Globals {
         line_visibility: create_rw_signal(
             crate::infrastructure::rendering::renderer::LineVisibility::default(),
         ),
+        forming_candle_throttle_ms: create_rw_signal(crate::app::FORMING_CANDLE_THROTTLE_MS),
+        price_tick_size: create_rw_signal(None),
+        price_rounding: create_rw_signal(false),
+        horizontal_lines: create_rw_signal(Vec::new()),
+        pan_inertia_enabled: create_rw_signal(true),
+        animate_transitions: create_rw_signal(true),
+        x_axis_mode: create_rw_signal(Default::default()),
+        msaa_samples: create_rw_signal(
+            crate::infrastructure::rendering::renderer::MSAA_SAMPLE_COUNT,
+        ),
+        device_lost: create_rw_signal(None),
+        stream_paused: create_rw_signal(false),
+        auto_scroll: create_rw_signal(true),
+        panel_layout: create_rw_signal(Default::default()),
+        time_zone_offset: create_rw_signal(None),
+        frame_timing: create_rw_signal(Default::default()),
+        exchange: create_rw_signal(Default::default()),
+        theme_preset: create_rw_signal(Default::default()),
+        min_body_height: create_rw_signal(Default::default()),
+        canvas_size: create_rw_signal((crate::app::CHART_WIDTH, crate::app::CHART_HEIGHT)),
+        history_limit: create_rw_signal(crate::app::DEFAULT_HISTORY_LIMIT),
+        history_depth: create_rw_signal(Default::default()),
+        selected_price_line: create_rw_signal(None),
+        current_price_marker: create_rw_signal(None),
+        indicator_values: create_rw_signal(Vec::new()),
+        rendered_price_range: create_rw_signal(None),
     })
 }
 
@@ -71,7 +137,8 @@ pub fn ensure_chart(symbol: &Symbol) -> RwSignal<Chart> {
     let charts = &globals().charts;
     charts.update(|map| {
         map.entry(symbol.clone()).or_insert_with(|| {
-            let chart = Chart::new(symbol.value().to_string(), ChartType::Candlestick, 1000);
+            let limit = globals().history_limit.get_untracked();
+            let chart = Chart::new(symbol.value().to_string(), ChartType::Candlestick, limit);
             ecs_world().lock().unwrap().spawn_chart(chart.clone());
             create_rw_signal(chart)
         });
@@ -87,11 +154,11 @@ pub fn stream_abort_handles() -> RwSignal<HashMap<Symbol, AbortHandle>> {
     globals().stream_abort_handles
 }
 
-/// Add a candle to the ECS world and process systems.
-pub fn push_realtime_candle(candle: Candle) {
-    use crate::ecs::components::CandleComponent;
+/// Queue a streamed candle for the chart of `symbol` and apply pending candles.
+pub fn push_realtime_candle(symbol: &Symbol, candle: Candle) {
+    use crate::ecs::components::PendingCandle;
     let mut world = ecs_world().lock().unwrap();
-    world.world.spawn((CandleComponent(candle),));
+    world.world.spawn((PendingCandle { symbol: symbol.clone(), candle },));
     world.run_candle_system();
 }
 
@@ -111,3 +178,16 @@ pub fn set_chart_in_ecs(symbol: &Symbol, chart: Chart) {
         world.world.spawn((ChartComponent(chart),));
     }
 }
+
+/// Mirror the chart of `symbol` into the ECS world, run the frame's systems and render it.
+pub fn render_ecs_frame(
+    symbol: &Symbol,
+    target: crate::ecs::components::RenderTargetComponent,
+    renderer: &mut dyn crate::infrastructure::rendering::renderer::Renderer,
+) -> Result<(), crate::domain::errors::ChartError> {
+    ensure_chart(symbol).with_untracked(|c| set_chart_in_ecs(symbol, c.clone()));
+    let mut world = ecs_world().lock().unwrap();
+    world.set_render_target(symbol.value(), target);
+    world.run_frame();
+    crate::ecs::systems::render_charts(&world.world, renderer)
+}
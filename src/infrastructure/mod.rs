@@ -51,7 +51,7 @@ pub mod services {
     impl Logger for ConsoleLogger {
         fn log(&self, entry: LogEntry) {
             if entry.level >= self.min_level {
-                use crate::domain::logging::get_time_provider;
+                use crate::domain::logging::{get_time_provider, record_log_line};
                 let formatted = self.format_log_entry(&entry, get_time_provider());
 
                 // Use gloo console methods
@@ -61,6 +61,7 @@ pub mod services {
                     LogLevel::Warn => console::warn!("{}", formatted.clone()),
                     LogLevel::Error => console::error!("{}", formatted.clone()),
                 }
+                record_log_line(formatted);
             }
         }
     }
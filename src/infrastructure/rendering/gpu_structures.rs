@@ -1,3 +1,5 @@
+use crate::infrastructure::rendering::palette::palette;
+use crate::infrastructure::rendering::renderer::nice_price_ticks;
 use bytemuck::{Pod, Zeroable};
 
 /// Indicator types for GPU rendering
@@ -13,6 +15,235 @@ pub enum IndicatorType {
     SenkouA,
     SenkouB,
     Chikou,
+    RSI,
+    MACD,
+    MacdSignal,
+    Bollinger,
+    /// Close-price line of the line and area chart modes
+    PriceLine,
+    Vwap,
+    StochasticK,
+    StochasticD,
+    Atr,
+    Keltner,
+}
+
+impl IndicatorType {
+    /// Line color in `theme`; the fixed ones mirror `simple_shader.wgsl`
+    pub fn color(self, theme: &ChartTheme) -> [f32; 4] {
+        match self {
+            IndicatorType::SMA20 => theme.sma20,
+            IndicatorType::SMA50 => theme.sma50,
+            IndicatorType::SMA200 => theme.sma200,
+            IndicatorType::EMA12 => theme.ema12,
+            IndicatorType::EMA26 => theme.ema26,
+            IndicatorType::RSI => theme.rsi,
+            IndicatorType::MACD => theme.macd,
+            IndicatorType::MacdSignal => theme.macd_signal,
+            IndicatorType::Bollinger => theme.bollinger,
+            IndicatorType::PriceLine => theme.price_line,
+            IndicatorType::Vwap => theme.vwap,
+            IndicatorType::StochasticK => theme.stochastic_k,
+            IndicatorType::StochasticD => theme.stochastic_d,
+            IndicatorType::Atr => theme.atr,
+            IndicatorType::Keltner => theme.keltner,
+            IndicatorType::Tenkan
+            | IndicatorType::Kijun
+            | IndicatorType::SenkouA
+            | IndicatorType::SenkouB
+            | IndicatorType::Chikou => [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+/// Raw-price bullish test: a close at or above the reference counts as bullish
+pub fn is_bullish_move(reference: f64, close: f64) -> bool {
+    close >= reference
+}
+
+/// Pack an RGB color into an integer-valued `f32` (exact below 2^24)
+pub fn pack_rgb(color: [f32; 4]) -> f32 {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u32;
+    ((channel(color[0]) << 16) | (channel(color[1]) << 8) | channel(color[2])) as f32
+}
+
+/// Indicator-line `color_type` carrying its own RGB
+///
+/// Stored negative so it never collides with the `IndicatorType` palette slots.
+pub fn packed_line_color(color: [f32; 4]) -> f32 {
+    -(pack_rgb(color) + 1.0)
+}
+
+/// Dash pattern in NDC units measured along the line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DashPattern {
+    pub on: f32,
+    pub off: f32,
+}
+
+impl DashPattern {
+    /// Continuous line without gaps
+    pub const SOLID: Self = Self { on: 1.0, off: 0.0 };
+
+    pub fn is_solid(&self) -> bool {
+        self.on <= 0.0 || self.off <= 0.0
+    }
+
+    /// Visible `(start, end)` ranges along a line of `length`.
+    ///
+    /// `phase` is the distance already covered before the line start, so a
+    /// pattern anchored to data coordinates does not crawl while panning.
+    pub fn segments(&self, length: f32, phase: f32) -> Vec<(f32, f32)> {
+        if length <= 0.0 {
+            return Vec::new();
+        }
+        if self.is_solid() {
+            return vec![(0.0, length)];
+        }
+
+        let period = self.on + self.off;
+        let mut start = -phase.rem_euclid(period);
+        let mut segments = Vec::new();
+        while start < length {
+            let (a, b) = (start.max(0.0), (start + self.on).min(length));
+            if b > a {
+                segments.push((a, b));
+            }
+            start += period;
+        }
+        segments
+    }
+}
+
+/// Dash patterns for each line type of the chart theme
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineStyles {
+    pub grid: DashPattern,
+    pub current_price: DashPattern,
+    /// Reference lines that notify when the price crosses them
+    pub alerts: DashPattern,
+    pub moving_averages: DashPattern,
+    pub session_levels: DashPattern,
+}
+
+impl Default for LineStyles {
+    fn default() -> Self {
+        Self {
+            grid: DashPattern::SOLID,
+            current_price: DashPattern::SOLID,
+            alerts: DashPattern { on: 0.02, off: 0.015 },
+            moving_averages: DashPattern::SOLID,
+            session_levels: DashPattern::SOLID,
+        }
+    }
+}
+
+/// Color palette of the chart; copied into [`ChartUniforms`] on every geometry rebuild
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartTheme {
+    pub background: [f32; 4],
+    pub bullish: [f32; 4],
+    pub bearish: [f32; 4],
+    pub wick: [f32; 4],
+    pub grid: [f32; 4],
+    pub sma20: [f32; 4],
+    pub sma50: [f32; 4],
+    pub sma200: [f32; 4],
+    pub ema12: [f32; 4],
+    pub ema26: [f32; 4],
+    pub current_price: [f32; 4],
+    pub macd: [f32; 4],
+    pub macd_signal: [f32; 4],
+    pub volume_ma: [f32; 4],
+    pub rsi: [f32; 4],
+    pub bollinger: [f32; 4],
+    pub price_line: [f32; 4],
+    pub vwap: [f32; 4],
+    pub stochastic_k: [f32; 4],
+    pub stochastic_d: [f32; 4],
+    pub atr: [f32; 4],
+    pub keltner: [f32; 4],
+}
+
+impl Default for ChartTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl ChartTheme {
+    /// Default dark scheme from DOCS/COLORS.md
+    ///
+    /// The five default moving averages take distinct [`palette`] colors.
+    pub fn dark() -> Self {
+        let ma = palette(5);
+        Self {
+            background: [0.145, 0.196, 0.259, 1.0], // #253242
+            bullish: [0.455, 0.780, 0.529, 1.0],    // #74c787 - buy
+            bearish: [0.882, 0.424, 0.282, 1.0],    // #e16c48 - sell
+            wick: [0.6, 0.6, 0.6, 0.9],             // light gray
+            grid: [0.3, 0.3, 0.3, 0.3],             // semi-transparent gray
+            sma20: ma[0],
+            sma50: ma[1],
+            sma200: ma[2],
+            ema12: ma[3],
+            ema26: ma[4],
+            current_price: [1.0, 1.0, 0.0, 0.8], // 💰 bright yellow
+            macd: [0.2, 0.6, 1.0, 0.9],          // blue
+            macd_signal: [1.0, 0.6, 0.2, 0.9],   // orange
+            volume_ma: [0.8, 0.8, 0.95, 0.9],    // pale lavender
+            rsi: [0.7, 0.5, 1.0, 1.0],           // 📉 violet
+            bollinger: [0.4, 0.6, 1.0, 0.9],     // blue
+            price_line: [0.35, 0.65, 1.0, 1.0],  // 📈 line/area blue
+            vwap: [0.95, 0.45, 0.85, 1.0],       // 📊 pink
+            stochastic_k: [0.3, 0.85, 0.85, 1.0], // 📉 cyan
+            stochastic_d: [1.0, 0.6, 0.2, 1.0],  // 📉 orange
+            atr: [0.85, 0.85, 0.4, 1.0],         // 📏 khaki
+            keltner: [0.55, 0.8, 0.55, 0.9],     // 📈 green
+        }
+    }
+
+    /// Colorblind-safe scheme (Okabe–Ito) for deuteranopia and protanopia
+    ///
+    /// Buy/sell are blue/orange instead of green/red. The grid and current
+    /// price are lifted further off the dark background than in `dark()`.
+    pub fn colorblind() -> Self {
+        Self {
+            bullish: [0.0, 0.447, 0.698, 1.0],     // #0072b2 blue - buy
+            bearish: [0.902, 0.624, 0.0, 1.0],     // #e69f00 orange - sell
+            wick: [0.75, 0.75, 0.75, 0.9],         // light gray
+            grid: [0.5, 0.5, 0.55, 0.35],          // brighter gray
+            sma20: [0.941, 0.894, 0.259, 0.9],     // #f0e442 yellow
+            sma50: [0.941, 0.894, 0.259, 0.9],     // #f0e442 yellow
+            sma200: [0.941, 0.894, 0.259, 0.9],    // #f0e442 yellow
+            ema12: [0.941, 0.894, 0.259, 0.9],     // #f0e442 yellow
+            ema26: [0.941, 0.894, 0.259, 0.9],     // #f0e442 yellow
+            current_price: [1.0, 1.0, 1.0, 0.9],   // 💰 white
+            macd: [0.337, 0.706, 0.914, 0.9],      // #56b4e9 sky blue
+            macd_signal: [0.835, 0.369, 0.0, 0.9], // #d55e00 vermillion
+            volume_ma: [0.8, 0.475, 0.655, 0.9],   // #cc79a7 reddish purple
+            ..Self::dark()
+        }
+    }
+
+    /// Light scheme: same buy/sell colors, darker lines for a white background
+    pub fn light() -> Self {
+        Self {
+            background: [0.98, 0.98, 0.98, 1.0],
+            wick: [0.35, 0.35, 0.35, 0.9],
+            grid: [0.8, 0.8, 0.8, 0.5],
+            sma20: [0.8, 0.6, 0.0, 0.9],         // amber
+            sma50: [0.8, 0.6, 0.0, 0.9],         // amber
+            sma200: [0.8, 0.6, 0.0, 0.9],        // amber
+            ema12: [0.8, 0.6, 0.0, 0.9],         // amber
+            ema26: [0.8, 0.6, 0.0, 0.9],         // amber
+            current_price: [0.8, 0.6, 0.0, 0.8], // amber
+            macd: [0.1, 0.4, 0.8, 0.9],          // blue
+            macd_signal: [0.9, 0.45, 0.1, 0.9],  // orange
+            volume_ma: [0.3, 0.3, 0.5, 0.9],     // slate
+            ..Self::dark()
+        }
+    }
 }
 
 /// GPU representation of a candle for the vertex buffer
@@ -30,6 +261,11 @@ pub struct CandleVertex {
 }
 
 impl CandleVertex {
+    /// Grid, volume and depth bars are drawn before the instanced candles, everything else after
+    pub fn draws_under_candles(&self) -> bool {
+        self.element_type == 3.0 || self.element_type == 5.0 || self.element_type == 10.0
+    }
+
     /// Create vertex for the candle body
     pub fn body_vertex(x: f32, y: f32, is_bullish: bool) -> Self {
         Self {
@@ -58,6 +294,16 @@ impl CandleVertex {
             IndicatorType::SenkouA => 12.0,
             IndicatorType::SenkouB => 13.0,
             IndicatorType::Chikou => 14.0,
+            IndicatorType::RSI => 7.0,
+            IndicatorType::MACD => 8.0,
+            IndicatorType::MacdSignal => 9.0,
+            IndicatorType::Bollinger => 15.0,
+            IndicatorType::PriceLine => 16.0,
+            IndicatorType::Vwap => 17.0,
+            IndicatorType::StochasticK => 18.0,
+            IndicatorType::StochasticD => 19.0,
+            IndicatorType::Atr => 20.0,
+            IndicatorType::Keltner => 21.0,
         };
 
         Self {
@@ -108,6 +354,46 @@ impl CandleVertex {
         }
     }
 
+    /// Create vertex for the session high/low lines
+    pub fn session_level_vertex(x: f32, y: f32) -> Self {
+        Self { position_x: x, position_y: y, element_type: 7.0, color_type: 0.0 }
+    }
+
+    /// Create vertex for an indicator line with its own RGB color
+    pub fn colored_indicator_vertex(x: f32, y: f32, color: [f32; 4]) -> Self {
+        Self {
+            position_x: x,
+            position_y: y,
+            element_type: 2.0,
+            color_type: packed_line_color(color),
+        }
+    }
+
+    /// Create vertex for a reference line with its own RGB color
+    pub fn reference_line_vertex(x: f32, y: f32, color: [f32; 4]) -> Self {
+        Self { position_x: x, position_y: y, element_type: 8.0, color_type: pack_rgb(color) }
+    }
+
+    /// Create vertex for the translucent area between two bands
+    pub fn band_fill_vertex(x: f32, y: f32) -> Self {
+        Self { position_x: x, position_y: y, element_type: 9.0, color_type: 0.0 }
+    }
+
+    /// Create vertex for the translucent area under the price line
+    pub fn area_fill_vertex(x: f32, y: f32) -> Self {
+        Self { position_x: x, position_y: y, element_type: 9.0, color_type: 1.0 }
+    }
+
+    /// Create vertex for the order book depth area; bids use the bullish color
+    pub fn depth_vertex(x: f32, y: f32, is_bid: bool) -> Self {
+        Self {
+            position_x: x,
+            position_y: y,
+            element_type: 10.0,
+            color_type: if is_bid { 1.0 } else { 0.0 },
+        }
+    }
+
     /// Vertex buffer descriptor for wgpu
     pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
         wgpu::VertexBufferLayout {
@@ -143,6 +429,41 @@ impl CandleVertex {
     }
 }
 
+/// Corner of the unit quad shared by every candle instance
+///
+/// `x` spans -0.5..0.5 of the instance width, `y` runs 0..1 from the bottom
+/// to the top of the body (or from low to high for wicks).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+pub struct QuadVertex {
+    pub corner: [f32; 2],
+}
+
+impl QuadVertex {
+    /// Template corners, counter-clockwise from bottom left
+    pub const UNIT: [QuadVertex; 4] = [
+        QuadVertex { corner: [-0.5, 0.0] },
+        QuadVertex { corner: [0.5, 0.0] },
+        QuadVertex { corner: [0.5, 1.0] },
+        QuadVertex { corner: [-0.5, 1.0] },
+    ];
+    /// Two counter-clockwise triangles over `UNIT`
+    pub const INDICES: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+    /// Template buffer layout
+    pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<QuadVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2,
+            }],
+        }
+    }
+}
+
 /// Attributes of a single candle for instanced drawing
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -239,6 +560,28 @@ pub struct ChartUniforms {
     pub ema26_color: [f32; 4],
     /// 💰 Current price color (current_price_r, current_price_g, current_price_b, current_price_a)
     pub current_price_color: [f32; 4],
+    /// MACD line color (macd_r, macd_g, macd_b, macd_a)
+    pub macd_color: [f32; 4],
+    /// MACD signal line color (signal_r, signal_g, signal_b, signal_a)
+    pub macd_signal_color: [f32; 4],
+    /// Grid line color (grid_r, grid_g, grid_b, grid_a)
+    pub grid_color: [f32; 4],
+    /// 📉 RSI line color
+    pub rsi_color: [f32; 4],
+    /// Bollinger band color; the fill between the bands uses its RGB
+    pub bollinger_color: [f32; 4],
+    /// 📈 Line/area price line color; the area under it uses its RGB
+    pub price_line_color: [f32; 4],
+    /// 📊 VWAP line color
+    pub vwap_color: [f32; 4],
+    /// 📉 Stochastic %K line color
+    pub stochastic_k_color: [f32; 4],
+    /// 📉 Stochastic %D line color
+    pub stochastic_d_color: [f32; 4],
+    /// 📏 ATR line color
+    pub atr_color: [f32; 4],
+    /// 📈 Keltner channel color
+    pub keltner_color: [f32; 4],
     /// Rendering parameters (candle_width, spacing, line_width, _padding)
     pub render_params: [f32; 4],
 }
@@ -251,6 +594,11 @@ impl Default for ChartUniforms {
 
 impl ChartUniforms {
     pub fn new() -> Self {
+        Self::from_theme(&ChartTheme::dark())
+    }
+
+    /// Default uniforms with every color taken from `theme`
+    pub fn from_theme(theme: &ChartTheme) -> Self {
         Self {
             view_proj_matrix: [
                 [1.0, 0.0, 0.0, 0.0],
@@ -260,16 +608,27 @@ impl ChartUniforms {
             ],
             viewport: [800.0, 600.0, 0.0, 100.0],
             time_range: [0.0, 0.0, 0.0, 0.0],
-            bullish_color: [0.455, 0.780, 0.529, 1.0], // #74c787 - buy
-            bearish_color: [0.882, 0.424, 0.282, 1.0], // #e16c48 - sell
-            wick_color: [0.6, 0.6, 0.6, 1.0],          // gray
-            sma20_color: [1.0, 1.0, 0.0, 1.0],         // yellow
-            sma50_color: [1.0, 1.0, 0.0, 1.0],         // yellow
-            sma200_color: [1.0, 1.0, 0.0, 1.0],        // yellow
-            ema12_color: [1.0, 1.0, 0.0, 1.0],         // yellow
-            ema26_color: [1.0, 1.0, 0.0, 1.0],         // yellow
-            current_price_color: [1.0, 1.0, 0.0, 0.8], // 💰 bright yellow with transparency
-            render_params: [8.0, 2.0, 1.0, 0.0],       // width, spacing, line_width, padding
+            bullish_color: theme.bullish,
+            bearish_color: theme.bearish,
+            wick_color: theme.wick,
+            sma20_color: theme.sma20,
+            sma50_color: theme.sma50,
+            sma200_color: theme.sma200,
+            ema12_color: theme.ema12,
+            ema26_color: theme.ema26,
+            current_price_color: theme.current_price,
+            macd_color: theme.macd,
+            macd_signal_color: theme.macd_signal,
+            grid_color: theme.grid,
+            rsi_color: theme.rsi,
+            bollinger_color: theme.bollinger,
+            price_line_color: theme.price_line,
+            vwap_color: theme.vwap,
+            stochastic_k_color: theme.stochastic_k,
+            stochastic_d_color: theme.stochastic_d,
+            atr_color: theme.atr,
+            keltner_color: theme.keltner,
+            render_params: [8.0, 2.0, 1.0, 0.0], // width, spacing, line_width, padding
         }
     }
 }
@@ -303,14 +662,34 @@ impl CandleGeometry {
         low_y: f32,
         close_y: f32,
         width: f32,
+    ) -> Vec<CandleVertex> {
+        Self::create_colored_candle_vertices(
+            x_normalized,
+            open_y,
+            high_y,
+            low_y,
+            close_y,
+            width,
+            is_bullish_move(open as f64, close as f64),
+        )
+    }
+
+    /// Create vertices for a single candle with a precomputed color decision
+    pub fn create_colored_candle_vertices(
+        x_normalized: f32,
+        open_y: f32,
+        high_y: f32,
+        low_y: f32,
+        close_y: f32,
+        width: f32,
+        is_bullish: bool,
     ) -> Vec<CandleVertex> {
         let mut vertices = Vec::new();
-        let is_bullish = close > open;
         let half_width = width * 0.5;
 
         // Determine candle body coordinates
-        let body_top = if is_bullish { close_y } else { open_y };
-        let body_bottom = if is_bullish { open_y } else { close_y };
+        let body_top = open_y.max(close_y);
+        let body_bottom = open_y.min(close_y);
 
         let corner =
             f32::min(width * Self::CORNER_RADIUS_RATIO, (body_top - body_bottom).abs() * 0.5);
@@ -443,17 +822,78 @@ impl CandleGeometry {
 
     /// 💰 Create vertices for the current price line
     pub fn create_current_price_line(current_price_y: f32, line_width: f32) -> Vec<CandleVertex> {
-        let half_width = line_width * 0.5;
+        Self::create_horizontal_line(
+            current_price_y,
+            line_width,
+            DashPattern::SOLID,
+            CandleVertex::current_price_vertex,
+        )
+    }
 
-        // Horizontal line across the entire screen
-        vec![
-            CandleVertex::current_price_vertex(-1.0, current_price_y - half_width),
-            CandleVertex::current_price_vertex(1.0, current_price_y - half_width),
-            CandleVertex::current_price_vertex(-1.0, current_price_y + half_width),
-            CandleVertex::current_price_vertex(-1.0, current_price_y + half_width),
-            CandleVertex::current_price_vertex(1.0, current_price_y - half_width),
-            CandleVertex::current_price_vertex(1.0, current_price_y + half_width),
-        ]
+    /// Horizontal line across the entire screen with the given dash pattern
+    pub fn create_horizontal_line(
+        y: f32,
+        line_width: f32,
+        pattern: DashPattern,
+        vertex: impl Fn(f32, f32) -> CandleVertex,
+    ) -> Vec<CandleVertex> {
+        Self::create_line_vertices(&[(-1.0, y), (1.0, y)], line_width * 0.5, pattern, 0.0, vertex)
+    }
+
+    /// Dash-aware polyline generator shared by all line types
+    ///
+    /// Each visible dash becomes a quad (two triangles) of `half_width` around
+    /// the polyline. `phase` shifts the pattern start, see [`DashPattern::segments`].
+    pub fn create_line_vertices(
+        points: &[(f32, f32)],
+        half_width: f32,
+        pattern: DashPattern,
+        phase: f32,
+        vertex: impl Fn(f32, f32) -> CandleVertex,
+    ) -> Vec<CandleVertex> {
+        let mut vertices = Vec::new();
+        let mut distance = phase;
+
+        for pair in points.windows(2) {
+            let (x1, y1) = pair[0];
+            let (x2, y2) = pair[1];
+
+            // Compute the perpendicular vector for the correct line thickness
+            let dx = x2 - x1;
+            let dy = y2 - y1;
+            let length = (dx * dx + dy * dy).sqrt();
+
+            // Normalized perpendicular vector
+            let (perp_x, perp_y) = if length > 0.0001 {
+                (-dy / length * half_width, dx / length * half_width)
+            } else {
+                (0.0, half_width) // vertical line
+            };
+
+            let segments =
+                if length > 0.0001 { pattern.segments(length, distance) } else { vec![(0.0, 1.0)] };
+            let lerp = |t: f32| {
+                let k = if length > 0.0001 { t / length } else { t };
+                (x1 + dx * k, y1 + dy * k)
+            };
+
+            for (a, b) in segments {
+                let (ax, ay) = lerp(a);
+                let (bx, by) = lerp(b);
+                // Create a rectangle as two triangles without gaps
+                vertices.extend_from_slice(&[
+                    vertex(ax - perp_x, ay - perp_y),
+                    vertex(bx - perp_x, by - perp_y),
+                    vertex(ax + perp_x, ay + perp_y),
+                    vertex(ax + perp_x, ay + perp_y),
+                    vertex(bx - perp_x, by - perp_y),
+                    vertex(bx + perp_x, by + perp_y),
+                ]);
+            }
+            distance += length;
+        }
+
+        vertices
     }
 
     /// Create vertices for a volume bar
@@ -478,55 +918,94 @@ impl CandleGeometry {
         ]
     }
 
-    /// Create vertices for an indicator line - improved algorithm for solid lines
+    /// Create vertices for a solid indicator line
     pub fn create_indicator_line_vertices(
         points: &[(f32, f32)], // (x_normalized, y_normalized) points
         indicator_type: IndicatorType,
         line_width: f32,
+    ) -> Vec<CandleVertex> {
+        Self::create_dashed_indicator_line_vertices(
+            points,
+            indicator_type,
+            line_width,
+            DashPattern::SOLID,
+            0.0,
+        )
+    }
+
+    /// Create vertices for an indicator line using a dash pattern
+    pub fn create_dashed_indicator_line_vertices(
+        points: &[(f32, f32)],
+        indicator_type: IndicatorType,
+        line_width: f32,
+        pattern: DashPattern,
+        phase: f32,
     ) -> Vec<CandleVertex> {
         if points.len() < 2 {
             return Vec::new();
         }
 
-        let mut vertices = Vec::new();
         let half_width = (line_width * 0.3).max(0.001); // thinner line for better look
+        Self::create_line_vertices(points, half_width, pattern, phase, |x, y| {
+            CandleVertex::indicator_vertex(x, y, indicator_type)
+        })
+    }
 
-        // Create a continuous line as a triangle strip
-        for i in 0..(points.len() - 1) {
-            let (x1, y1) = points[i];
-            let (x2, y2) = points[i + 1];
-
-            // Compute the perpendicular vector for the correct line thickness
-            let dx = x2 - x1;
-            let dy = y2 - y1;
-            let length = (dx * dx + dy * dy).sqrt();
-
-            // Normalized perpendicular vector
-            let (perp_x, perp_y) = if length > 0.0001 {
-                (-dy / length * half_width, dx / length * half_width)
-            } else {
-                (0.0, half_width) // vertical line
-            };
+    /// Create vertices for a dashed indicator line in an arbitrary color
+    pub fn create_colored_line_vertices(
+        points: &[(f32, f32)],
+        color: [f32; 4],
+        line_width: f32,
+        pattern: DashPattern,
+        phase: f32,
+    ) -> Vec<CandleVertex> {
+        if points.len() < 2 {
+            return Vec::new();
+        }
 
-            // Create a rectangle as two triangles without gaps
-            let segment_vertices = [
-                // First triangle
-                CandleVertex::indicator_vertex(x1 - perp_x, y1 - perp_y, indicator_type),
-                CandleVertex::indicator_vertex(x1 + perp_x, y1 + perp_y, indicator_type),
-                CandleVertex::indicator_vertex(x2 - perp_x, y2 - perp_y, indicator_type),
-                // Second triangle
-                CandleVertex::indicator_vertex(x1 + perp_x, y1 + perp_y, indicator_type),
-                CandleVertex::indicator_vertex(x2 + perp_x, y2 + perp_y, indicator_type),
-                CandleVertex::indicator_vertex(x2 - perp_x, y2 - perp_y, indicator_type),
-            ];
+        let half_width = (line_width * 0.3).max(0.001);
+        Self::create_line_vertices(points, half_width, pattern, phase, |x, y| {
+            CandleVertex::colored_indicator_vertex(x, y, color)
+        })
+    }
 
-            vertices.extend_from_slice(&segment_vertices);
+    /// Create vertices for the Ichimoku cloud (Span A/B area and lines)
+    /// Fill the area between an upper and a lower line with two triangles per segment
+    pub fn create_band_fill(upper: &[(f32, f32)], lower: &[(f32, f32)]) -> Vec<CandleVertex> {
+        let len = upper.len().min(lower.len());
+        let mut vertices = Vec::with_capacity(len.saturating_sub(1) * 6);
+        for i in 1..len {
+            let ((x1, u1), (x2, u2)) = (upper[i - 1], upper[i]);
+            let ((_, l1), (_, l2)) = (lower[i - 1], lower[i]);
+            vertices.extend_from_slice(&[
+                CandleVertex::band_fill_vertex(x1, l1),
+                CandleVertex::band_fill_vertex(x2, l2),
+                CandleVertex::band_fill_vertex(x1, u1),
+                CandleVertex::band_fill_vertex(x2, l2),
+                CandleVertex::band_fill_vertex(x2, u2),
+                CandleVertex::band_fill_vertex(x1, u1),
+            ]);
         }
+        vertices
+    }
 
+    /// Fill the area between a line and the horizontal `baseline`
+    pub fn create_area_fill(points: &[(f32, f32)], baseline: f32) -> Vec<CandleVertex> {
+        let mut vertices = Vec::with_capacity(points.len().saturating_sub(1) * 6);
+        for pair in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            vertices.extend_from_slice(&[
+                CandleVertex::area_fill_vertex(x1, baseline),
+                CandleVertex::area_fill_vertex(x2, baseline),
+                CandleVertex::area_fill_vertex(x1, y1),
+                CandleVertex::area_fill_vertex(x2, baseline),
+                CandleVertex::area_fill_vertex(x2, y2),
+                CandleVertex::area_fill_vertex(x1, y1),
+            ]);
+        }
         vertices
     }
 
-    /// Create vertices for the Ichimoku cloud (Span A/B area and lines)
     pub fn create_ichimoku_cloud(
         span_a: &[(f32, f32)],
         span_b: &[(f32, f32)],
@@ -579,38 +1058,30 @@ impl CandleGeometry {
         grid_lines_y: u32,
     ) -> Vec<CandleVertex> {
         let mut vertices = Vec::new();
-        let line_width = 0.002; // thin grid lines
+        let half_width = 0.002 * 0.5; // thin grid lines
 
         // Vertical lines
         for i in 0..=grid_lines_x {
             let x = i as f32 / grid_lines_x as f32 * 2.0 - 1.0; // normalize to [-1, 1]
-            let half_width = line_width * 0.5;
-
-            // Vertical line as a thin rectangle
-            vertices.extend_from_slice(&[
-                CandleVertex::wick_vertex(x - half_width, -1.0),
-                CandleVertex::wick_vertex(x + half_width, -1.0),
-                CandleVertex::wick_vertex(x - half_width, 1.0),
-                CandleVertex::wick_vertex(x + half_width, -1.0),
-                CandleVertex::wick_vertex(x + half_width, 1.0),
-                CandleVertex::wick_vertex(x - half_width, 1.0),
-            ]);
+            vertices.extend(Self::create_line_vertices(
+                &[(x, -1.0), (x, 1.0)],
+                half_width,
+                DashPattern::SOLID,
+                0.0,
+                CandleVertex::wick_vertex,
+            ));
         }
 
         // Horizontal lines
         for i in 0..=grid_lines_y {
             let y = i as f32 / grid_lines_y as f32 * 2.0 - 1.0; // normalize to [-1, 1]
-            let half_width = line_width * 0.5;
-
-            // Horizontal line as a thin rectangle
-            vertices.extend_from_slice(&[
-                CandleVertex::wick_vertex(-1.0, y - half_width),
-                CandleVertex::wick_vertex(1.0, y - half_width),
-                CandleVertex::wick_vertex(-1.0, y + half_width),
-                CandleVertex::wick_vertex(1.0, y - half_width),
-                CandleVertex::wick_vertex(1.0, y + half_width),
-                CandleVertex::wick_vertex(-1.0, y + half_width),
-            ]);
+            vertices.extend(Self::create_line_vertices(
+                &[(-1.0, y), (1.0, y)],
+                half_width,
+                DashPattern::SOLID,
+                0.0,
+                CandleVertex::wick_vertex,
+            ));
         }
 
         vertices
@@ -624,6 +1095,7 @@ impl CandleGeometry {
         chart_height: f32,
         time_lines: u32,
         price_lines: u32,
+        pattern: DashPattern,
     ) -> Vec<CandleVertex> {
         let mut vertices = Vec::new();
         let grid_line_width = 0.001; // very thin grid lines
@@ -633,67 +1105,29 @@ impl CandleGeometry {
         for i in 1..time_lines {
             // Skip the outer lines
             let x = (i as f32 / time_lines as f32) * chart_width - 1.0;
-
-            // Vertical line
-            vertices.extend_from_slice(&[
-                CandleVertex::grid_vertex(x - half_width, -1.0),
-                CandleVertex::grid_vertex(x + half_width, -1.0),
-                CandleVertex::grid_vertex(x - half_width, 1.0),
-                CandleVertex::grid_vertex(x + half_width, -1.0),
-                CandleVertex::grid_vertex(x + half_width, 1.0),
-                CandleVertex::grid_vertex(x - half_width, 1.0),
-            ]);
+            vertices.extend(Self::create_line_vertices(
+                &[(x, -1.0), (x, 1.0)],
+                half_width,
+                pattern,
+                0.0,
+                CandleVertex::grid_vertex,
+            ));
         }
 
-        // Horizontal lines (price grid)
+        // Horizontal lines (price grid) at the same round levels as the axis labels
         let price_range = max_price - min_price;
-        let nice_step = Self::calculate_nice_price_step(price_range, price_lines);
-
-        // Find the first nice price level
-        let first_price = ((min_price / nice_step).ceil() * nice_step).max(min_price);
-
-        let mut current_price = first_price;
-        while current_price <= max_price {
+        let ticks = nice_price_ticks(min_price as f64, max_price as f64, price_lines as usize);
+        for price in ticks {
             // Convert price to Y coordinate
-            let y = -1.0 + ((current_price - min_price) / price_range) * chart_height;
-
-            // Horizontal line
-            vertices.extend_from_slice(&[
-                CandleVertex::grid_vertex(-1.0, y - half_width),
-                CandleVertex::grid_vertex(1.0, y - half_width),
-                CandleVertex::grid_vertex(-1.0, y + half_width),
-                CandleVertex::grid_vertex(1.0, y - half_width),
-                CandleVertex::grid_vertex(1.0, y + half_width),
-                CandleVertex::grid_vertex(-1.0, y + half_width),
-            ]);
-
-            current_price += nice_step;
+            let y = -1.0 + ((price as f32 - min_price) / price_range) * chart_height;
+            vertices.extend(Self::create_horizontal_line(
+                y,
+                grid_line_width,
+                pattern,
+                CandleVertex::grid_vertex,
+            ));
         }
 
         vertices
     }
-
-    /// Calculate a nice step for the price grid
-    fn calculate_nice_price_step(price_range: f32, target_lines: u32) -> f32 {
-        let raw_step = price_range / target_lines as f32;
-
-        // Determine the order of magnitude
-        let magnitude = 10.0_f32.powf(raw_step.log10().floor());
-
-        // Normalize to the range [1, 10)
-        let normalized = raw_step / magnitude;
-
-        // Choose a nice value
-        let nice_normalized = if normalized <= 1.0 {
-            1.0
-        } else if normalized <= 2.0 {
-            2.0
-        } else if normalized <= 5.0 {
-            5.0
-        } else {
-            10.0
-        };
-
-        nice_normalized * magnitude
-    }
 }
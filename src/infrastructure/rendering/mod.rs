@@ -4,8 +4,12 @@
 //! application.
 
 pub mod gpu_structures;
+pub mod palette;
+pub mod panel_layout;
 pub mod renderer;
 
 // Re-exports for convenient access - WebGPU only! 🚀
 pub use gpu_structures::*;
-pub use renderer::WebGpuRenderer;
+pub use palette::palette;
+pub use panel_layout::{MAIN_PANEL, PanelBand, PanelId, PanelLayout};
+pub use renderer::{ChartGeometryBuilder, WebGpuRenderer};
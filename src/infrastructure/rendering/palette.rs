@@ -0,0 +1,43 @@
+//! Deterministic colors for indicator lines.
+//!
+//! Hues are spread evenly around the color wheel starting from the default
+//! indicator yellow (see `DOCS/COLORS.md`). Lightness stays high so every
+//! color reads well on the dark `#253242` chart background.
+
+/// Hue of the first color in degrees (indicator yellow)
+const START_HUE: f32 = 60.0;
+/// Saturation shared by all generated colors
+const SATURATION: f32 = 0.8;
+/// Lightness of even entries; odd entries are slightly lighter
+const LIGHTNESS: f32 = 0.65;
+/// Extra lightness of odd entries so neighbours differ even for large `n`
+const LIGHTNESS_STEP: f32 = 0.1;
+
+/// Generate `n` visually distinct RGBA colors
+pub fn palette(n: usize) -> Vec<[f32; 4]> {
+    (0..n)
+        .map(|i| {
+            let hue = (START_HUE + 360.0 * i as f32 / n as f32) % 360.0;
+            let lightness = if i % 2 == 0 { LIGHTNESS } else { LIGHTNESS + LIGHTNESS_STEP };
+            let [r, g, b] = hsl_to_rgb(hue, SATURATION, lightness);
+            [r, g, b, 1.0]
+        })
+        .collect()
+}
+
+/// Convert HSL (hue in degrees, saturation and lightness in 0..=1) to RGB
+fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    [(r + m).clamp(0.0, 1.0), (g + m).clamp(0.0, 1.0), (b + m).clamp(0.0, 1.0)]
+}
@@ -0,0 +1,192 @@
+//! Vertical layout of the chart area.
+//!
+//! The canvas is split into a main price panel on top followed by any number
+//! of indicator sub-panels. Each panel gets a share of the height proportional
+//! to its weight, and indicators are assigned to panels by name. Geometry is
+//! normalized into the NDC band of the panel it belongs to.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Identifier of a chart panel
+pub type PanelId = u32;
+
+/// Panel holding candles, volume and price overlays
+pub const MAIN_PANEL: PanelId = 0;
+
+/// Vertical NDC gap between adjacent panels
+pub const PANEL_GAP: f32 = 0.02;
+
+/// Smallest accepted relative height
+const MIN_WEIGHT: f32 = 0.05;
+
+/// A panel and its relative height
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Panel {
+    pub id: PanelId,
+    pub weight: f32,
+}
+
+/// Vertical NDC range occupied by a panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelBand {
+    pub bottom: f32,
+    pub top: f32,
+}
+
+impl PanelBand {
+    /// The whole canvas
+    pub const FULL: Self = Self { bottom: -1.0, top: 1.0 };
+
+    pub fn height(&self) -> f32 {
+        self.top - self.bottom
+    }
+
+    /// Map a full-canvas NDC `y` into this band
+    pub fn map_ndc(&self, y: f32) -> f32 {
+        self.bottom + (y + 1.0) * 0.5 * self.height()
+    }
+
+    /// Normalize `value` from `[min, max]` into this band
+    pub fn normalize(&self, value: f32, min: f32, max: f32) -> f32 {
+        let range = max - min;
+        let t = if range.abs() > f32::EPSILON { (value - min) / range } else { 0.5 };
+        self.bottom + t * self.height()
+    }
+
+    /// Convert a position in percent from the band top to percent from the canvas top
+    pub fn css_percent(&self, percent: f64) -> f64 {
+        let top = (1.0 - self.top as f64) * 50.0;
+        top + percent * self.height() as f64 * 0.5
+    }
+}
+
+/// Main panel plus indicator sub-panels with relative heights
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelLayout {
+    panels: Vec<Panel>,
+    assignments: HashMap<String, PanelId>,
+    next_id: PanelId,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            panels: vec![Panel { id: MAIN_PANEL, weight: 1.0 }],
+            assignments: HashMap::new(),
+            next_id: MAIN_PANEL + 1,
+        }
+    }
+}
+
+/// Usable panel weight for `weight`; non-finite values fall back to an even share
+fn valid_weight(weight: f32) -> f32 {
+    if weight.is_finite() { weight.max(MIN_WEIGHT) } else { 1.0 }
+}
+
+impl PanelLayout {
+    /// 🧱 Layout safe to draw from one read back from storage
+    ///
+    /// Weights get the same floor as `set_weight`, assignments to missing
+    /// panels are dropped, and a layout without the main panel is replaced
+    /// by the default.
+    pub fn validated(mut self) -> Self {
+        if self.panels.first().is_none_or(|p| p.id != MAIN_PANEL) {
+            return Self::default();
+        }
+        for panel in &mut self.panels {
+            panel.weight = valid_weight(panel.weight);
+        }
+        let ids: Vec<PanelId> = self.panels.iter().map(|p| p.id).collect();
+        self.assignments.retain(|_, id| ids.contains(id));
+        self.next_id = self.next_id.max(ids.iter().max().map_or(MAIN_PANEL, |id| id + 1));
+        self
+    }
+
+    /// Panels from top to bottom
+    pub fn panels(&self) -> &[Panel] {
+        &self.panels
+    }
+
+    /// Append a sub-panel below the existing ones and return its id
+    pub fn add_panel(&mut self, weight: f32) -> PanelId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.panels.push(Panel { id, weight: valid_weight(weight) });
+        id
+    }
+
+    /// Remove a sub-panel; its indicators move back to the main panel
+    pub fn remove_panel(&mut self, id: PanelId) -> bool {
+        if id == MAIN_PANEL {
+            return false;
+        }
+        let before = self.panels.len();
+        self.panels.retain(|p| p.id != id);
+        if self.panels.len() == before {
+            return false;
+        }
+        self.assignments.retain(|_, panel| *panel != id);
+        true
+    }
+
+    /// Change the relative height of a panel
+    pub fn set_weight(&mut self, id: PanelId, weight: f32) -> bool {
+        match self.panels.iter_mut().find(|p| p.id == id) {
+            Some(panel) => {
+                panel.weight = valid_weight(weight);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Draw `indicator` in panel `id`; fails for unknown panels
+    pub fn assign(&mut self, indicator: &str, id: PanelId) -> bool {
+        if !self.panels.iter().any(|p| p.id == id) {
+            return false;
+        }
+        if id == MAIN_PANEL {
+            self.assignments.remove(indicator);
+        } else {
+            self.assignments.insert(indicator.to_string(), id);
+        }
+        true
+    }
+
+    /// Panel an indicator is drawn in; unassigned indicators overlay the main panel
+    pub fn panel_of(&self, indicator: &str) -> PanelId {
+        self.assignments.get(indicator).copied().unwrap_or(MAIN_PANEL)
+    }
+
+    /// NDC bands of all panels from top to bottom
+    pub fn bands(&self) -> Vec<(PanelId, PanelBand)> {
+        let total: f32 = self.panels.iter().map(|p| p.weight).sum();
+        let available = 2.0 - PANEL_GAP * self.panels.len().saturating_sub(1) as f32;
+        let mut top = 1.0;
+        self.panels
+            .iter()
+            .map(|p| {
+                let height = available * p.weight / total;
+                let band = PanelBand { bottom: top - height, top };
+                top -= height + PANEL_GAP;
+                (p.id, band)
+            })
+            .collect()
+    }
+
+    /// NDC band of panel `id`
+    pub fn band(&self, id: PanelId) -> Option<PanelBand> {
+        self.bands().into_iter().find(|(panel, _)| *panel == id).map(|(_, band)| band)
+    }
+
+    /// NDC band of the main price panel
+    pub fn main_band(&self) -> PanelBand {
+        self.band(MAIN_PANEL).unwrap_or(PanelBand::FULL)
+    }
+
+    /// NDC band the given indicator is drawn in
+    pub fn band_for(&self, indicator: &str) -> PanelBand {
+        self.band(self.panel_of(indicator)).unwrap_or_else(|| self.main_band())
+    }
+}
@@ -0,0 +1,103 @@
+//! 🎞️ Short tweens between two data updates.
+//!
+//! When the newest candle ticks or a new one opens, the builder eases its
+//! OHLC and the auto-fit price range from what is on screen to the new
+//! values instead of snapping.
+
+use crate::domain::market_data::Candle;
+
+/// Length of a transition in milliseconds
+pub const TRANSITION_MS: f64 = 150.0;
+
+/// Ease-out cubic: fast start, gentle settle; `t` is clamped to `0..=1`
+pub fn ease_out_cubic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// 🎞️ The values a transition interpolates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimatedFrame {
+    /// Auto-fit price range of the main panel
+    pub min_price: f32,
+    pub max_price: f32,
+    /// Open, high, low and close of the newest candle
+    pub last: [f64; 4],
+    /// Timestamp of the newest candle
+    pub timestamp: u64,
+}
+
+impl AnimatedFrame {
+    /// Frame for `last` drawn with the price range `min..max`
+    pub fn new(last: &Candle, min_price: f32, max_price: f32) -> Self {
+        let o = &last.ohlcv;
+        Self {
+            min_price,
+            max_price,
+            last: [o.open.value(), o.high.value(), o.low.value(), o.close.value()],
+            timestamp: last.timestamp.value(),
+        }
+    }
+
+    /// Same range with the newest candle collapsed onto its open, as it looks when it appears
+    pub fn collapsed(&self) -> Self {
+        Self { last: [self.last[0]; 4], ..*self }
+    }
+
+    /// Same candle drawn with `range`'s price range
+    fn with_range(self, range: &Self) -> Self {
+        Self { min_price: range.min_price, max_price: range.max_price, ..self }
+    }
+
+    /// Linear blend towards `to`; the newest candle is taken from `to`'s timestamp
+    pub fn lerp(&self, to: &Self, t: f64) -> Self {
+        let mix = |a: f64, b: f64| a + (b - a) * t;
+        Self {
+            min_price: mix(self.min_price as f64, to.min_price as f64) as f32,
+            max_price: mix(self.max_price as f64, to.max_price as f64) as f32,
+            last: std::array::from_fn(|i| mix(self.last[i], to.last[i])),
+            timestamp: to.timestamp,
+        }
+    }
+}
+
+/// 🎞️ One running tween started at `start_ms`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    from: AnimatedFrame,
+    to: AnimatedFrame,
+    start_ms: f64,
+    duration_ms: f64,
+}
+
+impl Transition {
+    /// Tween from what is `shown` to `target`
+    ///
+    /// A candle that was not on screen before grows out of its open.
+    pub fn new(shown: AnimatedFrame, target: AnimatedFrame, start_ms: f64) -> Self {
+        let from = if shown.timestamp == target.timestamp {
+            shown
+        } else {
+            target.collapsed().with_range(&shown)
+        };
+        Self { from, to: target, start_ms, duration_ms: TRANSITION_MS }
+    }
+
+    pub fn target(&self) -> AnimatedFrame {
+        self.to
+    }
+
+    /// Linear progress in `0..=1` at `now_ms`
+    pub fn progress(&self, now_ms: f64) -> f64 {
+        ((now_ms - self.start_ms) / self.duration_ms).clamp(0.0, 1.0)
+    }
+
+    pub fn is_finished(&self, now_ms: f64) -> bool {
+        self.progress(now_ms) >= 1.0
+    }
+
+    /// Eased frame to draw at `now_ms`
+    pub fn frame_at(&self, now_ms: f64) -> AnimatedFrame {
+        self.from.lerp(&self.to, ease_out_cubic(self.progress(now_ms)))
+    }
+}
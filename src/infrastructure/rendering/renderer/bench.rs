@@ -0,0 +1,208 @@
+//! ⏱️ Geometry generation benchmark, built with the `bench` feature.
+//!
+//! Times [`ChartGeometryBuilder::create_geometry`] on synthetic series so
+//! buffer and instancing changes can be compared run against run, and the
+//! [`MovingAverageCache`] against recomputing every average.
+
+use super::{ChartGeometryBuilder, LineVisibility, MaConfig, MovingAverageCache};
+use crate::domain::chart::{Chart, value_objects::ChartType};
+use crate::domain::logging::{LogComponent, get_logger};
+use crate::domain::market_data::{
+    Candle, OHLCV, Price, TimeInterval, Timestamp, Volume, services::MarketAnalysisService,
+};
+
+/// Series lengths measured by [`run_geometry_bench`]
+pub const BENCH_CANDLE_COUNTS: [usize; 4] = [100, 300, 1000, 5000];
+
+/// `create_geometry` calls averaged per measurement
+pub const BENCH_ITERATIONS: u32 = 20;
+
+/// One measured series length and indicator setting
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct GeometryBenchResult {
+    pub candles: usize,
+    pub indicators: bool,
+    pub iterations: u32,
+    pub mean_ms: f64,
+    pub instances: usize,
+    pub vertices: usize,
+}
+
+/// Default moving averages recomputed vs updated for one changed last candle
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MovingAverageBenchResult {
+    pub candles: usize,
+    pub iterations: u32,
+    pub full_ms: f64,
+    pub incremental_ms: f64,
+    pub speedup: f64,
+}
+
+/// Every overlay on or off
+fn indicator_visibility(on: bool) -> LineVisibility {
+    LineVisibility {
+        sma_20: on,
+        sma_50: on,
+        sma_200: on,
+        ema_12: on,
+        ema_26: on,
+        session_levels: on,
+        rsi_14: on,
+        macd: on,
+        bollinger: on,
+        vwap: on,
+        depth: on,
+        stochastic: on,
+        atr: on,
+        keltner: on,
+        volume_ma: on,
+        gaps: on,
+    }
+}
+
+/// Deterministic one-minute random walk of `count` candles
+fn bench_chart(count: usize) -> Chart {
+    let mut price = 100.0;
+    let candles = (0..count)
+        .map(|i| {
+            let open = price;
+            price += ((i * 7919) % 13) as f64 * 0.1 - 0.6;
+            let spread = 0.2 + (i % 5) as f64 * 0.1;
+            Candle::new(
+                Timestamp::from_millis(i as u64 * 60_000),
+                OHLCV::new(
+                    Price::from(open),
+                    Price::from(open.max(price) + spread),
+                    Price::from(open.min(price) - spread),
+                    Price::from(price),
+                    Volume::from(10.0 + (i % 17) as f64),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("bench".into(), ChartType::Candlestick, count);
+    chart.set_historical_data(candles);
+    chart
+}
+
+#[cfg(target_arch = "wasm32")]
+fn time_ms(f: impl FnOnce()) -> f64 {
+    let perf = web_sys::window().and_then(|w| w.performance());
+    let start = perf.as_ref().map(|p| p.now()).unwrap_or_default();
+    f();
+    perf.map(|p| p.now() - start).unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn time_ms(f: impl FnOnce()) -> f64 {
+    let start = std::time::Instant::now();
+    f();
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// ⏱️ Time `create_geometry` for each of [`BENCH_CANDLE_COUNTS`] with indicators off and on
+///
+/// Runs fully zoomed out on an 800x600 canvas so the most candles are drawn.
+pub fn geometry_bench() -> Vec<GeometryBenchResult> {
+    let mut results = Vec::new();
+    for candles in BENCH_CANDLE_COUNTS {
+        let chart = bench_chart(candles);
+        for indicators in [false, true] {
+            let mut builder = ChartGeometryBuilder::new(800, 600);
+            builder.set_zoom_params(0.0, 0.0);
+            builder.set_line_visibility(indicator_visibility(indicators));
+
+            let (mut instances, mut vertices) = (0, 0);
+            let total_ms = time_ms(|| {
+                for _ in 0..BENCH_ITERATIONS {
+                    let (inst, verts, _) = builder.create_geometry(&chart);
+                    (instances, vertices) = (inst.len(), verts.len());
+                }
+            });
+            let result = GeometryBenchResult {
+                candles,
+                indicators,
+                iterations: BENCH_ITERATIONS,
+                mean_ms: total_ms / BENCH_ITERATIONS as f64,
+                instances,
+                vertices,
+            };
+            get_logger().info(
+                LogComponent::Infrastructure("GeometryBench"),
+                &format!(
+                    "⏱️ {candles} candles, indicators {}: {:.3}ms, {instances} instances, {vertices} vertices",
+                    if indicators { "on" } else { "off" },
+                    result.mean_ms
+                ),
+            );
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// ⏱️ [`geometry_bench`] results as a JSON array
+pub fn run_geometry_bench() -> String {
+    serde_json::to_string(&geometry_bench()).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// ⏱️ Time the default moving averages from scratch and through the cache
+///
+/// Each iteration rewrites the close of the last candle, like a live update.
+pub fn moving_average_bench() -> Vec<MovingAverageBenchResult> {
+    let lines: Vec<_> =
+        MaConfig::defaults(&Default::default()).iter().map(|c| (c.kind, c.period)).collect();
+    let analysis = MarketAnalysisService::new();
+    BENCH_CANDLE_COUNTS
+        .into_iter()
+        .map(|candles| {
+            let chart = bench_chart(candles);
+            let mut series: Vec<Candle> = chart
+                .get_series(TimeInterval::TwoSeconds)
+                .map(|s| s.get_candles().iter().cloned().collect())
+                .unwrap_or_default();
+            let last_close = series[candles - 1].ohlcv.close.value();
+            let tick = |series: &mut Vec<Candle>, i: u32| {
+                series[candles - 1].ohlcv.close = (last_close + i as f64 * 0.01).into();
+            };
+
+            let full_ms = time_ms(|| {
+                for i in 0..BENCH_ITERATIONS {
+                    tick(&mut series, i);
+                    for &(kind, period) in &lines {
+                        analysis.calculate_ma(&series, kind, period);
+                    }
+                }
+            });
+            let mut cache = MovingAverageCache::default();
+            cache.update(&series, lines.iter().copied());
+            let incremental_ms = time_ms(|| {
+                for i in 0..BENCH_ITERATIONS {
+                    tick(&mut series, i + 1);
+                    cache.update(&series, lines.iter().copied());
+                }
+            });
+
+            let result = MovingAverageBenchResult {
+                candles,
+                iterations: BENCH_ITERATIONS,
+                full_ms: full_ms / BENCH_ITERATIONS as f64,
+                incremental_ms: incremental_ms / BENCH_ITERATIONS as f64,
+                speedup: full_ms / incremental_ms.max(f64::EPSILON),
+            };
+            get_logger().info(
+                LogComponent::Infrastructure("GeometryBench"),
+                &format!(
+                    "⏱️ {candles} candles, moving averages: {:.3}ms full, {:.3}ms incremental ({:.1}x)",
+                    result.full_ms, result.incremental_ms, result.speedup
+                ),
+            );
+            result
+        })
+        .collect()
+}
+
+/// ⏱️ [`moving_average_bench`] results as a JSON array
+pub fn run_moving_average_bench() -> String {
+    serde_json::to_string(&moving_average_bench()).unwrap_or_else(|_| "[]".to_string())
+}
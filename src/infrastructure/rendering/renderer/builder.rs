@@ -0,0 +1,878 @@
+use super::*;
+use crate::domain::logging::LogComponent;
+use crate::domain::market_data::TimeInterval;
+use crate::log_info;
+use leptos::{SignalGetUntracked, SignalSet, SignalWithUntracked};
+use std::hash::{Hash, Hasher};
+
+impl ChartGeometryBuilder {
+    /// Builder for a `width` x `height` canvas with default settings and empty caches
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixel_ratio: 1.0,
+            template_vertices: 0,
+            cached_vertices: Vec::new(),
+            cached_instances: Vec::new(),
+            overlay_vertex_start: 0,
+            cached_uniforms: ChartUniforms::new(),
+            cached_candle_count: 0,
+            cached_zoom_level: 1.0,
+            cached_hash: 0,
+            cached_data_hash: 0,
+            cached_line_visibility: LineVisibility::default(),
+            zoom_level: 1.0,
+            pan_offset: 0.0,
+            line_visibility: LineVisibility::default(),
+            vertex_buffer_stats: VertexBufferStats::default(),
+            instance_buffer_stats: VertexBufferStats::with_capacity(INITIAL_INSTANCE_CAPACITY),
+            interval_aware_width: false,
+            x_axis_mode: XAxisMode::default(),
+            line_styles: LineStyles::default(),
+            theme: ChartTheme::default(),
+            price_tick: None,
+            candle_coloring: CandleColoring::default(),
+            min_body_height: MinBodyHeight::default(),
+            empty_state: EmptyChartState::default(),
+            rsi_period: DEFAULT_RSI_PERIOD,
+            stochastic_period: DEFAULT_STOCHASTIC_PERIOD,
+            bollinger_fill: true,
+            auto_fit_y: true,
+            vwap_anchor: None,
+            order_book: None,
+            moving_averages: None,
+            moving_average_configs: MaConfig::defaults(&ChartTheme::default()),
+            moving_average_cache: RefCell::default(),
+            animate_transitions: false,
+            transition: None,
+            shown_frame: None,
+            frame_override: None,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 📐 Build geometry for a `width` x `height` canvas from the next update on
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// 🖥️ Device pixels per CSS pixel of the canvas, e.g. `devicePixelRatio`
+    ///
+    /// `width` and `height` count device pixels, so lines are scaled by this
+    /// to look equally thick on standard and HiDPI screens.
+    pub fn set_pixel_ratio(&mut self, ratio: f32) {
+        let ratio = if ratio.is_finite() && ratio > 0.0 { ratio } else { 1.0 };
+        if ratio != self.pixel_ratio {
+            self.pixel_ratio = ratio;
+            // Force geometry refresh on next render
+            self.cached_zoom_level = f64::MAX;
+        }
+    }
+
+    pub fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    /// 📐 Enable or disable interval-aware candle width
+    pub fn set_interval_aware_width(&mut self, enabled: bool) {
+        self.interval_aware_width = enabled;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// ↔️ Place candles evenly by index or proportionally to their open time
+    pub fn set_x_axis_mode(&mut self, mode: XAxisMode) {
+        self.x_axis_mode = mode;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn x_axis_mode(&self) -> XAxisMode {
+        self.x_axis_mode
+    }
+
+    /// ↔️ X layout of `visible` candles under the current mode and interval
+    pub fn x_axis_for(&self, visible: &[Candle]) -> XAxis {
+        match self.x_axis_mode {
+            XAxisMode::Ordinal => XAxis::ordinal(visible.len()),
+            XAxisMode::Time => {
+                XAxis::new(XAxisMode::Time, visible, crate::app::current_interval().get_untracked())
+            }
+        }
+    }
+
+    /// ➖ Set dash patterns for grid, price, alert and moving-average lines
+    pub fn set_line_styles(&mut self, styles: LineStyles) {
+        self.line_styles = styles;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn line_styles(&self) -> LineStyles {
+        self.line_styles
+    }
+
+    /// 🎨 Switch the color theme used for candles, grid, indicators and background
+    ///
+    /// Moving averages still in the old theme's default colors are recolored.
+    pub fn set_theme(&mut self, theme: ChartTheme) {
+        if self.moving_average_configs == MaConfig::defaults(&self.theme) {
+            self.moving_average_configs = MaConfig::defaults(&theme);
+        }
+        self.theme = theme;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn theme(&self) -> ChartTheme {
+        self.theme
+    }
+
+    /// 👁️ Replace which indicator lines and overlays are drawn
+    pub fn set_line_visibility(&mut self, visibility: LineVisibility) {
+        self.line_visibility = visibility;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// 📏 Round rendered prices to `tick`, or render raw prices with `None`
+    pub fn set_price_tick(&mut self, tick: Option<f64>) {
+        self.price_tick = tick.filter(|t| *t > 0.0);
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn price_tick(&self) -> Option<f64> {
+        self.price_tick
+    }
+
+    /// 🎨 Choose how candles and volume bars are colored
+    pub fn set_candle_coloring(&mut self, coloring: CandleColoring) {
+        self.candle_coloring = coloring;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// ⬛ Choose how far short candle bodies are stretched
+    pub fn set_min_body_height(&mut self, min_height: MinBodyHeight) {
+        self.min_body_height = min_height;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn min_body_height(&self) -> MinBodyHeight {
+        self.min_body_height
+    }
+
+    /// 🕳️ Choose the placeholder drawn while the chart has no candles
+    pub fn set_empty_state(&mut self, state: EmptyChartState) {
+        self.empty_state = state;
+    }
+
+    pub fn empty_state(&self) -> EmptyChartState {
+        self.empty_state
+    }
+
+    /// 📉 Set the RSI lookback period (at least 1)
+    pub fn set_rsi_period(&mut self, period: usize) {
+        self.rsi_period = period.max(1);
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn rsi_period(&self) -> usize {
+        self.rsi_period
+    }
+
+    /// 📉 Set the stochastic %K lookback period (at least 1)
+    pub fn set_stochastic_period(&mut self, period: usize) {
+        self.stochastic_period = period.max(1);
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn stochastic_period(&self) -> usize {
+        self.stochastic_period
+    }
+
+    /// 🌫️ Enable or disable the translucent fill between the Bollinger bands
+    pub fn set_bollinger_fill(&mut self, enabled: bool) {
+        self.bollinger_fill = enabled;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// 📊 Reset VWAP every day from `anchor` (ms), or accumulate from the first visible candle
+    pub fn set_vwap_anchor(&mut self, anchor: Option<u64>) {
+        self.vwap_anchor = anchor;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn vwap_anchor(&self) -> Option<u64> {
+        self.vwap_anchor
+    }
+
+    /// 📚 Order book shown by the depth histogram; `None` clears it
+    pub fn set_order_book(&mut self, book: Option<OrderBook>) {
+        self.order_book = book;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn order_book(&self) -> Option<&OrderBook> {
+        self.order_book.as_ref()
+    }
+
+    /// 🧮 Moving averages computed elsewhere for the candles identified by `source`
+    ///
+    /// Geometry reuses them while the chart still matches `source` and
+    /// recomputes them otherwise.
+    pub fn set_moving_averages(&mut self, averages: Option<(IndicatorSource, MovingAveragesData)>) {
+        self.moving_averages = averages;
+    }
+
+    /// 📈 Replace the moving-average lines; averages with a zero period are dropped
+    pub fn set_moving_average_configs(&mut self, configs: Vec<MaConfig>) {
+        self.moving_average_configs = configs.into_iter().filter(|c| c.period >= 1).collect();
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn moving_average_configs(&self) -> &[MaConfig] {
+        &self.moving_average_configs
+    }
+
+    /// 📐 Fit the price scale to the visible candles, or follow the viewport when off
+    pub fn set_auto_fit_y(&mut self, enabled: bool) {
+        self.auto_fit_y = enabled;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    pub fn auto_fit_y(&self) -> bool {
+        self.auto_fit_y
+    }
+
+    /// 🔍 Set zoom and pan parameters; zoom is clamped to the allowed range
+    /// ⏮️ Default zoom, no pan and prices fitted to the visible candles
+    pub fn reset_view(&mut self) {
+        self.set_zoom_params(crate::app::DEFAULT_ZOOM_LEVEL, 0.0);
+        self.set_auto_fit_y(true);
+    }
+
+    pub fn set_zoom_params(&mut self, zoom_level: f64, pan_offset: f64) {
+        let zoom_level = crate::app::clamp_zoom(zoom_level);
+        // Zooming and panning jump straight to the new view
+        if (zoom_level - self.zoom_level).abs() > ZOOM_EPSILON || pan_offset != self.pan_offset {
+            self.transition = None;
+        }
+        self.zoom_level = zoom_level;
+        self.pan_offset = pan_offset;
+        // Force geometry refresh on next render
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// 🔍 Zoom by a wheel `delta` keeping the candle under `cursor_ndc_x` fixed
+    ///
+    /// Returns the new `(zoom_level, pan_offset)`.
+    pub fn zoom_at(&mut self, delta: f64, cursor_ndc_x: f32) -> (f64, f64) {
+        let (zoom, pan) =
+            crate::app::zoom_at_cursor(self.zoom_level, self.pan_offset, delta, cursor_ndc_x);
+        self.set_zoom_params(zoom, pan);
+        (zoom, pan)
+    }
+
+    fn geometry_hash(
+        vertices: &[CandleVertex],
+        instances: &[CandleInstance],
+        uniforms: &ChartUniforms,
+    ) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytemuck::cast_slice::<CandleVertex, u8>(vertices).hash(&mut hasher);
+        bytemuck::cast_slice::<CandleInstance, u8>(instances).hash(&mut hasher);
+        bytemuck::bytes_of(uniforms).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn data_hash(chart: &Chart, _zoom: f64) -> u64 {
+        let candles = chart
+            .get_series(crate::app::current_interval().get_untracked())
+            .or_else(|| chart.get_series(TimeInterval::TwoSeconds))
+            .expect("base series not found")
+            .get_candles();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        chart.chart_type.hash(&mut hasher);
+        // Vertical pan/zoom changes the price scale when auto-fit is off
+        chart.viewport.min_price.to_bits().hash(&mut hasher);
+        chart.viewport.max_price.to_bits().hash(&mut hasher);
+        candles.len().hash(&mut hasher);
+        for c in candles {
+            c.timestamp.value().hash(&mut hasher);
+            c.ohlcv.open.value().to_bits().hash(&mut hasher);
+            c.ohlcv.high.value().to_bits().hash(&mut hasher);
+            c.ohlcv.low.value().to_bits().hash(&mut hasher);
+            c.ohlcv.close.value().to_bits().hash(&mut hasher);
+            c.ohlcv.volume.value().to_bits().hash(&mut hasher);
+        }
+        // Reference lines come from the app state rather than the chart
+        crate::app::horizontal_lines().with_untracked(|lines| {
+            for line in lines {
+                line.price.to_bits().hash(&mut hasher);
+                line.color.map(f32::to_bits).hash(&mut hasher);
+                line.alert.hash(&mut hasher);
+            }
+        });
+        hasher.finish()
+    }
+
+    fn update_cached_geometry(
+        &mut self,
+        mut vertices: Vec<CandleVertex>,
+        instances: Vec<CandleInstance>,
+        uniforms: ChartUniforms,
+    ) -> bool {
+        let new_hash = Self::geometry_hash(&vertices, &instances, &uniforms);
+        if new_hash == self.cached_hash {
+            return false;
+        }
+
+        // Grid and volume go first so the instanced candles can be drawn between the layers
+        vertices.sort_by_key(|v| !v.draws_under_candles());
+        self.overlay_vertex_start =
+            vertices.iter().filter(|v| v.draws_under_candles()).count() as u32;
+
+        self.cached_vertices = vertices;
+        self.cached_instances = instances;
+        self.cached_uniforms = uniforms;
+        self.cached_hash = new_hash;
+        self.cached_line_visibility = self.line_visibility.clone();
+        self.template_vertices = self.cached_vertices.len() as u32;
+        self.vertex_buffer_stats.record(self.cached_vertices.len());
+        self.instance_buffer_stats.record(self.cached_instances.len());
+        true
+    }
+
+    /// 🕳️ Cache the placeholder of a chart without candles; `true` if it changed
+    pub fn update_placeholder(&mut self) -> bool {
+        let (vertices, uniforms) = self.placeholder_geometry(self.empty_state);
+        // The first candles always rebuild
+        self.cached_candle_count = 0;
+        self.cached_data_hash = 0;
+        self.update_cached_geometry(vertices, Vec::new(), uniforms)
+    }
+
+    /// 🗄️ Rebuild the cached geometry for `chart` if its data or settings changed
+    ///
+    /// Returns `None` when the chart has nothing to draw, otherwise whether
+    /// the cached geometry changed and has to be uploaded again.
+    pub fn update(&mut self, chart: &Chart) -> Option<bool> {
+        // Native builds (headless tests) have no clock and never animate
+        #[cfg(target_arch = "wasm32")]
+        let now = web_sys::window().and_then(|w| w.performance()).map(|p| p.now());
+        #[cfg(not(target_arch = "wasm32"))]
+        let now = None;
+        self.update_at(chart, now)
+    }
+
+    /// 🗄️ `update` at animation time `now_ms`; `None` never animates
+    pub fn update_at(&mut self, chart: &Chart, now_ms: Option<f64>) -> Option<bool> {
+        let interval = crate::app::current_interval().get_untracked();
+        let candle_count = chart
+            .get_series(interval)
+            .or_else(|| chart.get_series(TimeInterval::TwoSeconds))
+            .map_or(0, |s| s.get_candles().len());
+        if candle_count == 0 {
+            return None;
+        }
+
+        let data_hash = Self::data_hash(chart, self.zoom_level);
+        let data_changed = data_hash != self.cached_data_hash;
+        let visibility_changed = self.line_visibility != self.cached_line_visibility;
+        let geometry_needs_update = candle_count != self.cached_candle_count
+            || (self.zoom_level - self.cached_zoom_level).abs() > ZOOM_EPSILON;
+
+        let mut changed = false;
+        if geometry_needs_update || data_changed || visibility_changed || self.transition.is_some()
+        {
+            let (instances, vertices, uniforms) =
+                self.create_animated_geometry(chart, data_changed, now_ms);
+            // Line and area modes draw without candle instances
+            if vertices.is_empty() {
+                return None;
+            }
+            self.cached_candle_count = candle_count;
+            self.cached_zoom_level = self.zoom_level;
+            self.cached_data_hash = data_hash;
+            changed = self.update_cached_geometry(vertices, instances, uniforms);
+        }
+
+        if self.cached_vertices.is_empty() { None } else { Some(changed) }
+    }
+
+    /// 🎞️ Geometry for the latest data, or for the tweened frame while a transition runs
+    ///
+    /// A data change starts a transition from the frame on screen; one that
+    /// arrives mid-tween restarts it from the current tweened values.
+    fn create_animated_geometry(
+        &mut self,
+        chart: &Chart,
+        data_changed: bool,
+        now_ms: Option<f64>,
+    ) -> (Vec<CandleInstance>, Vec<CandleVertex>, ChartUniforms) {
+        self.frame_override = None;
+        let latest = self.create_geometry(chart);
+
+        let interval = crate::app::current_interval().get_untracked();
+        let [_, _, min_price, max_price] = latest.2.viewport;
+        let target = chart
+            .get_series(interval)
+            .and_then(|s| s.get_candles().back().cloned())
+            .map(|last| AnimatedFrame::new(&last, min_price, max_price));
+        let shown = self
+            .shown_frame
+            .take()
+            .filter(|(id, shown_interval, _)| *id == chart.id && *shown_interval == interval);
+
+        let (Some(now), Some(target)) = (now_ms.filter(|_| self.animate_transitions), target)
+        else {
+            self.transition = None;
+            self.shown_frame = target.map(|frame| (chart.id.clone(), interval, frame));
+            return latest;
+        };
+
+        match shown {
+            Some((_, _, frame)) if data_changed && frame != target => {
+                self.transition = Some(Transition::new(frame, target, now));
+            }
+            // A new chart or interval appears without a tween
+            None => self.transition = None,
+            _ => {}
+        }
+
+        let running = self.transition.filter(|t| !t.is_finished(now));
+        self.transition = running;
+        let (frame, geometry) = match running {
+            Some(transition) => {
+                let frame = transition.frame_at(now);
+                self.frame_override = Some(frame);
+                let geometry = self.create_geometry(chart);
+                self.frame_override = None;
+                (frame, geometry)
+            }
+            None => (target, latest),
+        };
+        self.shown_frame = Some((chart.id.clone(), interval, frame));
+        geometry
+    }
+
+    /// 🎞️ Ease the newest candle and the auto-fit range between updates
+    ///
+    /// Disabling drops a running transition and redraws the latest data.
+    pub fn set_animate_transitions(&mut self, enabled: bool) {
+        self.animate_transitions = enabled;
+        if !enabled && self.transition.take().is_some() {
+            // Force geometry refresh on next render
+            self.cached_zoom_level = f64::MAX;
+        }
+    }
+
+    pub fn animate_transitions(&self) -> bool {
+        self.animate_transitions
+    }
+
+    /// Whether a transition still needs frames
+    pub fn is_animating(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Newest candle and price range in the last built frame
+    pub fn shown_frame(&self) -> Option<AnimatedFrame> {
+        self.shown_frame.as_ref().map(|(_, _, frame)| *frame)
+    }
+
+    /// 🗄️ Forget the cached geometry so the next update rebuilds and re-uploads it
+    pub fn invalidate(&mut self) {
+        self.cached_hash = 0;
+        self.cached_zoom_level = f64::MAX;
+    }
+
+    /// Hash of the cached vertices, instances and uniforms
+    pub fn cached_hash(&self) -> u64 {
+        self.cached_hash
+    }
+
+    pub fn cached_vertices(&self) -> &[CandleVertex] {
+        &self.cached_vertices
+    }
+
+    pub fn cached_instances(&self) -> &[CandleInstance] {
+        &self.cached_instances
+    }
+
+    pub fn cached_uniforms(&self) -> ChartUniforms {
+        self.cached_uniforms
+    }
+
+    /// 💰 NDC height of the current price line in the last built frame
+    ///
+    /// `None` until a frame with candles has been built.
+    pub fn current_price_y_ndc(&self) -> Option<f32> {
+        let [_, _, min_price, max_price] = self.cached_uniforms.viewport;
+        if self.cached_vertices.is_empty() || max_price <= min_price {
+            return None;
+        }
+        let band = crate::app::panel_layout().get_untracked().main_band();
+        Some(self.current_price_ndc(band, min_price, max_price))
+    }
+
+    /// Toggle indicator line visibility
+    pub fn toggle_line_visibility(&mut self, line_name: &str) {
+        let state = match line_name {
+            "sma20" => {
+                self.line_visibility.sma_20 = !self.line_visibility.sma_20;
+                Some(self.line_visibility.sma_20)
+            }
+            "sma50" => {
+                self.line_visibility.sma_50 = !self.line_visibility.sma_50;
+                Some(self.line_visibility.sma_50)
+            }
+            "sma200" => {
+                self.line_visibility.sma_200 = !self.line_visibility.sma_200;
+                Some(self.line_visibility.sma_200)
+            }
+            "ema12" => {
+                self.line_visibility.ema_12 = !self.line_visibility.ema_12;
+                Some(self.line_visibility.ema_12)
+            }
+            "ema26" => {
+                self.line_visibility.ema_26 = !self.line_visibility.ema_26;
+                Some(self.line_visibility.ema_26)
+            }
+            "session" => {
+                self.line_visibility.session_levels = !self.line_visibility.session_levels;
+                Some(self.line_visibility.session_levels)
+            }
+            "rsi" => {
+                self.line_visibility.rsi_14 = !self.line_visibility.rsi_14;
+                Some(self.line_visibility.rsi_14)
+            }
+            "macd" => {
+                self.line_visibility.macd = !self.line_visibility.macd;
+                Some(self.line_visibility.macd)
+            }
+            "bollinger" => {
+                self.line_visibility.bollinger = !self.line_visibility.bollinger;
+                Some(self.line_visibility.bollinger)
+            }
+            "vwap" => {
+                self.line_visibility.vwap = !self.line_visibility.vwap;
+                Some(self.line_visibility.vwap)
+            }
+            "depth" => {
+                self.line_visibility.depth = !self.line_visibility.depth;
+                Some(self.line_visibility.depth)
+            }
+            "stochastic" => {
+                self.line_visibility.stochastic = !self.line_visibility.stochastic;
+                Some(self.line_visibility.stochastic)
+            }
+            "atr" => {
+                self.line_visibility.atr = !self.line_visibility.atr;
+                Some(self.line_visibility.atr)
+            }
+            "keltner" => {
+                self.line_visibility.keltner = !self.line_visibility.keltner;
+                Some(self.line_visibility.keltner)
+            }
+            "volume_ma" => {
+                self.line_visibility.volume_ma = !self.line_visibility.volume_ma;
+                Some(self.line_visibility.volume_ma)
+            }
+            "gaps" => {
+                self.line_visibility.gaps = !self.line_visibility.gaps;
+                Some(self.line_visibility.gaps)
+            }
+            _ => None,
+        };
+
+        if let Some(state) = state {
+            log_info!(
+                LogComponent::Infrastructure("LegendToggle"),
+                "Line {} visible: {}",
+                line_name,
+                state
+            );
+        }
+        crate::app::global_line_visibility().set(self.line_visibility.clone());
+    }
+
+    pub fn line_visibility(&self) -> LineVisibility {
+        self.line_visibility.clone()
+    }
+
+    pub fn vertex_buffer_stats(&self) -> VertexBufferStats {
+        self.vertex_buffer_stats.clone()
+    }
+
+    /// Check if the legend checkbox was clicked
+    pub fn check_legend_checkbox_click(&self, mouse_x: f32, mouse_y: f32) -> Option<String> {
+        const LEGEND_LEFT: f32 = 10.0;
+        const LEGEND_TOP: f32 = 10.0;
+        const BOX_SIZE: f32 = 20.0;
+        const BOX_GAP: f32 = 30.0;
+
+        let lines = ["sma20", "sma50", "sma200", "ema12", "ema26"];
+
+        for (i, name) in lines.iter().enumerate() {
+            let x0 = LEGEND_LEFT;
+            let y0 = LEGEND_TOP + i as f32 * BOX_GAP;
+            let x1 = x0 + BOX_SIZE;
+            let y1 = y0 + BOX_SIZE;
+            if mouse_x >= x0 && mouse_x <= x1 && mouse_y >= y0 && mouse_y <= y1 {
+                return Some((*name).to_string());
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggles_visibility() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        assert!(r.line_visibility.sma_20);
+        r.toggle_line_visibility("sma20");
+        assert!(!r.line_visibility.sma_20);
+    }
+
+    #[test]
+    fn visibility_signal_updates() {
+        use crate::app::global_line_visibility;
+
+        global_line_visibility().set(LineVisibility::default());
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        r.toggle_line_visibility("sma20");
+        assert!(!global_line_visibility().get_untracked().sma_20);
+        r.toggle_line_visibility("sma20");
+        assert!(global_line_visibility().get_untracked().sma_20);
+    }
+
+    #[test]
+    fn legend_click_detection() {
+        let r = ChartGeometryBuilder::new(800, 600);
+        assert_eq!(r.check_legend_checkbox_click(15.0, 15.0), Some("sma20".to_string()));
+        assert_eq!(r.check_legend_checkbox_click(100.0, 100.0), None);
+    }
+
+    #[test]
+    fn no_buffer_reupload_when_unchanged() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let verts = vec![CandleVertex::body_vertex(0.0, 0.0, true)];
+        let inst = vec![CandleInstance {
+            x: 0.0,
+            width: 0.1,
+            body_top: 0.5,
+            body_bottom: 0.0,
+            high: 0.6,
+            low: -0.1,
+            bullish: 1.0,
+            _padding: 0.0,
+        }];
+        let uniforms = ChartUniforms::default();
+        assert!(r.update_cached_geometry(verts.clone(), inst.clone(), uniforms));
+        let cached = r.cached_hash;
+        assert!(!r.update_cached_geometry(verts, inst, ChartUniforms::default()));
+        assert_eq!(r.cached_hash, cached);
+    }
+
+    #[test]
+    fn vertex_buffer_stats_track_uploads() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let verts = vec![CandleVertex::body_vertex(0.0, 0.0, true); 3];
+        r.update_cached_geometry(verts, Vec::new(), ChartUniforms::default());
+
+        let stats = r.vertex_buffer_stats();
+        assert_eq!(stats.last_vertex_count, 3);
+        assert_eq!(stats.peak_vertex_count, 3);
+        assert_eq!(stats.realloc_count, 0);
+    }
+
+    #[test]
+    fn grid_volume_and_depth_are_drawn_under_candles() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let verts = vec![
+            CandleVertex::indicator_vertex(
+                0.0,
+                0.0,
+                crate::infrastructure::rendering::gpu_structures::IndicatorType::SMA20,
+            ),
+            CandleVertex::grid_vertex(0.0, 0.0),
+            CandleVertex::current_price_vertex(0.0, 0.0),
+            CandleVertex::volume_vertex(0.0, 0.0, true),
+            CandleVertex::depth_vertex(0.0, 0.0, false),
+        ];
+        assert!(r.update_cached_geometry(verts, Vec::new(), ChartUniforms::default()));
+
+        assert_eq!(r.overlay_vertex_start, 3);
+        let types: Vec<f32> = r.cached_vertices.iter().map(|v| v.element_type).collect();
+        assert_eq!(types, vec![3.0, 5.0, 10.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn only_changed_geometry_is_recached() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let verts = vec![CandleVertex::grid_vertex(0.0, 0.0)];
+        assert!(r.update_cached_geometry(verts.clone(), Vec::new(), ChartUniforms::default()));
+
+        // Same hash: the frame on screen is still current
+        assert!(!r.update_cached_geometry(verts, Vec::new(), ChartUniforms::default()));
+
+        let moved = vec![CandleVertex::grid_vertex(0.5, 0.0)];
+        assert!(r.update_cached_geometry(moved, Vec::new(), ChartUniforms::default()));
+    }
+
+    #[test]
+    fn instance_count_matches_instances() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let verts = vec![CandleVertex::body_vertex(0.0, 0.0, true)];
+        let inst = vec![
+            CandleInstance {
+                x: 0.0,
+                width: 0.1,
+                body_top: 0.5,
+                body_bottom: 0.0,
+                high: 0.6,
+                low: -0.1,
+                bullish: 1.0,
+                _padding: 0.0,
+            },
+            CandleInstance {
+                x: 0.2,
+                width: 0.1,
+                body_top: 0.4,
+                body_bottom: -0.1,
+                high: 0.5,
+                low: -0.2,
+                bullish: 0.0,
+                _padding: 0.0,
+            },
+        ];
+        assert!(r.update_cached_geometry(verts, inst.clone(), ChartUniforms::default()));
+        assert_eq!(r.cached_instances().len(), inst.len());
+    }
+
+    #[test]
+    fn geometry_updates_on_data_change() {
+        use crate::domain::chart::{Chart, value_objects::ChartType};
+        use crate::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+
+        let mut chart = Chart::new("t".to_string(), ChartType::Candlestick, 10);
+        chart.add_candle(Candle::new(
+            Timestamp::from_millis(0),
+            OHLCV::new(
+                Price::from(1.0),
+                Price::from(1.5),
+                Price::from(0.5),
+                Price::from(1.2),
+                Volume::from(1.0),
+            ),
+        ));
+        chart.add_candle(Candle::new(
+            Timestamp::from_millis(60_000),
+            OHLCV::new(
+                Price::from(1.2),
+                Price::from(1.7),
+                Price::from(0.8),
+                Price::from(1.4),
+                Volume::from(1.0),
+            ),
+        ));
+
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let (inst, verts, uni) = r.create_geometry(&chart);
+        r.update_cached_geometry(verts, inst, uni);
+        r.cached_data_hash = ChartGeometryBuilder::data_hash(&chart, r.zoom_level);
+        let old = r.cached_hash;
+
+        chart.add_candle(Candle::new(
+            Timestamp::from_millis(60_000),
+            OHLCV::new(
+                Price::from(1.2),
+                Price::from(1.9),
+                Price::from(0.8),
+                Price::from(1.6),
+                Volume::from(1.0),
+            ),
+        ));
+
+        assert_eq!(chart.get_candle_count(), 2);
+        let new_hash = ChartGeometryBuilder::data_hash(&chart, r.zoom_level);
+        assert_ne!(new_hash, r.cached_data_hash);
+        let (inst2, verts2, uni2) = r.create_geometry(&chart);
+        assert!(r.update_cached_geometry(verts2, inst2, uni2));
+        r.cached_data_hash = new_hash;
+        assert_ne!(r.cached_hash, old);
+    }
+
+    #[test]
+    fn line_visibility_toggle_updates_geometry() {
+        use crate::domain::chart::{Chart, value_objects::ChartType};
+        use crate::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+
+        // Enough candles for SMA 20 to draw a line
+        let mut chart = Chart::new("t".to_string(), ChartType::Candlestick, 30);
+        for i in 0..30u64 {
+            let base = 1.0 + i as f64 * 0.1;
+            chart.add_candle(Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(base),
+                    Price::from(base + 0.5),
+                    Price::from(base - 0.5),
+                    Price::from(base + 0.2),
+                    Volume::from(1.0),
+                ),
+            ));
+        }
+
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        let (inst, verts, uni) = r.create_geometry(&chart);
+        r.update_cached_geometry(verts.clone(), inst.clone(), uni);
+        r.cached_candle_count = chart.get_candle_count();
+        r.cached_zoom_level = r.zoom_level;
+        r.cached_data_hash = ChartGeometryBuilder::data_hash(&chart, r.zoom_level);
+        let cached = r.cached_hash;
+
+        r.toggle_line_visibility("sma20");
+        assert_eq!(r.update(&chart), Some(true));
+        assert_ne!(r.cached_hash, cached);
+    }
+
+    #[test]
+    fn reset_view_restores_default_zoom_pan_and_fit() {
+        let mut r = ChartGeometryBuilder::new(800, 600);
+        r.set_zoom_params(3.0, -40.0);
+        r.set_auto_fit_y(false);
+
+        r.reset_view();
+        assert_eq!(r.zoom_level, crate::app::DEFAULT_ZOOM_LEVEL);
+        assert_eq!(r.pan_offset, 0.0);
+        assert!(r.auto_fit_y());
+    }
+}
@@ -0,0 +1,136 @@
+//! 🖌️ Canvas 2D fallback for browsers without WebGPU.
+//!
+//! Draws the candles and the current price line from the same
+//! [`ChartGeometryBuilder`] output the GPU path uploads; volume, indicators
+//! and the other overlays are left out.
+
+use super::{ChartGeometryBuilder, Renderer};
+use crate::app::css_hex;
+use crate::domain::{chart::Chart, errors::ChartError};
+use gloo::utils::document;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+thread_local! {
+    static FALLBACK_RENDERER: RefCell<Option<Rc<RefCell<Canvas2dRenderer>>>> = const { RefCell::new(None) };
+}
+
+/// Store the canvas 2D renderer used instead of the global WebGPU renderer
+pub fn set_fallback_renderer(renderer: Rc<RefCell<Canvas2dRenderer>>) {
+    FALLBACK_RENDERER.with(|cell| *cell.borrow_mut() = Some(renderer));
+}
+
+/// Obtain a mutable reference to the fallback renderer, if one is active
+pub fn with_fallback_renderer<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce(&mut Canvas2dRenderer) -> R,
+{
+    FALLBACK_RENDERER.with(|cell| {
+        let opt = cell.borrow();
+        opt.as_ref().and_then(|rc| rc.try_borrow_mut().ok().map(|mut r| f(&mut r)))
+    })
+}
+
+/// 📐 Canvas pixel of NDC (`x`, `y`) on a `width`x`height` canvas
+pub fn ndc_to_canvas_px(x: f32, y: f32, width: f64, height: f64) -> (f64, f64) {
+    ((x as f64 + 1.0) / 2.0 * width, (1.0 - y as f64) / 2.0 * height)
+}
+
+/// Slower CPU renderer drawing candles with the 2D canvas API
+pub struct Canvas2dRenderer {
+    context: CanvasRenderingContext2d,
+    width: u32,
+    height: u32,
+    geometry: ChartGeometryBuilder,
+}
+
+impl Canvas2dRenderer {
+    pub fn new(canvas_id: &str, width: u32, height: u32) -> Result<Self, ChartError> {
+        let canvas = document()
+            .get_element_by_id(canvas_id)
+            .and_then(|el| el.dyn_into::<HtmlCanvasElement>().ok())
+            .ok_or_else(|| ChartError::NotInitialized(format!("Canvas '{canvas_id}' not found")))?;
+        canvas.set_width(width);
+        canvas.set_height(height);
+        let context = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().ok())
+            .ok_or_else(|| ChartError::NotInitialized("No 2D canvas context".to_string()))?;
+        Ok(Self { context, width, height, geometry: ChartGeometryBuilder::new(width, height) })
+    }
+
+    pub fn geometry(&self) -> &ChartGeometryBuilder {
+        &self.geometry
+    }
+
+    fn draw(&self) {
+        let ctx = &self.context;
+        let (width, height) = (self.width as f64, self.height as f64);
+        let theme = self.geometry.theme();
+        let line_width = self.geometry.pixel_ratio() as f64;
+
+        ctx.set_fill_style_str(&css_hex(theme.background));
+        ctx.fill_rect(0.0, 0.0, width, height);
+
+        // 🕯️ Wick line, then the body on top, like the instanced GPU pass
+        ctx.set_line_width(line_width);
+        for candle in self.geometry.cached_instances() {
+            let color = css_hex(if candle.bullish > 0.5 { theme.bullish } else { theme.bearish });
+            let (x, high) = ndc_to_canvas_px(candle.x, candle.high, width, height);
+            let (_, low) = ndc_to_canvas_px(candle.x, candle.low, width, height);
+            ctx.set_stroke_style_str(&color);
+            ctx.begin_path();
+            ctx.move_to(x, high);
+            ctx.line_to(x, low);
+            ctx.stroke();
+
+            let half = candle.width / 2.0;
+            let (left, top) = ndc_to_canvas_px(candle.x - half, candle.body_top, width, height);
+            let (right, bottom) =
+                ndc_to_canvas_px(candle.x + half, candle.body_bottom, width, height);
+            ctx.set_fill_style_str(&color);
+            ctx.fill_rect(left, top, (right - left).max(1.0), (bottom - top).max(1.0));
+        }
+
+        // 💰 Current price line across the whole chart
+        if let Some(y) = self.geometry.current_price_y_ndc() {
+            let (_, y) = ndc_to_canvas_px(0.0, y, width, height);
+            ctx.set_stroke_style_str(&css_hex(theme.current_price));
+            ctx.begin_path();
+            ctx.move_to(0.0, y);
+            ctx.line_to(width, y);
+            ctx.stroke();
+        }
+    }
+}
+
+impl Renderer for Canvas2dRenderer {
+    fn render(&mut self, chart: &Chart) -> Result<(), ChartError> {
+        // Nothing to draw still clears the canvas to the background
+        if self.geometry.update(chart) == Some(true) {
+            crate::app::publish_frame_overlays(&self.geometry, chart);
+        }
+        self.draw();
+        Ok(())
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width > 0 && height > 0 {
+            self.width = width;
+            self.height = height;
+            self.geometry.resize(width, height);
+        }
+    }
+
+    fn geometry_mut(&mut self) -> &mut ChartGeometryBuilder {
+        &mut self.geometry
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "Canvas 2D"
+    }
+}
@@ -0,0 +1,191 @@
+use super::*;
+use futures::channel::oneshot;
+use wasm_bindgen::Clamped;
+
+/// Bytes per exported pixel (RGBA8)
+const BYTES_PER_PIXEL: u32 = 4;
+
+/// Row stride of a texture-to-buffer copy: `width` pixels rounded up to 256 bytes
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * BYTES_PER_PIXEL;
+    unpadded.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT
+}
+
+/// Tightly packed pixels from `height` rows copied with `padded_bytes_per_row`
+pub fn unpad_rows(data: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row = (width * BYTES_PER_PIXEL) as usize;
+    let stride = padded_bytes_per_row(width) as usize;
+    data.chunks(stride)
+        .take(height as usize)
+        .flat_map(|r| &r[..row.min(r.len())])
+        .copied()
+        .collect()
+}
+
+/// Convert surface pixels in place to straight-alpha RGBA
+///
+/// `bgra` swaps the red and blue channels; `premultiplied` divides the
+/// color channels by alpha, leaving fully transparent pixels black.
+pub fn to_straight_rgba(pixels: &mut [u8], bgra: bool, premultiplied: bool) {
+    for px in pixels.chunks_exact_mut(BYTES_PER_PIXEL as usize) {
+        if bgra {
+            px.swap(0, 2);
+        }
+        let alpha = px[3] as u32;
+        if premultiplied && alpha < 255 {
+            for c in &mut px[..3] {
+                *c = (*c as u32 * 255 + alpha / 2).checked_div(alpha).unwrap_or(0).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Frame copied into a mappable buffer, waiting to be read back
+pub struct FrameCapture {
+    device: wgpu::Device,
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    bgra: bool,
+    premultiplied: bool,
+}
+
+impl FrameCapture {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Map the buffer and return straight-alpha RGBA rows, top to bottom
+    pub async fn read_rgba(self) -> Result<Vec<u8>, ChartError> {
+        let (tx, rx) = oneshot::channel();
+        self.buffer.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = tx.send(result);
+        });
+        // Resolves the mapping on native; a no-op in the browser
+        let _ = self.device.poll(wgpu::PollType::Wait);
+        rx.await
+            .map_err(|_| ChartError::Gpu("Readback cancelled".to_string()))?
+            .map_err(|e| ChartError::Gpu(format!("Failed to map readback buffer: {e}")))?;
+
+        let mut pixels = unpad_rows(&self.buffer.get_mapped_range(..), self.width, self.height);
+        self.buffer.unmap();
+        to_straight_rgba(&mut pixels, self.bgra, self.premultiplied);
+        Ok(pixels)
+    }
+}
+
+impl WebGpuRenderer {
+    /// 📸 Draw the current frame into an offscreen texture and copy it for readback
+    ///
+    /// Only the GPU layer is captured; DOM overlays such as axis labels,
+    /// the legend and the tooltip are not part of the image.
+    pub fn capture_frame(&self) -> Result<FrameCapture, ChartError> {
+        if self.geometry.cached_vertices.is_empty() {
+            return Err(ChartError::NotInitialized("No frame rendered yet".to_string()));
+        }
+        let (bgra, straight_format) = match self.config.format {
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => (true, true),
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => (false, true),
+            _ => (false, false),
+        };
+        if !straight_format {
+            return Err(ChartError::Unsupported(format!(
+                "Cannot export surface format {:?}",
+                self.config.format
+            )));
+        }
+
+        let (width, height) = (self.config.width, self.config.height);
+        let size = wgpu::Extent3d { width, height, depth_or_array_layers: 1 };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Export Texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bytes_per_row = padded_bytes_per_row(width);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Export Readback Buffer"),
+            size: bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Export Encoder"),
+        });
+        self.encode_chart_pass(&mut encoder, &view);
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            size,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        Ok(FrameCapture {
+            device: self.device.clone(),
+            buffer,
+            width,
+            height,
+            bgra,
+            premultiplied: self.config.alpha_mode == wgpu::CompositeAlphaMode::PreMultiplied,
+        })
+    }
+}
+
+/// PNG data URL of straight-alpha RGBA `pixels`, encoded by a 2D canvas
+pub fn rgba_to_png_data_url(pixels: &[u8], width: u32, height: u32) -> Result<String, ChartError> {
+    let canvas = document()
+        .create_element("canvas")
+        .ok()
+        .and_then(|el| el.dyn_into::<HtmlCanvasElement>().ok())
+        .ok_or_else(|| ChartError::NotInitialized("Cannot create export canvas".to_string()))?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|ctx| ctx.dyn_into::<web_sys::CanvasRenderingContext2d>().ok())
+        .ok_or_else(|| ChartError::NotInitialized("No 2D context for export".to_string()))?;
+    let image =
+        web_sys::ImageData::new_with_u8_clamped_array_and_sh(Clamped(pixels), width, height)
+            .map_err(|e| ChartError::InvalidData(format!("Invalid export pixels: {e:?}")))?;
+    context
+        .put_image_data(&image, 0.0, 0.0)
+        .map_err(|e| ChartError::InvalidData(format!("Failed to draw export pixels: {e:?}")))?;
+    canvas
+        .to_data_url_with_type("image/png")
+        .map_err(|e| ChartError::InvalidData(format!("Failed to encode PNG: {e:?}")))
+}
+
+/// 📸 PNG data URL of the main chart's GPU layer
+pub async fn export_chart_png_data_url() -> Result<String, ChartError> {
+    let capture = with_global_renderer(|r| r.capture_frame())
+        .ok_or_else(|| ChartError::NotInitialized("Renderer is not ready".to_string()))??;
+    let (width, height) = (capture.width(), capture.height());
+    let pixels = capture.read_rgba().await?;
+    rgba_to_png_data_url(&pixels, width, height)
+}
@@ -1,12 +1,16 @@
 use super::*;
+use crate::domain::chart::value_objects::ChartType;
+use crate::domain::chart::{GAP_MIN_MISSING_CANDLES, missing_candles};
 use crate::domain::logging::{LogComponent, get_logger};
 use crate::domain::market_data::services::MarketAnalysisService;
-use crate::domain::market_data::{Price, TimeInterval};
+use crate::domain::market_data::{OHLCV, Price, TimeInterval};
 use crate::infrastructure::rendering::gpu_structures::{
-    CandleGeometry, CandleInstance, IndicatorType,
+    CandleGeometry, CandleInstance, DashPattern, IndicatorType, is_bullish_move,
 };
+use crate::infrastructure::rendering::panel_layout::{MAIN_PANEL, PANEL_GAP, PanelBand};
 use crate::{log_info, log_warn};
-use leptos::SignalGetUntracked;
+use leptos::{SignalGetUntracked, SignalWithUntracked};
+use std::borrow::Cow;
 
 /// Minimum element width (candle or volume bar)
 pub const MIN_ELEMENT_WIDTH: f32 = 0.002;
@@ -24,22 +28,1286 @@ pub fn spacing_ratio_for(visible_len: usize) -> f32 {
     SPACING_RATIO * factor
 }
 
+/// Width multiplier for an interval so higher timeframes render thicker
+pub fn interval_width_multiplier(interval: TimeInterval) -> f32 {
+    match interval {
+        TimeInterval::TwoSeconds => 0.8,
+        TimeInterval::OneMinute => 0.9,
+        TimeInterval::FiveMinutes | TimeInterval::FifteenMinutes => 1.0,
+        TimeInterval::OneHour | TimeInterval::FourHours => 1.1,
+        TimeInterval::OneDay | TimeInterval::OneWeek | TimeInterval::OneMonth => 1.2,
+    }
+}
+
+/// 📐 Distance between neighbouring candle centers in NDC
+///
+/// The visible count already reflects the zoom level, so every element
+/// laid out per candle (body, wick, volume bar) derives its slot from this.
+pub fn candle_step(visible_len: usize) -> f32 {
+    assert!(visible_len > 0, "visible_len must be > 0");
+    2.0 / visible_len as f32
+}
+
+/// Candle width for the visible count, optionally scaled by interval
+pub fn candle_width_for(visible_len: usize, interval: Option<TimeInterval>) -> f32 {
+    let step_size = candle_step(visible_len);
+    let base = step_size * (1.0 - spacing_ratio_for(visible_len));
+    let scaled = match interval {
+        // Never wider than one step so neighbours do not overlap
+        Some(interval) => (base * interval_width_multiplier(interval)).min(step_size),
+        None => base,
+    };
+    scaled.clamp(MIN_ELEMENT_WIDTH, MAX_ELEMENT_WIDTH)
+}
+
 /// Candle/bar position taking right edge into account
+///
+/// Centers do not move with the interval-aware width; they are laid out with
+/// the unscaled `candle_width_for(visible_len, None)`.
 pub fn candle_x_position(index: usize, visible_len: usize) -> f32 {
-    assert!(visible_len > 0, "visible_len must be > 0");
-    let step_size = 2.0 / visible_len as f32;
-    let spacing = spacing_ratio_for(visible_len);
-    let width = (step_size * (1.0 - spacing)).clamp(MIN_ELEMENT_WIDTH, MAX_ELEMENT_WIDTH);
-    let base_x = 1.0 - (visible_len as f32 - index as f32 - 1.0) * step_size;
+    let width = candle_width_for(visible_len, None);
+    let base_x = 1.0 - (visible_len as f32 - index as f32 - 1.0) * candle_step(visible_len);
     base_x - width / 2.0 - EDGE_GAP
 }
 
-impl WebGpuRenderer {
-    /// Convert pixel size to normalized device coordinates
+/// Open/close tick length of an OHLC bar as a fraction of `candle_width`
+pub const OHLC_TICK_RATIO: f32 = 0.5;
+
+/// 📊 OHLC bars: a low–high stem with a left open tick and a right close tick
+///
+/// Ticks scale with `candle_width` so bars stay readable across zoom levels.
+/// Colors use `is_bullish_candle` with `coloring`, like the volume bars;
+/// `prev` is the candle before the first visible one.
+pub fn create_ohlc_bars(
+    visible_candles: &[Candle],
+    prev: Option<&Candle>,
+    coloring: CandleColoring,
+    x_axis: &XAxis,
+    candle_width: f32,
+    line_width: f32,
+    price_to_ndc: impl Fn(f64) -> f32,
+) -> Vec<CandleVertex> {
+    let tick = candle_width * OHLC_TICK_RATIO;
+    let half = line_width / 2.0;
+
+    let mut vertices = Vec::with_capacity(visible_candles.len() * 18);
+    for (i, candle) in visible_candles.iter().enumerate() {
+        let x = x_axis.x(i);
+        let open_y = price_to_ndc(candle.ohlcv.open.value());
+        let close_y = price_to_ndc(candle.ohlcv.close.value());
+        let high_y = price_to_ndc(candle.ohlcv.high.value());
+        let low_y = price_to_ndc(candle.ohlcv.low.value());
+        let prev = match i {
+            0 => prev,
+            _ => visible_candles.get(i - 1),
+        };
+        let bullish = is_bullish_candle(candle, prev, coloring);
+
+        let rects = [
+            (x - half, x + half, low_y, high_y),
+            (x - tick, x, open_y - half, open_y + half),
+            (x, x + tick, close_y - half, close_y + half),
+        ];
+        for (left, right, bottom, top) in rects {
+            vertices.extend([
+                CandleVertex::body_vertex(left, bottom, bullish),
+                CandleVertex::body_vertex(right, bottom, bullish),
+                CandleVertex::body_vertex(left, top, bullish),
+                CandleVertex::body_vertex(right, bottom, bullish),
+                CandleVertex::body_vertex(right, top, bullish),
+                CandleVertex::body_vertex(left, top, bullish),
+            ]);
+        }
+    }
+    vertices
+}
+
+/// Width of the depth histogram at the right edge, in NDC
+pub const DEPTH_PANEL_WIDTH: f32 = 0.3;
+
+/// 📚 Cumulative bid/ask depth as horizontal steps at the right edge of `band`
+///
+/// Between two levels the step length is the volume available up to the
+/// nearer one; both sides share one scale so they compare directly.
+pub fn create_depth_bars(
+    book: &OrderBook,
+    band: PanelBand,
+    price_to_ndc: impl Fn(f64) -> f32,
+) -> Vec<CandleVertex> {
+    let bids = book.cumulative_bids();
+    let asks = book.cumulative_asks();
+    let total = bids.last().map_or(0.0, |b| b.1).max(asks.last().map_or(0.0, |a| a.1));
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut vertices = Vec::new();
+    for (side, is_bid) in [(&bids, true), (&asks, false)] {
+        for step in side.windows(2) {
+            let (near, cumulative) = step[0];
+            let far = step[1].0;
+            let (y_a, y_b) = (price_to_ndc(near), price_to_ndc(far));
+            let bottom = y_a.min(y_b).max(band.bottom);
+            let top = y_a.max(y_b).min(band.top);
+            if bottom >= top {
+                continue;
+            }
+            let left = 1.0 - (cumulative / total) as f32 * DEPTH_PANEL_WIDTH;
+            vertices.extend([
+                CandleVertex::depth_vertex(left, bottom, is_bid),
+                CandleVertex::depth_vertex(1.0, bottom, is_bid),
+                CandleVertex::depth_vertex(left, top, is_bid),
+                CandleVertex::depth_vertex(1.0, bottom, is_bid),
+                CandleVertex::depth_vertex(1.0, top, is_bid),
+                CandleVertex::depth_vertex(left, top, is_bid),
+            ]);
+        }
+    }
+    vertices
+}
+
+/// Session (current UTC day) low and high, anchored to the latest candle's day
+pub fn session_high_low(candles: &[Candle]) -> Option<(f64, f64)> {
+    let day = crate::time_utils::utc_day_start(candles.last()?.timestamp.value());
+    candles
+        .iter()
+        .rev()
+        .take_while(|c| crate::time_utils::utc_day_start(c.timestamp.value()) == day)
+        .fold(None, |acc, c| {
+            let (low, high) = (c.ohlcv.low.value(), c.ohlcv.high.value());
+            Some(match acc {
+                Some((lo, hi)) => (low.min(lo), high.max(hi)),
+                None => (low, high),
+            })
+        })
+}
+
+/// Default RSI lookback period
+pub const DEFAULT_RSI_PERIOD: usize = 14;
+
+/// RSI band when no sub-panel is assigned to it
+pub const RSI_PANEL_BAND: PanelBand = PanelBand { bottom: -1.0, top: -0.85 };
+
+/// Relative Strength Index with Wilder's smoothing
+///
+/// Value `k` belongs to close `k + period`; fewer than `period + 1` closes yield nothing.
+pub fn wilder_rsi(closes: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 || closes.len() < period + 1 {
+        return Vec::new();
+    }
+
+    let changes: Vec<f64> = closes.windows(2).map(|w| w[1] - w[0]).collect();
+    let n = period as f64;
+    let mut avg_gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / n;
+    let mut avg_loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / n;
+
+    let rsi = |gain: f64, loss: f64| {
+        if loss == 0.0 {
+            if gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        }
+    };
+
+    let mut values = Vec::with_capacity(changes.len() - period + 1);
+    values.push(rsi(avg_gain, avg_loss));
+    for change in &changes[period..] {
+        avg_gain = (avg_gain * (n - 1.0) + change.max(0.0)) / n;
+        avg_loss = (avg_loss * (n - 1.0) + (-change).max(0.0)) / n;
+        values.push(rsi(avg_gain, avg_loss));
+    }
+    values
+}
+
+/// Bollinger moving-average period
+pub const BOLLINGER_PERIOD: usize = 20;
+/// Bollinger band distance in standard deviations
+pub const BOLLINGER_STD_DEV: f64 = 2.0;
+
+/// Length of a VWAP session in milliseconds
+pub const VWAP_SESSION_MS: i64 = 86_400_000;
+
+/// VWAP session of `timestamp`: day index counted from `anchor`, or a single session without one
+pub fn vwap_session(timestamp: u64, anchor: Option<u64>) -> i64 {
+    anchor.map_or(0, |a| (timestamp as i64 - a as i64).div_euclid(VWAP_SESSION_MS))
+}
+
+/// 📊 Volume-weighted average of the typical price `(high + low + close) / 3`
+///
+/// Sums accumulate from the first candle and reset whenever `vwap_session`
+/// changes. Candles whose session has no volume yet yield `None`.
+pub fn vwap_series(candles: &[Candle], anchor: Option<u64>) -> Vec<Option<f64>> {
+    let mut session = None;
+    let (mut price_volume, mut volume) = (0.0, 0.0);
+    candles
+        .iter()
+        .map(|candle| {
+            let current = vwap_session(candle.timestamp.value(), anchor);
+            if session != Some(current) {
+                session = Some(current);
+                price_volume = 0.0;
+                volume = 0.0;
+            }
+            let ohlcv = &candle.ohlcv;
+            let typical = (ohlcv.high.value() + ohlcv.low.value() + ohlcv.close.value()) / 3.0;
+            price_volume += typical * ohlcv.volume.value();
+            volume += ohlcv.volume.value();
+            if volume > 0.0 { Some(price_volume / volume) } else { None }
+        })
+        .collect()
+}
+
+/// 🕯️ Heikin-Ashi candles smoothed over `candles`
+///
+/// The first open is seeded with the first raw candle's open/close average;
+/// timestamps and volumes are kept.
+pub fn heikin_ashi(candles: &[Candle]) -> Vec<Candle> {
+    let mut result: Vec<Candle> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        let o = &candle.ohlcv;
+        let close = (o.open + o.high + o.low + o.close) / 4.0;
+        let open = match result.last() {
+            Some(prev) => Price::midpoint(prev.ohlcv.open, prev.ohlcv.close),
+            None => Price::midpoint(o.open, o.close),
+        };
+        let high = o.high.max(open).max(close);
+        let low = o.low.min(open).min(close);
+        result.push(Candle::new(candle.timestamp, OHLCV::new(open, high, low, close, o.volume)));
+    }
+    result
+}
+
+/// 🗜️ Visible candles merged into one drawn candle so at most one lands per pixel
+pub fn lod_bucket_size(visible_len: usize, width_px: u32) -> usize {
+    visible_len.div_ceil(width_px.max(1) as usize).max(1)
+}
+
+/// 🗜️ Composite candles of `bucket` consecutive candles each
+///
+/// Keeps the first open and timestamp, the highest high, the lowest low, the
+/// last close and the summed volume; the last bucket may be shorter.
+pub fn downsample_candles(candles: &[Candle], bucket: usize) -> Vec<Candle> {
+    candles
+        .chunks(bucket.max(1))
+        .map(|chunk| {
+            let (first, last) = (&chunk[0], &chunk[chunk.len() - 1]);
+            let high = chunk.iter().map(|c| c.ohlcv.high).fold(first.ohlcv.high, Price::max);
+            let low = chunk.iter().map(|c| c.ohlcv.low).fold(first.ohlcv.low, Price::min);
+            let volume = chunk.iter().map(|c| c.ohlcv.volume).sum();
+            Candle::new(
+                first.timestamp,
+                OHLCV::new(first.ohlcv.open, high, low, last.ohlcv.close, volume),
+            )
+        })
+        .collect()
+}
+
+/// Number of vertical time grid lines with labels
+pub const TIME_GRID_LINES: usize = 5;
+
+/// 📈 NDC points of a moving average over the visible candles
+///
+/// `values[k]` belongs to candle `k + period - 1` of the full series; values
+/// outside `start_index..start_index + x_axis.len()` are dropped and prices
+/// are mapped to y with `price_norm`.
+pub fn moving_average_points(
+    values: &[Price],
+    period: usize,
+    start_index: usize,
+    x_axis: &XAxis,
+    price_norm: impl Fn(f64) -> f32,
+) -> Vec<(f32, f32)> {
+    values
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, val)| {
+            let candle_idx = idx + period - 1;
+            if candle_idx < start_index || candle_idx >= start_index + x_axis.len() {
+                return None;
+            }
+            let x = x_axis.x(candle_idx - start_index);
+            Some((x, price_norm(val.value())))
+        })
+        .collect()
+}
+
+/// Visible candle indices that carry a vertical time grid line
+pub fn time_grid_indices(visible_len: usize) -> Vec<usize> {
+    let Some(last) = visible_len.checked_sub(1) else {
+        return Vec::new();
+    };
+    let mut indices: Vec<usize> =
+        (0..TIME_GRID_LINES).map(|k| k * last / (TIME_GRID_LINES - 1)).collect();
+    indices.dedup();
+    indices
+}
+
+/// Dash pattern of the vertical gap markers
+pub const GAP_MARKER_DASH: DashPattern = DashPattern { on: 0.015, off: 0.015 };
+
+/// 🕳️ Indices `i` of `candles` preceded by an outage of `GAP_MIN_MISSING_CANDLES` or more
+pub fn gap_indices(candles: &[Candle], interval: TimeInterval) -> Vec<usize> {
+    (1..candles.len())
+        .filter(|&i| {
+            let missing = missing_candles(candles[i - 1].timestamp, candles[i].timestamp, interval);
+            missing >= GAP_MIN_MISSING_CANDLES
+        })
+        .collect()
+}
+
+/// Roughly how many horizontal price grid lines to aim for
+pub const PRICE_GRID_LINES: usize = 8;
+
+/// 📏 Round step of 1, 2 or 5 × 10ⁿ giving about `count` ticks over `min..max`
+///
+/// Same thresholds as D3's `tickStep`; degenerate ranges give 0.
+pub fn nice_price_step(min: f64, max: f64, count: usize) -> f64 {
+    let raw = (max - min) / count.max(1) as f64;
+    if !(raw.is_finite() && raw > 0.0) {
+        return 0.0;
+    }
+    let power = 10f64.powf(raw.log10().floor());
+    let error = raw / power;
+    let factor = if error >= 50f64.sqrt() {
+        10.0
+    } else if error >= 10f64.sqrt() {
+        5.0
+    } else if error >= 2f64.sqrt() {
+        2.0
+    } else {
+        1.0
+    };
+    factor * power
+}
+
+/// 📏 Round price levels inside `min..=max`, lowest first
+///
+/// Shared by the horizontal grid and the price axis labels so they line up.
+pub fn nice_price_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let step = nice_price_step(min, max, count);
+    if step == 0.0 {
+        return Vec::new();
+    }
+    let decimals = price_step_decimals(step) as i32;
+    let scale = 10f64.powi(decimals);
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    // Multiplying integers keeps 0.1 + 0.2 style drift out of the labels
+    (first..=last).map(|k| (k as f64 * step * scale).round() / scale).collect()
+}
+
+/// Decimal places needed to tell price levels `step` apart, at least two
+pub fn price_step_decimals(step: f64) -> usize {
+    if !(step.is_finite() && step > 0.0) {
+        return 2;
+    }
+    (-step.log10().floor()).max(2.0) as usize
+}
+
+/// 🏷️ Legend entry: an indicator's latest value and line color
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorValue {
+    /// `toggle_line_visibility` name; `None` for lines without a toggle
+    pub toggle: Option<&'static str>,
+    pub label: String,
+    /// One value per line, e.g. upper, middle and lower band
+    pub values: Vec<f64>,
+    pub color: [f32; 4],
+}
+
+/// 🕒 Time range of the candles currently drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibleSpan {
+    pub first: u64,
+    pub last: u64,
+    pub count: usize,
+}
+
+impl VisibleSpan {
+    /// Timestamp of visible candle `index`, interpolated between the ends
+    pub fn timestamp_at(&self, index: usize) -> u64 {
+        if self.count < 2 {
+            return self.first;
+        }
+        let t = index.min(self.count - 1) as f64 / (self.count - 1) as f64;
+        self.first + ((self.last - self.first) as f64 * t).round() as u64
+    }
+}
+
+/// Share of the fitted price range added above and below
+pub const PRICE_PADDING: f32 = 0.05;
+
+/// Half-height of the range drawn around a flat price, as a share of that price
+pub const FLAT_PRICE_SPAN_RATIO: f32 = 0.01;
+
+/// 📐 Price scale fitted to `prices` with `PRICE_PADDING` on both sides
+///
+/// Flat prices get the synthetic range of `ensure_price_span`.
+pub fn fit_price_range(prices: impl IntoIterator<Item = f32>) -> (f32, f32) {
+    let (min, max) = prices
+        .into_iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| (lo.min(p), hi.max(p)));
+    let range = max - min;
+    ensure_price_span(min - range * PRICE_PADDING, max + range * PRICE_PADDING)
+}
+
+/// 📐 `min..max`, widened around its middle when too narrow to draw
+///
+/// A single trade or an illiquid symbol leaves all prices equal; they get
+/// ±`FLAT_PRICE_SPAN_RATIO` of the price (±1 around zero) so the flat
+/// chart sits centered in the panel.
+pub fn ensure_price_span(min: f32, max: f32) -> (f32, f32) {
+    let mid = min / 2.0 + max / 2.0;
+    // A few dozen f32 steps at this magnitude still map to distinct heights
+    if max - min > mid.abs() * f32::EPSILON * 64.0 {
+        return (min, max);
+    }
+    let half = if mid == 0.0 { 1.0 } else { mid.abs() * FLAT_PRICE_SPAN_RATIO };
+    (mid - half, mid + half)
+}
+
+/// Bollinger `(lower, middle, upper)` bands of the closes
+///
+/// Value `k` belongs to close `k + period - 1`. Flat prices collapse all three
+/// bands onto the SMA.
+pub fn bollinger_bands(closes: &[f64], period: usize, std_dev: f64) -> Vec<(f64, f64, f64)> {
+    if period == 0 || closes.len() < period {
+        return Vec::new();
+    }
+    closes
+        .windows(period)
+        .map(|window| {
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / period as f64;
+            let offset = std_dev * variance.max(0.0).sqrt();
+            (mean - offset, mean, mean + offset)
+        })
+        .collect()
+}
+
+/// ATR smoothing period
+pub const ATR_PERIOD: usize = 14;
+/// Keltner channel EMA period
+pub const KELTNER_PERIOD: usize = 20;
+/// Keltner channel distance from the EMA in ATRs
+pub const KELTNER_MULTIPLIER: f64 = 2.0;
+/// Index of the candle the first Keltner value belongs to
+pub const KELTNER_OFFSET: usize =
+    if KELTNER_PERIOD > ATR_PERIOD { KELTNER_PERIOD } else { ATR_PERIOD } - 1;
+
+/// Lookback of the moving average over the volume bars
+pub const VOLUME_MA_PERIOD: usize = 20;
+
+/// ATR band when no sub-panel is assigned to it
+pub const ATR_PANEL_BAND: PanelBand = PanelBand { bottom: -0.45, top: -0.29 };
+
+/// True range of each candle against the previous close
+///
+/// The first candle has no previous close, so its range is high − low.
+pub fn true_ranges(candles: &[Candle]) -> Vec<f64> {
+    let mut prev_close: Option<f64> = None;
+    candles
+        .iter()
+        .map(|c| {
+            let (high, low) = (c.ohlcv.high.value(), c.ohlcv.low.value());
+            let range = match prev_close {
+                Some(prev) => (high - low).max((high - prev).abs()).max((low - prev).abs()),
+                None => high - low,
+            };
+            prev_close = Some(c.ohlcv.close.value());
+            range
+        })
+        .collect()
+}
+
+/// Average True Range with Wilder's smoothing
+///
+/// Value `k` belongs to candle `k + period - 1`; fewer than `period` candles yield nothing.
+pub fn atr_series(candles: &[Candle], period: usize) -> Vec<f64> {
+    let ranges = true_ranges(candles);
+    if period == 0 || ranges.len() < period {
+        return Vec::new();
+    }
+
+    let mut atr = ranges[..period].iter().sum::<f64>() / period as f64;
+    let mut values = vec![atr];
+    for range in &ranges[period..] {
+        atr = (atr * (period - 1) as f64 + range) / period as f64;
+        values.push(atr);
+    }
+    values
+}
+
+/// Keltner `(lower, middle, upper)` bands: EMA of the closes ± `multiplier` ATRs
+///
+/// Value `k` belongs to candle `k + KELTNER_OFFSET`.
+pub fn keltner_bands(candles: &[Candle], multiplier: f64) -> Vec<(f64, f64, f64)> {
+    let ema = MarketAnalysisService::new().calculate_ema(candles, KELTNER_PERIOD);
+    let atr = atr_series(candles, ATR_PERIOD);
+    ema.iter()
+        .skip(KELTNER_OFFSET + 1 - KELTNER_PERIOD)
+        .zip(atr.iter().skip(KELTNER_OFFSET + 1 - ATR_PERIOD))
+        .map(|(mid, atr)| {
+            let mid = mid.value();
+            (mid - multiplier * atr, mid, mid + multiplier * atr)
+        })
+        .collect()
+}
+
+/// MACD fast EMA period
+pub const MACD_FAST: usize = 12;
+/// MACD slow EMA period
+pub const MACD_SLOW: usize = 26;
+/// MACD signal EMA period
+pub const MACD_SIGNAL: usize = 9;
+
+/// MACD band when no sub-panel is assigned to it
+pub const MACD_PANEL_BAND: PanelBand = PanelBand { bottom: -0.83, top: -0.65 };
+
+/// MACD (fast EMA − slow EMA), its signal EMA and their difference
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MacdSeries {
+    /// Value `k` belongs to candle `k + MACD_SLOW - 1`
+    pub macd: Vec<f64>,
+    /// Value `k` belongs to candle `k + MACD_SLOW + MACD_SIGNAL - 2`
+    pub signal: Vec<f64>,
+    /// MACD minus signal, aligned with `signal`
+    pub histogram: Vec<f64>,
+}
+
+impl MacdSeries {
+    /// Index of the candle the first MACD value belongs to
+    pub const MACD_OFFSET: usize = MACD_SLOW - 1;
+    /// Index of the candle the first signal and histogram values belong to
+    pub const SIGNAL_OFFSET: usize = MACD_SLOW + MACD_SIGNAL - 2;
+}
+
+/// Compute MACD from candle closes; fewer than `MACD_SLOW` candles yield nothing
+pub fn macd_series(candles: &[Candle]) -> MacdSeries {
+    let analysis = MarketAnalysisService::new();
+    let fast = analysis.calculate_ema(candles, MACD_FAST);
+    let slow = analysis.calculate_ema(candles, MACD_SLOW);
+    if slow.is_empty() {
+        return MacdSeries::default();
+    }
+
+    // The fast EMA starts MACD_SLOW - MACD_FAST candles earlier
+    let macd: Vec<f64> = slow
+        .iter()
+        .zip(fast.iter().skip(MACD_SLOW - MACD_FAST))
+        .map(|(s, f)| f.value() - s.value())
+        .collect();
+    let signal = analysis.calculate_ema_values(&macd, MACD_SIGNAL);
+    let histogram = macd.iter().skip(MACD_SIGNAL - 1).zip(&signal).map(|(m, s)| m - s).collect();
+    MacdSeries { macd, signal, histogram }
+}
+
+/// 📊 MACD and signal lines plus a histogram inside `band`
+///
+/// Bars share `x_axis` with the candles and are green while the
+/// histogram rises, red while it falls.
+pub fn create_macd(
+    candles: &[Candle],
+    x_axis: &XAxis,
+    band: PanelBand,
+    line_width: f32,
+    bar_width: f32,
+) -> Vec<CandleVertex> {
+    let series = macd_series(candles);
+    if series.macd.is_empty() {
+        return Vec::new();
+    }
+
+    let max_abs = series
+        .macd
+        .iter()
+        .chain(&series.signal)
+        .chain(&series.histogram)
+        .fold(0.0f64, |acc, v| acc.max(v.abs()))
+        .max(f64::EPSILON) as f32;
+    let y = |v: f64| band.normalize(v as f32, -max_abs, max_abs);
+    let zero = y(0.0);
+
+    let mut vertices = Vec::new();
+    let mut previous = 0.0;
+    for (k, &value) in series.histogram.iter().enumerate() {
+        let x = x_axis.x(k + MacdSeries::SIGNAL_OFFSET);
+        let rising = is_bullish_move(previous, value);
+        previous = value;
+        let (left, right) = (x - bar_width * 0.5, x + bar_width * 0.5);
+        let (top, bottom) = (zero.max(y(value)), zero.min(y(value)));
+        vertices.extend_from_slice(&[
+            CandleVertex::body_vertex(left, bottom, rising),
+            CandleVertex::body_vertex(right, bottom, rising),
+            CandleVertex::body_vertex(left, top, rising),
+            CandleVertex::body_vertex(right, bottom, rising),
+            CandleVertex::body_vertex(right, top, rising),
+            CandleVertex::body_vertex(left, top, rising),
+        ]);
+    }
+
+    let points = |values: &[f64], offset: usize| -> Vec<(f32, f32)> {
+        values.iter().enumerate().map(|(k, &v)| (x_axis.x(k + offset), y(v))).collect()
+    };
+    vertices.extend(CandleGeometry::create_indicator_line_vertices(
+        &points(&series.macd, MacdSeries::MACD_OFFSET),
+        IndicatorType::MACD,
+        line_width,
+    ));
+    vertices.extend(CandleGeometry::create_indicator_line_vertices(
+        &points(&series.signal, MacdSeries::SIGNAL_OFFSET),
+        IndicatorType::MacdSignal,
+        line_width,
+    ));
+    vertices
+}
+
+/// Default stochastic %K lookback period
+pub const DEFAULT_STOCHASTIC_PERIOD: usize = 14;
+/// Stochastic %D period (SMA of %K)
+pub const STOCHASTIC_SMOOTHING: usize = 3;
+/// Oversold and overbought stochastic reference levels
+pub const STOCHASTIC_LEVELS: [f32; 2] = [20.0, 80.0];
+
+/// Stochastic band when no sub-panel is assigned to it
+pub const STOCHASTIC_PANEL_BAND: PanelBand = PanelBand { bottom: -0.63, top: -0.47 };
+
+/// Stochastic %K and its %D average, both in `0..=100`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StochasticSeries {
+    /// Value `k` belongs to candle `k + period - 1`
+    pub k: Vec<f64>,
+    /// Value `k` belongs to candle `k + period + STOCHASTIC_SMOOTHING - 2`
+    pub d: Vec<f64>,
+}
+
+/// Stochastic oscillator of the closes against the `period` high/low range
+///
+/// A window whose highest high equals its lowest low has no range, so it
+/// holds the previous %K (50 if it is the first). Fewer than `period`
+/// candles yield nothing.
+pub fn stochastic_series(candles: &[Candle], period: usize) -> StochasticSeries {
+    if period == 0 || candles.len() < period {
+        return StochasticSeries::default();
+    }
+
+    let mut previous = 50.0;
+    let k: Vec<f64> = candles
+        .windows(period)
+        .map(|window| {
+            let (low, high) =
+                window.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), c| {
+                    (lo.min(c.ohlcv.low.value()), hi.max(c.ohlcv.high.value()))
+                });
+            let close = window[period - 1].ohlcv.close.value();
+            if high - low > f64::EPSILON {
+                previous = 100.0 * (close - low) / (high - low);
+            }
+            previous
+        })
+        .collect();
+    let d = k
+        .windows(STOCHASTIC_SMOOTHING)
+        .map(|w| w.iter().sum::<f64>() / STOCHASTIC_SMOOTHING as f64)
+        .collect();
+    StochasticSeries { k, d }
+}
+
+/// 🕳️ Why a chart without candles is empty, choosing its placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyChartState {
+    /// History or the first live candle is still on its way
+    #[default]
+    Loading,
+    /// Loading finished or failed without any candles
+    NoData,
+}
+
+/// Dash pattern of the grid drawn while candles load
+pub const LOADING_GRID_DASH: DashPattern = DashPattern { on: 0.02, off: 0.02 };
+
+/// Default floor on the drawn body height, in NDC units
+pub const DEFAULT_MIN_BODY_HEIGHT: f32 = 0.005;
+
+/// ⬛ Minimum drawn height of candle bodies; wicks are never stretched
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MinBodyHeight {
+    /// True body heights; anything thinner than a pixel, like a doji, is a
+    /// one-pixel sliver centered on its price
+    Off,
+    /// Short bodies grow upward from their bottom to this many NDC units
+    Ndc(f32),
+}
+
+impl Default for MinBodyHeight {
+    fn default() -> Self {
+        Self::Ndc(DEFAULT_MIN_BODY_HEIGHT)
+    }
+}
+
+/// Largest accepted [`MinBodyHeight::Ndc`], a twentieth of the chart height
+pub const MAX_MIN_BODY_HEIGHT: f32 = 0.1;
+
+impl MinBodyHeight {
+    /// Whether an `Ndc` height lies within `0..=MAX_MIN_BODY_HEIGHT`; NaN never does
+    pub fn is_valid(self) -> bool {
+        match self {
+            Self::Off => true,
+            Self::Ndc(min) => (0.0..=MAX_MIN_BODY_HEIGHT).contains(&min),
+        }
+    }
+}
+
+/// ⬛ Drawn `(top, bottom)` of a body between `open_y` and `close_y`
+///
+/// `pixel` is the NDC height of one device pixel.
+pub fn candle_body(open_y: f32, close_y: f32, min_height: MinBodyHeight, pixel: f32) -> (f32, f32) {
+    let (top, bottom) = (open_y.max(close_y), open_y.min(close_y));
+    match min_height {
+        MinBodyHeight::Ndc(min) if top - bottom < min => (bottom + min, bottom),
+        MinBodyHeight::Off if top - bottom < pixel => {
+            let mid = (top + bottom) / 2.0;
+            (mid + pixel / 2.0, mid - pixel / 2.0)
+        }
+        _ => (top, bottom),
+    }
+}
+
+/// Rule used to classify candles as bullish or bearish
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CandleColoring {
+    /// Close against the candle's own open; a doji counts as bullish
+    #[default]
+    OpenClose,
+    /// Close against the previous close, so gaps color by direction
+    PreviousClose,
+}
+
+/// Single bullish/bearish decision on raw prices shared by bodies and volume bars
+pub fn is_bullish_candle(candle: &Candle, prev: Option<&Candle>, coloring: CandleColoring) -> bool {
+    let close = candle.ohlcv.close.value();
+    let reference = match (coloring, prev) {
+        (CandleColoring::PreviousClose, Some(prev)) => prev.ohlcv.close.value(),
+        _ => candle.ohlcv.open.value(),
+    };
+    is_bullish_move(reference, close)
+}
+
+/// Fill color for a candle body or its volume bar
+pub fn candle_color(
+    candle: &Candle,
+    prev: Option<&Candle>,
+    coloring: CandleColoring,
+    theme: &ChartUniforms,
+) -> [f32; 4] {
+    if is_bullish_candle(candle, prev, coloring) {
+        theme.bullish_color
+    } else {
+        theme.bearish_color
+    }
+}
+
+/// Round `price` to the nearest multiple of `tick`; non-positive ticks keep it as is
+pub fn round_to_tick(price: f64, tick: f64) -> f64 {
+    if tick > 0.0 { (price / tick).round() * tick } else { price }
+}
+
+/// Round all prices of a candle to the tick size
+pub fn round_ohlc_to_tick(ohlcv: &mut OHLCV, tick: f64) {
+    for price in [&mut ohlcv.open, &mut ohlcv.high, &mut ohlcv.low, &mut ohlcv.close] {
+        *price = Price::new(round_to_tick(price.value(), tick));
+    }
+}
+
+/// Close-price polyline spanning the full NDC area, colored by overall trend
+pub fn sparkline_vertices(closes: &[f64], line_width: f32) -> Vec<CandleVertex> {
+    if closes.len() < 2 {
+        return Vec::new();
+    }
+
+    let (min, max) = closes
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &c| (lo.min(c), hi.max(c)));
+    let range = max - min;
+    // Keep the stroke inside the canvas at the extremes
+    let height = 1.0 - line_width;
+    let last = (closes.len() - 1) as f32;
+    let points: Vec<(f32, f32)> = closes
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let y = if range > 0.0 { ((c - min) / range) as f32 * 2.0 - 1.0 } else { 0.0 };
+            (i as f32 / last * 2.0 - 1.0, y * height)
+        })
+        .collect();
+
+    let bullish = is_bullish_move(closes[0], closes[closes.len() - 1]);
+    CandleGeometry::create_line_vertices(
+        &points,
+        line_width * 0.5,
+        DashPattern::SOLID,
+        0.0,
+        |x, y| CandleVertex::body_vertex(x, y, bullish),
+    )
+}
+
+/// Bytes uploaded for `instances` as `(per-candle vertices, instances)`
+///
+/// The first value rebuilds the body and wick triangles candles used before
+/// instancing; the second is the instance records alone, since the unit quad
+/// template is uploaded once at startup.
+pub fn candle_upload_sizes(instances: &[CandleInstance]) -> (usize, usize) {
+    let legacy_vertices: usize = instances
+        .iter()
+        .map(|c| {
+            let (open, close) = if c.bullish > 0.5 {
+                (c.body_bottom, c.body_top)
+            } else {
+                (c.body_top, c.body_bottom)
+            };
+            CandleGeometry::create_colored_candle_vertices(
+                c.x,
+                open,
+                c.high,
+                c.low,
+                close,
+                c.width,
+                c.bullish > 0.5,
+            )
+            .len()
+        })
+        .sum();
+    (legacy_vertices * std::mem::size_of::<CandleVertex>(), std::mem::size_of_val(instances))
+}
+
+impl ChartGeometryBuilder {
+    /// Convert a vertical CSS pixel size to normalized device coordinates
     fn px_to_ndc(&self, px: f32) -> f32 {
-        (px / self.height as f32) * 2.0
+        (px * self.pixel_ratio / self.height as f32) * 2.0
+    }
+
+    /// 🕳️ Placeholder drawn instead of candles while the chart has none
+    ///
+    /// A dashed grid over the main panel while loading, a single flat line
+    /// across its middle once the chart is known to be empty. The uniforms
+    /// carry no price range, so pointer lookups find nothing.
+    pub fn placeholder_geometry(
+        &self,
+        state: EmptyChartState,
+    ) -> (Vec<CandleVertex>, ChartUniforms) {
+        let band = crate::app::panel_layout().get_untracked().main_band();
+        let mut vertices = Vec::new();
+        match state {
+            EmptyChartState::Loading => {
+                let half_width = 0.5 * self.pixel_ratio / self.width as f32;
+                for slot in 0..TIME_GRID_LINES {
+                    let x = candle_x_position(slot, TIME_GRID_LINES);
+                    vertices.extend(CandleGeometry::create_line_vertices(
+                        &[(x, band.bottom), (x, band.top)],
+                        half_width,
+                        LOADING_GRID_DASH,
+                        0.0,
+                        CandleVertex::grid_vertex,
+                    ));
+                }
+                let level_width = self.px_to_ndc(0.5);
+                for level in 0..=PRICE_GRID_LINES {
+                    let y = band.bottom + band.height() * level as f32 / PRICE_GRID_LINES as f32;
+                    vertices.extend(CandleGeometry::create_horizontal_line(
+                        y,
+                        level_width,
+                        LOADING_GRID_DASH,
+                        CandleVertex::grid_vertex,
+                    ));
+                }
+            }
+            EmptyChartState::NoData => {
+                vertices.extend(CandleGeometry::create_horizontal_line(
+                    (band.top + band.bottom) / 2.0,
+                    self.px_to_ndc(1.0),
+                    DashPattern::SOLID,
+                    CandleVertex::grid_vertex,
+                ));
+            }
+        }
+        let uniforms = ChartUniforms {
+            viewport: [self.width as f32, self.height as f32, 0.0, 0.0],
+            ..ChartUniforms::from_theme(&self.theme)
+        };
+        (vertices, uniforms)
+    }
+
+    /// 💰 NDC height of the live price, rounded to the tick, inside `band` showing `min..max`
+    pub(super) fn current_price_ndc(&self, band: PanelBand, min: f32, max: f32) -> f32 {
+        let price = crate::app::global_current_price().get_untracked();
+        let price = self.price_tick.map_or(price, |tick| round_to_tick(price, tick)) as f32;
+        band.normalize(price, min, max)
+    }
+
+    /// 📈 Bollinger bands of the visible closes mapped with the candles' `price_to_ndc`
+    pub fn create_bollinger_bands(
+        &self,
+        visible_candles: &[Candle],
+        price_to_ndc: impl Fn(f64) -> f32,
+    ) -> Vec<CandleVertex> {
+        let closes: Vec<f64> = visible_candles.iter().map(|c| c.ohlcv.close.value()).collect();
+        let bands = bollinger_bands(&closes, BOLLINGER_PERIOD, BOLLINGER_STD_DEV);
+        if bands.is_empty() {
+            return Vec::new();
+        }
+        let x_axis = self.x_axis_for(visible_candles);
+
+        let line = |pick: fn(&(f64, f64, f64)) -> f64| -> Vec<(f32, f32)> {
+            bands
+                .iter()
+                .enumerate()
+                .map(|(k, band)| {
+                    let x = x_axis.x(k + BOLLINGER_PERIOD - 1);
+                    (x, price_to_ndc(pick(band)))
+                })
+                .collect()
+        };
+        let lower = line(|b| b.0);
+        let middle = line(|b| b.1);
+        let upper = line(|b| b.2);
+
+        let mut vertices = Vec::new();
+        if self.bollinger_fill {
+            vertices.extend(CandleGeometry::create_band_fill(&upper, &lower));
+        }
+        let line_width = self.px_to_ndc(1.5);
+        for points in [&upper, &middle, &lower] {
+            vertices.extend(CandleGeometry::create_indicator_line_vertices(
+                points,
+                IndicatorType::Bollinger,
+                line_width,
+            ));
+        }
+        vertices
+    }
+
+    /// 📊 Volume SMA over the volume bars, scaled by the tallest visible bar
+    ///
+    /// Averages use the candles before the window, so only the first
+    /// `VOLUME_MA_PERIOD - 1` candles of the history have no point.
+    pub fn create_volume_ma(
+        &self,
+        candles: &[Candle],
+        start_index: usize,
+        visible_len: usize,
+        max_volume: f32,
+        band: PanelBand,
+    ) -> Vec<CandleVertex> {
+        let volumes: Vec<f64> = candles.iter().map(|c| c.ohlcv.volume.value()).collect();
+        let end = (start_index + visible_len).min(candles.len());
+        let x_axis = self.x_axis_for(candles.get(start_index..end).unwrap_or_default());
+        let points: Vec<(f32, f32)> = volumes
+            .windows(VOLUME_MA_PERIOD)
+            .enumerate()
+            .filter_map(|(i, window)| {
+                let visible = (i + VOLUME_MA_PERIOD - 1).checked_sub(start_index)?;
+                if visible >= x_axis.len() {
+                    return None;
+                }
+                let average = window.iter().sum::<f64>() / VOLUME_MA_PERIOD as f64;
+                let ratio = (average as f32 / max_volume).clamp(0.0, 1.0);
+                let y = band.map_ndc(-1.0 + ratio * CandleGeometry::VOLUME_HEIGHT);
+                Some((x_axis.x(visible), y))
+            })
+            .collect();
+        CandleGeometry::create_colored_line_vertices(
+            &points,
+            self.theme.volume_ma,
+            self.px_to_ndc(1.5),
+            DashPattern::SOLID,
+            0.0,
+        )
+    }
+
+    /// 📈 Keltner channel of the visible candles, aligned with the candle x positions
+    pub fn create_keltner_channel(
+        &self,
+        visible_candles: &[Candle],
+        price_to_ndc: impl Fn(f64) -> f32,
+    ) -> Vec<CandleVertex> {
+        let bands = keltner_bands(visible_candles, KELTNER_MULTIPLIER);
+        let x_axis = self.x_axis_for(visible_candles);
+        let line_width = self.px_to_ndc(1.5);
+        let mut vertices = Vec::new();
+        let upper: Vec<f64> = bands.iter().map(|b| b.0).collect();
+        let middle: Vec<f64> = bands.iter().map(|b| b.1).collect();
+        let lower: Vec<f64> = bands.iter().map(|b| b.2).collect();
+        for series in [upper, middle, lower] {
+            let points: Vec<(f32, f32)> = series
+                .iter()
+                .enumerate()
+                .map(|(k, price)| (x_axis.x(k + KELTNER_OFFSET), price_to_ndc(*price)))
+                .collect();
+            vertices.extend(CandleGeometry::create_indicator_line_vertices(
+                &points,
+                IndicatorType::Keltner,
+                line_width,
+            ));
+        }
+        vertices
+    }
+
+    /// 📏 ATR line of the visible candles inside `band`, scaled from zero to its peak
+    pub fn create_atr(&self, visible_candles: &[Candle], band: PanelBand) -> Vec<CandleVertex> {
+        let values = atr_series(visible_candles, ATR_PERIOD);
+        let peak = values.iter().fold(0.0f64, |acc, v| acc.max(*v)).max(f64::EPSILON) as f32;
+        let x_axis = self.x_axis_for(visible_candles);
+        let points: Vec<(f32, f32)> = values
+            .iter()
+            .enumerate()
+            .map(|(k, &v)| {
+                let x = x_axis.x(k + ATR_PERIOD - 1);
+                (x, band.normalize(v as f32, 0.0, peak))
+            })
+            .collect();
+        CandleGeometry::create_indicator_line_vertices(
+            &points,
+            IndicatorType::Atr,
+            self.px_to_ndc(1.5),
+        )
     }
-    pub(super) fn create_geometry(
+
+    /// 📊 VWAP line of the visible candles, broken at session starts and volume-less points
+    pub fn create_vwap(
+        &self,
+        visible_candles: &[Candle],
+        price_to_ndc: impl Fn(f64) -> f32,
+    ) -> Vec<CandleVertex> {
+        let values = vwap_series(visible_candles, self.vwap_anchor);
+        let line_width = self.px_to_ndc(1.5);
+        let x_axis = self.x_axis_for(visible_candles);
+
+        let mut vertices = Vec::new();
+        let mut segment: Vec<(f32, f32)> = Vec::new();
+        let mut session = None;
+        for (i, (candle, value)) in visible_candles.iter().zip(&values).enumerate() {
+            let current = vwap_session(candle.timestamp.value(), self.vwap_anchor);
+            if session != Some(current) || value.is_none() {
+                vertices.extend(CandleGeometry::create_indicator_line_vertices(
+                    &segment,
+                    IndicatorType::Vwap,
+                    line_width,
+                ));
+                segment.clear();
+                session = Some(current);
+            }
+            if let Some(price) = value {
+                segment.push((x_axis.x(i), price_to_ndc(*price)));
+            }
+        }
+        vertices.extend(CandleGeometry::create_indicator_line_vertices(
+            &segment,
+            IndicatorType::Vwap,
+            line_width,
+        ));
+        vertices
+    }
+
+    /// 📉 RSI line of the visible closes with 30/70 reference levels inside `band`
+    pub fn create_rsi(&self, visible_candles: &[Candle], band: PanelBand) -> Vec<CandleVertex> {
+        let closes: Vec<f64> = visible_candles.iter().map(|c| c.ohlcv.close.value()).collect();
+        let values = wilder_rsi(&closes, self.rsi_period);
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let x_axis = self.x_axis_for(visible_candles);
+
+        let points: Vec<(f32, f32)> = values
+            .iter()
+            .enumerate()
+            .map(|(k, &v)| {
+                let x = x_axis.x(k + self.rsi_period);
+                (x, band.normalize(v as f32, 0.0, 100.0))
+            })
+            .collect();
+
+        let level_width = self.px_to_ndc(0.5);
+        let mut vertices = Vec::new();
+        for level in [30.0, 70.0] {
+            vertices.extend(CandleGeometry::create_horizontal_line(
+                band.normalize(level, 0.0, 100.0),
+                level_width,
+                self.line_styles.grid,
+                CandleVertex::grid_vertex,
+            ));
+        }
+        vertices.extend(CandleGeometry::create_indicator_line_vertices(
+            &points,
+            IndicatorType::RSI,
+            self.px_to_ndc(1.5),
+        ));
+        vertices
+    }
+    /// 📉 Stochastic %K and %D of the visible candles with 20/80 reference levels inside `band`
+    pub fn create_stochastic(
+        &self,
+        visible_candles: &[Candle],
+        band: PanelBand,
+    ) -> Vec<CandleVertex> {
+        let period = self.stochastic_period;
+        let series = stochastic_series(visible_candles, period);
+        if series.k.is_empty() {
+            return Vec::new();
+        }
+
+        let x_axis = self.x_axis_for(visible_candles);
+        let points = |values: &[f64], offset: usize| -> Vec<(f32, f32)> {
+            values
+                .iter()
+                .enumerate()
+                .map(|(k, &v)| (x_axis.x(k + offset), band.normalize(v as f32, 0.0, 100.0)))
+                .collect()
+        };
+
+        let level_width = self.px_to_ndc(0.5);
+        let mut vertices = Vec::new();
+        for level in STOCHASTIC_LEVELS {
+            vertices.extend(CandleGeometry::create_horizontal_line(
+                band.normalize(level, 0.0, 100.0),
+                level_width,
+                self.line_styles.grid,
+                CandleVertex::grid_vertex,
+            ));
+        }
+        vertices.extend(CandleGeometry::create_indicator_line_vertices(
+            &points(&series.k, period - 1),
+            IndicatorType::StochasticK,
+            self.px_to_ndc(1.5),
+        ));
+        vertices.extend(CandleGeometry::create_indicator_line_vertices(
+            &points(&series.d, period + STOCHASTIC_SMOOTHING - 2),
+            IndicatorType::StochasticD,
+            self.px_to_ndc(1.5),
+        ));
+        vertices
+    }
+
+    /// 🕒 First/last timestamps and x slots of the candles drawn for `chart`
+    ///
+    /// `count` is one slot per candle, or one per interval in time mode.
+    pub fn visible_time_span(&self, chart: &Chart) -> Option<VisibleSpan> {
+        let interval = crate::app::current_interval().get_untracked();
+        let candles: Vec<Candle> =
+            chart.get_series(interval)?.get_candles().iter().cloned().collect();
+        let (start, count) =
+            crate::app::visible_range_by_time(&candles, &chart.viewport, self.zoom_level);
+        let visible = candles.get(start..start + count)?;
+        Some(VisibleSpan {
+            first: visible.first()?.timestamp.value(),
+            last: visible.last()?.timestamp.value(),
+            count: self.x_axis_for(visible).slot_count(),
+        })
+    }
+
+    /// 🏷️ Values of the visible indicators at the newest candle of the current interval
+    ///
+    /// Computed over the whole loaded series; indicators that need more
+    /// history than is loaded are left out.
+    pub fn compute_indicator_values(&self, chart: &Chart) -> Vec<IndicatorValue> {
+        let interval = crate::app::current_interval().get_untracked();
+        let Some(series) = chart.get_series(interval) else {
+            return Vec::new();
+        };
+        let candles: Vec<Candle> = series.get_candles().iter().cloned().collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.ohlcv.close.value()).collect();
+        let visibility = &self.line_visibility;
+        let mut values = Vec::new();
+        let mut push = |toggle, label: String, lines: Vec<f64>, color| {
+            if !lines.is_empty() {
+                values.push(IndicatorValue { toggle, label, values: lines, color });
+            }
+        };
+
+        let source = IndicatorSource::of(&candles);
+        let cached = self.moving_averages.as_ref().filter(|(s, _)| *s == source).map(|(_, m)| m);
+        let analysis = MarketAnalysisService::new();
+        for (i, config) in self.moving_average_configs.iter().enumerate() {
+            if !visibility.shows_moving_average(i) {
+                continue;
+            }
+            let last = match cached.and_then(|m| m.get(config.kind, config.period)) {
+                Some(values) => values.last().map(|p| p.value()),
+                None => analysis
+                    .calculate_ma(&candles, config.kind, config.period)
+                    .last()
+                    .map(|p| p.value()),
+            };
+            let toggle = LineVisibility::MOVING_AVERAGE_TOGGLES.get(i).copied();
+            push(toggle, config.label(), last.into_iter().collect(), config.color);
+        }
+
+        if visibility.bollinger {
+            let last = bollinger_bands(&closes, BOLLINGER_PERIOD, BOLLINGER_STD_DEV).pop();
+            let lines = last.map(|(lower, middle, upper)| vec![upper, middle, lower]);
+            let color = IndicatorType::Bollinger.color(&self.theme);
+            push(
+                Some("bollinger"),
+                format!("BB {BOLLINGER_PERIOD}"),
+                lines.unwrap_or_default(),
+                color,
+            );
+        }
+        if visibility.keltner {
+            let last = keltner_bands(&candles, KELTNER_MULTIPLIER).pop();
+            let lines = last.map(|(lower, middle, upper)| vec![upper, middle, lower]);
+            let color = IndicatorType::Keltner.color(&self.theme);
+            push(Some("keltner"), format!("KC {KELTNER_PERIOD}"), lines.unwrap_or_default(), color);
+        }
+        if visibility.vwap {
+            let last = vwap_series(&candles, self.vwap_anchor).last().copied().flatten();
+            let color = IndicatorType::Vwap.color(&self.theme);
+            push(Some("vwap"), "VWAP".to_string(), last.into_iter().collect(), color);
+        }
+        if visibility.rsi_14 {
+            let last = wilder_rsi(&closes, self.rsi_period).last().copied();
+            let color = IndicatorType::RSI.color(&self.theme);
+            push(
+                Some("rsi"),
+                format!("RSI {}", self.rsi_period),
+                last.into_iter().collect(),
+                color,
+            );
+        }
+        if visibility.macd {
+            let series = macd_series(&candles);
+            let lines = match (series.macd.last(), series.signal.last()) {
+                (Some(&macd), Some(&signal)) => vec![macd, signal],
+                _ => Vec::new(),
+            };
+            push(Some("macd"), "MACD".to_string(), lines, IndicatorType::MACD.color(&self.theme));
+        }
+        if visibility.stochastic {
+            let series = stochastic_series(&candles, self.stochastic_period);
+            let lines = match (series.k.last(), series.d.last()) {
+                (Some(&k), Some(&d)) => vec![k, d],
+                _ => Vec::new(),
+            };
+            let color = IndicatorType::StochasticK.color(&self.theme);
+            push(Some("stochastic"), format!("Stoch {}", self.stochastic_period), lines, color);
+        }
+        if visibility.atr {
+            let last = atr_series(&candles, ATR_PERIOD).last().copied();
+            let color = IndicatorType::Atr.color(&self.theme);
+            push(Some("atr"), format!("ATR {ATR_PERIOD}"), last.into_iter().collect(), color);
+        }
+        if visibility.volume_ma && candles.len() >= VOLUME_MA_PERIOD {
+            let recent = &candles[candles.len() - VOLUME_MA_PERIOD..];
+            let average = recent.iter().map(|c| c.ohlcv.volume.value()).sum::<f64>()
+                / VOLUME_MA_PERIOD as f64;
+            let label = format!("Vol MA {VOLUME_MA_PERIOD}");
+            push(Some("volume_ma"), label, vec![average], self.theme.volume_ma);
+        }
+        values
+    }
+
+    /// Instances, vertices and uniforms drawing `chart` with the current settings
+    pub fn create_geometry(
         &self,
         chart: &Chart,
     ) -> (Vec<CandleInstance>, Vec<CandleVertex>, ChartUniforms) {
@@ -54,7 +1322,14 @@ impl WebGpuRenderer {
             get_logger()
                 .error(LogComponent::Infrastructure("WebGpuRenderer"), "⚠️ No candles to render");
 
-            return (Vec::new(), Vec::new(), ChartUniforms::new());
+            return (Vec::new(), Vec::new(), ChartUniforms::from_theme(&self.theme));
+        }
+
+        // ✨ Sparkline: only the close line, no grid, volume or indicators
+        if chart.chart_type == ChartType::Sparkline {
+            let closes: Vec<f64> = candles.iter().map(|c| c.ohlcv.close.value()).collect();
+            let vertices = sparkline_vertices(&closes, self.px_to_ndc(1.5));
+            return (Vec::new(), vertices, ChartUniforms::from_theme(&self.theme));
         }
 
         // ⚡ Performance: log less frequently
@@ -65,27 +1340,76 @@ impl WebGpuRenderer {
             );
         }
 
-        let chart_width = 2.0; // NDC width (-1 to 1)
-
         // 🔍 Apply zoom - show fewer candles when zooming in
         let candle_vec: Vec<Candle> = candles.iter().cloned().collect();
         let (start_index, visible_count) =
             crate::app::visible_range_by_time(&candle_vec, &chart.viewport, self.zoom_level);
-        let visible_candles: Vec<Candle> =
+        let mut visible_candles: Vec<Candle> =
             candle_vec.iter().skip(start_index).take(visible_count).cloned().collect();
 
+        // 🎞️ Mid-transition the newest candle is drawn with the tweened prices
+        if let Some(frame) = self.frame_override {
+            let newest =
+                visible_candles.last_mut().filter(|c| c.timestamp.value() == frame.timestamp);
+            if let Some(candle) = newest {
+                let [open, high, low, close] = frame.last.map(Price::from);
+                candle.ohlcv = OHLCV::new(open, high, low, close, candle.ohlcv.volume);
+            }
+        }
+
+        let heikin_ashi_mode = chart.chart_type == ChartType::HeikinAshi;
+        if heikin_ashi_mode {
+            visible_candles = heikin_ashi(&visible_candles);
+        }
+
+        // 📏 Snap rendered prices to the tick size; raw data stays intact for tooltips
+        if let Some(tick) = self.price_tick {
+            for candle in &mut visible_candles {
+                round_ohlc_to_tick(&mut candle.ohlcv, tick);
+            }
+        }
+
         let mut vertices = Vec::with_capacity(visible_candles.len() * 24);
 
-        // Calculate moving averages for indicator lines using the full data set
-        let analysis = MarketAnalysisService::new();
-        let mas = analysis.calculate_multiple_mas(&candle_vec);
+        // Calculate moving averages for indicator lines using the full data set,
+        // reusing the ECS indicator system's result while it matches these candles
+        let source = IndicatorSource::of(&candle_vec);
+        let mas = match &self.moving_averages {
+            Some((cached_source, cached)) if *cached_source == source => Some(cached),
+            _ => None,
+        };
+        // Averages longer than the loaded history have no values and are skipped
+        let shown: Vec<MaConfig> = self
+            .moving_average_configs
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                self.line_visibility.shows_moving_average(*i) && c.period <= candle_vec.len()
+            })
+            .map(|(_, c)| *c)
+            .collect();
+        // 🧮 The cache keeps every shown line warm, so a frame the ECS result
+        // doesn't match only costs an incremental update
+        self.moving_average_cache
+            .borrow_mut()
+            .update(&candle_vec, shown.iter().map(|c| (c.kind, c.period)));
+        let cache = self.moving_average_cache.borrow();
+        let averages: Vec<(MaConfig, &[Price])> = shown
+            .into_iter()
+            .map(|c| {
+                let values = mas
+                    .and_then(|m| m.get(c.kind, c.period))
+                    .or_else(|| cache.get(c.kind, c.period))
+                    .unwrap_or_default();
+                (c, values)
+            })
+            .collect();
 
         // Scale candles based on currently visible data and indicator values
-        let mut min_price = f32::INFINITY;
-        let mut max_price = f32::NEG_INFINITY;
+        let mut fitted: Vec<f32> = Vec::with_capacity(visible_candles.len() * 2);
         for candle in &visible_candles {
-            min_price = min_price.min(candle.ohlcv.low.value() as f32);
-            max_price = max_price.max(candle.ohlcv.high.value() as f32);
+            fitted.push(candle.ohlcv.low.value() as f32);
+            fitted.push(candle.ohlcv.high.value() as f32);
         }
 
         let mut consider_ma = |values: &[Price], period: usize| {
@@ -94,49 +1418,60 @@ impl WebGpuRenderer {
                 if candle_idx < start_index || candle_idx >= start_index + visible_candles.len() {
                     continue;
                 }
-                min_price = min_price.min(val.value() as f32);
-                max_price = max_price.max(val.value() as f32);
+                fitted.push(val.value() as f32);
             }
         };
 
-        if self.line_visibility.sma_20 {
-            consider_ma(&mas.sma_20, 20);
-        }
-        if self.line_visibility.sma_50 {
-            consider_ma(&mas.sma_50, 50);
+        for (config, values) in &averages {
+            consider_ma(values, config.period);
         }
-        if self.line_visibility.sma_200 {
-            consider_ma(&mas.sma_200, 200);
-        }
-        if self.line_visibility.ema_12 {
-            consider_ma(&mas.ema_12, 12);
+
+        // Keep the Bollinger bands inside the price scale
+        if self.line_visibility.bollinger {
+            let closes: Vec<f64> = visible_candles.iter().map(|c| c.ohlcv.close.value()).collect();
+            for (lower, _, upper) in bollinger_bands(&closes, BOLLINGER_PERIOD, BOLLINGER_STD_DEV) {
+                fitted.extend([lower as f32, upper as f32]);
+            }
         }
-        if self.line_visibility.ema_26 {
-            consider_ma(&mas.ema_26, 26);
+
+        // Same for the Keltner channel
+        if self.line_visibility.keltner {
+            for (lower, _, upper) in keltner_bands(&visible_candles, KELTNER_MULTIPLIER) {
+                fitted.extend([lower as f32, upper as f32]);
+            }
         }
 
-        let price_range = (max_price - min_price).abs().max(1e-6);
-        min_price -= price_range * 0.05;
-        max_price += price_range * 0.05;
+        // 📐 Manual vertical zoom/pan lives in the viewport when auto-fit is off;
+        // a viewport never fitted to data falls back to the visible candles
+        let (min_price, max_price) = match self.frame_override {
+            _ if !self.auto_fit_y && !chart.viewport.is_empty() => {
+                ensure_price_span(chart.viewport.min_price, chart.viewport.max_price)
+            }
+            Some(frame) => (frame.min_price, frame.max_price),
+            None => fit_price_range(fitted),
+        };
 
-        // Log estimated candle width using the number of visible candles
-        let step_size = chart_width / visible_candles.len() as f64;
-        let candle_width_estimate =
-            step_size * (1.0 - spacing_ratio_for(visible_candles.len()) as f64);
+        // 📐 One slot per visible candle (per interval in time mode) shared by
+        // bodies, wicks and volume bars
+        let x_axis = self.x_axis_for(&visible_candles);
+        let step_size = candle_step(x_axis.slot_count());
+        let spacing = spacing_ratio_for(x_axis.slot_count());
+        let candle_width =
+            candle_width_for(x_axis.slot_count(), self.interval_aware_width.then_some(interval));
 
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
             &format!(
                 "📏 Price range: {:.2} - {:.2}, Candle width: {:.4}, step:{:.4}",
-                min_price, max_price, candle_width_estimate, step_size
+                min_price, max_price, candle_width, step_size
             ),
         );
 
-        // Ensure we have a valid price range
-        if (max_price - min_price).abs() < 0.01 {
+        // Flat ranges are widened above, only corrupt prices are left
+        if !(min_price.is_finite() && max_price.is_finite()) {
             get_logger()
                 .error(LogComponent::Infrastructure("WebGpuRenderer"), "❌ Invalid price range!");
-            return (Vec::new(), Vec::new(), ChartUniforms::new());
+            return (Vec::new(), Vec::new(), ChartUniforms::from_theme(&self.theme));
         }
 
         // Log less often for performance
@@ -154,17 +1489,14 @@ impl WebGpuRenderer {
         }
 
         // Create instance data for each visible candle
-        let step_size = 2.0 / visible_candles.len() as f32;
-        let spacing = spacing_ratio_for(visible_candles.len());
-        let candle_width =
-            (step_size * (1.0 - spacing)).clamp(MIN_ELEMENT_WIDTH, MAX_ELEMENT_WIDTH);
         let mut instances = Vec::with_capacity(visible_candles.len());
 
-        let price_range = max_price - min_price;
-        let price_norm = |price: f64| -> f32 {
-            let normalized = (price as f32 - min_price) / price_range;
-            normalized * 2.0 - 1.0
-        };
+        // 🧱 Candles, volume and price overlays share the main panel's band
+        let layout = crate::app::panel_layout().get_untracked();
+        let main_band = layout.main_band();
+
+        let price_norm =
+            |price: f64| -> f32 { main_band.normalize(price as f32, min_price, max_price) };
 
         let mut max_volume = 0.0f32;
         for c in &visible_candles {
@@ -174,8 +1506,23 @@ impl WebGpuRenderer {
             max_volume = 1.0;
         }
 
-        for (i, candle) in visible_candles.iter().enumerate() {
-            let x = candle_x_position(i, visible_candles.len());
+        // 📈 Line and area modes replace candle bodies with a close-price line
+        let draws_candles =
+            !matches!(chart.chart_type, ChartType::Line | ChartType::Area | ChartType::OhlcBar);
+
+        // 🗜️ More candles than pixels: one composite candle per bucket, centered
+        // on and as wide as the candles it replaces
+        let bucket = lod_bucket_size(visible_candles.len(), self.width);
+        let drawn: Cow<'_, [Candle]> = match bucket {
+            1 => Cow::Borrowed(&visible_candles),
+            _ => Cow::Owned(downsample_candles(&visible_candles, bucket)),
+        };
+
+        for (i, candle) in drawn.iter().enumerate() {
+            let first = i * bucket;
+            let last = (first + bucket).min(visible_candles.len()) - 1;
+            let x = (x_axis.x(first) + x_axis.x(last)) / 2.0;
+            let width = candle_width + x_axis.x(last) - x_axis.x(first);
 
             let open_y = price_norm(candle.ohlcv.open.value());
             let high_y = price_norm(candle.ohlcv.high.value());
@@ -183,199 +1530,206 @@ impl WebGpuRenderer {
             let close_y = price_norm(candle.ohlcv.close.value());
 
             // Log only the first 3 and last 3 candles
-            if i < 3 || i >= visible_candles.len() - 3 {
+            if i < 3 || i >= drawn.len() - 3 {
                 get_logger().info(
                     LogComponent::Infrastructure("WebGpuRenderer"),
                     &format!(
                         "🕯️ Candle {}: x={:.3}, Y=({:.3},{:.3},{:.3},{:.3}) width={:.4}",
-                        i, x, open_y, high_y, low_y, close_y, candle_width
+                        i, x, open_y, high_y, low_y, close_y, width
                     ),
                 );
             }
 
-            let body_top = open_y.max(close_y);
-            let body_bottom = open_y.min(close_y);
+            let (body_top, body_bottom) =
+                candle_body(open_y, close_y, self.min_body_height, self.px_to_ndc(1.0));
 
-            // Minimum height for visibility
-            let min_height = 0.005;
-            let actual_body_top = if (body_top - body_bottom).abs() < min_height {
-                body_bottom + min_height
+            let prev = match i {
+                0 => start_index.checked_sub(1).and_then(|p| candle_vec.get(p)),
+                _ => drawn.get(i - 1),
+            };
+            // Heikin-Ashi colors always follow its own open vs close
+            let is_bullish = if heikin_ashi_mode {
+                is_bullish_move(candle.ohlcv.open.value(), candle.ohlcv.close.value())
             } else {
-                body_top
+                is_bullish_candle(candle, prev, self.candle_coloring)
             };
 
-            let is_bullish = close_y >= open_y;
+            // Bucket bars show the mean volume so they stay comparable to the volume MA
+            let mean_volume = candle.ohlcv.volume.value() as f32 / (last - first + 1) as f32;
+            let vol_ratio = mean_volume / max_volume;
+            let volume_vertices =
+                CandleGeometry::create_volume_vertices(x, width, vol_ratio, is_bullish);
+            vertices.extend(volume_vertices.into_iter().map(|mut v| {
+                v.position_y = main_band.map_ndc(v.position_y);
+                v
+            }));
+
+            if !draws_candles {
+                continue;
+            }
 
+            // 🕯️ Bodies and wicks are drawn instanced from this single record
             instances.push(CandleInstance {
                 x,
-                width: candle_width,
-                body_top: actual_body_top,
+                width,
+                body_top,
                 body_bottom,
                 high: high_y,
                 low: low_y,
                 bullish: if is_bullish { 1.0 } else { 0.0 },
                 _padding: 0.0,
             });
+        }
 
-            let candle_vertices = CandleGeometry::create_candle_vertices(
-                candle.timestamp.as_f64(),
-                candle.ohlcv.open.value() as f32,
-                candle.ohlcv.high.value() as f32,
-                candle.ohlcv.low.value() as f32,
-                candle.ohlcv.close.value() as f32,
-                x,
-                open_y,
-                high_y,
-                low_y,
-                close_y,
-                candle_width,
-            );
-            vertices.extend_from_slice(&candle_vertices);
+        if self.line_visibility.volume_ma {
+            vertices.extend(self.create_volume_ma(
+                &candle_vec,
+                start_index,
+                visible_candles.len(),
+                max_volume,
+                main_band,
+            ));
+        }
 
-            let vol_ratio = (candle.ohlcv.volume.value() as f32) / max_volume;
-            let volume_vertices =
-                CandleGeometry::create_volume_vertices(x, candle_width, vol_ratio, is_bullish);
-            vertices.extend_from_slice(&volume_vertices);
+        // ⚡ Performance: compare against per-vertex candles at the same cadence as above
+        if candles.len().is_multiple_of(100) {
+            let (legacy, instanced) = candle_upload_sizes(&instances);
+            log_info!(
+                LogComponent::Infrastructure("WebGpuRenderer"),
+                "🕯️ {} candles: {} bytes instanced vs {} bytes as per-candle vertices",
+                instances.len(),
+                instanced,
+                legacy
+            );
         }
 
-        let to_points = |values: &[Price], period: usize| -> Vec<(f32, f32)> {
-            values
+        if chart.chart_type == ChartType::OhlcBar {
+            // Same rule as the volume bars under them
+            let coloring =
+                if heikin_ashi_mode { CandleColoring::OpenClose } else { self.candle_coloring };
+            vertices.extend(create_ohlc_bars(
+                &visible_candles,
+                start_index.checked_sub(1).and_then(|p| candle_vec.get(p)),
+                coloring,
+                &x_axis,
+                candle_width,
+                self.px_to_ndc(1.5),
+                price_norm,
+            ));
+        } else if !draws_candles {
+            let points: Vec<(f32, f32)> = visible_candles
                 .iter()
                 .enumerate()
-                .filter_map(|(idx, val)| {
-                    let candle_idx = idx + period - 1;
-                    if candle_idx < start_index || candle_idx >= start_index + visible_candles.len()
-                    {
-                        return None;
-                    }
-                    let x = candle_x_position(candle_idx - start_index, visible_candles.len());
-                    let y = price_norm(val.value());
-                    Some((x, y))
-                })
-                .collect()
+                .map(|(i, c)| (x_axis.x(i), price_norm(c.ohlcv.close.value())))
+                .collect();
+            if chart.chart_type == ChartType::Area {
+                vertices.extend(CandleGeometry::create_area_fill(&points, main_band.bottom));
+            }
+            vertices.extend(CandleGeometry::create_indicator_line_vertices(
+                &points,
+                IndicatorType::PriceLine,
+                self.px_to_ndc(4.0),
+            ));
+        }
+
+        let to_points = |values: &[Price], period: usize| {
+            moving_average_points(values, period, start_index, &x_axis, price_norm)
         };
 
         let line_width = self.px_to_ndc(2.0);
+        // Anchor MA dashes to the first candle so they move with the data while panning
+        let dash_phase = start_index as f32 * step_size;
 
-        if self.line_visibility.sma_20 {
-            let points = to_points(&mas.sma_20, 20);
+        for (config, values) in &averages {
+            let points = to_points(values, config.period);
             log_info!(
                 LogComponent::Infrastructure("WebGpuRenderer"),
-                "SMA20 points: {}",
+                "{} points: {}",
+                config.label(),
                 points.len()
             );
             if points.len() < 2 {
                 log_warn!(
                     LogComponent::Infrastructure("WebGpuRenderer"),
-                    "Not enough points for SMA20"
+                    "Not enough points for {}",
+                    config.label()
                 );
             }
-            vertices.extend_from_slice(&CandleGeometry::create_indicator_line_vertices(
+            vertices.extend(CandleGeometry::create_colored_line_vertices(
                 &points,
-                IndicatorType::SMA20,
+                config.color,
                 line_width,
+                self.line_styles.moving_averages,
+                dash_phase,
             ));
         }
 
-        if self.line_visibility.sma_50 {
-            let points = to_points(&mas.sma_50, 50);
-            log_info!(
-                LogComponent::Infrastructure("WebGpuRenderer"),
-                "SMA50 points: {}",
-                points.len()
-            );
-            if points.len() < 2 {
-                log_warn!(
-                    LogComponent::Infrastructure("WebGpuRenderer"),
-                    "Not enough points for SMA50"
-                );
-            }
-            vertices.extend_from_slice(&CandleGeometry::create_indicator_line_vertices(
-                &points,
-                IndicatorType::SMA50,
-                line_width,
-            ));
+        // 📈 Bollinger bands overlay the candles
+        if self.line_visibility.bollinger {
+            vertices.extend(self.create_bollinger_bands(&visible_candles, price_norm));
         }
 
-        if self.line_visibility.sma_200 {
-            let points = to_points(&mas.sma_200, 200);
-            log_info!(
-                LogComponent::Infrastructure("WebGpuRenderer"),
-                "SMA200 points: {}",
-                points.len()
-            );
-            if points.len() < 2 {
-                log_warn!(
-                    LogComponent::Infrastructure("WebGpuRenderer"),
-                    "Not enough points for SMA200"
-                );
-            }
-            vertices.extend_from_slice(&CandleGeometry::create_indicator_line_vertices(
-                &points,
-                IndicatorType::SMA200,
-                line_width,
-            ));
+        // 📈 Keltner channel overlays the candles
+        if self.line_visibility.keltner {
+            vertices.extend(self.create_keltner_channel(&visible_candles, price_norm));
         }
 
-        if self.line_visibility.ema_12 {
-            let points = to_points(&mas.ema_12, 12);
-            log_info!(
-                LogComponent::Infrastructure("WebGpuRenderer"),
-                "EMA12 points: {}",
-                points.len()
-            );
-            if points.len() < 2 {
-                log_warn!(
-                    LogComponent::Infrastructure("WebGpuRenderer"),
-                    "Not enough points for EMA12"
-                );
-            }
-            vertices.extend_from_slice(&CandleGeometry::create_indicator_line_vertices(
-                &points,
-                IndicatorType::EMA12,
-                line_width,
-            ));
+        // 📊 VWAP overlays the candles
+        if self.line_visibility.vwap {
+            vertices.extend(self.create_vwap(&visible_candles, price_norm));
         }
 
-        if self.line_visibility.ema_26 {
-            let points = to_points(&mas.ema_26, 26);
-            log_info!(
-                LogComponent::Infrastructure("WebGpuRenderer"),
-                "EMA26 points: {}",
-                points.len()
-            );
-            if points.len() < 2 {
-                log_warn!(
-                    LogComponent::Infrastructure("WebGpuRenderer"),
-                    "Not enough points for EMA26"
-                );
-            }
-            vertices.extend_from_slice(&CandleGeometry::create_indicator_line_vertices(
-                &points,
-                IndicatorType::EMA26,
-                line_width,
-            ));
+        // 📚 Depth histogram along the right edge of the price panel
+        if let Some(book) = self.order_book.as_ref().filter(|_| self.line_visibility.depth) {
+            vertices.extend(create_depth_bars(book, main_band, price_norm));
         }
 
         // Add a solid line for the current price
         if !visible_candles.is_empty() {
-            let current_price = crate::app::global_current_price().get_untracked() as f32;
-            let price_y = ((current_price - min_price) / price_range) * 2.0 - 1.0; // same area as candles
+            let price_y = self.current_price_ndc(main_band, min_price, max_price); // same area as candles
 
             // Keep the line width constant regardless of zoom level
-            let line_thickness = 2.0 / self.height as f32;
-
-            let price_line = vec![
-                CandleVertex::current_price_vertex(-1.0, price_y - line_thickness),
-                CandleVertex::current_price_vertex(1.0, price_y - line_thickness),
-                CandleVertex::current_price_vertex(-1.0, price_y + line_thickness),
-                CandleVertex::current_price_vertex(1.0, price_y - line_thickness),
-                CandleVertex::current_price_vertex(1.0, price_y + line_thickness),
-                CandleVertex::current_price_vertex(-1.0, price_y + line_thickness),
-            ];
+            let line_thickness = self.px_to_ndc(1.0);
+
+            let price_line = CandleGeometry::create_horizontal_line(
+                price_y,
+                line_thickness * 2.0,
+                self.line_styles.current_price,
+                CandleVertex::current_price_vertex,
+            );
             vertices.extend_from_slice(&price_line);
         }
 
+        // 📌 User reference lines; alert lines are dashed
+        crate::app::horizontal_lines().with_untracked(|lines| {
+            let line_thickness = self.px_to_ndc(0.75);
+            for line in lines {
+                let color = line.color;
+                let pattern = if line.alert { self.line_styles.alerts } else { DashPattern::SOLID };
+                vertices.extend(CandleGeometry::create_horizontal_line(
+                    price_norm(line.price),
+                    line_thickness * 2.0,
+                    pattern,
+                    |x, y| CandleVertex::reference_line_vertex(x, y, color),
+                ));
+            }
+        });
+
+        // Session high/low lines for the current UTC day
+        let session_levels =
+            self.line_visibility.session_levels.then(|| session_high_low(&candle_vec)).flatten();
+        if let Some((session_low, session_high)) = session_levels {
+            let line_thickness = self.px_to_ndc(0.5);
+            for price in [session_low, session_high] {
+                vertices.extend(CandleGeometry::create_horizontal_line(
+                    price_norm(price),
+                    line_thickness * 2.0,
+                    self.line_styles.session_levels,
+                    CandleVertex::session_level_vertex,
+                ));
+            }
+        }
+
         // Ichimoku cloud
         let ichimoku = &chart.ichimoku;
         if !ichimoku.senkou_span_a.is_empty() && !ichimoku.senkou_span_b.is_empty() {
@@ -383,13 +1737,9 @@ impl WebGpuRenderer {
             let mut span_a_pts = Vec::new();
             let mut span_b_pts = Vec::new();
             for i in 0..span_len {
-                let x = candle_x_position(i, visible_count);
-                let y_a = ((ichimoku.senkou_span_a[i].value() as f32 - min_price) / price_range)
-                    * 2.0
-                    - 1.0;
-                let y_b = ((ichimoku.senkou_span_b[i].value() as f32 - min_price) / price_range)
-                    * 2.0
-                    - 1.0;
+                let x = x_axis.x(i);
+                let y_a = price_norm(ichimoku.senkou_span_a[i].value());
+                let y_b = price_norm(ichimoku.senkou_span_b[i].value());
                 span_a_pts.push((x, y_a));
                 span_b_pts.push((x, y_b));
             }
@@ -401,6 +1751,102 @@ impl WebGpuRenderer {
             ));
         }
 
+        // 📉 RSI oscillator in its own panel
+        if self.line_visibility.rsi_14 {
+            let rsi_band = if layout.panel_of("rsi") == MAIN_PANEL {
+                RSI_PANEL_BAND
+            } else {
+                layout.band_for("rsi")
+            };
+            vertices.extend(self.create_rsi(&visible_candles, rsi_band));
+        }
+
+        // 📊 MACD histogram and lines in their own panel
+        if self.line_visibility.macd {
+            let macd_band = if layout.panel_of("macd") == MAIN_PANEL {
+                MACD_PANEL_BAND
+            } else {
+                layout.band_for("macd")
+            };
+            vertices.extend(create_macd(
+                &visible_candles,
+                &x_axis,
+                macd_band,
+                self.px_to_ndc(1.5),
+                candle_width,
+            ));
+        }
+
+        // 📏 ATR in its own panel
+        if self.line_visibility.atr {
+            let atr_band = if layout.panel_of("atr") == MAIN_PANEL {
+                ATR_PANEL_BAND
+            } else {
+                layout.band_for("atr")
+            };
+            vertices.extend(self.create_atr(&visible_candles, atr_band));
+        }
+
+        // 📉 Stochastic %K/%D in its own panel
+        if self.line_visibility.stochastic {
+            let stochastic_band = if layout.panel_of("stochastic") == MAIN_PANEL {
+                STOCHASTIC_PANEL_BAND
+            } else {
+                layout.band_for("stochastic")
+            };
+            vertices.extend(self.create_stochastic(&visible_candles, stochastic_band));
+        }
+
+        // 🕒 Vertical time grid under the time axis labels
+        let grid_half_width = 0.5 * self.pixel_ratio / self.width as f32;
+        // Grid lines sit on interval slots so they match the axis labels in time mode
+        for slot in time_grid_indices(x_axis.slot_count()) {
+            let x = candle_x_position(slot, x_axis.slot_count());
+            vertices.extend(CandleGeometry::create_line_vertices(
+                &[(x, main_band.bottom), (x, main_band.top)],
+                grid_half_width,
+                self.line_styles.grid,
+                0.0,
+                CandleVertex::grid_vertex,
+            ));
+        }
+
+        // 🕳️ Dashed marker between neighbours with missing candles in between
+        if self.line_visibility.gaps {
+            for i in gap_indices(&visible_candles, interval) {
+                let x = (x_axis.x(i - 1) + x_axis.x(i)) / 2.0;
+                vertices.extend(CandleGeometry::create_line_vertices(
+                    &[(x, main_band.bottom), (x, main_band.top)],
+                    grid_half_width,
+                    GAP_MARKER_DASH,
+                    0.0,
+                    CandleVertex::grid_vertex,
+                ));
+            }
+        }
+
+        // 📏 Horizontal price grid at the price axis labels
+        let level_width = self.px_to_ndc(0.5);
+        for price in nice_price_ticks(min_price as f64, max_price as f64, PRICE_GRID_LINES) {
+            vertices.extend(CandleGeometry::create_horizontal_line(
+                price_norm(price),
+                level_width,
+                self.line_styles.grid,
+                CandleVertex::grid_vertex,
+            ));
+        }
+
+        // Separator above each indicator sub-panel
+        let separator_width = self.px_to_ndc(0.5);
+        for (_, band) in layout.bands().iter().skip(1) {
+            vertices.extend(CandleGeometry::create_horizontal_line(
+                band.top + PANEL_GAP * 0.5,
+                separator_width,
+                DashPattern::SOLID,
+                CandleVertex::grid_vertex,
+            ));
+        }
+
         // Identity matrix - vertices are already in NDC coordinates [-1, 1]
         let view_proj_matrix = [
             [1.0, 0.0, 0.0, 0.0],
@@ -414,16 +1860,8 @@ impl WebGpuRenderer {
             view_proj_matrix,
             viewport: [self.width as f32, self.height as f32, min_price, max_price],
             time_range: [0.0, visible_candles.len() as f32, visible_candles.len() as f32, 0.0],
-            bullish_color: [0.455, 0.780, 0.529, 1.0], // #74c787 - green
-            bearish_color: [0.882, 0.424, 0.282, 1.0], // #e16c48 - red
-            wick_color: [0.6, 0.6, 0.6, 0.9],          // light gray
-            sma20_color: [1.0, 1.0, 0.0, 0.9],         // yellow
-            sma50_color: [1.0, 1.0, 0.0, 0.9],         // yellow
-            sma200_color: [1.0, 1.0, 0.0, 0.9],        // yellow
-            ema12_color: [1.0, 1.0, 0.0, 0.9],         // yellow
-            ema26_color: [1.0, 1.0, 0.0, 0.9],         // yellow
-            current_price_color: [1.0, 1.0, 0.0, 0.8], // 💰 bright yellow
             render_params: [candle_width, spacing, line_width, 0.0],
+            ..ChartUniforms::from_theme(&self.theme)
         };
 
         (instances, vertices, uniforms)
@@ -437,42 +1875,8 @@ mod tests {
         chart::{Chart, value_objects::ChartType},
         market_data::{Candle, OHLCV, Price, Timestamp, Volume},
     };
+    use crate::infrastructure::rendering::gpu_structures::packed_line_color;
     use leptos::SignalSet;
-    use std::collections::VecDeque;
-
-    #[allow(invalid_value)]
-    fn dummy_renderer() -> WebGpuRenderer {
-        unsafe {
-            WebGpuRenderer {
-                _canvas_id: String::new(),
-                width: 800,
-                height: 600,
-                surface: std::mem::MaybeUninit::zeroed().assume_init(),
-                device: std::mem::MaybeUninit::zeroed().assume_init(),
-                queue: std::mem::MaybeUninit::zeroed().assume_init(),
-                config: std::mem::MaybeUninit::zeroed().assume_init(),
-                render_pipeline: std::mem::MaybeUninit::zeroed().assume_init(),
-                vertex_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-                uniform_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-                uniform_bind_group: std::mem::MaybeUninit::zeroed().assume_init(),
-                msaa_texture: std::mem::MaybeUninit::zeroed().assume_init(),
-                msaa_view: std::mem::MaybeUninit::zeroed().assume_init(),
-                template_vertices: 0,
-                cached_vertices: Vec::new(),
-                cached_uniforms: ChartUniforms::new(),
-                cached_candle_count: 0,
-                cached_zoom_level: 1.0,
-                cached_hash: 0,
-                cached_data_hash: 0,
-                cached_line_visibility: LineVisibility::default(),
-                zoom_level: 1.0,
-                pan_offset: 0.0,
-                last_frame_time: 0.0,
-                fps_log: VecDeque::new(),
-                line_visibility: LineVisibility::default(),
-            }
-        }
-    }
 
     fn make_candle(i: u64) -> Candle {
         let base = 100.0 + i as f64;
@@ -493,15 +1897,17 @@ mod tests {
         let mut chart = Chart::new("test".to_string(), ChartType::Candlestick, 300);
         let candles: Vec<Candle> = (0..210).map(make_candle).collect();
         chart.set_historical_data(candles);
+        // Show the latest candles, where even SMA 200 has values
+        chart.viewport.start_time = 209.0 * 60_000.0;
 
-        let renderer = dummy_renderer();
-        let (_, verts, _) = renderer.create_geometry(&chart);
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (_, verts, _) = builder.create_geometry(&chart);
 
-        assert!(verts.iter().any(|v| (v.color_type - 2.0).abs() < f32::EPSILON));
-        assert!(verts.iter().any(|v| (v.color_type - 3.0).abs() < f32::EPSILON));
-        assert!(verts.iter().any(|v| (v.color_type - 4.0).abs() < f32::EPSILON));
-        assert!(verts.iter().any(|v| (v.color_type - 5.0).abs() < f32::EPSILON));
-        assert!(verts.iter().any(|v| (v.color_type - 6.0).abs() < f32::EPSILON));
+        // Each configured average is drawn in its own color
+        for config in builder.moving_average_configs() {
+            let color = packed_line_color(config.color);
+            assert!(verts.iter().any(|v| v.color_type == color), "{} missing", config.label());
+        }
     }
 
     #[test]
@@ -542,8 +1948,8 @@ mod tests {
         let mut chart = Chart::new("test".to_string(), ChartType::Candlestick, 50);
         chart.set_historical_data(candles);
 
-        let renderer = dummy_renderer();
-        let (instances, _verts, _uni) = renderer.create_geometry(&chart);
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (instances, _verts, _uni) = builder.create_geometry(&chart);
 
         assert_eq!(instances.len(), 3);
         assert!(instances[0].bullish > 0.5);
@@ -557,11 +1963,11 @@ mod tests {
         let candles: Vec<Candle> = (0..250).map(make_candle).collect();
         chart.set_historical_data(candles.clone());
 
-        let renderer = dummy_renderer();
-        let (_, verts, _) = renderer.create_geometry(&chart);
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (_, verts, _) = builder.create_geometry(&chart);
 
         let (start_index, visible_count) =
-            crate::app::visible_range_by_time(&candles, &chart.viewport, renderer.zoom_level);
+            crate::app::visible_range_by_time(&candles, &chart.viewport, builder.zoom_level);
         let visible: Vec<Candle> =
             candles.iter().skip(start_index).take(visible_count).cloned().collect();
 
@@ -595,24 +2001,31 @@ mod tests {
                 .collect()
         };
 
-        let line_width = renderer.px_to_ndc(2.0);
+        let line_width = builder.px_to_ndc(2.0);
         let checks = [
-            (&mas.sma_20, IndicatorType::SMA20, 2.0, 20usize),
-            (&mas.sma_50, IndicatorType::SMA50, 3.0, 50usize),
-            (&mas.sma_200, IndicatorType::SMA200, 4.0, 200usize),
-            (&mas.ema_12, IndicatorType::EMA12, 5.0, 12usize),
-            (&mas.ema_26, IndicatorType::EMA26, 6.0, 26usize),
+            (&mas.sma_20, 20usize),
+            (&mas.sma_50, 50),
+            (&mas.sma_200, 200),
+            (&mas.ema_12, 12),
+            (&mas.ema_26, 26),
         ];
 
-        for (values, t, color, period) in checks {
-            let pts = to_points(values, period);
-            let expected = CandleGeometry::create_indicator_line_vertices(&pts, t, line_width);
+        // Each line keeps its own palette color
+        let configs = MaConfig::defaults(&ChartTheme::default());
+        for ((values, period), config) in checks.iter().zip(&configs) {
+            let expected = CandleGeometry::create_colored_line_vertices(
+                &to_points(values, *period),
+                config.color,
+                line_width,
+                DashPattern::SOLID,
+                0.0,
+            );
             let actual: Vec<CandleVertex> = verts
                 .iter()
-                .filter(|v| (v.color_type - color).abs() < f32::EPSILON)
+                .filter(|v| v.color_type == packed_line_color(config.color))
                 .cloned()
                 .collect();
-            assert_eq!(actual.len(), expected.len());
+            assert_eq!(actual.len(), expected.len(), "{:?} {}", config.kind, config.period);
             for (a, e) in actual.iter().zip(expected.iter()) {
                 assert!((a.position_x - e.position_x).abs() < 1e-6);
                 assert!((a.position_y - e.position_y).abs() < 1e-6);
@@ -620,6 +2033,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn moving_averages_follow_their_config() {
+        let mut chart = Chart::new("test".to_string(), ChartType::Candlestick, 300);
+        chart.set_historical_data((0..40).map(make_candle).collect());
+
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let blue = [0.0, 0.0, 1.0, 1.0];
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        builder.set_moving_average_configs(vec![
+            MaConfig { kind: MaKind::Sma, period: 0, color: red },
+            MaConfig { kind: MaKind::Sma, period: 7, color: red },
+            MaConfig { kind: MaKind::Ema, period: 41, color: blue },
+        ]);
+        assert_eq!(builder.moving_average_configs().len(), 2);
+
+        let (_, verts, _) = builder.create_geometry(&chart);
+        let count =
+            |color| verts.iter().filter(|v| v.color_type == packed_line_color(color)).count();
+        assert!(count(red) > 0);
+        // Longer than the 40 loaded candles
+        assert_eq!(count(blue), 0);
+    }
+
     #[test]
     fn price_normalization_range() {
         let candles = vec![
@@ -658,8 +2094,8 @@ mod tests {
         let mut chart = Chart::new("test".to_string(), ChartType::Candlestick, 50);
         chart.set_historical_data(candles);
 
-        let renderer = dummy_renderer();
-        let (instances, _verts, _uni) = renderer.create_geometry(&chart);
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (instances, _verts, _uni) = builder.create_geometry(&chart);
 
         assert_eq!(instances.len(), 3);
 
@@ -703,9 +2139,9 @@ mod tests {
         let mut chart = Chart::new("t".to_string(), ChartType::Candlestick, 300);
         chart.set_historical_data(candles.clone());
 
-        let mut renderer = dummy_renderer();
-        renderer.zoom_level = 3.0; // show only last ~10 candles
-        let (_, _, uni) = renderer.create_geometry(&chart);
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        builder.zoom_level = 3.0; // show only last ~10 candles
+        let (_, _, uni) = builder.create_geometry(&chart);
 
         // Price range from visible candles only
         let visible: Vec<Candle> = candles.iter().skip(20).cloned().collect();
@@ -731,11 +2167,11 @@ mod tests {
         let new_price = candles.last().unwrap().ohlcv.close.value() + 5.0;
         crate::app::global_current_price().set(new_price);
 
-        let renderer = dummy_renderer();
-        let (_, verts, _) = renderer.create_geometry(&chart);
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (_, verts, _) = builder.create_geometry(&chart);
 
         let (start_index, visible_count) =
-            crate::app::visible_range_by_time(&candles, &chart.viewport, renderer.zoom_level);
+            crate::app::visible_range_by_time(&candles, &chart.viewport, builder.zoom_level);
         let visible: Vec<Candle> =
             candles.iter().skip(start_index).take(visible_count).cloned().collect();
 
@@ -761,4 +2197,187 @@ mod tests {
 
         assert!((mid_y - expected_y).abs() < 1e-6);
     }
+
+    #[test]
+    fn current_price_y_ndc_matches_the_drawn_line() {
+        let mut chart = Chart::new("test".to_string(), ChartType::Candlestick, 50);
+        let candles: Vec<Candle> = (0..10).map(make_candle).collect();
+        chart.set_historical_data(candles.clone());
+        crate::app::global_current_price().set(candles.last().unwrap().ohlcv.close.value());
+
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        assert_eq!(builder.current_price_y_ndc(), None);
+        builder.update(&chart);
+
+        let line: Vec<f32> = builder
+            .cached_vertices()
+            .iter()
+            .filter(|v| (v.color_type - 7.0).abs() < f32::EPSILON)
+            .map(|v| v.position_y)
+            .collect();
+        let mid_y = (line.iter().copied().fold(f32::INFINITY, f32::min)
+            + line.iter().copied().fold(f32::NEG_INFINITY, f32::max))
+            * 0.5;
+        let y = builder.current_price_y_ndc().unwrap();
+        assert!((y - mid_y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sparkline_skips_optional_layers() {
+        let mut chart = Chart::new("spark".to_string(), ChartType::Sparkline, 300);
+        chart.set_historical_data((0..250).map(make_candle).collect());
+
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let (instances, verts, _) = builder.create_geometry(&chart);
+
+        // One quad per close segment and nothing else
+        assert!(instances.is_empty());
+        assert_eq!(verts.len(), 249 * 6);
+        assert!(verts.iter().all(|v| v.element_type == 0.0));
+        assert!(verts.iter().all(|v| v.position_y.abs() <= 1.0));
+    }
+
+    #[test]
+    fn auto_fit_y_ignores_stale_viewport() {
+        let mut chart = Chart::new("fit".to_string(), ChartType::Candlestick, 300);
+        chart.set_historical_data((0..50).map(make_candle).collect());
+        chart.viewport.min_price = 0.0;
+        chart.viewport.max_price = 10_000.0;
+
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        let body_span = |r: &ChartGeometryBuilder| {
+            let (instances, _, _) = r.create_geometry(&chart);
+            instances.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), c| {
+                (lo.min(c.body_bottom), hi.max(c.body_top))
+            })
+        };
+
+        // Fitted candles fill most of the main panel
+        let (lo, hi) = body_span(&builder);
+        assert!(hi - lo > 1.5);
+
+        // The viewport's wide range squeezes them into a thin strip
+        builder.set_auto_fit_y(false);
+        let (lo, hi) = body_span(&builder);
+        assert!(hi - lo < 0.1);
+    }
+
+    #[test]
+    fn manual_scale_falls_back_to_candles_for_empty_viewport() {
+        let mut chart = Chart::new("empty-vp".to_string(), ChartType::Candlestick, 300);
+        chart.set_historical_data((0..50).map(make_candle).collect());
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        let highs = |r: &ChartGeometryBuilder, chart: &Chart| {
+            r.create_geometry(chart).0.iter().map(|c| c.high).collect::<Vec<_>>()
+        };
+        let fitted = highs(&builder, &chart);
+
+        chart.viewport.min_price = 0.0;
+        chart.viewport.max_price = 0.0;
+        builder.set_auto_fit_y(false);
+        assert_eq!(highs(&builder, &chart), fitted);
+    }
+
+    #[test]
+    fn vwap_overlays_price_panel_when_enabled() {
+        let mut chart = Chart::new("vwap".to_string(), ChartType::Candlestick, 300);
+        chart.set_historical_data((0..30).map(make_candle).collect());
+
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        let vwap_vertices = |r: &ChartGeometryBuilder| {
+            let (_, verts, _) = r.create_geometry(&chart);
+            verts
+                .into_iter()
+                .filter(|v| v.element_type == 2.0 && v.color_type == 17.0)
+                .collect::<Vec<_>>()
+        };
+        assert!(vwap_vertices(&builder).is_empty());
+
+        builder.toggle_line_visibility("vwap");
+        let verts = vwap_vertices(&builder);
+        assert!(!verts.is_empty());
+        assert!(verts.iter().all(|v| v.position_y.abs() <= 1.0));
+    }
+
+    #[test]
+    fn theme_colors_reach_uniforms() {
+        let mut chart = Chart::new("theme".to_string(), ChartType::Candlestick, 300);
+        chart.set_historical_data((0..20).map(make_candle).collect());
+
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        let (_, _, uniforms) = builder.create_geometry(&chart);
+        assert_eq!(uniforms.bullish_color, ChartTheme::dark().bullish);
+
+        let light = ChartTheme::light();
+        builder.set_theme(light);
+        assert_eq!(builder.cached_zoom_level, f64::MAX);
+        let (_, _, uniforms) = builder.create_geometry(&chart);
+        assert_eq!(uniforms.wick_color, light.wick);
+        assert_eq!(uniforms.grid_color, light.grid);
+        assert_eq!(uniforms.sma20_color, light.sma20);
+    }
+
+    #[test]
+    fn line_and_area_modes_replace_candles() {
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let geometry = |chart_type| {
+            let mut chart = Chart::new("modes".to_string(), chart_type, 300);
+            chart.set_historical_data((0..50).map(make_candle).collect());
+            builder.create_geometry(&chart)
+        };
+
+        let (instances, line, _) = geometry(ChartType::Line);
+        assert!(instances.is_empty());
+        assert!(line.iter().all(|v| v.element_type != 0.0 && v.element_type != 1.0));
+        assert!(line.iter().any(|v| v.element_type == 2.0 && v.color_type == 16.0));
+        assert!(line.iter().any(|v| v.element_type == 5.0));
+        assert!(line.iter().any(|v| v.element_type == 4.0));
+        assert!(!line.iter().any(|v| v.element_type == 9.0 && v.color_type == 1.0));
+
+        let (_, area, _) = geometry(ChartType::Area);
+        assert!(area.iter().any(|v| v.element_type == 9.0 && v.color_type == 1.0));
+    }
+
+    #[test]
+    fn rsi_stays_inside_its_band() {
+        let builder = ChartGeometryBuilder::new(800, 600);
+        let candles: Vec<Candle> = (0..40).map(make_candle).collect();
+
+        assert!(builder.create_rsi(&candles[..DEFAULT_RSI_PERIOD], RSI_PANEL_BAND).is_empty());
+
+        let verts = builder.create_rsi(&candles, RSI_PANEL_BAND);
+        assert!(verts.iter().any(|v| v.element_type == 2.0));
+        assert!(verts.iter().all(|v| {
+            v.position_y >= RSI_PANEL_BAND.bottom - 0.01
+                && v.position_y <= RSI_PANEL_BAND.top + 0.01
+        }));
+    }
+
+    #[test]
+    fn line_widths_follow_pixel_ratio() {
+        let standard = ChartGeometryBuilder::new(800, 600);
+        let mut hidpi = ChartGeometryBuilder::new(1600, 1200);
+        hidpi.set_pixel_ratio(2.0);
+        // Twice the device pixels cover the same share of the canvas
+        assert!((standard.px_to_ndc(1.5) - hidpi.px_to_ndc(1.5)).abs() < f32::EPSILON);
+
+        hidpi.set_pixel_ratio(0.0);
+        assert_eq!(hidpi.pixel_ratio(), 1.0);
+    }
+
+    #[test]
+    fn invalidate_forces_a_reupload() {
+        let mut chart = Chart::new("lost".to_string(), ChartType::Candlestick, 50);
+        chart.set_historical_data((0..10).map(make_candle).collect());
+
+        let mut builder = ChartGeometryBuilder::new(800, 600);
+        assert_eq!(builder.update(&chart), Some(true));
+        assert_eq!(builder.update(&chart), Some(false));
+
+        // A renderer on a new device starts with empty buffers
+        let hash = builder.cached_hash();
+        builder.invalidate();
+        assert_eq!(builder.update(&chart), Some(true));
+        assert_eq!(builder.cached_hash(), hash);
+    }
 }
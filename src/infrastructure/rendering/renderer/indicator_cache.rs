@@ -0,0 +1,149 @@
+//! 🧮 Moving averages carried over between frames.
+//!
+//! Live updates only rewrite the last candle or append new ones, so the cache
+//! keeps the closes its lines were built from and recomputes values from the
+//! first changed candle on. Any other change, such as prepended history or a
+//! different symbol, rebuilds every line from scratch.
+
+use crate::domain::market_data::{
+    Candle, Price,
+    services::{MaKind, MarketAnalysisService},
+};
+use std::collections::HashMap;
+
+/// How the last [`MovingAverageCache::update`] brought the lines up to date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaCacheUpdate {
+    /// Same closes as before
+    Unchanged,
+    /// Values recomputed from candle `from` on
+    Incremental { from: usize },
+    /// Every line computed from scratch
+    Rebuild,
+}
+
+/// ⏱️ Live timings of [`MovingAverageCache::update`], incremental versus rebuild
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MaUpdateTiming {
+    /// Duration of the latest rebuild
+    pub rebuild_ms: f64,
+    /// Summed duration of the incremental updates since the last report
+    pub incremental_ms: f64,
+    /// Incremental updates since the last report
+    pub incremental_updates: u32,
+}
+
+impl MaUpdateTiming {
+    /// Record an update that took `elapsed_ms`
+    ///
+    /// Every `every` incremental updates returns the average incremental and the
+    /// last rebuild duration, then starts counting again.
+    pub fn record(
+        &mut self,
+        update: MaCacheUpdate,
+        elapsed_ms: f64,
+        every: u32,
+    ) -> Option<(f64, f64)> {
+        match update {
+            MaCacheUpdate::Unchanged => None,
+            MaCacheUpdate::Rebuild => {
+                self.rebuild_ms = elapsed_ms;
+                None
+            }
+            MaCacheUpdate::Incremental { .. } => {
+                self.incremental_ms += elapsed_ms;
+                self.incremental_updates += 1;
+                if self.incremental_updates < every.max(1) {
+                    return None;
+                }
+                let average = self.incremental_ms / f64::from(self.incremental_updates);
+                self.incremental_ms = 0.0;
+                self.incremental_updates = 0;
+                Some((average, self.rebuild_ms))
+            }
+        }
+    }
+}
+
+/// SMA and EMA lines of one candle series, updated in place
+#[derive(Debug, Clone, Default)]
+pub struct MovingAverageCache {
+    closes: Vec<f64>,
+    lines: HashMap<(MaKind, usize), Vec<Price>>,
+}
+
+impl MovingAverageCache {
+    /// Bring the `lines` averages up to date with `candles`; other lines are dropped
+    pub fn update(
+        &mut self,
+        candles: &[Candle],
+        lines: impl IntoIterator<Item = (MaKind, usize)>,
+    ) -> MaCacheUpdate {
+        let closes: Vec<f64> = candles.iter().map(|c| c.ohlcv.close.value()).collect();
+        let kept = self
+            .closes
+            .iter()
+            .zip(&closes)
+            .take_while(|(old, new)| old.to_bits() == new.to_bits())
+            .count();
+        // Only the last cached candle may differ; everything after it is new
+        let update = if kept == closes.len() && kept == self.closes.len() {
+            MaCacheUpdate::Unchanged
+        } else if !self.closes.is_empty()
+            && closes.len() >= self.closes.len()
+            && kept + 1 >= self.closes.len()
+        {
+            MaCacheUpdate::Incremental { from: kept }
+        } else {
+            MaCacheUpdate::Rebuild
+        };
+        self.closes = closes;
+
+        let analysis = MarketAnalysisService::new();
+        let mut lines_before = std::mem::take(&mut self.lines);
+        for (kind, period) in lines {
+            let values = match (lines_before.remove(&(kind, period)), update) {
+                (Some(values), MaCacheUpdate::Unchanged) => values,
+                (Some(values), MaCacheUpdate::Incremental { from }) => {
+                    self.extend(&analysis, candles, kind, period, values, from)
+                }
+                _ => analysis.calculate_ma(candles, kind, period),
+            };
+            self.lines.insert((kind, period), values);
+        }
+        update
+    }
+
+    /// Values of the `kind` average over `period` from the last update
+    pub fn get(&self, kind: MaKind, period: usize) -> Option<&[Price]> {
+        self.lines.get(&(kind, period)).map(Vec::as_slice)
+    }
+
+    /// `values` with every value from candle `from` on recomputed
+    fn extend(
+        &self,
+        analysis: &MarketAnalysisService,
+        candles: &[Candle],
+        kind: MaKind,
+        period: usize,
+        mut values: Vec<Price>,
+        from: usize,
+    ) -> Vec<Price> {
+        // Value `k` belongs to candle `k + period - 1`; the first one has no predecessor
+        if period == 0 || from < period {
+            return analysis.calculate_ma(candles, kind, period);
+        }
+        values.truncate(from + 1 - period);
+        match kind {
+            MaKind::Sma => {
+                values.extend(analysis.calculate_sma(&candles[from + 1 - period..], period))
+            }
+            MaKind::Ema => {
+                let prev = values.last().map_or(0.0, Price::value);
+                let fresh = analysis.extend_ema_values(prev, &self.closes[from..], period);
+                values.extend(fresh.into_iter().map(Price::from));
+            }
+        }
+        values
+    }
+}
@@ -1,7 +1,6 @@
 use super::*;
 use crate::domain::market_data::TimeInterval;
-use leptos::SignalGetUntracked;
-use std::collections::VecDeque;
+use leptos::{SignalGetUntracked, SignalSet};
 
 impl WebGpuRenderer {
     pub async fn is_webgpu_supported() -> bool {
@@ -26,12 +25,14 @@ impl WebGpuRenderer {
         }
     }
 
-    pub async fn new(canvas_id: &str, width: u32, height: u32) -> Result<Self, JsValue> {
+    pub async fn new(canvas_id: &str, width: u32, height: u32) -> Result<Self, ChartError> {
         let canvas = document()
             .get_element_by_id(canvas_id)
-            .ok_or_else(|| JsValue::from_str(&format!("Canvas with id '{}' not found", canvas_id)))?
+            .ok_or_else(|| {
+                ChartError::NotInitialized(format!("Canvas with id '{}' not found", canvas_id))
+            })?
             .dyn_into::<HtmlCanvasElement>()
-            .map_err(|_| JsValue::from_str("Element is not a canvas"))?;
+            .map_err(|_| ChartError::NotInitialized("Element is not a canvas".to_string()))?;
 
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
@@ -56,7 +57,7 @@ impl WebGpuRenderer {
 
         let surface = instance
             .create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))
-            .map_err(|e| JsValue::from_str(&format!("Failed to create surface: {}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Failed to create surface: {}", e)))?;
 
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
@@ -70,7 +71,7 @@ impl WebGpuRenderer {
                 force_fallback_adapter: false,
             })
             .await
-            .map_err(|e| JsValue::from_str(&format!("Failed to find adapter: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Failed to find adapter: {:?}", e)))?;
 
         let adapter_info = adapter.get_info();
         get_logger().info(
@@ -87,14 +88,32 @@ impl WebGpuRenderer {
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor {
                 label: Some("Device"),
-                required_features: wgpu::Features::empty(),
+                // Adapter-specific format features unlock sample counts beyond 4
+                required_features: adapter.features()
+                    & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
                 // Use the adapter's own supported limits
                 required_limits: supported_limits,
                 memory_hints: wgpu::MemoryHints::default(),
                 trace: wgpu::Trace::Off,
             })
             .await
-            .map_err(|e| JsValue::from_str(&format!("Failed to create device: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Failed to create device: {:?}", e)))?;
+
+        // 💥 A lost device (GPU reset, driver update) stops rendering and asks the
+        // app to rebuild the renderer; `Destroyed` is our own teardown
+        let device_lost = Arc::new(AtomicBool::new(false));
+        let lost_flag = device_lost.clone();
+        let lost_canvas = canvas.id();
+        device.set_device_lost_callback(move |reason, message| {
+            lost_flag.store(true, Ordering::SeqCst);
+            get_logger().error(
+                LogComponent::Infrastructure("WebGpuRenderer"),
+                &format!("💥 WebGPU device lost ({reason:?}): {message}"),
+            );
+            if reason == wgpu::DeviceLostReason::Unknown {
+                crate::app::device_lost().set(Some((lost_canvas.clone(), message)));
+            }
+        });
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps
@@ -130,17 +149,14 @@ impl WebGpuRenderer {
             "🎯 Surface configured successfully",
         );
 
-        let msaa_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("MSAA Texture"),
-            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
-            mip_level_count: 1,
-            sample_count: MSAA_SAMPLE_COUNT,
-            dimension: wgpu::TextureDimension::D2,
-            format: config.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[],
-        });
-        let msaa_view = msaa_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // 🔬 Without adapter-specific features only the WebGPU baseline counts are valid
+        let mut supported_sample_counts =
+            adapter.get_texture_format_features(surface_format).flags.supported_sample_counts();
+        if !device.features().contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+            supported_sample_counts.retain(|count| [1, 4].contains(count));
+        }
+        let sample_count = nearest_sample_count(MSAA_SAMPLE_COUNT, &supported_sample_counts);
+        let (msaa_texture, msaa_view) = create_msaa_texture(&device, &config, sample_count);
 
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Uniform Buffer"),
@@ -184,47 +200,51 @@ impl WebGpuRenderer {
                 push_constant_ranges: &[],
             });
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[CandleVertex::desc()],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: MSAA_SAMPLE_COUNT,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        });
+        let render_pipeline = create_main_pipeline(
+            &device,
+            &render_pipeline_layout,
+            &shader,
+            config.format,
+            sample_count,
+        );
 
         let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Vertex Buffer"),
-            size: (std::mem::size_of::<CandleVertex>() * 100000) as u64,
+            size: (std::mem::size_of::<CandleVertex>() * INITIAL_VERTEX_CAPACITY) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // 🕯️ Instanced candles share the uniforms and stretch one unit quad per candle
+        let wick_pipeline = create_candle_pipeline(
+            &device,
+            &render_pipeline_layout,
+            &shader,
+            config.format,
+            sample_count,
+            "vs_candle_wick",
+        );
+        let body_pipeline = create_candle_pipeline(
+            &device,
+            &render_pipeline_layout,
+            &shader,
+            config.format,
+            sample_count,
+            "vs_candle_body",
+        );
+        let quad_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Candle Quad Vertex Buffer"),
+            contents: bytemuck::cast_slice(&QuadVertex::UNIT),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let quad_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Candle Quad Index Buffer"),
+            contents: bytemuck::cast_slice(&QuadVertex::INDICES),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Candle Instance Buffer"),
+            size: (std::mem::size_of::<CandleInstance>() * INITIAL_INSTANCE_CAPACITY) as u64,
             usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
@@ -235,32 +255,31 @@ impl WebGpuRenderer {
         );
 
         let renderer = Self {
-            _canvas_id: canvas.id(),
-            width,
-            height,
+            canvas_id: canvas.id(),
+            device_lost,
             surface,
             device,
             queue,
             config,
+            shader,
+            pipeline_layout: render_pipeline_layout,
             render_pipeline,
             vertex_buffer,
+            wick_pipeline,
+            body_pipeline,
+            quad_vertex_buffer,
+            quad_index_buffer,
+            instance_buffer,
             uniform_buffer,
             uniform_bind_group,
+            sample_count,
+            supported_sample_counts,
             msaa_texture,
             msaa_view,
-            template_vertices: 0,
-            cached_vertices: Vec::new(),
-            cached_uniforms: ChartUniforms::new(),
-            cached_candle_count: 0,
-            cached_zoom_level: 1.0,
-            cached_hash: 0,
-            cached_data_hash: 0,
-            cached_line_visibility: LineVisibility::default(),
-            zoom_level: 1.0,
-            pan_offset: 0.0,
-            last_frame_time: 0.0,
-            fps_log: VecDeque::new(),
-            line_visibility: LineVisibility::default(),
+            geometry: ChartGeometryBuilder::new(width, height),
+            fps: FpsCounter::default(),
+            frame_stats: FrameStats::default(),
+            needs_redraw: true,
         };
 
         renderer.log_gpu_memory_usage();
@@ -270,27 +289,48 @@ impl WebGpuRenderer {
 
     pub fn resize(&mut self, new_width: u32, new_height: u32) {
         if new_width > 0 && new_height > 0 {
-            self.width = new_width;
-            self.height = new_height;
+            self.geometry.resize(new_width, new_height);
             self.config.width = new_width;
             self.config.height = new_height;
             self.surface.configure(&self.device, &self.config);
-            self.msaa_texture = self.device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("MSAA Texture"),
-                size: wgpu::Extent3d {
-                    width: new_width,
-                    height: new_height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: MSAA_SAMPLE_COUNT,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.config.format,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-                view_formats: &[],
-            });
-            self.msaa_view = self.msaa_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            (self.msaa_texture, self.msaa_view) =
+                create_msaa_texture(&self.device, &self.config, self.sample_count);
+            // The reconfigured surface starts blank
+            self.mark_dirty();
+        }
+    }
+
+    /// 🔬 Switch MSAA to `requested` samples, or the nearest count the GPU supports
+    ///
+    /// Rebuilds the pipelines and the multisampled texture; callers run this
+    /// between frames (the render queue does) so no pass sees a half-switched
+    /// renderer. Returns the count now in use.
+    pub fn set_msaa_sample_count(&mut self, requested: u32) -> u32 {
+        let sample_count = nearest_sample_count(requested, &self.supported_sample_counts);
+        if sample_count == self.sample_count {
+            return sample_count;
         }
+        let format = self.config.format;
+        let (device, layout, shader) = (&self.device, &self.pipeline_layout, &self.shader);
+        self.render_pipeline = create_main_pipeline(device, layout, shader, format, sample_count);
+        self.wick_pipeline =
+            create_candle_pipeline(device, layout, shader, format, sample_count, "vs_candle_wick");
+        self.body_pipeline =
+            create_candle_pipeline(device, layout, shader, format, sample_count, "vs_candle_body");
+        (self.msaa_texture, self.msaa_view) =
+            create_msaa_texture(&self.device, &self.config, sample_count);
+        self.sample_count = sample_count;
+        get_logger().info(
+            LogComponent::Infrastructure("WebGpuRenderer"),
+            &format!("🔬 MSAA set to {sample_count}x (requested {requested}x)"),
+        );
+        self.mark_dirty();
+        sample_count
+    }
+
+    /// 🔬 Samples per pixel currently rendered
+    pub fn msaa_sample_count(&self) -> u32 {
+        self.sample_count
     }
 
     pub fn update(&mut self, chart: &Chart) {
@@ -305,12 +345,123 @@ impl WebGpuRenderer {
             &format!("📊 Updated chart data: {} candles", candles.len()),
         );
     }
+}
 
-    /// 🔍 Set zoom and pan parameters
-    pub fn set_zoom_params(&mut self, zoom_level: f64, pan_offset: f64) {
-        self.zoom_level = zoom_level;
-        self.pan_offset = pan_offset;
-        // Force geometry refresh on next render
-        self.cached_zoom_level = f64::MAX;
-    }
+/// 🔬 Multisampled color target matching the surface, resolved into it each frame
+fn create_msaa_texture(
+    device: &wgpu::Device,
+    config: &wgpu::SurfaceConfiguration,
+    sample_count: u32,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("MSAA Texture"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// Pipeline drawing the `CandleVertex` list: grid, volume, indicators and overlays
+fn create_main_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Render Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[CandleVertex::desc()],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: Some(wgpu::Face::Back),
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Pipeline drawing `QuadVertex::UNIT` once per `CandleInstance` with the given vertex entry point
+fn create_candle_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    entry_point: &str,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(entry_point),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some(entry_point),
+            buffers: &[QuadVertex::desc(), CandleInstance::desc()],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_candle"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: Some(wgpu::Face::Back),
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
 }
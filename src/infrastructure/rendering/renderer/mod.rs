@@ -3,31 +3,58 @@
 //! This module manages GPU buffers and performs the render loop. The renderer
 //! is kept behind a global handle to simplify access from the UI layer.
 
-use crate::domain::market_data::Candle;
+use crate::domain::market_data::{
+    Candle, OrderBook, TimeInterval,
+    services::{MaKind, MovingAveragesData},
+};
 use crate::domain::{
     chart::Chart,
+    errors::ChartError,
     logging::{LogComponent, get_logger},
 };
+use crate::ecs::components::IndicatorSource;
 use crate::infrastructure::rendering::gpu_structures::{
-    CandleInstance, CandleVertex, ChartUniforms,
+    CandleInstance, CandleVertex, ChartTheme, ChartUniforms, LineStyles, QuadVertex,
 };
+use crate::infrastructure::rendering::palette::palette;
 use gloo::utils::document;
 use js_sys;
 use leptos::SignalSet;
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use wasm_bindgen::JsCast;
-use wasm_bindgen::JsValue;
 use web_sys::HtmlCanvasElement;
 use wgpu::util::DeviceExt;
 thread_local! {
     static GLOBAL_RENDERER: RefCell<Option<Rc<RefCell<WebGpuRenderer>>>> = const { RefCell::new(None) };
 }
 
-/// Number of samples for MSAA
+/// Default number of samples for MSAA
 pub const MSAA_SAMPLE_COUNT: u32 = 4;
 
+/// MSAA sample counts offered in the UI; 1 turns MSAA off
+pub const MSAA_SAMPLE_OPTIONS: [u32; 3] = [1, 4, 8];
+
+/// 🔬 Highest count in `supported` not above `requested`, else 1 (always valid)
+pub fn nearest_sample_count(requested: u32, supported: &[u32]) -> u32 {
+    supported.iter().copied().filter(|&count| count <= requested).max().unwrap_or(1)
+}
+
+/// Initial vertex buffer capacity in vertices
+pub const INITIAL_VERTEX_CAPACITY: usize = 100_000;
+
+/// Initial candle instance buffer capacity in instances
+pub const INITIAL_INSTANCE_CAPACITY: usize = 4_096;
+
+/// Factor applied to the vertex buffer capacity when it overflows
+pub const VERTEX_BUFFER_GROWTH: f64 = 1.5;
+
+/// Zoom changes smaller than this reuse the cached geometry
+pub const ZOOM_EPSILON: f64 = 1e-6;
+
 /// Store the global renderer instance
 pub fn set_global_renderer(renderer: Rc<RefCell<WebGpuRenderer>>) {
     GLOBAL_RENDERER.with(|cell| {
@@ -35,7 +62,7 @@ pub fn set_global_renderer(renderer: Rc<RefCell<WebGpuRenderer>>) {
     });
     GLOBAL_RENDERER.with(|cell| {
         if let Some(rc) = &*cell.borrow() {
-            crate::app::global_line_visibility().set(rc.borrow().line_visibility.clone());
+            crate::app::global_line_visibility().set(rc.borrow().geometry.line_visibility.clone());
         }
     });
 }
@@ -55,11 +82,51 @@ where
     })
 }
 
+/// 🖼️ Drawing surface the chart renders through
+///
+/// Implemented by [`WebGpuRenderer`] and by the [`Canvas2dRenderer`] fallback
+/// for browsers without WebGPU.
+pub trait Renderer {
+    /// Draw `chart` with the current geometry settings
+    fn render(&mut self, chart: &Chart) -> Result<(), ChartError>;
+
+    /// Resize the drawing surface to `width`x`height` device pixels
+    fn resize(&mut self, width: u32, height: u32);
+
+    /// Geometry settings and caches shared by every backend
+    fn geometry_mut(&mut self) -> &mut ChartGeometryBuilder;
+
+    /// Short backend name for status messages
+    fn backend_name(&self) -> &'static str;
+}
+
+impl Renderer for WebGpuRenderer {
+    fn render(&mut self, chart: &Chart) -> Result<(), ChartError> {
+        WebGpuRenderer::render(self, chart)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        WebGpuRenderer::resize(self, width, height)
+    }
+
+    fn geometry_mut(&mut self) -> &mut ChartGeometryBuilder {
+        WebGpuRenderer::geometry_mut(self)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "WebGPU"
+    }
+}
+
 /// Actual WebGPU renderer for candles
+///
+/// Owns the GPU resources and uploads whatever [`ChartGeometryBuilder`]
+/// produced for the current frame.
 pub struct WebGpuRenderer {
-    _canvas_id: String,
-    width: u32,
-    height: u32,
+    canvas_id: String,
+
+    // 💥 Set by the device-lost callback; rendering no-ops from then on
+    device_lost: Arc<AtomicBool>,
 
     // WGPU state
     surface: wgpu::Surface<'static>,
@@ -68,16 +135,53 @@ pub struct WebGpuRenderer {
     config: wgpu::SurfaceConfiguration,
 
     // Rendering pipeline
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
     render_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
+
+    // 🕯️ Instanced candles: wicks first, bodies on top, both stretching one unit quad
+    wick_pipeline: wgpu::RenderPipeline,
+    body_pipeline: wgpu::RenderPipeline,
+    quad_vertex_buffer: wgpu::Buffer,
+    quad_index_buffer: wgpu::Buffer,
+    instance_buffer: wgpu::Buffer,
+
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
+    // 🔬 Samples per pixel and the counts the surface format allows
+    sample_count: u32,
+    supported_sample_counts: Vec<u32>,
     msaa_texture: wgpu::Texture,
     msaa_view: wgpu::TextureView,
+
+    // 📐 Geometry, settings and caches that never touch the GPU
+    geometry: ChartGeometryBuilder,
+
+    // ⏱️ Performance metrics
+    fps: FpsCounter,
+    frame_stats: FrameStats,
+
+    // 🚩 Set when the cached geometry changed since the last presented frame
+    needs_redraw: bool,
+}
+
+/// CPU side of the renderer: builds chart geometry and caches the last result
+///
+/// Holds no GPU resources, so it can be created and exercised without a
+/// WebGPU device.
+pub struct ChartGeometryBuilder {
+    width: u32,
+    height: u32,
+    // 🖥️ Device pixels per CSS pixel; line widths are given in CSS pixels
+    pixel_ratio: f32,
     template_vertices: u32,
 
     // 🗄️ Cached data
     cached_vertices: Vec<CandleVertex>,
+    cached_instances: Vec<CandleInstance>,
+    // Cached vertices before this index are drawn under the candles
+    overlay_vertex_start: u32,
     cached_uniforms: ChartUniforms,
     cached_candle_count: usize,
     cached_zoom_level: f64,
@@ -89,73 +193,363 @@ pub struct WebGpuRenderer {
     zoom_level: f64,
     pan_offset: f64,
 
-    // ⏱️ Performance metrics
-    last_frame_time: f64,
-    fps_log: VecDeque<f64>,
-
     // 📊 Indicator line visibility
     line_visibility: LineVisibility,
+
+    // 🧮 Vertex and instance buffer growth diagnostics
+    vertex_buffer_stats: VertexBufferStats,
+    instance_buffer_stats: VertexBufferStats,
+
+    // 📐 Scale candle width by the current interval
+    interval_aware_width: bool,
+
+    // ↔️ Place candles by index or by open time
+    x_axis_mode: XAxisMode,
+
+    // ➖ Dash patterns per line type
+    line_styles: LineStyles,
+
+    // 🎨 Colors for candles, grid, indicators and background
+    theme: ChartTheme,
+
+    // 📏 Optional tick size for rendered prices
+    price_tick: Option<f64>,
+
+    // 🎨 Bullish/bearish classification rule
+    candle_coloring: CandleColoring,
+
+    // ⬛ Floor on the drawn candle body height
+    min_body_height: MinBodyHeight,
+
+    // 🕳️ Placeholder drawn while the chart has no candles
+    empty_state: EmptyChartState,
+
+    // 📉 RSI lookback period
+    rsi_period: usize,
+
+    // 📉 Stochastic %K lookback period
+    stochastic_period: usize,
+
+    // 🌫️ Fill the area between the Bollinger bands
+    bollinger_fill: bool,
+
+    // 📐 Fit the price scale to the visible candles instead of the viewport
+    auto_fit_y: bool,
+
+    // 📊 VWAP session anchor; `None` anchors at the first visible candle
+    vwap_anchor: Option<u64>,
+
+    // 📚 Latest order book for the depth histogram
+    order_book: Option<OrderBook>,
+
+    // 🧮 Moving averages precomputed by the ECS indicator system
+    moving_averages: Option<(IndicatorSource, MovingAveragesData)>,
+
+    // 📈 Moving-average lines to draw
+    moving_average_configs: Vec<MaConfig>,
+    // Every shown line, updated incrementally per frame as a fallback for the ECS result
+    moving_average_cache: RefCell<MovingAverageCache>,
+
+    // 🎞️ Ease candle updates instead of snapping to them
+    animate_transitions: bool,
+    transition: Option<Transition>,
+    // Frame on screen with the chart id and interval it was built for
+    shown_frame: Option<(String, TimeInterval, AnimatedFrame)>,
+    // Tweened values `create_geometry` draws instead of the latest data
+    frame_override: Option<AnimatedFrame>,
+}
+
+/// 📈 One moving-average line: its kind, lookback period and color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaConfig {
+    pub kind: MaKind,
+    pub period: usize,
+    pub color: [f32; 4],
+}
+
+impl MaConfig {
+    /// SMA 20/50/200 and EMA 12/26 in the colors of `theme`
+    pub fn defaults(theme: &ChartTheme) -> Vec<MaConfig> {
+        let colors = [theme.sma20, theme.sma50, theme.sma200, theme.ema12, theme.ema26];
+        MovingAveragesData::LINES
+            .into_iter()
+            .zip(colors)
+            .map(|((kind, period), color)| MaConfig { kind, period, color })
+            .collect()
+    }
+
+    /// Configs for `lines`, each in a distinct [`palette`] color
+    pub fn from_palette(lines: &[(MaKind, usize)]) -> Vec<MaConfig> {
+        lines
+            .iter()
+            .zip(palette(lines.len()))
+            .map(|(&(kind, period), color)| MaConfig { kind, period, color })
+            .collect()
+    }
+
+    /// Legend text such as `SMA 20`
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            MaKind::Sma => "SMA",
+            MaKind::Ema => "EMA",
+        };
+        format!("{kind} {}", self.period)
+    }
 }
 
 /// State of indicator line visibility
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Digit keys toggle lines while the chart has keyboard input
+/// (see [`LineVisibility::HOTKEYS`]):
+///
+/// | Key | Line | Key | Line |
+/// |-----|------|-----|------|
+/// | `1` | SMA 20 | `6` | Bollinger bands |
+/// | `2` | SMA 50 | `7` | VWAP |
+/// | `3` | SMA 200 | `8` | RSI 14 |
+/// | `4` | EMA 12 | `9` | MACD |
+/// | `5` | EMA 26 | `0` | Session levels |
+///
+/// The depth histogram, stochastic oscillator, ATR, Keltner channel, volume
+/// average and gap markers have no hotkey.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct LineVisibility {
     pub sma_20: bool,
     pub sma_50: bool,
     pub sma_200: bool,
     pub ema_12: bool,
     pub ema_26: bool,
+    pub session_levels: bool,
+    pub rsi_14: bool,
+    pub macd: bool,
+    pub bollinger: bool,
+    pub vwap: bool,
+    pub depth: bool,
+    pub stochastic: bool,
+    pub atr: bool,
+    pub keltner: bool,
+    pub volume_ma: bool,
+    /// 🕳️ Dashed markers where candles are missing
+    pub gaps: bool,
+}
+
+impl LineVisibility {
+    /// Toggle names of the first five configured moving averages, by position
+    pub const MOVING_AVERAGE_TOGGLES: [&'static str; 5] =
+        ["sma20", "sma50", "sma200", "ema12", "ema26"];
+
+    /// Whether the moving average at `index` of the configured list is drawn
+    ///
+    /// The first five follow the `sma_20` … `ema_26` toggles by position;
+    /// any further averages are always shown.
+    pub fn shows_moving_average(&self, index: usize) -> bool {
+        match index {
+            0 => self.sma_20,
+            1 => self.sma_50,
+            2 => self.sma_200,
+            3 => self.ema_12,
+            4 => self.ema_26,
+            _ => true,
+        }
+    }
 }
 
 impl Default for LineVisibility {
     fn default() -> Self {
-        Self { sma_20: true, sma_50: true, sma_200: true, ema_12: true, ema_26: true }
+        Self {
+            sma_20: true,
+            sma_50: true,
+            sma_200: true,
+            ema_12: true,
+            ema_26: true,
+            session_levels: false,
+            rsi_14: false,
+            macd: false,
+            bollinger: false,
+            vwap: false,
+            depth: false,
+            stochastic: false,
+            atr: false,
+            keltner: false,
+            volume_ma: false,
+            gaps: true,
+        }
+    }
+}
+
+impl LineVisibility {
+    /// Digit key and the `toggle_line_visibility` name it switches
+    pub const HOTKEYS: [(&'static str, &'static str); 10] = [
+        ("1", "sma20"),
+        ("2", "sma50"),
+        ("3", "sma200"),
+        ("4", "ema12"),
+        ("5", "ema26"),
+        ("6", "bollinger"),
+        ("7", "vwap"),
+        ("8", "rsi"),
+        ("9", "macd"),
+        ("0", "session"),
+    ];
+
+    /// Line toggled by `key`, if it is one of the [`HOTKEYS`](Self::HOTKEYS)
+    pub fn line_for_key(key: &str) -> Option<&'static str> {
+        Self::HOTKEYS.iter().find(|(k, _)| *k == key).map(|(_, line)| *line)
     }
 }
 
+/// Labeled reference line at a fixed price (e.g. liquidation or entry level)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HorizontalLine {
+    pub id: u32,
+    pub price: f64,
+    pub color: [f32; 4],
+    pub label: String,
+    /// Notify when the live price crosses the level
+    #[serde(default)]
+    pub alert: bool,
+}
+
+/// ⏱️ Smoothed render rate and the latest frame interval; zero before the first frame
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameTiming {
+    pub fps: f64,
+    pub frame_ms: f64,
+}
+
+/// ⏱️ Frame rate over the last 60 presented frames
+#[derive(Debug, Clone, Default)]
+pub struct FpsCounter {
+    last_frame_time: f64,
+    fps_log: VecDeque<f64>,
+}
+
+impl FpsCounter {
+    /// Record a frame presented at `now` (ms)
+    pub fn record(&mut self, now: f64) {
+        if self.last_frame_time > 0.0 {
+            let delta = now - self.last_frame_time;
+            if delta > 0.0 {
+                self.fps_log.push_back(1000.0 / delta);
+                if self.fps_log.len() > 60 {
+                    self.fps_log.pop_front();
+                }
+            }
+        }
+        self.last_frame_time = now;
+    }
+
+    /// Average FPS over the last 60 presented frames, 0 before the second one
+    pub fn average_fps(&self) -> f64 {
+        if self.fps_log.is_empty() {
+            return 0.0;
+        }
+        self.fps_log.iter().sum::<f64>() / self.fps_log.len() as f64
+    }
+
+    /// Interval between the last two presented frames in ms, 0 until known
+    pub fn last_frame_ms(&self) -> f64 {
+        self.fps_log.back().map_or(0.0, |fps| 1000.0 / fps)
+    }
+
+    pub fn timing(&self) -> FrameTiming {
+        FrameTiming { fps: self.average_fps(), frame_ms: self.last_frame_ms() }
+    }
+}
+
+/// Render calls that presented a frame vs. those skipped as redundant
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    pub presented: u64,
+    pub skipped: u64,
+}
+
+/// Vertex buffer capacity and growth statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexBufferStats {
+    /// Current capacity in vertices
+    pub capacity: usize,
+    /// Number of times the buffer was reallocated
+    pub realloc_count: u32,
+    /// Vertex count of the last uploaded geometry
+    pub last_vertex_count: usize,
+    /// Largest vertex count seen so far
+    pub peak_vertex_count: usize,
+}
+
+impl Default for VertexBufferStats {
+    fn default() -> Self {
+        Self::with_capacity(INITIAL_VERTEX_CAPACITY)
+    }
+}
+
+impl VertexBufferStats {
+    /// Fresh statistics for a buffer of `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { capacity, realloc_count: 0, last_vertex_count: 0, peak_vertex_count: 0 }
+    }
+
+    /// Record an upload, growing capacity by `VERTEX_BUFFER_GROWTH` when exceeded
+    ///
+    /// Returns `true` when the buffer has to be reallocated.
+    pub fn record(&mut self, vertex_count: usize) -> bool {
+        self.last_vertex_count = vertex_count;
+        self.peak_vertex_count = self.peak_vertex_count.max(vertex_count);
+        if vertex_count <= self.capacity {
+            return false;
+        }
+        let grown = (self.capacity as f64 * VERTEX_BUFFER_GROWTH).ceil() as usize;
+        self.capacity = grown.max(vertex_count);
+        self.realloc_count += 1;
+        true
+    }
+}
+
+mod animation;
+pub use animation::{AnimatedFrame, TRANSITION_MS, Transition, ease_out_cubic};
+#[cfg(feature = "bench")]
+mod bench;
+#[cfg(feature = "bench")]
+pub use bench::{
+    BENCH_CANDLE_COUNTS, BENCH_ITERATIONS, GeometryBenchResult, MovingAverageBenchResult,
+    geometry_bench, moving_average_bench, run_geometry_bench, run_moving_average_bench,
+};
+mod builder;
+mod canvas2d;
+pub use canvas2d::{
+    Canvas2dRenderer, ndc_to_canvas_px, set_fallback_renderer, with_fallback_renderer,
+};
 mod geometry;
+mod indicator_cache;
 pub use geometry::{
-    EDGE_GAP, MAX_ELEMENT_WIDTH, MIN_ELEMENT_WIDTH, SPACING_RATIO, candle_x_position,
-    spacing_ratio_for,
+    ATR_PANEL_BAND, ATR_PERIOD, BOLLINGER_PERIOD, BOLLINGER_STD_DEV, CandleColoring,
+    DEFAULT_MIN_BODY_HEIGHT, DEFAULT_RSI_PERIOD, DEFAULT_STOCHASTIC_PERIOD, DEPTH_PANEL_WIDTH,
+    EDGE_GAP, EmptyChartState, FLAT_PRICE_SPAN_RATIO, GAP_MARKER_DASH, IndicatorValue,
+    KELTNER_MULTIPLIER, KELTNER_OFFSET, KELTNER_PERIOD, LOADING_GRID_DASH, MACD_FAST,
+    MACD_PANEL_BAND, MACD_SIGNAL, MACD_SLOW, MAX_ELEMENT_WIDTH, MAX_MIN_BODY_HEIGHT,
+    MIN_ELEMENT_WIDTH, MacdSeries, MinBodyHeight, OHLC_TICK_RATIO, PRICE_GRID_LINES, PRICE_PADDING,
+    RSI_PANEL_BAND, SPACING_RATIO, STOCHASTIC_LEVELS, STOCHASTIC_PANEL_BAND, STOCHASTIC_SMOOTHING,
+    StochasticSeries, TIME_GRID_LINES, VOLUME_MA_PERIOD, VWAP_SESSION_MS, VisibleSpan, atr_series,
+    bollinger_bands, candle_body, candle_color, candle_step, candle_upload_sizes, candle_width_for,
+    candle_x_position, create_depth_bars, create_macd, create_ohlc_bars, downsample_candles,
+    ensure_price_span, fit_price_range, gap_indices, heikin_ashi, interval_width_multiplier,
+    is_bullish_candle, keltner_bands, lod_bucket_size, macd_series, moving_average_points,
+    nice_price_step, nice_price_ticks, price_step_decimals, round_ohlc_to_tick, round_to_tick,
+    session_high_low, spacing_ratio_for, sparkline_vertices, stochastic_series, time_grid_indices,
+    true_ranges, vwap_series, vwap_session, wilder_rsi,
+};
+pub use indicator_cache::{MaCacheUpdate, MaUpdateTiming, MovingAverageCache};
+mod export;
+pub use export::{
+    FrameCapture, export_chart_png_data_url, padded_bytes_per_row, rgba_to_png_data_url,
+    to_straight_rgba, unpad_rows,
 };
 mod initialization;
 mod performance;
 mod render_loop;
 mod render_queue;
+mod x_axis;
 
 pub use render_queue::{enqueue_render_task, init_render_queue};
-
-#[allow(invalid_value)]
-pub fn dummy_renderer() -> WebGpuRenderer {
-    use std::collections::VecDeque;
-    unsafe {
-        WebGpuRenderer {
-            _canvas_id: String::new(),
-            width: 800,
-            height: 600,
-            surface: std::mem::MaybeUninit::zeroed().assume_init(),
-            device: std::mem::MaybeUninit::zeroed().assume_init(),
-            queue: std::mem::MaybeUninit::zeroed().assume_init(),
-            config: std::mem::MaybeUninit::zeroed().assume_init(),
-            render_pipeline: std::mem::MaybeUninit::zeroed().assume_init(),
-            vertex_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-            uniform_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-            uniform_bind_group: std::mem::MaybeUninit::zeroed().assume_init(),
-            msaa_texture: std::mem::MaybeUninit::zeroed().assume_init(),
-            msaa_view: std::mem::MaybeUninit::zeroed().assume_init(),
-            template_vertices: 0,
-            cached_vertices: Vec::new(),
-            cached_uniforms: ChartUniforms::new(),
-            cached_candle_count: 0,
-            cached_zoom_level: 1.0,
-            cached_hash: 0,
-            cached_data_hash: 0,
-            cached_line_visibility: LineVisibility::default(),
-            zoom_level: 1.0,
-            pan_offset: 0.0,
-            last_frame_time: 0.0,
-            fps_log: VecDeque::new(),
-            line_visibility: LineVisibility::default(),
-        }
-    }
-}
+pub use x_axis::{XAxis, XAxisMode};
@@ -7,6 +7,8 @@ impl WebGpuRenderer {
         let performance = window.performance().expect("performance not available");
         let start = performance.now();
         for _ in 0..num_frames {
+            // Present every frame; unchanged geometry would otherwise be skipped
+            self.mark_dirty();
             let _ = self.render(chart);
         }
         let end = performance.now();
@@ -1,111 +1,139 @@
 use super::*;
+use crate::domain::chart::value_objects::ChartType;
 use crate::domain::logging::LogComponent;
 use crate::domain::market_data::TimeInterval;
 use crate::log_info;
 use leptos::{SignalGetUntracked, SignalSet};
 use serde_json;
-use std::hash::{Hash, Hasher};
 
 impl WebGpuRenderer {
-    fn geometry_hash(
-        vertices: &[CandleVertex],
-        instances: &[CandleInstance],
-        uniforms: &ChartUniforms,
-    ) -> u64 {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        bytemuck::cast_slice::<CandleVertex, u8>(vertices).hash(&mut hasher);
-        bytemuck::cast_slice::<CandleInstance, u8>(instances).hash(&mut hasher);
-        bytemuck::bytes_of(uniforms).hash(&mut hasher);
-        hasher.finish()
+    /// Upload the builder's cached geometry, growing the GPU buffers as needed
+    fn write_buffers(&mut self) {
+        let geometry = &self.geometry;
+        let required_size =
+            (std::mem::size_of::<CandleVertex>() * geometry.vertex_buffer_stats.capacity) as u64;
+        // Reuse the persistent buffer unless the capacity had to grow
+        if self.vertex_buffer.size() < required_size {
+            log_info!(
+                LogComponent::Infrastructure("WebGpuRenderer"),
+                "🧮 Vertex buffer reallocated: {} vertices ({} bytes, #{})",
+                geometry.vertex_buffer_stats.capacity,
+                required_size,
+                geometry.vertex_buffer_stats.realloc_count
+            );
+            self.vertex_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Vertex Buffer"),
+                size: required_size,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+        }
+        let required_instance_size = (std::mem::size_of::<CandleInstance>()
+            * geometry.instance_buffer_stats.capacity) as u64;
+        if self.instance_buffer.size() < required_instance_size {
+            log_info!(
+                LogComponent::Infrastructure("WebGpuRenderer"),
+                "🕯️ Instance buffer reallocated: {} instances ({} bytes, #{})",
+                geometry.instance_buffer_stats.capacity,
+                required_instance_size,
+                geometry.instance_buffer_stats.realloc_count
+            );
+            self.instance_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Candle Instance Buffer"),
+                size: required_instance_size,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+        }
+        let vertex_bytes = bytemuck::cast_slice(&geometry.cached_vertices);
+        let uniform_copy = geometry.cached_uniforms;
+        let uniform_bytes = bytemuck::bytes_of(&uniform_copy);
+        self.queue.write_buffer(&self.vertex_buffer, 0, vertex_bytes);
+        if !geometry.cached_instances.is_empty() {
+            let instance_bytes = bytemuck::cast_slice(&geometry.cached_instances);
+            self.queue.write_buffer(&self.instance_buffer, 0, instance_bytes);
+        }
+        self.queue.write_buffer(&self.uniform_buffer, 0, uniform_bytes);
     }
 
-    pub fn data_hash(chart: &Chart, _zoom: f64) -> u64 {
-        let candles = chart
-            .get_series(crate::app::current_interval().get_untracked())
-            .or_else(|| chart.get_series(TimeInterval::TwoSeconds))
-            .expect("base series not found")
-            .get_candles();
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        candles.len().hash(&mut hasher);
-        for c in candles {
-            c.timestamp.value().hash(&mut hasher);
-            c.ohlcv.open.value().to_bits().hash(&mut hasher);
-            c.ohlcv.high.value().to_bits().hash(&mut hasher);
-            c.ohlcv.low.value().to_bits().hash(&mut hasher);
-            c.ohlcv.close.value().to_bits().hash(&mut hasher);
-            c.ohlcv.volume.value().to_bits().hash(&mut hasher);
-        }
-        hasher.finish()
+    /// 📐 Geometry settings and caches used for the next frame
+    pub fn geometry(&self) -> &ChartGeometryBuilder {
+        &self.geometry
     }
 
-    fn update_cached_geometry(
-        &mut self,
-        vertices: Vec<CandleVertex>,
-        instances: Vec<CandleInstance>,
-        uniforms: ChartUniforms,
-    ) -> bool {
-        let new_hash = Self::geometry_hash(&vertices, &instances, &uniforms);
-        if new_hash == self.cached_hash {
-            return false;
-        }
+    /// 💰 NDC height of the current price line, for overlays placed over the canvas
+    pub fn current_price_y_ndc(&self) -> Option<f32> {
+        self.geometry.current_price_y_ndc()
+    }
 
-        self.cached_vertices = vertices;
-        self.cached_uniforms = uniforms;
-        self.cached_hash = new_hash;
-        self.cached_line_visibility = self.line_visibility.clone();
-        self.template_vertices = self.cached_vertices.len() as u32;
+    /// Id of the canvas this renderer draws into
+    pub fn canvas_id(&self) -> &str {
+        &self.canvas_id
+    }
 
-        #[cfg(not(test))]
-        self.write_buffers();
+    /// 💥 Whether the GPU device is gone and this renderer must be replaced
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::SeqCst)
+    }
 
-        true
+    /// 💥 Take over the geometry builder of a renderer whose device was lost
+    ///
+    /// Zoom, pan, theme, settings and cached data carry over; the next
+    /// `render` uploads everything to the new device.
+    pub fn adopt_geometry(&mut self, lost: &mut WebGpuRenderer) {
+        std::mem::swap(&mut self.geometry, &mut lost.geometry);
+        self.geometry.invalidate();
+        self.needs_redraw = true;
     }
 
-    #[cfg(not(test))]
-    fn write_buffers(&self) {
-        let vertex_bytes = bytemuck::cast_slice(&self.cached_vertices);
-        let uniform_copy = self.cached_uniforms;
-        let uniform_bytes = bytemuck::bytes_of(&uniform_copy);
-        self.queue.write_buffer(&self.vertex_buffer, 0, vertex_bytes);
-        self.queue.write_buffer(&self.uniform_buffer, 0, uniform_bytes);
+    /// 📐 Change geometry settings; takes effect on the next `render`
+    pub fn geometry_mut(&mut self) -> &mut ChartGeometryBuilder {
+        &mut self.geometry
     }
 
-    pub fn cache_geometry_for_test(&mut self, chart: &Chart) {
-        let (inst, verts, uni) = self.create_geometry(chart);
-        self.update_cached_geometry(verts, inst, uni);
-        self.cached_data_hash = Self::data_hash(chart, self.zoom_level);
+    /// ⏮️ Default zoom, no pan and prices fitted to the visible candles
+    pub fn reset_view(&mut self) {
+        self.geometry.reset_view();
+        self.needs_redraw = true;
     }
 
-    pub fn cached_hash_for_test(&self) -> u64 {
-        self.cached_hash
+    /// 🚩 Force the next `render` to present even if the geometry is unchanged
+    pub fn mark_dirty(&mut self) {
+        self.needs_redraw = true;
     }
 
-    pub fn create_geometry_for_test(
-        &self,
-        chart: &Chart,
-    ) -> (Vec<CandleInstance>, Vec<CandleVertex>, ChartUniforms) {
-        self.create_geometry(chart)
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw
     }
 
-    pub fn render(&mut self, chart: &Chart) -> Result<(), JsValue> {
-        // ⏱️ Measure frame time
-        if let Some(window) = web_sys::window() {
-            if let Some(perf) = window.performance() {
-                let now = perf.now();
-                if self.last_frame_time > 0.0 {
-                    let delta = now - self.last_frame_time;
-                    if delta > 0.0 {
-                        let fps = 1000.0 / delta;
-                        self.fps_log.push_back(fps);
-                        if self.fps_log.len() > 60 {
-                            self.fps_log.pop_front();
-                        }
-                    }
-                }
-                self.last_frame_time = now;
-            }
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_stats
+    }
+
+    pub fn average_fps(&self) -> f64 {
+        self.fps.average_fps()
+    }
+
+    pub fn last_frame_ms(&self) -> f64 {
+        self.fps.last_frame_ms()
+    }
+
+    pub fn frame_timing(&self) -> FrameTiming {
+        self.fps.timing()
+    }
+
+    /// ⏱️ Record the interval since the previous presented frame
+    fn record_frame_time(&mut self) {
+        if let Some(perf) = web_sys::window().and_then(|w| w.performance()) {
+            self.fps.record(perf.now());
         }
+    }
 
+    pub fn render(&mut self, chart: &Chart) -> Result<(), ChartError> {
+        // Handles of a lost device are invalid until the app replaces this renderer
+        if self.is_device_lost() {
+            return Ok(());
+        }
         use crate::app::current_interval;
         let interval = current_interval().get_untracked();
         let candle_count =
@@ -126,76 +154,65 @@ impl WebGpuRenderer {
             );
         }
 
-        if candle_count == 0 {
-            return Ok(());
+        // 🕳️ An empty main chart still draws its loading or no-data placeholder
+        let changed = match candle_count {
+            0 if chart.chart_type == ChartType::Sparkline => return Ok(()),
+            0 => Some(self.geometry.update_placeholder()),
+            _ => self.geometry.update(chart),
+        };
+        match changed {
+            None => return Ok(()),
+            Some(true) => {
+                self.write_buffers();
+                self.needs_redraw = true;
+                // Minimap sparklines have their own renderer and no overlays
+                if chart.chart_type != ChartType::Sparkline {
+                    crate::app::publish_frame_overlays(&self.geometry, chart);
+                }
+            }
+            Some(false) => {}
         }
 
-        let data_hash = Self::data_hash(chart, self.zoom_level);
-        let data_changed = data_hash != self.cached_data_hash;
-        let visibility_changed = self.line_visibility != self.cached_line_visibility;
-
-        let geometry_needs_update = candle_count != self.cached_candle_count
-            || (self.zoom_level - self.cached_zoom_level).abs() > f64::EPSILON;
-
-        if geometry_needs_update || data_changed || visibility_changed {
-            let (instances, vertices, uniforms) = self.create_geometry(chart);
-            if instances.is_empty() {
-                return Ok(());
-            }
-            self.cached_candle_count = candle_count;
-            self.cached_zoom_level = self.zoom_level;
-            self.cached_data_hash = data_hash;
-            self.update_cached_geometry(vertices, instances, uniforms);
+        // 🎞️ Keep drawing frames until the running transition settles
+        if self.geometry.is_animating() {
+            crate::app::schedule_frame_render();
         }
 
-        // Skip empty check for simple shader - we don't use instances
-        if self.cached_vertices.is_empty() {
+        // 🚩 Identical geometry is already on screen
+        if !self.needs_redraw {
+            self.frame_stats.skipped += 1;
             return Ok(());
         }
-
-        let num_vertices = self.template_vertices;
+        self.record_frame_time();
 
         // Get surface texture and start rendering
-        let output = self.surface.get_current_texture().map_err(|e| {
-            let error_msg = format!("Failed to get surface texture: {:?}", e);
-            get_logger().error(LogComponent::Infrastructure("WebGpuRenderer"), &error_msg);
-            JsValue::from_str(&error_msg)
-        })?;
+        let output = match self.surface.get_current_texture() {
+            Ok(output) => output,
+            // A lost or stale surface is reconfigured and drawn on the next frame
+            Err(e @ (wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated)) => {
+                get_logger().warn(
+                    LogComponent::Infrastructure("WebGpuRenderer"),
+                    &format!("⚠️ Surface {e:?}, reconfiguring"),
+                );
+                self.surface.configure(&self.device, &self.config);
+                crate::app::schedule_frame_render();
+                return Ok(());
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to get surface texture: {:?}", e);
+                get_logger().error(LogComponent::Infrastructure("WebGpuRenderer"), &error_msg);
+                return Err(ChartError::Gpu(error_msg));
+            }
+        };
 
         let surface_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         let start_pass = web_sys::window().and_then(|w| w.performance()).map(|p| p.now());
-
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_view,
-                    resolve_target: Some(&surface_view),
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.145,
-                            g: 0.196,
-                            b: 0.259,
-                            a: 1.0, // Chart background color
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
-
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.draw(0..num_vertices, 0..1);
-        }
+        self.encode_chart_pass(&mut encoder, &surface_view);
 
         self.queue.submit(std::iter::once(encoder.finish()));
 
@@ -214,24 +231,126 @@ impl WebGpuRenderer {
         }
 
         output.present();
+        self.needs_redraw = false;
+        self.frame_stats.presented += 1;
+
+        // Minimap sparklines have their own renderer and stay out of the header readout
+        if chart.chart_type != ChartType::Sparkline {
+            crate::app::frame_timing().set(self.frame_timing());
+        }
+
+        // 🎞️ Presented rate vs. redundant renders avoided, once per 60 frames
+        if self.frame_stats.presented.is_multiple_of(60) {
+            log_info!(
+                LogComponent::Infrastructure("WebGpuRenderer"),
+                "🎞️ {:.1} fps presented, {} redundant renders skipped",
+                self.average_fps(),
+                self.frame_stats.skipped
+            );
+        }
 
         Ok(())
     }
 
-    /// Get renderer performance information
-    pub fn get_performance_info(&self) -> String {
-        let avg_fps = if self.fps_log.is_empty() {
-            0.0
-        } else {
-            self.fps_log.iter().sum::<f64>() / self.fps_log.len() as f64
+    /// 🎨 Color attachment clearing to `clear` and ending up in `target`
+    ///
+    /// With MSAA on, the pass draws into the multisampled texture and resolves
+    /// into `target`; with a sample count of 1 it draws into `target` directly.
+    fn color_attachment<'a>(
+        &'a self,
+        target: &'a wgpu::TextureView,
+        clear: wgpu::Color,
+    ) -> wgpu::RenderPassColorAttachment<'a> {
+        let (view, resolve_target) = match self.sample_count {
+            1 => (target, None),
+            _ => (&self.msaa_view, Some(target)),
         };
+        wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations { load: wgpu::LoadOp::Clear(clear), store: wgpu::StoreOp::Store },
+        }
+    }
+
+    /// 🎨 Record the chart pass drawing the cached geometry into `target`
+    ///
+    /// `target` must match the surface size and format.
+    pub(super) fn encode_chart_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+    ) {
+        let num_vertices = self.geometry.template_vertices;
+        let overlay_start = self.geometry.overlay_vertex_start.min(num_vertices);
+        let num_instances = self.geometry.cached_instances.len() as u32;
+        let background = self.geometry.theme.background;
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Render Pass"),
+            color_attachments: &[Some(self.color_attachment(
+                target,
+                wgpu::Color {
+                    r: background[0] as f64,
+                    g: background[1] as f64,
+                    b: background[2] as f64,
+                    a: background[3] as f64,
+                },
+            ))],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        // Grid and volume under the candles
+        render_pass.draw(0..overlay_start, 0..1);
+
+        // 🕯️ One quad per candle: wicks first, bodies on top
+        if num_instances > 0 {
+            render_pass.set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
+            render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+            render_pass
+                .set_index_buffer(self.quad_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            let index_count = QuadVertex::INDICES.len() as u32;
+            render_pass.set_pipeline(&self.wick_pipeline);
+            render_pass.draw_indexed(0..index_count, 0, 0..num_instances);
+            render_pass.set_pipeline(&self.body_pipeline);
+            render_pass.draw_indexed(0..index_count, 0, 0..num_instances);
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        }
+
+        // Indicators, price lines and labels over the candles
+        render_pass.draw(overlay_start..num_vertices, 0..1);
+    }
 
+    /// Get renderer performance information
+    pub fn get_performance_info(&self) -> String {
         serde_json::json!({
             "backend": "WebGPU",
             "parallel": true,
             "status": "ready",
             "gpu_threads": "unlimited",
-            "avg_fps": avg_fps
+            "avg_fps": self.average_fps(),
+            "last_frame_ms": self.last_frame_ms(),
+            "frames": {
+                "presented": self.frame_stats.presented,
+                "skipped": self.frame_stats.skipped
+            },
+            "vertex_buffer": {
+                "capacity": self.geometry.vertex_buffer_stats.capacity,
+                "realloc_count": self.geometry.vertex_buffer_stats.realloc_count,
+                "last_vertices": self.geometry.vertex_buffer_stats.last_vertex_count,
+                "peak_vertices": self.geometry.vertex_buffer_stats.peak_vertex_count
+            },
+            "instance_buffer": {
+                "capacity": self.geometry.instance_buffer_stats.capacity,
+                "realloc_count": self.geometry.instance_buffer_stats.realloc_count,
+                "last_instances": self.geometry.instance_buffer_stats.last_vertex_count
+            }
         })
         .to_string()
     }
@@ -260,71 +379,8 @@ impl WebGpuRenderer {
         }
     }
 
-    /// Toggle indicator line visibility
-    pub fn toggle_line_visibility(&mut self, line_name: &str) {
-        let state = match line_name {
-            "sma20" => {
-                self.line_visibility.sma_20 = !self.line_visibility.sma_20;
-                Some(self.line_visibility.sma_20)
-            }
-            "sma50" => {
-                self.line_visibility.sma_50 = !self.line_visibility.sma_50;
-                Some(self.line_visibility.sma_50)
-            }
-            "sma200" => {
-                self.line_visibility.sma_200 = !self.line_visibility.sma_200;
-                Some(self.line_visibility.sma_200)
-            }
-            "ema12" => {
-                self.line_visibility.ema_12 = !self.line_visibility.ema_12;
-                Some(self.line_visibility.ema_12)
-            }
-            "ema26" => {
-                self.line_visibility.ema_26 = !self.line_visibility.ema_26;
-                Some(self.line_visibility.ema_26)
-            }
-            _ => None,
-        };
-
-        if let Some(state) = state {
-            log_info!(
-                LogComponent::Infrastructure("LegendToggle"),
-                "Line {} visible: {}",
-                line_name,
-                state
-            );
-        }
-        crate::app::global_line_visibility().set(self.line_visibility.clone());
-    }
-
-    pub fn line_visibility(&self) -> LineVisibility {
-        self.line_visibility.clone()
-    }
-
-    /// Check if the legend checkbox was clicked
-    pub fn check_legend_checkbox_click(&self, mouse_x: f32, mouse_y: f32) -> Option<String> {
-        const LEGEND_LEFT: f32 = 10.0;
-        const LEGEND_TOP: f32 = 10.0;
-        const BOX_SIZE: f32 = 20.0;
-        const BOX_GAP: f32 = 30.0;
-
-        let lines = ["sma20", "sma50", "sma200", "ema12", "ema26"];
-
-        for (i, name) in lines.iter().enumerate() {
-            let x0 = LEGEND_LEFT;
-            let y0 = LEGEND_TOP + i as f32 * BOX_GAP;
-            let x1 = x0 + BOX_SIZE;
-            let y1 = y0 + BOX_SIZE;
-            if mouse_x >= x0 && mouse_x <= x1 && mouse_y >= y0 && mouse_y <= y1 {
-                return Some((*name).to_string());
-            }
-        }
-
-        None
-    }
-
     /// Simplest test - clear the screen with a bright color (no geometry)
-    pub fn test_clear_only(&self) -> Result<(), JsValue> {
+    pub fn test_clear_only(&self) -> Result<(), ChartError> {
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
             "🌈 CLEAR-ONLY: Testing surface with bright yellow clear color...",
@@ -333,7 +389,7 @@ impl WebGpuRenderer {
         let output = self
             .surface
             .get_current_texture()
-            .map_err(|e| JsValue::from_str(&format!("Surface error: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Surface error: {:?}", e)))?;
 
         let surface_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -343,19 +399,15 @@ impl WebGpuRenderer {
         {
             let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Clear Only Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_view,
-                    resolve_target: Some(&surface_view),
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 1.0,
-                            g: 1.0,
-                            b: 0.0,
-                            a: 1.0, // bright yellow
-                        }),
-                        store: wgpu::StoreOp::Store,
+                color_attachments: &[Some(self.color_attachment(
+                    &surface_view,
+                    wgpu::Color {
+                        r: 1.0,
+                        g: 1.0,
+                        b: 0.0,
+                        a: 1.0, // bright yellow
                     },
-                })],
+                ))],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
                 timestamp_writes: None,
@@ -378,7 +430,7 @@ impl WebGpuRenderer {
     }
 
     /// Ultra-simple test - red rectangle with fixed shader color
-    pub fn test_simple_red_quad(&self) -> Result<(), JsValue> {
+    pub fn test_simple_red_quad(&self) -> Result<(), ChartError> {
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
             "🔴 ULTRA-SIMPLE: Drawing red quad with fixed shader color...",
@@ -436,7 +488,7 @@ impl WebGpuRenderer {
         let output = self
             .surface
             .get_current_texture()
-            .map_err(|e| JsValue::from_str(&format!("Surface error: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Surface error: {:?}", e)))?;
 
         let surface_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -446,19 +498,15 @@ impl WebGpuRenderer {
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Test Simple Quad Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_view,
-                    resolve_target: Some(&surface_view),
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.2,
-                            g: 0.0,
-                            b: 0.5,
-                            a: 1.0, // purple background for contrast
-                        }),
-                        store: wgpu::StoreOp::Store,
+                color_attachments: &[Some(self.color_attachment(
+                    &surface_view,
+                    wgpu::Color {
+                        r: 0.2,
+                        g: 0.0,
+                        b: 0.5,
+                        a: 1.0, // purple background for contrast
                     },
-                })],
+                ))],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
                 timestamp_writes: None,
@@ -485,7 +533,7 @@ impl WebGpuRenderer {
     }
 
     /// Simple test - draw a large rectangle in the center
-    pub fn test_big_rectangle(&self) -> Result<(), JsValue> {
+    pub fn test_big_rectangle(&self) -> Result<(), ChartError> {
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
             "🟩 TESTING: Drawing big green rectangle in center...",
@@ -518,7 +566,7 @@ impl WebGpuRenderer {
         let output = self
             .surface
             .get_current_texture()
-            .map_err(|e| JsValue::from_str(&format!("Surface error: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Surface error: {:?}", e)))?;
 
         let surface_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -528,19 +576,15 @@ impl WebGpuRenderer {
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Test Rectangle Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_view,
-                    resolve_target: Some(&surface_view),
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.3,
-                            a: 1.0, // dark blue background
-                        }),
-                        store: wgpu::StoreOp::Store,
+                color_attachments: &[Some(self.color_attachment(
+                    &surface_view,
+                    wgpu::Color {
+                        r: 0.1,
+                        g: 0.1,
+                        b: 0.3,
+                        a: 1.0, // dark blue background
                     },
-                })],
+                ))],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
                 timestamp_writes: None,
@@ -569,7 +613,7 @@ impl WebGpuRenderer {
     }
 
     /// Basic rendering test - draws a red triangle
-    pub fn test_basic_triangle(&self) -> Result<(), JsValue> {
+    pub fn test_basic_triangle(&self) -> Result<(), ChartError> {
         get_logger().info(
             LogComponent::Infrastructure("WebGpuRenderer"),
             "🔴 TESTING: Drawing basic red triangle...",
@@ -597,7 +641,7 @@ impl WebGpuRenderer {
         let output = self
             .surface
             .get_current_texture()
-            .map_err(|e| JsValue::from_str(&format!("Surface error: {:?}", e)))?;
+            .map_err(|e| ChartError::Gpu(format!("Surface error: {:?}", e)))?;
 
         let surface_view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
         let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -607,19 +651,15 @@ impl WebGpuRenderer {
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Test Triangle Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.msaa_view,
-                    resolve_target: Some(&surface_view),
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.0,
-                            g: 0.0,
-                            b: 0.3,
-                            a: 1.0, // dark blue background
-                        }),
-                        store: wgpu::StoreOp::Store,
+                color_attachments: &[Some(self.color_attachment(
+                    &surface_view,
+                    wgpu::Color {
+                        r: 0.0,
+                        g: 0.0,
+                        b: 0.3,
+                        a: 1.0, // dark blue background
                     },
-                })],
+                ))],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
                 timestamp_writes: None,
@@ -652,220 +692,23 @@ impl WebGpuRenderer {
 mod tests {
     use super::*;
 
-    #[allow(invalid_value)]
-    fn dummy_renderer() -> WebGpuRenderer {
-        unsafe {
-            WebGpuRenderer {
-                _canvas_id: String::new(),
-                width: 0,
-                height: 0,
-                surface: std::mem::MaybeUninit::zeroed().assume_init(),
-                device: std::mem::MaybeUninit::zeroed().assume_init(),
-                queue: std::mem::MaybeUninit::zeroed().assume_init(),
-                config: std::mem::MaybeUninit::zeroed().assume_init(),
-                render_pipeline: std::mem::MaybeUninit::zeroed().assume_init(),
-                vertex_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-                uniform_buffer: std::mem::MaybeUninit::zeroed().assume_init(),
-                uniform_bind_group: std::mem::MaybeUninit::zeroed().assume_init(),
-                msaa_texture: std::mem::MaybeUninit::zeroed().assume_init(),
-                msaa_view: std::mem::MaybeUninit::zeroed().assume_init(),
-                template_vertices: 0,
-                cached_vertices: Vec::new(),
-                cached_uniforms: ChartUniforms::new(),
-                cached_candle_count: 0,
-                cached_zoom_level: 1.0,
-                cached_hash: 0,
-                cached_data_hash: 0,
-                cached_line_visibility: LineVisibility::default(),
-                zoom_level: 1.0,
-                pan_offset: 0.0,
-                last_frame_time: 0.0,
-                fps_log: VecDeque::new(),
-                line_visibility: LineVisibility::default(),
-            }
-        }
-    }
-
-    #[test]
-    fn toggles_visibility() {
-        let mut r = dummy_renderer();
-        assert!(r.line_visibility.sma_20);
-        r.toggle_line_visibility("sma20");
-        assert!(!r.line_visibility.sma_20);
-    }
-
-    #[test]
-    fn visibility_signal_updates() {
-        use crate::app::global_line_visibility;
-
-        global_line_visibility().set(LineVisibility::default());
-        let mut r = dummy_renderer();
-        r.toggle_line_visibility("sma20");
-        assert!(!global_line_visibility().get_untracked().sma_20);
-        r.toggle_line_visibility("sma20");
-        assert!(global_line_visibility().get_untracked().sma_20);
-    }
-
-    #[test]
-    fn legend_click_detection() {
-        let r = dummy_renderer();
-        assert_eq!(r.check_legend_checkbox_click(15.0, 15.0), Some("sma20".to_string()));
-        assert_eq!(r.check_legend_checkbox_click(100.0, 100.0), None);
-    }
-
     #[test]
     fn fps_ring_buffer() {
-        let mut r = dummy_renderer();
-        for i in 0..65 {
-            r.fps_log.push_back(i as f64);
-            if r.fps_log.len() > 60 {
-                r.fps_log.pop_front();
-            }
+        let mut fps = FpsCounter::default();
+        for i in 1..=66 {
+            fps.record(i as f64 * 10.0);
         }
-        assert_eq!(r.fps_log.len(), 60);
-        assert_eq!(r.fps_log.front().copied(), Some(5.0));
-    }
-
-    #[test]
-    fn no_buffer_reupload_when_unchanged() {
-        let mut r = dummy_renderer();
-        let verts = vec![CandleVertex::body_vertex(0.0, 0.0, true)];
-        let inst = vec![CandleInstance {
-            x: 0.0,
-            width: 0.1,
-            body_top: 0.5,
-            body_bottom: 0.0,
-            high: 0.6,
-            low: -0.1,
-            bullish: 1.0,
-            _padding: 0.0,
-        }];
-        let uniforms = ChartUniforms::default();
-        assert!(r.update_cached_geometry(verts.clone(), inst.clone(), uniforms));
-        let cached = r.cached_hash;
-        assert!(!r.update_cached_geometry(verts, inst, ChartUniforms::default()));
-        assert_eq!(r.cached_hash, cached);
+        assert_eq!(fps.fps_log.len(), 60);
+        assert_eq!(fps.fps_log.front().copied(), Some(100.0));
     }
 
     #[test]
-    fn instance_count_matches_instances() {
-        let mut r = dummy_renderer();
-        let verts = vec![CandleVertex::body_vertex(0.0, 0.0, true)];
-        let inst = vec![
-            CandleInstance {
-                x: 0.0,
-                width: 0.1,
-                body_top: 0.5,
-                body_bottom: 0.0,
-                high: 0.6,
-                low: -0.1,
-                bullish: 1.0,
-                _padding: 0.0,
-            },
-            CandleInstance {
-                x: 0.2,
-                width: 0.1,
-                body_top: 0.4,
-                body_bottom: -0.1,
-                high: 0.5,
-                low: -0.2,
-                bullish: 0.0,
-                _padding: 0.0,
-            },
-        ];
-        assert!(r.update_cached_geometry(verts, inst.clone(), ChartUniforms::default()));
-    }
+    fn frame_metrics_default_to_zero() {
+        let mut fps = FpsCounter::default();
+        assert_eq!(fps.timing(), FrameTiming::default());
 
-    #[test]
-    fn geometry_updates_on_data_change() {
-        use crate::domain::chart::{Chart, value_objects::ChartType};
-        use crate::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
-
-        let mut chart = Chart::new("t".to_string(), ChartType::Candlestick, 10);
-        chart.add_candle(Candle::new(
-            Timestamp::from_millis(0),
-            OHLCV::new(
-                Price::from(1.0),
-                Price::from(1.5),
-                Price::from(0.5),
-                Price::from(1.2),
-                Volume::from(1.0),
-            ),
-        ));
-        chart.add_candle(Candle::new(
-            Timestamp::from_millis(60_000),
-            OHLCV::new(
-                Price::from(1.2),
-                Price::from(1.7),
-                Price::from(0.8),
-                Price::from(1.4),
-                Volume::from(1.0),
-            ),
-        ));
-
-        let mut r = dummy_renderer();
-        let (inst, verts, uni) = r.create_geometry(&chart);
-        r.update_cached_geometry(verts, inst, uni);
-        r.cached_data_hash = WebGpuRenderer::data_hash(&chart, r.zoom_level);
-        let old = r.cached_hash;
-
-        chart.add_candle(Candle::new(
-            Timestamp::from_millis(60_000),
-            OHLCV::new(
-                Price::from(1.2),
-                Price::from(1.9),
-                Price::from(0.8),
-                Price::from(1.6),
-                Volume::from(1.0),
-            ),
-        ));
-
-        assert_eq!(chart.get_candle_count(), 2);
-        let new_hash = WebGpuRenderer::data_hash(&chart, r.zoom_level);
-        assert_ne!(new_hash, r.cached_data_hash);
-        let (inst2, verts2, uni2) = r.create_geometry(&chart);
-        assert!(r.update_cached_geometry(verts2, inst2, uni2));
-        r.cached_data_hash = new_hash;
-        assert_ne!(r.cached_hash, old);
-    }
-
-    #[test]
-    fn line_visibility_toggle_updates_geometry() {
-        use crate::domain::chart::{Chart, value_objects::ChartType};
-        use crate::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
-
-        let mut chart = Chart::new("t".to_string(), ChartType::Candlestick, 10);
-        chart.add_candle(Candle::new(
-            Timestamp::from_millis(0),
-            OHLCV::new(
-                Price::from(1.0),
-                Price::from(1.5),
-                Price::from(0.5),
-                Price::from(1.2),
-                Volume::from(1.0),
-            ),
-        ));
-        chart.add_candle(Candle::new(
-            Timestamp::from_millis(60_000),
-            OHLCV::new(
-                Price::from(1.2),
-                Price::from(1.7),
-                Price::from(0.8),
-                Price::from(1.4),
-                Volume::from(1.0),
-            ),
-        ));
-
-        let mut r = dummy_renderer();
-        let (inst, verts, uni) = r.create_geometry(&chart);
-        r.update_cached_geometry(verts.clone(), inst.clone(), uni);
-        r.cached_candle_count = chart.get_candle_count();
-        r.cached_zoom_level = r.zoom_level;
-        r.cached_data_hash = WebGpuRenderer::data_hash(&chart, r.zoom_level);
-        let cached = r.cached_hash;
-
-        r.toggle_line_visibility("sma20");
-        let _ = r.render(&chart);
-        assert_ne!(r.cached_hash, cached);
+        fps.fps_log.extend([50.0, 70.0, 40.0]);
+        assert_eq!(fps.average_fps(), 160.0 / 3.0);
+        assert_eq!(fps.last_frame_ms(), 25.0);
     }
 }
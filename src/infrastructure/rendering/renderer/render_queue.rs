@@ -1,7 +1,7 @@
 use super::{WebGpuRenderer, with_global_renderer};
 use futures::{
     StreamExt,
-    channel::mpsc::{UnboundedSender, unbounded},
+    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
 };
 use std::cell::RefCell;
 
@@ -32,22 +32,27 @@ pub fn init_render_queue() {
         if cell.borrow().is_some() {
             return;
         }
-        let (tx, mut rx) = unbounded::<RenderTask>();
+        let (tx, rx) = unbounded::<RenderTask>();
         *cell.borrow_mut() = Some(tx);
-        spawn_async(async move {
-            while let Some(task) = rx.next().await {
-                if with_global_renderer(|r| {
-                    task(r);
-                })
-                .is_none()
-                {
-                    // renderer not available
-                }
+        spawn_async(run_tasks(rx, |task| {
+            if with_global_renderer(|r| {
+                task(r);
+            })
+            .is_none()
+            {
+                // renderer not available
             }
-        });
+        }));
     });
 }
 
+/// Hand every task from `rx` to `run` in the order it was queued
+async fn run_tasks<T>(mut rx: UnboundedReceiver<T>, mut run: impl FnMut(T)) {
+    while let Some(task) = rx.next().await {
+        run(task);
+    }
+}
+
 pub fn enqueue_render_task(task: RenderTask) {
     RENDER_QUEUE.with(|cell| {
         if let Some(tx) = &*cell.borrow() {
@@ -59,22 +64,17 @@ pub fn enqueue_render_task(task: RenderTask) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::infrastructure::rendering::renderer::{dummy_renderer, set_global_renderer};
-    use std::cell::RefCell;
-    use std::rc::Rc;
 
     #[test]
     fn tasks_execute_in_order() {
-        init_render_queue();
-        let renderer = Rc::new(RefCell::new(dummy_renderer()));
-        set_global_renderer(renderer);
-
-        let result = Rc::new(RefCell::new(Vec::new()));
-        let r1 = result.clone();
-        enqueue_render_task(Box::new(move |_| r1.borrow_mut().push(1)));
-        let r2 = result.clone();
-        enqueue_render_task(Box::new(move |_| r2.borrow_mut().push(2)));
+        let (tx, rx) = unbounded::<u32>();
+        for task in [1, 2, 3] {
+            tx.unbounded_send(task).unwrap();
+        }
+        drop(tx);
 
-        assert_eq!(*result.borrow(), vec![1, 2]);
+        let mut result = Vec::new();
+        futures::executor::block_on(run_tasks(rx, |task| result.push(task)));
+        assert_eq!(result, vec![1, 2, 3]);
     }
 }
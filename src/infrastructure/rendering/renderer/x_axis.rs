@@ -0,0 +1,96 @@
+//! ↔️ Horizontal placement of the visible candles.
+//!
+//! `Ordinal` spaces candles by index, so missing candles collapse. `Time`
+//! gives every interval of the visible time range a slot of its own and
+//! leaves the slots of missing candles empty. Candles, volume bars, grid and
+//! indicators all read their x from the same [`XAxis`].
+
+use super::geometry::{EDGE_GAP, candle_step, candle_width_for, candle_x_position};
+use crate::domain::market_data::{Candle, TimeInterval};
+
+/// ↔️ How visible candles are spread along the x axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum XAxisMode {
+    /// Evenly by index; gaps in the data collapse
+    #[default]
+    Ordinal,
+    /// By open time within the visible range; gaps stay blank
+    Time,
+}
+
+/// ↔️ Visible candle index → NDC x for one frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct XAxis {
+    /// Slot of each visible candle; `None` when slots equal indices
+    slots: Option<Vec<usize>>,
+    len: usize,
+    slot_count: usize,
+}
+
+impl XAxis {
+    /// One slot per candle
+    pub fn ordinal(len: usize) -> Self {
+        Self { slots: None, len, slot_count: len }
+    }
+
+    /// Layout of `candles` (oldest first) in `mode`
+    pub fn new(mode: XAxisMode, candles: &[Candle], interval: TimeInterval) -> Self {
+        let Some(first) = candles.first().filter(|_| mode == XAxisMode::Time) else {
+            return Self::ordinal(candles.len());
+        };
+        let origin = first.timestamp.value();
+        let step = interval.duration_ms();
+        let mut slots: Vec<usize> = Vec::with_capacity(candles.len());
+        for candle in candles {
+            let slot = (candle.timestamp.value().saturating_sub(origin) / step) as usize;
+            // Candles sharing a bucket still get distinct slots
+            let slot = slots.last().map_or(slot, |&prev| slot.max(prev + 1));
+            slots.push(slot);
+        }
+        let slot_count = slots.last().map_or(0, |last| last + 1);
+        Self { slots: Some(slots), len: candles.len(), slot_count }
+    }
+
+    /// Number of visible candles
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of interval slots across the chart; sizes bodies and volume bars
+    pub fn slot_count(&self) -> usize {
+        self.slot_count
+    }
+
+    /// Slot of visible candle `index`
+    pub fn slot(&self, index: usize) -> usize {
+        self.slots.as_ref().and_then(|s| s.get(index).copied()).unwrap_or(index)
+    }
+
+    /// NDC x of the center of visible candle `index`
+    pub fn x(&self, index: usize) -> f32 {
+        candle_x_position(self.slot(index), self.slot_count)
+    }
+
+    /// 🎯 Visible candle nearest to NDC `ndc_x`, clamped to the chart edges
+    ///
+    /// Inverse of [`XAxis::x`]; in time mode a pointer over a gap picks the
+    /// closer neighbour. `None` only when nothing is visible.
+    pub fn index_at(&self, ndc_x: f64) -> Option<usize> {
+        let last = self.len.checked_sub(1)?;
+        let slot_count = self.slot_count;
+        let step = candle_step(slot_count) as f64;
+        let half_width = candle_width_for(slot_count, None) as f64 / 2.0;
+        let slot = (slot_count - 1) as f64 - (1.0 - EDGE_GAP as f64 - half_width - ndc_x) / step;
+        let Some(slots) = &self.slots else {
+            return Some(slot.round().clamp(0.0, last as f64) as usize);
+        };
+        let after = slots.partition_point(|&s| (s as f64) < slot).min(last);
+        let before = after.saturating_sub(1);
+        let distance = |i: usize| (slots[i] as f64 - slot).abs();
+        Some(if distance(before) <= distance(after) { before } else { after })
+    }
+}
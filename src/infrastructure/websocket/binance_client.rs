@@ -1,20 +1,272 @@
 use crate::domain::{
+    errors::ChartError,
     logging::{LogComponent, get_logger},
     market_data::{
         entities::{Candle, OHLCV},
+        order_book::{DepthSync, DepthUpdate, OrderBook},
         value_objects::{Price, Symbol, TimeInterval, Timestamp, Volume},
     },
 };
+use crate::infrastructure::websocket::connection_state::ConnectionState;
+use crate::infrastructure::websocket::dto::BinanceExchangeInfo;
+use crate::infrastructure::websocket::rest::{
+    DEPTH_SNAPSHOT_WEIGHT, EXCHANGE_INFO_WEIGHT, FetchError, KLINES_WEIGHT, get_with_retry,
+};
+use crate::infrastructure::websocket::trade_aggregator::{Trade, TradeAggregator};
 use futures::StreamExt;
-use gloo_net::http::Request;
-use gloo_net::websocket::futures::WebSocket;
+use gloo_net::websocket::{Message, WebSocketError, futures::WebSocket};
 use serde::Deserialize;
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
+/// Delay before the first reconnect attempt
+const RECONNECT_BASE_DELAY_SECS: u64 = 1;
+/// Upper bound for the reconnect backoff
+const RECONNECT_MAX_DELAY_SECS: u64 = 30;
+
+/// Shortest silence treated as a stale connection
+const STALE_MIN_SECS: u64 = 30;
+/// Longest silence tolerated on a kline stream
+const STALE_MAX_SECS: u64 = 120;
+
+/// ⏰ Silence after which a socket reading `streams` counts as stale
+///
+/// Klines are pushed every few seconds while a candle forms, so twice the
+/// interval (30s to 120s) is generous; trade and depth streams allow 30s.
+/// Several streams on one socket use the shortest timeout.
+pub fn stale_timeout(streams: &[BinanceStream], interval: TimeInterval) -> Duration {
+    streams
+        .iter()
+        .map(|stream| match stream {
+            BinanceStream::Kline => Duration::from_millis(
+                interval
+                    .duration_ms()
+                    .saturating_mul(2)
+                    .clamp(STALE_MIN_SECS * 1000, STALE_MAX_SECS * 1000),
+            ),
+            BinanceStream::Trade | BinanceStream::Depth => Duration::from_secs(STALE_MIN_SECS),
+        })
+        .min()
+        .unwrap_or(Duration::from_secs(STALE_MIN_SECS))
+}
+
+/// ⏰ Next message of `stream`, or `None` once it closes or stays silent for `timeout`
+///
+/// A silent socket is dropped (and thereby closed) by the caller, which then
+/// reconnects. Binance pings are answered with pongs by the browser itself.
+async fn next_or_stale(
+    stream: &mut WebSocket,
+    timeout: Duration,
+) -> Option<Result<Message, WebSocketError>> {
+    use futures::future::{Either, select};
+    match select(stream.next(), gloo_timers::future::sleep(timeout)).await {
+        Either::Left((msg, _)) => msg,
+        Either::Right(_) => {
+            get_logger().warn(
+                LogComponent::Infrastructure("BinanceWS"),
+                &format!("⏰ No message for {}s, reconnecting stale stream", timeout.as_secs()),
+            );
+            None
+        }
+    }
+}
+
+/// ⏳ Exponential backoff before reconnect `attempt` (0-based): 1s, 2s, 4s… capped at 30s
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    Duration::from_secs(
+        RECONNECT_BASE_DELAY_SECS.saturating_mul(factor).min(RECONNECT_MAX_DELAY_SECS),
+    )
+}
+
+/// 🧹 Candle from raw exchange values, as the chart pipeline accepts them
+///
+/// NaN, infinite or negative values reject the candle; a high/low that does
+/// not cover open and close is widened. Both are logged as warnings.
+pub fn ingest_candle(
+    timestamp: u64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+) -> Result<Candle, ChartError> {
+    let ohlcv = OHLCV::validated(open, high, low, close, volume).inspect_err(|e| {
+        get_logger().warn(
+            LogComponent::Infrastructure("Ingest"),
+            &format!("⚠️ Rejected candle {timestamp}: {e}"),
+        )
+    })?;
+    if !ohlcv.is_valid() {
+        get_logger().warn(
+            LogComponent::Infrastructure("Ingest"),
+            &format!("⚠️ Repaired high/low of candle {timestamp}"),
+        );
+    }
+    Ok(Candle::new(Timestamp::new(timestamp), ohlcv.repaired()))
+}
+
+/// Largest number of klines Binance returns per REST request
+pub const MAX_BACKFILL_CANDLES: u64 = 1000;
+
+/// 🕳️ Whether candles are missing between `last_ts` and the next kline at `next_ts`
+pub fn has_gap(last_ts: u64, next_ts: u64, interval: TimeInterval) -> bool {
+    next_ts.saturating_sub(last_ts) > interval.duration_ms()
+}
+
+/// Number of klines to request to cover `last_ts..next_ts`, seam included
+pub fn backfill_limit(last_ts: u64, next_ts: u64, interval: TimeInterval) -> u32 {
+    let missing = next_ts.saturating_sub(last_ts) / interval.duration_ms().max(1);
+    (missing + 1).min(MAX_BACKFILL_CANDLES) as u32
+}
+
+/// 🧵 Backfilled candles to replay before the kline at `next_ts`
+///
+/// Keeps the range `last_ts..next_ts` in order with one candle per timestamp.
+/// The candle at `last_ts` is kept so its final values replace the one
+/// received before the disconnect.
+pub fn backfill_seam(last_ts: u64, next_ts: u64, candles: Vec<Candle>) -> Vec<Candle> {
+    sorted_unique_in(candles, last_ts..next_ts)
+}
+
+/// `candles` within `range`, ascending with the first of each timestamp kept
+fn sorted_unique_in(
+    mut candles: Vec<Candle>,
+    range: impl std::ops::RangeBounds<u64>,
+) -> Vec<Candle> {
+    candles.retain(|c| range.contains(&c.timestamp.value()));
+    candles.sort_by_key(|c| c.timestamp.value());
+    candles.dedup_by_key(|c| c.timestamp.value());
+    candles
+}
+
+/// Candles fetched before a paginated range load failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialRange {
+    /// Candles already loaded, ascending and deduplicated
+    pub candles: Vec<Candle>,
+    pub error: FetchError,
+}
+
+impl std::fmt::Display for PartialRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} after {} candles", self.error, self.candles.len())
+    }
+}
+
+impl std::error::Error for PartialRange {}
+
+/// Candles of `pages` within `start_time..=end_time`, strictly ascending by timestamp
+pub fn stitch_pages(pages: Vec<Candle>, start_time: u64, end_time: u64) -> Vec<Candle> {
+    sorted_unique_in(pages, start_time..=end_time)
+}
+
+/// 📚 Load `start_time..=end_time` by paging backwards with `fetch_before(end, limit)`
+///
+/// Stops once a page reaches `start_time` or comes back empty. A failed page
+/// returns the candles loaded so far together with its error.
+pub async fn paginate_range<F, Fut>(
+    start_time: u64,
+    end_time: u64,
+    mut fetch_before: F,
+) -> Result<Vec<Candle>, PartialRange>
+where
+    F: FnMut(u64, u32) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Candle>, FetchError>>,
+{
+    let mut pages = Vec::new();
+    let mut cursor = end_time;
+    while cursor >= start_time {
+        let page = match fetch_before(cursor, MAX_BACKFILL_CANDLES as u32).await {
+            Ok(page) => page,
+            Err(error) => {
+                let candles = stitch_pages(pages, start_time, end_time);
+                return Err(PartialRange { candles, error });
+            }
+        };
+        let Some(oldest) = page.iter().map(|c| c.timestamp.value()).min() else {
+            break;
+        };
+        pages.extend(page);
+        // Guard against pages that do not move backwards
+        if oldest == 0 || oldest > cursor {
+            break;
+        }
+        cursor = oldest - 1;
+    }
+    Ok(stitch_pages(pages, start_time, end_time))
+}
+
+/// 📡 Binance market stream a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinanceStream {
+    Kline,
+    Trade,
+    Depth,
+}
+
+impl BinanceStream {
+    /// Candle source for `interval`: its kline, or trades to aggregate when Binance has none
+    pub fn candles_for(interval: TimeInterval) -> Self {
+        if interval.is_binance_kline() { Self::Kline } else { Self::Trade }
+    }
+
+    /// Stream name for `symbol`, e.g. `btcusdt@kline_1m`
+    pub fn name(self, symbol: &Symbol, interval: TimeInterval) -> String {
+        let symbol = symbol.value().to_lowercase();
+        match self {
+            Self::Kline => format!("{symbol}@kline_{}", interval.to_binance_str()),
+            Self::Trade => format!("{symbol}@trade"),
+            Self::Depth => format!("{symbol}@depth@100ms"),
+        }
+    }
+
+    /// Kind of the stream called `name`, as found in a combined message's `stream` key
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (_, kind) = name.split_once('@')?;
+        if kind.starts_with("kline_") {
+            Some(Self::Kline)
+        } else if kind == "trade" {
+            Some(Self::Trade)
+        } else if kind.starts_with("depth") {
+            Some(Self::Depth)
+        } else {
+            None
+        }
+    }
+}
+
+/// 🔗 Socket URL for `names`: the raw stream for one, the combined endpoint for several
+pub fn stream_url(names: &[String]) -> String {
+    match names {
+        [name] => format!("wss://stream.binance.com:9443/ws/{name}"),
+        _ => format!("wss://stream.binance.com:9443/stream?streams={}", names.join("/")),
+    }
+}
+
+/// Message of a subscribed stream, parsed according to its kind
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    Candle(Candle),
+    Trade(Trade),
+    Depth(DepthUpdate),
+}
+
 /// Binance WebSocket client based on gloo
 pub struct BinanceWebSocketClient {
     symbol: Symbol,
     interval: TimeInterval,
+    /// Reconnect attempts in a row before giving up; `None` retries forever
+    max_retries: Option<u32>,
+    /// Streams read over one connection; more than one uses the combined endpoint
+    streams: Vec<BinanceStream>,
+}
+
+/// Wrapper the combined endpoint puts around every event
+#[derive(Debug, Deserialize)]
+struct CombinedStreamMessage {
+    stream: String,
+    data: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +291,69 @@ struct KlineInfo {
     volume: String,
 }
 
+/// Trade event of the `@trade` stream
+#[derive(Debug, Deserialize)]
+struct BinanceTradeData {
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    trade_time: u64,
+}
+
+/// Diff event of the `@depth` stream
+#[derive(Debug, Deserialize)]
+struct BinanceDepthUpdate {
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    final_update_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    asks: Vec<[String; 2]>,
+}
+
+/// Response of `/api/v3/depth`
+#[derive(Debug, Deserialize)]
+struct BinanceDepthSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+/// Levels requested for the order book snapshot
+const DEPTH_SNAPSHOT_LIMIT: u32 = 1000;
+
+fn parse_levels(levels: &[[String; 2]]) -> Result<Vec<(f64, f64)>, ChartError> {
+    levels
+        .iter()
+        .map(|[price, quantity]| {
+            let price = price
+                .parse::<f64>()
+                .map_err(|_| ChartError::Parse("Invalid level price".to_string()))?;
+            let quantity = quantity
+                .parse::<f64>()
+                .map_err(|_| ChartError::Parse("Invalid level quantity".to_string()))?;
+            Ok((price, quantity))
+        })
+        .collect()
+}
+
+/// 📚 Order book from a `/api/v3/depth` response, bounded to `max_levels` per side
+pub fn parse_depth_snapshot(data: &str, max_levels: usize) -> Result<OrderBook, ChartError> {
+    let snapshot: BinanceDepthSnapshot = serde_json::from_str(data)
+        .map_err(|e| ChartError::Parse(format!("Failed to parse depth snapshot: {e}")))?;
+    Ok(OrderBook::from_snapshot(
+        snapshot.last_update_id,
+        &parse_levels(&snapshot.bids)?,
+        &parse_levels(&snapshot.asks)?,
+        max_levels,
+    ))
+}
+
 /// Structure for historical Binance Klines API data
 #[derive(Debug, Deserialize)]
 struct BinanceHistoricalKline(
@@ -56,25 +371,86 @@ struct BinanceHistoricalKline(
     serde::de::IgnoredAny, // Ignore
 );
 
+/// State of one `run_stream` connection, shared by its event handlers
+struct StreamSession<'a, F, S, D> {
+    handler: &'a mut F,
+    attempt: &'a mut u32,
+    on_status: &'a mut S,
+    /// Whether `Live` was reported for this connection
+    live: bool,
+    last_timestamp: &'a mut Option<u64>,
+    aggregator: Option<&'a mut TradeAggregator>,
+    /// Order book rebuilt from a fresh snapshot after each reconnect
+    book: Option<OrderBook>,
+    max_levels: usize,
+    on_depth: &'a mut D,
+}
+
+impl BinanceHistoricalKline {
+    fn to_candle(&self) -> Result<Candle, ChartError> {
+        kline_to_candle(self.0, &self.1, &self.2, &self.3, &self.4, &self.5)
+    }
+}
+
 impl BinanceWebSocketClient {
     pub fn new(symbol: Symbol, interval: TimeInterval) -> Self {
-        Self { symbol, interval }
+        Self::with_max_retries(symbol, interval, None)
+    }
+
+    /// Client that stops reconnecting after `max_retries` failed attempts in a row
+    pub fn with_max_retries(
+        symbol: Symbol,
+        interval: TimeInterval,
+        max_retries: Option<u32>,
+    ) -> Self {
+        let streams = vec![BinanceStream::candles_for(interval)];
+        Self { symbol, interval, max_retries, streams }
+    }
+
+    pub fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+
+    /// 📡 Also read `stream` over the connection; several streams share one socket
+    pub fn subscribe(&mut self, stream: BinanceStream) {
+        if !self.streams.contains(&stream) {
+            self.streams.push(stream);
+        }
+    }
+
+    /// Subscribed streams, the candle source first
+    pub fn streams(&self) -> &[BinanceStream] {
+        &self.streams
+    }
+
+    /// Names of the subscribed streams, e.g. `btcusdt@kline_1m`
+    pub fn stream_names(&self) -> Vec<String> {
+        self.streams.iter().map(|s| s.name(&self.symbol, self.interval)).collect()
     }
 
     /// Connect to the Binance WebSocket stream
-    pub async fn connect(&mut self) -> Result<WebSocket, String> {
-        let symbol_lower = self.symbol.value().to_lowercase();
-        let interval_str = self.interval.to_binance_str();
+    ///
+    /// Intervals without a kline read individual trades; several subscribed
+    /// streams are opened as one combined stream.
+    pub async fn connect(&mut self) -> Result<WebSocket, ChartError> {
+        Self::open_streams(&self.stream_names())
+    }
+
+    fn open_stream(stream_name: &str) -> Result<WebSocket, ChartError> {
+        Self::open_streams(&[stream_name.to_string()])
+    }
 
-        let stream_name = format!("{symbol_lower}@kline_{interval_str}");
-        let url = format!("wss://stream.binance.com:9443/ws/{stream_name}");
+    fn open_streams(names: &[String]) -> Result<WebSocket, ChartError> {
+        let url = stream_url(names);
+        let stream_name = names.join("/");
 
         get_logger().info(
             LogComponent::Infrastructure("BinanceWS"),
             &format!("🔌 Connecting to Binance: {url}"),
         );
 
-        let ws = WebSocket::open(&url).map_err(|e| format!("Failed to open WebSocket: {e:?}"))?;
+        let ws = WebSocket::open(&url)
+            .map_err(|e| ChartError::Network(format!("Failed to open WebSocket: {e:?}")))?;
 
         get_logger().info(
             LogComponent::Infrastructure("BinanceWS"),
@@ -85,136 +461,454 @@ impl BinanceWebSocketClient {
     }
 
     /// Handle a message from Binance
-    pub fn parse_message(&self, data: &str) -> Result<Candle, String> {
+    pub fn parse_message(&self, data: &str) -> Result<Candle, ChartError> {
         let kline_data: BinanceKlineData = serde_json::from_str(data)
-            .map_err(|e| format!("Failed to parse Binance message: {e}"))?;
+            .map_err(|e| ChartError::Parse(format!("Failed to parse Binance message: {e}")))?;
 
         let kline = &kline_data.kline;
+        kline_to_candle(
+            kline.open_time,
+            &kline.open,
+            &kline.high,
+            &kline.low,
+            &kline.close,
+            &kline.volume,
+        )
+    }
+
+    /// 📡 Parse a message of the connection opened by `connect`
+    ///
+    /// A single stream sends bare events. The combined endpoint wraps each in
+    /// `{"stream": …, "data": …}` and the `stream` key picks the parser.
+    pub fn parse_event(&self, data: &str) -> Result<StreamEvent, ChartError> {
+        if let [only] = self.streams.as_slice() {
+            return self.parse_stream_event(*only, data);
+        }
+        let message: CombinedStreamMessage = serde_json::from_str(data)
+            .map_err(|e| ChartError::Parse(format!("Failed to parse combined message: {e}")))?;
+        let kind = BinanceStream::from_name(&message.stream)
+            .ok_or_else(|| ChartError::Parse(format!("Unknown stream: {}", message.stream)))?;
+        self.parse_stream_event(kind, &message.data.to_string())
+    }
 
-        // Parse prices
-        let open = kline.open.parse::<f64>().map_err(|_| "Invalid open price")?;
-        let high = kline.high.parse::<f64>().map_err(|_| "Invalid high price")?;
-        let low = kline.low.parse::<f64>().map_err(|_| "Invalid low price")?;
-        let close = kline.close.parse::<f64>().map_err(|_| "Invalid close price")?;
-        let volume = kline.volume.parse::<f64>().map_err(|_| "Invalid volume")?;
-
-        // Create OHLCV
-        let ohlcv = OHLCV::new(
-            Price::new(open),
-            Price::new(high),
-            Price::new(low),
-            Price::new(close),
-            Volume::new(volume),
+    fn parse_stream_event(
+        &self,
+        kind: BinanceStream,
+        data: &str,
+    ) -> Result<StreamEvent, ChartError> {
+        match kind {
+            BinanceStream::Kline => self.parse_message(data).map(StreamEvent::Candle),
+            BinanceStream::Trade => self.parse_trade(data).map(StreamEvent::Trade),
+            BinanceStream::Depth => self.parse_depth_update(data).map(StreamEvent::Depth),
+        }
+    }
+
+    /// Parse a `@trade` stream event
+    pub fn parse_trade(&self, data: &str) -> Result<Trade, ChartError> {
+        let trade: BinanceTradeData = serde_json::from_str(data)
+            .map_err(|e| ChartError::Parse(format!("Failed to parse Binance trade: {e}")))?;
+        let price = trade
+            .price
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid trade price".to_string()))?;
+        let quantity = trade
+            .quantity
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid trade quantity".to_string()))?;
+        let price = Price::validate(price)?.value();
+        let quantity = Volume::validate(quantity)?.value();
+        Ok(Trade { price, quantity, timestamp: trade.trade_time })
+    }
+
+    /// Parse a `@depth` stream diff event
+    pub fn parse_depth_update(&self, data: &str) -> Result<DepthUpdate, ChartError> {
+        let update: BinanceDepthUpdate = serde_json::from_str(data)
+            .map_err(|e| ChartError::Parse(format!("Failed to parse depth update: {e}")))?;
+        Ok(DepthUpdate {
+            first_update_id: update.first_update_id,
+            final_update_id: update.final_update_id,
+            bids: parse_levels(&update.bids)?,
+            asks: parse_levels(&update.asks)?,
+        })
+    }
+
+    /// 📚 Fetch the order book snapshot bounded to `max_levels` per side
+    pub async fn fetch_depth_snapshot(&self, max_levels: usize) -> Result<OrderBook, FetchError> {
+        let symbol_upper = self.symbol.value().to_uppercase();
+        let url = format!(
+            "https://api.binance.com/api/v3/depth?symbol={symbol_upper}&limit={DEPTH_SNAPSHOT_LIMIT}"
         );
 
-        // Create a candle
-        let candle = Candle::new(Timestamp::new(kline.open_time), ohlcv);
+        let response = get_with_retry(&url, DEPTH_SNAPSHOT_WEIGHT).await?;
+        let body = response.text().await.map_err(|e| FetchError::Network(format!("{e:?}")))?;
+        parse_depth_snapshot(&body, max_levels)
+            .map_err(|e| FetchError::Parse(e.message().to_string()))
+    }
+
+    /// 📚 Stream the order book, passing it to `handler` after every applied diff
+    ///
+    /// Diffs are buffered by the socket while the REST snapshot loads; stale
+    /// ones are skipped and a sequence gap reloads the snapshot. Reconnects
+    /// like `start_stream` and gives up after `max_retries` failures in a row.
+    pub async fn start_depth_stream<F>(
+        &mut self,
+        max_levels: usize,
+        mut handler: F,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(&OrderBook),
+    {
+        use gloo_timers::future::sleep;
+
+        let stream_name = format!("{}@depth@100ms", self.symbol.value().to_lowercase());
+        let mut attempt = 0u32;
+        loop {
+            match Self::open_stream(&stream_name) {
+                Ok(mut stream) => {
+                    self.process_depth(&mut stream, max_levels, &mut handler, &mut attempt).await
+                }
+                Err(e) => get_logger().error(
+                    LogComponent::Infrastructure("BinanceWS"),
+                    &format!("❌ Connection error: {e}"),
+                ),
+            }
+
+            if self.max_retries.is_some_and(|max| attempt >= max) {
+                let message = format!("Gave up after {attempt} reconnect attempts");
+                get_logger()
+                    .error(LogComponent::Infrastructure("BinanceWS"), &format!("❌ {message}"));
+                return Err(ChartError::Network(message));
+            }
 
-        Ok(candle)
+            let delay = reconnect_delay(attempt);
+            attempt += 1;
+            get_logger().warn(
+                LogComponent::Infrastructure("BinanceWS"),
+                &format!("🔌 Depth reconnect attempt {attempt} in {}s", delay.as_secs()),
+            );
+            sleep(delay).await;
+        }
+    }
+
+    /// Keep a snapshot in sync with the diffs of `stream` until it closes, fails or goes stale
+    async fn process_depth<F>(
+        &self,
+        stream: &mut WebSocket,
+        max_levels: usize,
+        handler: &mut F,
+        attempt: &mut u32,
+    ) where
+        F: FnMut(&OrderBook),
+    {
+        let mut book = None;
+        let timeout = stale_timeout(&[BinanceStream::Depth], self.interval);
+        while let Some(msg) = next_or_stale(stream, timeout).await {
+            let data = match msg {
+                Ok(Message::Text(data)) => data,
+                Ok(_) => continue,
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("❌ WebSocket error: {e:?}"),
+                    );
+                    break;
+                }
+            };
+            let update = match self.parse_depth_update(&data) {
+                Ok(update) => update,
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("❌ Failed to parse depth update: {e}"),
+                    );
+                    continue;
+                }
+            };
+            if let Some(book) = self.sync_depth(&mut book, &update, max_levels).await {
+                *attempt = 0;
+                handler(book);
+            }
+        }
+    }
+
+    /// Apply `update` to `book`, reloading the snapshot on a sequence gap
+    ///
+    /// Returns the book once the diff is applied, `None` while it is stale or
+    /// the snapshot failed.
+    async fn sync_depth<'a>(
+        &self,
+        book: &'a mut Option<OrderBook>,
+        update: &DepthUpdate,
+        max_levels: usize,
+    ) -> Option<&'a OrderBook> {
+        let mut sync = book.as_mut().map_or(DepthSync::Gap, |b: &mut OrderBook| b.apply(update));
+        if sync == DepthSync::Gap {
+            match self.fetch_depth_snapshot(max_levels).await {
+                Ok(mut snapshot) => {
+                    get_logger().info(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("📚 Order book snapshot at update {}", snapshot.last_update_id()),
+                    );
+                    sync = snapshot.apply(update);
+                    *book = Some(snapshot);
+                }
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("❌ Depth snapshot failed: {e}"),
+                    );
+                    *book = None;
+                }
+            }
+        }
+        (sync == DepthSync::Applied).then_some(book.as_ref()).flatten()
     }
 
     /// Start the stream with a handler
-    pub async fn start_stream<F>(&mut self, handler: F) -> Result<(), String>
+    ///
+    /// The same handler keeps receiving candles across reconnects. Returns an
+    /// error once `max_retries` reconnect attempts fail in a row.
+    pub async fn start_stream<F>(&mut self, handler: F) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+    {
+        self.run_stream(handler, |_| {}, 0, |_: &OrderBook| {}).await
+    }
+
+    /// 🚦 Like `start_stream`, reporting every connection state change to `on_status`
+    ///
+    /// `Connecting` comes first, `Live` with the first message of each
+    /// connection, `Reconnecting` before every retry and `Error` on giving up.
+    pub async fn start_stream_with_status<F, S>(
+        &mut self,
+        handler: F,
+        on_status: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        self.run_stream(handler, on_status, 0, |_: &OrderBook| {}).await
+    }
+
+    /// 📡 Like `start_stream`, also keeping the order book over the same socket
+    ///
+    /// `on_depth` receives the book bounded to `max_levels` per side after every
+    /// applied diff; the snapshot is reloaded on sequence gaps and reconnects.
+    pub async fn start_stream_with_depth<F, D>(
+        &mut self,
+        max_levels: usize,
+        handler: F,
+        on_depth: D,
+    ) -> Result<(), ChartError>
     where
-        F: FnMut(Candle) + 'static,
+        F: FnMut(Candle),
+        D: FnMut(&OrderBook),
     {
-        self.run_stream(handler, || {}).await
+        self.subscribe(BinanceStream::Depth);
+        self.run_stream(handler, |_| {}, max_levels, on_depth).await
     }
 
     pub async fn start_stream_with_callback<F, R>(
         &mut self,
         handler: F,
         on_reconnect: R,
-    ) -> Result<(), String>
+    ) -> Result<(), ChartError>
     where
-        F: FnMut(Candle) + 'static,
+        F: FnMut(Candle),
         R: FnMut(),
     {
-        self.run_stream(handler, on_reconnect).await
+        let mut on_reconnect = on_reconnect;
+        let on_status = move |state| {
+            if state == ConnectionState::Reconnecting {
+                on_reconnect();
+            }
+        };
+        self.run_stream(handler, on_status, 0, |_: &OrderBook| {}).await
     }
 
-    async fn run_stream<F, R>(&mut self, mut handler: F, mut on_reconnect: R) -> Result<(), String>
+    async fn run_stream<F, S, D>(
+        &mut self,
+        mut handler: F,
+        mut on_status: S,
+        max_levels: usize,
+        mut on_depth: D,
+    ) -> Result<(), ChartError>
     where
-        F: FnMut(Candle) + 'static,
-        R: FnMut(),
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+        D: FnMut(&OrderBook),
     {
         use gloo_timers::future::sleep;
-        use std::time::Duration;
 
-        let mut delay = 1u64;
+        let mut attempt = 0u32;
+        let mut last_timestamp = None;
+        let mut aggregator =
+            (!self.interval.is_binance_kline()).then(|| TradeAggregator::new(self.interval));
+        on_status(ConnectionState::Connecting);
         loop {
-            let mut stream = match self.connect().await {
+            let stream = match self.connect().await {
                 Ok(ws) => {
                     get_logger().info(
                         LogComponent::Infrastructure("BinanceWS"),
                         "🚀 Starting Binance WebSocket stream processing...",
                     );
-                    delay = 1;
-                    ws
+                    Some(ws)
                 }
                 Err(e) => {
                     get_logger().error(
                         LogComponent::Infrastructure("BinanceWS"),
                         &format!("❌ Connection error: {e}"),
                     );
-                    on_reconnect();
-                    sleep(Duration::from_secs(delay)).await;
-                    delay = (delay * 2).min(32);
-                    continue;
+                    None
                 }
             };
 
-            while let Some(msg) = stream.next().await {
-                match msg {
-                    Ok(gloo_net::websocket::Message::Text(data)) => match self.parse_message(&data)
-                    {
-                        Ok(candle) => {
-                            get_logger().debug(
-                                    LogComponent::Infrastructure("BinanceWS"),
-                                    &format!(
-                                        "📊 Received candle: {} - O:{:.2} H:{:.2} L:{:.2} C:{:.2} V:{:.2}",
-                                        self.symbol.value(),
-                                        candle.ohlcv.open.value(),
-                                        candle.ohlcv.high.value(),
-                                        candle.ohlcv.low.value(),
-                                        candle.ohlcv.close.value(),
-                                        candle.ohlcv.volume.value()
-                                    ),
-                                );
-                            handler(candle);
-                        }
-                        Err(e) => {
-                            get_logger().error(
-                                LogComponent::Infrastructure("BinanceWS"),
-                                &format!("❌ Failed to parse message: {e}"),
-                            );
+            if let Some(mut stream) = stream {
+                let mut session = StreamSession {
+                    handler: &mut handler,
+                    attempt: &mut attempt,
+                    on_status: &mut on_status,
+                    live: false,
+                    last_timestamp: &mut last_timestamp,
+                    aggregator: aggregator.as_mut(),
+                    book: None,
+                    max_levels,
+                    on_depth: &mut on_depth,
+                };
+                self.process_events(&mut stream, &mut session).await
+            }
+
+            if self.max_retries.is_some_and(|max| attempt >= max) {
+                let message = format!("Gave up after {attempt} reconnect attempts");
+                get_logger()
+                    .error(LogComponent::Infrastructure("BinanceWS"), &format!("❌ {message}"));
+                on_status(ConnectionState::Error(message.clone()));
+                return Err(ChartError::Network(message));
+            }
+
+            let delay = reconnect_delay(attempt);
+            attempt += 1;
+            get_logger().warn(
+                LogComponent::Infrastructure("BinanceWS"),
+                &format!("🔌 Reconnect attempt {attempt} in {}s", delay.as_secs()),
+            );
+            on_status(ConnectionState::Reconnecting);
+            sleep(delay).await;
+        }
+    }
+
+    /// Route stream events until the socket closes, fails or goes stale
+    ///
+    /// Every parsed event resets the reconnect `attempt` counter. Klines go to
+    /// the handler, after backfilling the candles missed since the last one
+    /// on a reconnect. Trades are bucketed into candles by the aggregator,
+    /// which outlives reconnects so buckets missed while offline are carried
+    /// flat. Depth diffs keep the session's order book in sync.
+    async fn process_events<F, S, D>(
+        &self,
+        stream: &mut WebSocket,
+        session: &mut StreamSession<'_, F, S, D>,
+    ) where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+        D: FnMut(&OrderBook),
+    {
+        let mut resumed_from = *session.last_timestamp;
+        let timeout = stale_timeout(&self.streams, self.interval);
+        while let Some(msg) = next_or_stale(stream, timeout).await {
+            let data = match msg {
+                Ok(Message::Text(data)) => data,
+                Ok(_) => continue, // Ignore binary messages
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("❌ WebSocket error: {e:?}"),
+                    );
+                    break;
+                }
+            };
+            let event = match self.parse_event(&data) {
+                Ok(event) => event,
+                Err(e) => {
+                    get_logger().error(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!("❌ Failed to parse message: {e}"),
+                    );
+                    continue;
+                }
+            };
+            *session.attempt = 0;
+            if !session.live {
+                session.live = true;
+                (session.on_status)(ConnectionState::Live);
+            }
+            match event {
+                StreamEvent::Candle(candle) => {
+                    get_logger().debug(
+                        LogComponent::Infrastructure("BinanceWS"),
+                        &format!(
+                            "📊 Received candle: {} - O:{:.2} H:{:.2} L:{:.2} C:{:.2} V:{:.2}",
+                            self.symbol.value(),
+                            candle.ohlcv.open.value(),
+                            candle.ohlcv.high.value(),
+                            candle.ohlcv.low.value(),
+                            candle.ohlcv.close.value(),
+                            candle.ohlcv.volume.value()
+                        ),
+                    );
+                    if let Some(last_ts) = resumed_from.take() {
+                        for missed in self.backfill(last_ts, candle.timestamp.value()).await {
+                            (session.handler)(missed);
                         }
-                    },
-                    Ok(_) => {
-                        // Ignore binary messages
                     }
-                    Err(e) => {
-                        get_logger().error(
-                            LogComponent::Infrastructure("BinanceWS"),
-                            &format!("❌ WebSocket error: {e:?}"),
-                        );
-                        break;
+                    *session.last_timestamp = Some(candle.timestamp.value());
+                    (session.handler)(candle);
+                }
+                StreamEvent::Trade(trade) => {
+                    // Kline intervals take their candles from the kline stream
+                    if let Some(aggregator) = session.aggregator.as_mut() {
+                        aggregator.push(trade).into_iter().for_each(&mut *session.handler);
+                    }
+                }
+                StreamEvent::Depth(update) => {
+                    if let Some(book) =
+                        self.sync_depth(&mut session.book, &update, session.max_levels).await
+                    {
+                        (session.on_depth)(book);
                     }
                 }
             }
+        }
+    }
 
-            get_logger().warn(
-                LogComponent::Infrastructure("BinanceWS"),
-                &format!("🔌 Reconnecting in {delay}s"),
-            );
-            on_reconnect();
-            sleep(Duration::from_secs(delay)).await;
-            delay = (delay * 2).min(32);
+    /// 🕳️ Fetch candles missed between `last_ts` and the kline at `next_ts`
+    async fn backfill(&self, last_ts: u64, next_ts: u64) -> Vec<Candle> {
+        if !has_gap(last_ts, next_ts, self.interval) {
+            return Vec::new();
+        }
+        let limit = backfill_limit(last_ts, next_ts, self.interval);
+        match self.fetch_historical_data_before(next_ts - 1, limit).await {
+            Ok(candles) => {
+                let missed = backfill_seam(last_ts, next_ts, candles);
+                get_logger().info(
+                    LogComponent::Infrastructure("BinanceWS"),
+                    &format!("🧵 Backfilled {} candles after reconnect", missed.len()),
+                );
+                missed
+            }
+            Err(e) => {
+                get_logger().error(
+                    LogComponent::Infrastructure("BinanceWS"),
+                    &format!("❌ Backfill failed: {e}"),
+                );
+                Vec::new()
+            }
         }
     }
 
     /// 📈 Load historical data from Binance REST API
-    pub async fn fetch_historical_data(&self, limit: u32) -> Result<Vec<Candle>, String> {
+    pub async fn fetch_historical_data(&self, limit: u32) -> Result<Vec<Candle>, FetchError> {
         let symbol_upper = self.symbol.value().to_uppercase();
         let interval_str = self.interval.to_binance_str();
 
@@ -227,42 +921,7 @@ impl BinanceWebSocketClient {
             &format!("📈 Fetching {limit} historical candles from: {url}"),
         );
 
-        let response = Request::get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch historical data: {e:?}"))?;
-
-        if !response.ok() {
-            return Err(format!("HTTP error: {}", response.status()));
-        }
-
-        let klines: Vec<BinanceHistoricalKline> =
-            response.json().await.map_err(|e| format!("Failed to parse JSON: {e:?}"))?;
-
-        let mut candles = Vec::new();
-
-        for kline in klines {
-            let open = kline.1.parse::<f64>().map_err(|_| "Invalid open price")?;
-            let high = kline.2.parse::<f64>().map_err(|_| "Invalid high price")?;
-            let low = kline.3.parse::<f64>().map_err(|_| "Invalid low price")?;
-            let close = kline.4.parse::<f64>().map_err(|_| "Invalid close price")?;
-            let volume = kline.5.parse::<f64>().map_err(|_| "Invalid volume")?;
-
-            let ohlcv = OHLCV::new(
-                Price::new(open),
-                Price::new(high),
-                Price::new(low),
-                Price::new(close),
-                Volume::new(volume),
-            );
-
-            let candle = Candle::new(
-                Timestamp::new(kline.0), // open_time
-                ohlcv,
-            );
-
-            candles.push(candle);
-        }
+        let candles = Self::fetch_klines(&url).await?;
 
         get_logger().info(
             LogComponent::Infrastructure("BinanceAPI"),
@@ -272,12 +931,25 @@ impl BinanceWebSocketClient {
         Ok(candles)
     }
 
+    /// 📏 Fetch the symbol's price tick size from exchangeInfo
+    pub async fn fetch_tick_size(&self) -> Result<f64, FetchError> {
+        let symbol_upper = self.symbol.value().to_uppercase();
+        let url = format!("https://api.binance.com/api/v3/exchangeInfo?symbol={symbol_upper}");
+
+        let response = get_with_retry(&url, EXCHANGE_INFO_WEIGHT).await?;
+        let info: BinanceExchangeInfo =
+            response.json().await.map_err(|e| FetchError::Parse(format!("{e:?}")))?;
+
+        info.tick_size(&symbol_upper)
+            .ok_or_else(|| FetchError::Parse(format!("No tick size for {symbol_upper}")))
+    }
+
     /// 📈 Load historical data up to the specified time
     pub async fn fetch_historical_data_before(
         &self,
         end_time: u64,
         limit: u32,
-    ) -> Result<Vec<Candle>, String> {
+    ) -> Result<Vec<Candle>, FetchError> {
         let symbol_upper = self.symbol.value().to_uppercase();
         let interval_str = self.interval.to_binance_str();
 
@@ -290,39 +962,7 @@ impl BinanceWebSocketClient {
             &format!("📈 Fetching {limit} candles before {end_time} from: {url}"),
         );
 
-        let response = Request::get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch historical data: {e:?}"))?;
-
-        if !response.ok() {
-            return Err(format!("HTTP error: {}", response.status()));
-        }
-
-        let klines: Vec<BinanceHistoricalKline> =
-            response.json().await.map_err(|e| format!("Failed to parse JSON: {e:?}"))?;
-
-        let mut candles = Vec::new();
-
-        for kline in klines {
-            let open = kline.1.parse::<f64>().map_err(|_| "Invalid open price")?;
-            let high = kline.2.parse::<f64>().map_err(|_| "Invalid high price")?;
-            let low = kline.3.parse::<f64>().map_err(|_| "Invalid low price")?;
-            let close = kline.4.parse::<f64>().map_err(|_| "Invalid close price")?;
-            let volume = kline.5.parse::<f64>().map_err(|_| "Invalid volume")?;
-
-            let ohlcv = OHLCV::new(
-                Price::new(open),
-                Price::new(high),
-                Price::new(low),
-                Price::new(close),
-                Volume::new(volume),
-            );
-
-            let candle = Candle::new(Timestamp::new(kline.0), ohlcv);
-
-            candles.push(candle);
-        }
+        let candles = Self::fetch_klines(&url).await?;
 
         get_logger().info(
             LogComponent::Infrastructure("BinanceAPI"),
@@ -331,16 +971,82 @@ impl BinanceWebSocketClient {
 
         Ok(candles)
     }
+
+    /// 📚 Load every candle in `start_time..=end_time`, 1000 per request
+    ///
+    /// Requests share the REST rate limiter. Candles come back strictly
+    /// ascending; a failure mid-way returns the loaded part in `PartialRange`.
+    pub async fn fetch_historical_range(
+        &self,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Candle>, PartialRange> {
+        get_logger().info(
+            LogComponent::Infrastructure("BinanceAPI"),
+            &format!("📚 Fetching candles from {start_time} to {end_time}"),
+        );
+        paginate_range(start_time, end_time, |end, limit| {
+            self.fetch_historical_data_before(end, limit)
+        })
+        .await
+    }
+
+    /// GET a klines `url` and convert the rows to candles
+    async fn fetch_klines(url: &str) -> Result<Vec<Candle>, FetchError> {
+        let response = get_with_retry(url, KLINES_WEIGHT).await?;
+        let body = response.text().await.map_err(|e| FetchError::Network(format!("{e:?}")))?;
+        parse_historical_klines(&body).map_err(|e| FetchError::Parse(e.message().to_string()))
+    }
+}
+
+/// 📈 Candles from a klines REST response, dropping rows that fail validation
+pub fn parse_historical_klines(data: &str) -> Result<Vec<Candle>, ChartError> {
+    let klines: Vec<BinanceHistoricalKline> = serde_json::from_str(data)
+        .map_err(|e| ChartError::Parse(format!("Failed to parse klines: {e}")))?;
+    Ok(klines.into_iter().filter_map(|kline| kline.to_candle().ok()).collect())
+}
+
+/// 🕯️ Candle from the string fields of a Binance kline
+///
+/// Every kline source (stream events, REST rows, DTOs) goes through here, so
+/// they parse and validate alike. Unparsable numbers are logged and rejected
+/// with `ChartError::Parse`; the values then pass through [`ingest_candle`].
+pub fn kline_to_candle(
+    open_time: u64,
+    open: &str,
+    high: &str,
+    low: &str,
+    close: &str,
+    volume: &str,
+) -> Result<Candle, ChartError> {
+    let parse = |value: &str, what: &str| {
+        value.parse::<f64>().map_err(|_| {
+            get_logger().warn(
+                LogComponent::Infrastructure("Ingest"),
+                &format!("⚠️ Rejected candle {open_time}: invalid {what}"),
+            );
+            ChartError::Parse(format!("Invalid {what}"))
+        })
+    };
+    ingest_candle(
+        open_time,
+        parse(open, "open price")?,
+        parse(high, "high price")?,
+        parse(low, "low price")?,
+        parse(close, "close price")?,
+        parse(volume, "volume")?,
+    )
 }
 
 /// Simple helper to create a WebSocket connection
 pub async fn create_binance_stream(
     symbol: &str,
     interval: &str,
-) -> Result<BinanceWebSocketClient, String> {
+) -> Result<BinanceWebSocketClient, ChartError> {
     let symbol = Symbol::from(symbol);
-    let interval =
-        interval.parse::<TimeInterval>().map_err(|_| format!("Invalid interval: {interval}"))?;
+    let interval = interval
+        .parse::<TimeInterval>()
+        .map_err(|_| ChartError::InvalidData(format!("Invalid interval: {interval}")))?;
 
     let client = BinanceWebSocketClient::new(symbol, interval);
     Ok(client)
@@ -354,8 +1060,7 @@ pub async fn test_binance_websocket() -> Result<(), JsValue> {
         "🧪 Testing Binance WebSocket with gloo...",
     );
 
-    let mut client =
-        create_binance_stream("BTCUSDT", "1m").await.map_err(|e| JsValue::from_str(&e))?;
+    let mut client = create_binance_stream("BTCUSDT", "1m").await?;
 
     // Test handler
     let handler = |candle: Candle| {
@@ -369,7 +1074,7 @@ pub async fn test_binance_websocket() -> Result<(), JsValue> {
     if let Err(e) = client.start_stream(handler).await {
         get_logger()
             .error(LogComponent::Infrastructure("BinanceWS"), &format!("❌ Stream error: {e}"));
-        return Err(JsValue::from_str(&e));
+        return Err(e.into());
     }
 
     get_logger()
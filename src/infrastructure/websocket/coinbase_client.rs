@@ -0,0 +1,416 @@
+use crate::domain::{
+    errors::ChartError,
+    logging::{LogComponent, get_logger},
+    market_data::{
+        entities::Candle,
+        value_objects::{Symbol, TimeInterval},
+    },
+};
+use crate::infrastructure::websocket::binance_client::{ingest_candle, reconnect_delay};
+use crate::infrastructure::websocket::connection_state::ConnectionState;
+use crate::time_utils::format_iso8601;
+use futures::{SinkExt, StreamExt};
+use gloo_net::http::Request;
+use gloo_net::websocket::{Message, futures::WebSocket};
+use serde::{Deserialize, Serialize};
+
+/// Coinbase Exchange REST endpoint
+const COINBASE_REST_URL: &str = "https://api.exchange.coinbase.com";
+/// Coinbase Advanced Trade WebSocket endpoint carrying the `candles` channel
+const COINBASE_WS_URL: &str = "wss://advanced-trade-ws.coinbase.com";
+/// Largest number of candles Coinbase returns per REST request
+pub const MAX_COINBASE_CANDLES: u32 = 300;
+/// The `candles` channel only publishes five-minute candles
+pub const COINBASE_STREAM_INTERVAL: TimeInterval = TimeInterval::FiveMinutes;
+
+/// Quote currencies recognised when splitting a Binance-style symbol
+const QUOTE_CURRENCIES: [&str; 7] = ["USDT", "USDC", "USD", "EUR", "GBP", "BTC", "ETH"];
+
+/// 🔀 Coinbase product id for `symbol`: `BTCUSDT` becomes `BTC-USDT`
+///
+/// Symbols that already contain a dash are only uppercased.
+pub fn coinbase_product_id(symbol: &Symbol) -> String {
+    let upper = symbol.value().to_uppercase();
+    if upper.contains('-') {
+        return upper;
+    }
+    QUOTE_CURRENCIES
+        .iter()
+        .find(|quote| upper.len() > quote.len() && upper.ends_with(*quote))
+        .map(|quote| format!("{}-{quote}", &upper[..upper.len() - quote.len()]))
+        .unwrap_or(upper)
+}
+
+/// Candle granularity in seconds, if Coinbase serves `interval`
+pub fn coinbase_granularity(interval: TimeInterval) -> Option<u32> {
+    match interval {
+        TimeInterval::OneMinute => Some(60),
+        TimeInterval::FiveMinutes => Some(300),
+        TimeInterval::FifteenMinutes => Some(900),
+        TimeInterval::OneHour => Some(3_600),
+        TimeInterval::OneDay => Some(86_400),
+        _ => None,
+    }
+}
+
+/// 📡 Whether Coinbase can chart `interval` live
+///
+/// History has to exist at that granularity, and the five-minute stream
+/// candles must fit a whole number of times into one candle.
+pub fn coinbase_streams(interval: TimeInterval) -> bool {
+    coinbase_granularity(interval).is_some()
+        && interval.duration_ms().is_multiple_of(COINBASE_STREAM_INTERVAL.duration_ms())
+}
+
+/// REST url for `product_id` candles, optionally limited to `start..end` (milliseconds)
+pub fn coinbase_candles_url(
+    product_id: &str,
+    granularity: u32,
+    range: Option<(u64, u64)>,
+) -> String {
+    let base =
+        format!("{COINBASE_REST_URL}/products/{product_id}/candles?granularity={granularity}");
+    match range {
+        Some((start, end)) => {
+            format!("{base}&start={}&end={}", format_iso8601(start), format_iso8601(end))
+        }
+        None => base,
+    }
+}
+
+/// Row of `/products/{id}/candles`: `[time, low, high, open, close, volume]`
+#[derive(Debug, Deserialize)]
+struct CoinbaseHistoricalCandle(u64, f64, f64, f64, f64, f64);
+
+/// 📈 Candles from a Coinbase REST response, oldest first
+///
+/// Coinbase lists newest first with times in seconds.
+pub fn parse_coinbase_candles(data: &str) -> Result<Vec<Candle>, ChartError> {
+    let rows: Vec<CoinbaseHistoricalCandle> = serde_json::from_str(data)
+        .map_err(|e| ChartError::Parse(format!("Failed to parse Coinbase candles: {e}")))?;
+    let mut candles: Vec<Candle> = rows
+        .into_iter()
+        .filter_map(|CoinbaseHistoricalCandle(time, low, high, open, close, volume)| {
+            ingest_candle(time * 1000, open, high, low, close, volume).ok()
+        })
+        .collect();
+    candles.sort_by_key(|c| c.timestamp.value());
+    Ok(candles)
+}
+
+#[derive(Debug, Serialize)]
+struct CoinbaseSubscription<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    product_ids: [&'a str; 1],
+    channel: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinbaseWsMessage {
+    channel: String,
+    #[serde(default)]
+    events: Vec<CoinbaseWsEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinbaseWsEvent {
+    #[serde(default)]
+    candles: Vec<CoinbaseWsCandle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinbaseWsCandle {
+    start: String,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: String,
+}
+
+/// 📊 Candles carried by a Coinbase `candles` channel message, oldest first
+///
+/// Subscription acknowledgements and heartbeats yield no candles.
+pub fn parse_coinbase_message(data: &str) -> Result<Vec<Candle>, ChartError> {
+    let message: CoinbaseWsMessage = serde_json::from_str(data)
+        .map_err(|e| ChartError::Parse(format!("Failed to parse Coinbase message: {e}")))?;
+    if message.channel != "candles" {
+        return Ok(Vec::new());
+    }
+
+    let mut candles = Vec::new();
+    for candle in message.events.iter().flat_map(|e| &e.candles) {
+        let start = candle
+            .start
+            .parse::<u64>()
+            .map_err(|_| ChartError::Parse("Invalid candle start".to_string()))?;
+        let open = candle
+            .open
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid open price".to_string()))?;
+        let high = candle
+            .high
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid high price".to_string()))?;
+        let low = candle
+            .low
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid low price".to_string()))?;
+        let close = candle
+            .close
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid close price".to_string()))?;
+        let volume = candle
+            .volume
+            .parse::<f64>()
+            .map_err(|_| ChartError::Parse("Invalid volume".to_string()))?;
+
+        if let Ok(candle) = ingest_candle(start * 1000, open, high, low, close, volume) {
+            candles.push(candle);
+        }
+    }
+    candles.sort_by_key(|c| c.timestamp.value());
+    Ok(candles)
+}
+
+/// DTO for `/products/{id}`; only the price increment is used
+#[derive(Debug, Deserialize)]
+struct CoinbaseProduct {
+    quote_increment: String,
+}
+
+/// Coinbase market data over the public REST API and the `candles` channel
+#[derive(Debug, Clone, Default)]
+pub struct CoinbaseDataSource {
+    /// Reconnect attempts in a row before giving up; `None` retries forever
+    max_retries: Option<u32>,
+}
+
+impl CoinbaseDataSource {
+    pub fn new() -> Self {
+        Self::with_max_retries(None)
+    }
+
+    /// Source that stops reconnecting after `max_retries` failed attempts in a row
+    pub fn with_max_retries(max_retries: Option<u32>) -> Self {
+        Self { max_retries }
+    }
+
+    pub fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+
+    async fn get_candles(&self, url: &str) -> Result<Vec<Candle>, ChartError> {
+        get_logger().info(
+            LogComponent::Infrastructure("CoinbaseAPI"),
+            &format!("📈 Fetching candles from: {url}"),
+        );
+
+        let response = Request::get(url)
+            .send()
+            .await
+            .map_err(|e| ChartError::Network(format!("Failed to fetch historical data: {e:?}")))?;
+
+        if !response.ok() {
+            return Err(ChartError::Network(format!("HTTP error: {}", response.status())));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ChartError::Parse(format!("Failed to read body: {e:?}")))?;
+        let candles = parse_coinbase_candles(&body)?;
+
+        get_logger().info(
+            LogComponent::Infrastructure("CoinbaseAPI"),
+            &format!("✅ Loaded {} historical candles", candles.len()),
+        );
+        Ok(candles)
+    }
+
+    /// 📈 Latest `limit` candles (at most 300) of `symbol` at `interval`
+    pub async fn fetch_historical_data(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        let granularity = coinbase_granularity(interval).ok_or_else(|| {
+            ChartError::Unsupported(format!("Coinbase has no {} candles", interval.as_ref()))
+        })?;
+        let url = coinbase_candles_url(&coinbase_product_id(symbol), granularity, None);
+        let mut candles = self.get_candles(&url).await?;
+        Ok(candles.split_off(candles.len().saturating_sub(limit as usize)))
+    }
+
+    /// 📈 Up to `limit` candles (at most 300) ending at `end_time` (milliseconds)
+    pub async fn fetch_historical_data_before(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        let granularity = coinbase_granularity(interval).ok_or_else(|| {
+            ChartError::Unsupported(format!("Coinbase has no {} candles", interval.as_ref()))
+        })?;
+        let span = u64::from(limit.min(MAX_COINBASE_CANDLES)) * u64::from(granularity) * 1000;
+        let range = (end_time.saturating_sub(span), end_time);
+        let url = coinbase_candles_url(&coinbase_product_id(symbol), granularity, Some(range));
+        self.get_candles(&url).await
+    }
+
+    /// 📏 Price increment of `symbol` from the product details
+    pub async fn fetch_tick_size(&self, symbol: &Symbol) -> Result<f64, ChartError> {
+        let product_id = coinbase_product_id(symbol);
+        let url = format!("{COINBASE_REST_URL}/products/{product_id}");
+
+        let response = Request::get(&url)
+            .send()
+            .await
+            .map_err(|e| ChartError::Network(format!("Failed to fetch product info: {e:?}")))?;
+
+        if !response.ok() {
+            return Err(ChartError::Network(format!("HTTP error: {}", response.status())));
+        }
+
+        let product: CoinbaseProduct = response
+            .json()
+            .await
+            .map_err(|e| ChartError::Parse(format!("Failed to parse JSON: {e:?}")))?;
+        product
+            .quote_increment
+            .parse::<f64>()
+            .ok()
+            .filter(|t| *t > 0.0)
+            .ok_or_else(|| ChartError::Parse(format!("No tick size for {product_id}")))
+    }
+
+    /// Connect and subscribe to the `candles` channel of `product_id`
+    async fn connect(&self, product_id: &str) -> Result<WebSocket, ChartError> {
+        get_logger().info(
+            LogComponent::Infrastructure("CoinbaseWS"),
+            &format!("🔌 Connecting to Coinbase: {COINBASE_WS_URL}"),
+        );
+
+        let mut ws = WebSocket::open(COINBASE_WS_URL)
+            .map_err(|e| ChartError::Network(format!("Failed to open WebSocket: {e:?}")))?;
+        let subscription = CoinbaseSubscription {
+            kind: "subscribe",
+            product_ids: [product_id],
+            channel: "candles",
+        };
+        let text = serde_json::to_string(&subscription)?;
+        ws.send(Message::Text(text))
+            .await
+            .map_err(|e| ChartError::Network(format!("Failed to subscribe: {e:?}")))?;
+
+        get_logger().info(
+            LogComponent::Infrastructure("CoinbaseWS"),
+            &format!("✅ Subscribed to Coinbase candles: {product_id}"),
+        );
+        Ok(ws)
+    }
+
+    /// Stream five-minute candles of `symbol` into `handler` for a chart at `interval`
+    ///
+    /// Longer intervals are aggregated from these candles by the chart. The
+    /// same handler keeps receiving candles across reconnects. Returns an
+    /// error for intervals `coinbase_streams` rejects or once `max_retries`
+    /// reconnects fail in a row.
+    pub async fn start_stream<F>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+    {
+        self.start_stream_with_status(symbol, interval, handler, |_| {}).await
+    }
+
+    /// 🚦 Like `start_stream`, reporting every connection state change to `on_status`
+    pub async fn start_stream_with_status<F, S>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        mut handler: F,
+        mut on_status: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        use gloo_timers::future::sleep;
+
+        if !coinbase_streams(interval) {
+            let message = format!("Coinbase cannot stream {} candles", interval.as_ref());
+            on_status(ConnectionState::Error(message.clone()));
+            return Err(ChartError::Unsupported(message));
+        }
+
+        let product_id = coinbase_product_id(symbol);
+        let mut attempt = 0u32;
+        on_status(ConnectionState::Connecting);
+        loop {
+            match self.connect(&product_id).await {
+                Ok(mut stream) => {
+                    let mut live = false;
+                    while let Some(msg) = stream.next().await {
+                        match msg {
+                            Ok(Message::Text(data)) => match parse_coinbase_message(&data) {
+                                Ok(candles) => {
+                                    if !candles.is_empty() {
+                                        attempt = 0;
+                                        if !live {
+                                            live = true;
+                                            on_status(ConnectionState::Live);
+                                        }
+                                    }
+                                    candles.into_iter().for_each(&mut handler);
+                                }
+                                Err(e) => get_logger().error(
+                                    LogComponent::Infrastructure("CoinbaseWS"),
+                                    &format!("❌ Failed to parse message: {e}"),
+                                ),
+                            },
+                            Ok(_) => {
+                                // Ignore binary messages
+                            }
+                            Err(e) => {
+                                get_logger().error(
+                                    LogComponent::Infrastructure("CoinbaseWS"),
+                                    &format!("❌ WebSocket error: {e:?}"),
+                                );
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => get_logger().error(
+                    LogComponent::Infrastructure("CoinbaseWS"),
+                    &format!("❌ Connection error: {e}"),
+                ),
+            }
+
+            if self.max_retries.is_some_and(|max| attempt >= max) {
+                let message = format!("Gave up after {attempt} reconnect attempts");
+                get_logger()
+                    .error(LogComponent::Infrastructure("CoinbaseWS"), &format!("❌ {message}"));
+                on_status(ConnectionState::Error(message.clone()));
+                return Err(ChartError::Network(message));
+            }
+
+            let delay = reconnect_delay(attempt);
+            attempt += 1;
+            get_logger().warn(
+                LogComponent::Infrastructure("CoinbaseWS"),
+                &format!("🔌 Reconnect attempt {attempt} in {}s", delay.as_secs()),
+            );
+            on_status(ConnectionState::Reconnecting);
+            sleep(delay).await;
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! 🚦 Lifecycle of the live market data connection.
+
+/// 🚦 State of the live stream, reported by the clients and shown in the header
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// No stream is running, e.g. while an embedder feeds candles
+    #[default]
+    Offline,
+    /// Opening the first connection
+    Connecting,
+    /// Messages are arriving
+    Live,
+    /// The connection dropped or went stale; a new one is being opened
+    Reconnecting,
+    /// Live updates are held back by the user
+    Paused,
+    /// The client gave up
+    Error(String),
+}
+
+impl ConnectionState {
+    /// Short header label, e.g. "🟡 Reconnecting…"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Offline => "🔴 OFF",
+            Self::Connecting => "🟡 Connecting…",
+            Self::Live => "🟢 LIVE",
+            Self::Reconnecting => "🟡 Reconnecting…",
+            Self::Paused => "⏸️ PAUSED",
+            Self::Error(_) => "❌ ERROR",
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        *self == Self::Live
+    }
+}
@@ -0,0 +1,321 @@
+use crate::domain::errors::ChartError;
+use crate::domain::logging::{LogComponent, get_logger};
+use crate::domain::market_data::{Candle, Symbol, TimeInterval};
+use crate::infrastructure::websocket::{
+    BinanceWebSocketClient, COINBASE_STREAM_INTERVAL, CoinbaseDataSource, ConnectionState,
+    MAX_BACKFILL_CANDLES, MAX_COINBASE_CANDLES, coinbase_streams,
+};
+use derive_more::Display;
+use std::future::Future;
+use strum::{AsRefStr, EnumIter, EnumString};
+
+/// 🌐 Exchange-agnostic access to candles
+///
+/// Every source maps its venue's wire format into the domain `Candle`.
+pub trait MarketDataSource {
+    /// Latest `limit` candles of `symbol` at `interval`, oldest first
+    fn fetch_historical(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        limit: u32,
+    ) -> impl Future<Output = Result<Vec<Candle>, ChartError>>;
+
+    /// Up to `limit` candles ending at `end_time` (milliseconds), oldest first
+    fn fetch_historical_before(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> impl Future<Output = Result<Vec<Candle>, ChartError>>;
+
+    /// Most candles a single history request returns
+    fn history_page_limit(&self) -> u32;
+
+    /// 📚 Latest `count` candles, paging back past one request, oldest first
+    ///
+    /// Stops early when the venue runs out of history. A failed page after
+    /// the first keeps the candles loaded so far.
+    fn fetch_history(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        count: u32,
+    ) -> impl Future<Output = Result<Vec<Candle>, ChartError>> {
+        async move {
+            let page = self.history_page_limit().max(1);
+            let mut candles = self.fetch_historical(symbol, interval, count.min(page)).await?;
+            while (candles.len() as u32) < count {
+                let Some(oldest) = candles.first().map(|c| c.timestamp.value()).filter(|&t| t > 0)
+                else {
+                    break;
+                };
+                let limit = (count - candles.len() as u32).min(page);
+                let older =
+                    match self.fetch_historical_before(symbol, interval, oldest - 1, limit).await {
+                        Ok(older) => older,
+                        Err(e) => {
+                            get_logger().warn(
+                                LogComponent::Infrastructure("History"),
+                                &format!("⚠️ Stopped after {} candles: {e}", candles.len()),
+                            );
+                            break;
+                        }
+                    };
+                let older: Vec<Candle> =
+                    older.into_iter().filter(|c| c.timestamp.value() < oldest).collect();
+                if older.is_empty() {
+                    break;
+                }
+                candles.splice(0..0, older);
+            }
+            Ok(candles)
+        }
+    }
+
+    /// Price tick size of `symbol`
+    fn fetch_tick_size(&self, symbol: &Symbol) -> impl Future<Output = Result<f64, ChartError>>;
+
+    /// Feed live candles to `handler` until the source gives up
+    fn stream<F>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+    ) -> impl Future<Output = Result<(), ChartError>>
+    where
+        F: FnMut(Candle),
+    {
+        self.stream_with_status(symbol, interval, handler, |_| {})
+    }
+
+    /// 🚦 Like `stream`, reporting every connection state change to `on_status`
+    fn stream_with_status<F, S>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+        on_status: S,
+    ) -> impl Future<Output = Result<(), ChartError>>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState);
+}
+
+impl BinanceWebSocketClient {
+    /// Client for another market keeping this client's retry limit
+    fn for_market(&self, symbol: &Symbol, interval: TimeInterval) -> Self {
+        Self::with_max_retries(symbol.clone(), interval, self.max_retries())
+    }
+}
+
+impl MarketDataSource for BinanceWebSocketClient {
+    async fn fetch_historical(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        self.for_market(symbol, interval)
+            .fetch_historical_data(limit)
+            .await
+            .map_err(ChartError::from)
+    }
+
+    async fn fetch_historical_before(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        self.for_market(symbol, interval)
+            .fetch_historical_data_before(end_time, limit)
+            .await
+            .map_err(ChartError::from)
+    }
+
+    fn history_page_limit(&self) -> u32 {
+        MAX_BACKFILL_CANDLES as u32
+    }
+
+    async fn fetch_tick_size(&self, symbol: &Symbol) -> Result<f64, ChartError> {
+        self.for_market(symbol, TimeInterval::OneMinute)
+            .fetch_tick_size()
+            .await
+            .map_err(ChartError::from)
+    }
+
+    async fn stream_with_status<F, S>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+        on_status: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        self.for_market(symbol, interval).start_stream_with_status(handler, on_status).await
+    }
+}
+
+impl MarketDataSource for CoinbaseDataSource {
+    async fn fetch_historical(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        self.fetch_historical_data(symbol, interval, limit).await
+    }
+
+    async fn fetch_historical_before(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        self.fetch_historical_data_before(symbol, interval, end_time, limit).await
+    }
+
+    fn history_page_limit(&self) -> u32 {
+        MAX_COINBASE_CANDLES
+    }
+
+    async fn fetch_tick_size(&self, symbol: &Symbol) -> Result<f64, ChartError> {
+        CoinbaseDataSource::fetch_tick_size(self, symbol).await
+    }
+
+    async fn stream_with_status<F, S>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+        on_status: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        self.start_stream_with_status(symbol, interval, handler, on_status).await
+    }
+}
+
+/// Venue the app loads market data from, chosen at startup
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString, AsRefStr,
+)]
+pub enum Exchange {
+    #[default]
+    #[display(fmt = "Binance")]
+    #[strum(serialize = "binance")]
+    Binance,
+    #[display(fmt = "Coinbase")]
+    #[strum(serialize = "coinbase")]
+    Coinbase,
+}
+
+impl Exchange {
+    /// Whether charts at `interval` can be loaded and streamed from this exchange
+    pub fn supports_interval(self, interval: TimeInterval) -> bool {
+        match self {
+            Self::Binance => true,
+            Self::Coinbase => coinbase_streams(interval),
+        }
+    }
+
+    /// `interval` if supported, otherwise the interval this exchange streams natively
+    pub fn supported_interval(self, interval: TimeInterval) -> TimeInterval {
+        match self {
+            _ if self.supports_interval(interval) => interval,
+            Self::Binance => interval,
+            Self::Coinbase => COINBASE_STREAM_INTERVAL,
+        }
+    }
+
+    /// Data source serving `symbol` at `interval` on this exchange
+    pub fn data_source(self, symbol: &Symbol, interval: TimeInterval) -> DataSource {
+        match self {
+            Self::Binance => {
+                DataSource::Binance(BinanceWebSocketClient::new(symbol.clone(), interval))
+            }
+            Self::Coinbase => DataSource::Coinbase(CoinbaseDataSource::new()),
+        }
+    }
+}
+
+/// Data source of the selected `Exchange`
+pub enum DataSource {
+    Binance(BinanceWebSocketClient),
+    Coinbase(CoinbaseDataSource),
+}
+
+impl MarketDataSource for DataSource {
+    async fn fetch_historical(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        match self {
+            Self::Binance(source) => source.fetch_historical(symbol, interval, limit).await,
+            Self::Coinbase(source) => source.fetch_historical(symbol, interval, limit).await,
+        }
+    }
+
+    async fn fetch_historical_before(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        match self {
+            Self::Binance(source) => {
+                source.fetch_historical_before(symbol, interval, end_time, limit).await
+            }
+            Self::Coinbase(source) => {
+                source.fetch_historical_before(symbol, interval, end_time, limit).await
+            }
+        }
+    }
+
+    fn history_page_limit(&self) -> u32 {
+        match self {
+            Self::Binance(source) => source.history_page_limit(),
+            Self::Coinbase(source) => source.history_page_limit(),
+        }
+    }
+
+    async fn fetch_tick_size(&self, symbol: &Symbol) -> Result<f64, ChartError> {
+        match self {
+            Self::Binance(source) => MarketDataSource::fetch_tick_size(source, symbol).await,
+            Self::Coinbase(source) => MarketDataSource::fetch_tick_size(source, symbol).await,
+        }
+    }
+
+    async fn stream_with_status<F, S>(
+        &self,
+        symbol: &Symbol,
+        interval: TimeInterval,
+        handler: F,
+        on_status: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        match self {
+            Self::Binance(source) => {
+                source.stream_with_status(symbol, interval, handler, on_status).await
+            }
+            Self::Coinbase(source) => {
+                source.stream_with_status(symbol, interval, handler, on_status).await
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use crate::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use crate::domain::errors::ChartError;
+use crate::domain::market_data::Candle;
+use crate::infrastructure::websocket::binance_client::kline_to_candle;
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::JsValue;
 
 /// DTO for Binance Kline data
 #[derive(Debug, Deserialize)]
@@ -53,44 +54,15 @@ pub struct BinanceKline {
 
 impl BinanceKline {
     /// Convert DTO to domain entity
-    pub fn to_domain_candle(&self) -> Result<Candle, JsValue> {
-        let timestamp = Timestamp::from(self.start_time);
-
-        let open = self
-            .open_price
-            .parse::<f32>()
-            .map_err(|e| JsValue::from_str(&format!("Parse open error: {}", e)))?;
-        let high = self
-            .high_price
-            .parse::<f32>()
-            .map_err(|e| JsValue::from_str(&format!("Parse high error: {}", e)))?;
-        let low = self
-            .low_price
-            .parse::<f32>()
-            .map_err(|e| JsValue::from_str(&format!("Parse low error: {}", e)))?;
-        let close = self
-            .close_price
-            .parse::<f32>()
-            .map_err(|e| JsValue::from_str(&format!("Parse close error: {}", e)))?;
-        let volume = self
-            .base_asset_volume
-            .parse::<f32>()
-            .map_err(|e| JsValue::from_str(&format!("Parse volume error: {}", e)))?;
-
-        let ohlcv = OHLCV::new(
-            Price::from(open as f64),
-            Price::from(high as f64),
-            Price::from(low as f64),
-            Price::from(close as f64),
-            Volume::from(volume as f64),
-        );
-
-        // Validate data
-        if !ohlcv.is_valid() {
-            return Err(JsValue::from_str("Invalid OHLCV data"));
-        }
-
-        Ok(Candle::new(timestamp, ohlcv))
+    pub fn to_domain_candle(&self) -> Result<Candle, ChartError> {
+        kline_to_candle(
+            self.start_time,
+            &self.open_price,
+            &self.high_price,
+            &self.low_price,
+            &self.close_price,
+            &self.base_asset_volume,
+        )
     }
 }
 
@@ -134,6 +106,44 @@ pub struct BinanceError {
     pub msg: String,
 }
 
+/// DTO for `/api/v3/exchangeInfo`
+#[derive(Debug, Deserialize)]
+pub struct BinanceExchangeInfo {
+    pub symbols: Vec<BinanceSymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BinanceSymbolInfo {
+    pub symbol: String,
+    pub filters: Vec<BinanceSymbolFilter>,
+}
+
+/// Symbol trading filters; only the price filter is used
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType")]
+pub enum BinanceSymbolFilter {
+    #[serde(rename = "PRICE_FILTER")]
+    PriceFilter {
+        #[serde(rename = "tickSize")]
+        tick_size: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl BinanceExchangeInfo {
+    /// Price tick size of `symbol`, if listed
+    pub fn tick_size(&self, symbol: &str) -> Option<f64> {
+        let info = self.symbols.iter().find(|s| s.symbol.eq_ignore_ascii_case(symbol))?;
+        info.filters.iter().find_map(|f| match f {
+            BinanceSymbolFilter::PriceFilter { tick_size } => {
+                tick_size.parse::<f64>().ok().filter(|t| *t > 0.0)
+            }
+            BinanceSymbolFilter::Other => None,
+        })
+    }
+}
+
 /// DTO for 24hr ticker statistics
 #[derive(Debug, Deserialize)]
 pub struct BinanceTicker24hr {
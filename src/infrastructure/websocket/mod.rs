@@ -1,15 +1,26 @@
 //! WebSocket client implementations and data transfer objects.
 //!
-//! Currently this module provides a client for Binance streaming data.
+//! Binance and Coinbase clients share the `MarketDataSource` trait; the
+//! `Exchange` chosen at startup picks which one feeds the chart.
 
 pub mod binance_client;
 pub mod client_handle;
+pub mod coinbase_client;
+pub mod connection_state;
+pub mod data_source;
 pub mod dto;
+pub mod rest;
+pub mod trade_aggregator;
 
-// Clean exports - only WebSocket client
+// Clean exports - market data clients
 pub use binance_client::*;
 pub use client_handle::{
     get_global_rest_client, get_global_stream_client, set_global_rest_client,
     set_global_stream_client,
 };
+pub use coinbase_client::*;
+pub use connection_state::ConnectionState;
+pub use data_source::*;
 pub use dto::*;
+pub use rest::{FetchError, TokenBucket};
+pub use trade_aggregator::{Trade, TradeAggregator};
@@ -0,0 +1,166 @@
+use crate::domain::errors::ChartError;
+use crate::domain::logging::{LogComponent, get_logger};
+use crate::infrastructure::websocket::binance_client::reconnect_delay;
+use gloo_net::http::{Request, Response};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Retries after a rate-limited or failed request before giving up
+pub const MAX_HTTP_RETRIES: u32 = 3;
+/// Request weight the limiter allows per minute; half of Binance's 6000
+pub const REST_WEIGHT_PER_MINUTE: f64 = 3000.0;
+
+/// Request weight of `/api/v3/klines`
+pub const KLINES_WEIGHT: u32 = 2;
+/// Request weight of `/api/v3/exchangeInfo` for one symbol
+pub const EXCHANGE_INFO_WEIGHT: u32 = 20;
+/// Request weight of `/api/v3/depth` with 1000 levels
+pub const DEPTH_SNAPSHOT_WEIGHT: u32 = 50;
+
+/// Failure of a REST call
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+    /// Still rate limited (HTTP 429/418) after every retry
+    RateLimited { retry_after: Option<Duration> },
+    /// The request did not produce a response
+    Network(String),
+    /// The server answered with a non-success status
+    Status(u16),
+    /// The response body did not have the expected format
+    Parse(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::RateLimited { retry_after: Some(wait) } => {
+                write!(f, "Rate limited, retry after {}s", wait.as_secs())
+            }
+            FetchError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            FetchError::Network(msg) => write!(f, "Network error: {}", msg),
+            FetchError::Status(status) => write!(f, "HTTP error: {}", status),
+            FetchError::Parse(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<FetchError> for ChartError {
+    fn from(e: FetchError) -> Self {
+        match e {
+            FetchError::Parse(msg) => ChartError::Parse(msg),
+            other => ChartError::Network(other.to_string()),
+        }
+    }
+}
+
+/// 🪣 Token bucket refilled continuously up to `capacity`
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_ms: f64,
+    tokens: f64,
+    last_refill_ms: f64,
+}
+
+impl TokenBucket {
+    /// Full bucket holding `capacity` tokens, refilled by `capacity` per `period`
+    pub fn new(capacity: f64, period: Duration) -> Self {
+        Self {
+            capacity,
+            refill_per_ms: capacity / period.as_millis().max(1) as f64,
+            tokens: capacity,
+            last_refill_ms: f64::NAN,
+        }
+    }
+
+    pub fn available(&self) -> f64 {
+        self.tokens
+    }
+
+    /// Take `weight` tokens at `now_ms`, or return how long until they are available
+    ///
+    /// Weights above the capacity wait for a full bucket and then drain it.
+    pub fn try_take(&mut self, weight: f64, now_ms: f64) -> Result<(), Duration> {
+        if !self.last_refill_ms.is_nan() {
+            let elapsed = (now_ms - self.last_refill_ms).max(0.0);
+            self.tokens = (self.tokens + elapsed * self.refill_per_ms).min(self.capacity);
+        }
+        self.last_refill_ms = now_ms;
+
+        let needed = weight.min(self.capacity);
+        if self.tokens >= needed {
+            self.tokens -= needed;
+            return Ok(());
+        }
+        let wait_ms = (needed - self.tokens) / self.refill_per_ms;
+        Err(Duration::from_millis(wait_ms.ceil() as u64))
+    }
+}
+
+/// Limiter shared by every REST call to stay under the exchange weight limit
+static REST_LIMITER: Lazy<Mutex<TokenBucket>> =
+    Lazy::new(|| Mutex::new(TokenBucket::new(REST_WEIGHT_PER_MINUTE, Duration::from_secs(60))));
+
+/// Seconds from a `Retry-After` header; HTTP dates are not supported
+pub fn parse_retry_after(value: Option<&str>) -> Option<Duration> {
+    value?.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Wait before retry `attempt` (0-based): `Retry-After` if given, else 1s, 2s, 4s…
+pub fn retry_delay(retry_after: Option<Duration>, attempt: u32) -> Duration {
+    retry_after.unwrap_or_else(|| reconnect_delay(attempt))
+}
+
+/// Whether `status` asks the client to back off
+pub fn is_rate_limited(status: u16) -> bool {
+    status == 429 || status == 418
+}
+
+/// Wait until the shared limiter grants `weight`
+async fn acquire_weight(weight: u32) {
+    loop {
+        let granted = REST_LIMITER
+            .lock()
+            .map(|mut bucket| bucket.try_take(weight as f64, js_sys::Date::now()))
+            .unwrap_or(Ok(()));
+        match granted {
+            Ok(()) => return,
+            Err(wait) => gloo_timers::future::sleep(wait).await,
+        }
+    }
+}
+
+/// 🔁 GET `url` through the shared limiter, retrying rate limits and network failures
+///
+/// Waits for `Retry-After` when the server sends it and backs off
+/// exponentially otherwise, for at most `MAX_HTTP_RETRIES` retries.
+pub async fn get_with_retry(url: &str, weight: u32) -> Result<Response, FetchError> {
+    let mut attempt = 0u32;
+    loop {
+        acquire_weight(weight).await;
+        let (error, retry_after) = match Request::get(url).send().await {
+            Ok(response) if is_rate_limited(response.status()) => {
+                let retry_after =
+                    parse_retry_after(response.headers().get("Retry-After").as_deref());
+                (FetchError::RateLimited { retry_after }, retry_after)
+            }
+            Ok(response) if !response.ok() => return Err(FetchError::Status(response.status())),
+            Ok(response) => return Ok(response),
+            Err(e) => (FetchError::Network(format!("{e:?}")), None),
+        };
+
+        if attempt >= MAX_HTTP_RETRIES {
+            return Err(error);
+        }
+        let delay = retry_delay(retry_after, attempt);
+        attempt += 1;
+        get_logger().warn(
+            LogComponent::Infrastructure("BinanceAPI"),
+            &format!("🔁 {error}; retry {attempt} in {}ms", delay.as_millis()),
+        );
+        gloo_timers::future::sleep(delay).await;
+    }
+}
@@ -0,0 +1,133 @@
+use crate::domain::market_data::{Candle, OHLCV, Price, TimeInterval, Timestamp, Volume};
+
+/// Single executed trade from the `@trade` stream
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub price: f64,
+    pub quantity: f64,
+    /// Trade time in milliseconds
+    pub timestamp: u64,
+}
+
+/// Trades of one bucket collected so far
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    start: u64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    first_trade: u64,
+    last_trade: u64,
+}
+
+impl Bucket {
+    fn new(start: u64, trade: Trade) -> Self {
+        Self {
+            start,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.quantity,
+            first_trade: trade.timestamp,
+            last_trade: trade.timestamp,
+        }
+    }
+
+    /// Zero-volume candle holding the previous close
+    fn flat(start: u64, close: f64) -> Self {
+        let trade = Trade { price: close, quantity: 0.0, timestamp: start };
+        Self::new(start, trade)
+    }
+
+    /// Add `trade`, keeping open and close tied to the earliest and latest trade time
+    fn add(&mut self, trade: Trade) {
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.volume += trade.quantity;
+        if trade.timestamp < self.first_trade {
+            self.first_trade = trade.timestamp;
+            self.open = trade.price;
+        }
+        if trade.timestamp >= self.last_trade {
+            self.last_trade = trade.timestamp;
+            self.close = trade.price;
+        }
+    }
+
+    fn candle(&self) -> Candle {
+        Candle::new(
+            Timestamp::from_millis(self.start),
+            OHLCV::new(
+                Price::from(self.open),
+                Price::from(self.high),
+                Price::from(self.low),
+                Price::from(self.close),
+                Volume::from(self.volume),
+            ),
+        )
+    }
+}
+
+/// 🧮 Buckets trades into OHLCV candles of a fixed interval
+///
+/// Bucket boundaries are trade timestamps floored to `interval.duration_ms()`.
+/// A bucket is finalized when the first trade of a later bucket arrives.
+#[derive(Debug, Clone)]
+pub struct TradeAggregator {
+    interval_ms: u64,
+    current: Option<Bucket>,
+}
+
+impl TradeAggregator {
+    pub fn new(interval: TimeInterval) -> Self {
+        Self { interval_ms: interval.duration_ms().max(1), current: None }
+    }
+
+    /// Start of the bucket containing `timestamp` (milliseconds)
+    pub fn bucket_start(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.interval_ms
+    }
+
+    /// In-progress candle of the current bucket, if any trade arrived
+    pub fn current(&self) -> Option<Candle> {
+        self.current.as_ref().map(Bucket::candle)
+    }
+
+    /// Feed `trade` and return the candles to emit, oldest first
+    ///
+    /// When `trade` opens a later bucket, the closed bucket is returned
+    /// finalized, followed by flat candles at its close for every bucket
+    /// without trades. The last candle is always the in-progress one. Late
+    /// trades for an already finalized bucket are dropped.
+    pub fn push(&mut self, trade: Trade) -> Vec<Candle> {
+        let start = self.bucket_start(trade.timestamp);
+        let Some(mut bucket) = self.current else {
+            let bucket = Bucket::new(start, trade);
+            self.current = Some(bucket);
+            return vec![bucket.candle()];
+        };
+
+        if start < bucket.start {
+            return Vec::new();
+        }
+        if start == bucket.start {
+            bucket.add(trade);
+            self.current = Some(bucket);
+            return vec![bucket.candle()];
+        }
+
+        let mut candles = vec![bucket.candle()];
+        let mut gap = bucket.start + self.interval_ms;
+        while gap < start {
+            candles.push(Bucket::flat(gap, bucket.close).candle());
+            gap += self.interval_ms;
+        }
+        bucket = Bucket::new(start, trade);
+        self.current = Some(bucket);
+        candles.push(bucket.candle());
+        candles
+    }
+}
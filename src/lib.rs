@@ -12,6 +12,7 @@ pub mod event_utils;
 pub mod global_state;
 pub mod infrastructure;
 pub mod macros;
+pub mod settings;
 pub mod time_utils;
 
 // === WASM EXPORTS ===
@@ -31,13 +32,15 @@ pub fn start_app() {
     // Initialize infrastructure services
     crate::infrastructure::initialize_infrastructure_services();
 
+    // Restore saved symbol, interval, theme and line visibility before mounting
+    let settings = crate::settings::restore_settings();
+
     // Initialize global clients
-    use crate::domain::market_data::{Symbol, TimeInterval};
     use crate::infrastructure::websocket::{
         BinanceWebSocketClient, set_global_rest_client, set_global_stream_client,
     };
-    let symbol = Symbol::from("BTCUSDT");
-    let interval = TimeInterval::OneMinute;
+    let symbol = settings.symbol;
+    let interval = settings.interval;
     set_global_rest_client(Arc::new(Mutex::new(BinanceWebSocketClient::new(
         symbol.clone(),
         interval,
@@ -73,7 +76,15 @@ pub async fn is_webgpu_supported() -> bool {
 #[wasm_bindgen]
 pub fn get_renderer_performance() -> String {
     crate::infrastructure::rendering::renderer::with_global_renderer(|r| r.get_performance_info())
-        .unwrap_or_else(|| "{\"backend\":\"WebGPU\",\"status\":\"not_ready\"}".to_string())
+        .unwrap_or_else(|| {
+            serde_json::json!({
+                "backend": "WebGPU",
+                "status": "not_ready",
+                "avg_fps": 0.0,
+                "last_frame_ms": 0.0
+            })
+            .to_string()
+        })
 }
 
 /// Get GPU memory statistics
@@ -84,4 +95,211 @@ pub fn get_gpu_memory_usage() -> String {
         .unwrap_or_else(|| "{}".to_string())
 }
 
+/// Export the main chart as a PNG data URL; resolves once the GPU readback completes
+///
+/// Contains the WebGPU layer only, without DOM overlays such as axis labels.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn export_chart_png() -> Result<String, JsValue> {
+    Ok(crate::infrastructure::rendering::renderer::export_chart_png_data_url().await?)
+}
+
+/// Request `count` MSAA samples (1 disables); falls back to the nearest count the GPU supports
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_msaa_samples(count: u32) {
+    crate::app::set_msaa_samples(count);
+}
+
+/// Time geometry generation for 100 to 5000 candles, indicators off and on; returns JSON
+#[cfg(all(target_arch = "wasm32", feature = "bench"))]
+#[wasm_bindgen]
+pub fn run_geometry_bench() -> String {
+    crate::infrastructure::rendering::renderer::run_geometry_bench()
+}
+
+/// Time the default moving averages recomputed vs updated incrementally; returns JSON
+#[cfg(all(target_arch = "wasm32", feature = "bench"))]
+#[wasm_bindgen]
+pub fn run_moving_average_bench() -> String {
+    crate::infrastructure::rendering::renderer::run_moving_average_bench()
+}
+
+/// Set the main chart's zoom level; ignored until the renderer is ready
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_chart_zoom(level: f64) {
+    crate::app::set_zoom(level);
+}
+
+/// Pan the main chart as if dragged by `delta_px` pixels; ignored until the renderer is ready
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn pan_chart(delta_px: f64) {
+    crate::app::pan(delta_px);
+}
+
+/// Restore the default zoom and show the latest candle; ignored until the renderer is ready
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn reset_chart_view() {
+    crate::app::reset_view();
+}
+
+/// Stretch the price scale by `factor` around `center_y` (0 top, 1 bottom); turns auto-fit off
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn zoom_chart_price(factor: f64, center_y: f64) {
+    crate::app::zoom_price(factor, center_y);
+}
+
+/// Fit the price scale to the visible candles again; ignored until the renderer is ready
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn reset_chart_price_view() {
+    crate::app::reset_price_view();
+}
+
+/// Retain up to `limit` candles per interval so the chart can scroll further back
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_history_limit(limit: usize) {
+    crate::app::set_history_limit(limit);
+}
+
+/// Load `count` candles when a chart opens; 0 restores the per-interval default
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_history_candles(count: u32) {
+    crate::app::set_history_depth(crate::domain::market_data::HistoryDepth::Candles(count));
+}
+
+/// Stretch candle bodies shorter than `ndc` NDC units; 0 draws true heights
+///
+/// With 0, bodies thinner than a pixel, like dojis, are drawn as one-pixel
+/// lines. Values that are negative or above 0.1 are ignored.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_min_body_height(ndc: f32) {
+    use crate::infrastructure::rendering::renderer::MinBodyHeight;
+    let min_height = if ndc == 0.0 { MinBodyHeight::Off } else { MinBodyHeight::Ndc(ndc) };
+    crate::app::set_min_body_height(min_height);
+}
+
+/// Load the candles of the last `days` days when a chart opens
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_history_lookback_days(days: u32) {
+    crate::app::set_history_depth(crate::domain::market_data::HistoryDepth::days(days.into()));
+}
+
+/// Feed one candle from your own data source instead of the exchange stream
+///
+/// `timestamp` is the candle's open time in Unix milliseconds. Stops the
+/// exchange stream of the current symbol; a candle with the open time of the
+/// last one replaces it, a later one appends. Invalid values are rejected.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn push_candle(
+    timestamp: f64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+) -> Result<(), JsValue> {
+    let candle =
+        crate::domain::market_data::Candle::from_raw(timestamp, open, high, low, close, volume)?;
+    crate::app::push_candle(candle);
+    Ok(())
+}
+
+/// Replace the chart's candles with a JSON array from your own data source
+///
+/// Each element is `{"timestamp", "open", "high", "low", "close", "volume"}`
+/// with `timestamp` the open time in Unix milliseconds and `volume` optional.
+/// Nothing is replaced if any candle is invalid.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn set_candles(json: &str) -> Result<(), JsValue> {
+    let candles = crate::domain::market_data::RawCandle::parse_list(json)?;
+    crate::app::set_candles(candles);
+    Ok(())
+}
+
+/// Build a sparkline chart with one flat candle per close; invalid closes are skipped
+///
+/// Candles are spaced by `interval`, the interval the chart is drawn at, so no
+/// two closes fall into the same candle.
+pub fn sparkline_chart(
+    chart_id: &str,
+    closes: &[f64],
+    interval: crate::domain::market_data::TimeInterval,
+) -> crate::domain::chart::Chart {
+    use crate::domain::chart::{Chart, value_objects::ChartType};
+    use crate::domain::market_data::Candle;
+
+    let step = interval.duration_ms();
+    let candles = closes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &c)| Candle::from_raw((i as u64 * step) as f64, c, c, c, c, 0.0).ok())
+        .collect();
+    let mut chart = Chart::new(chart_id.to_string(), ChartType::Sparkline, closes.len().max(1));
+    chart.set_historical_data(candles);
+    chart
+}
+
+/// Sparkline renderer of one canvas with the size it was configured for
+#[cfg(target_arch = "wasm32")]
+struct SparklineSlot {
+    size: (u32, u32),
+    renderer: crate::infrastructure::WebGpuRenderer,
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// Sparkline renderers by canvas id
+    static SPARKLINE_RENDERERS: std::cell::RefCell<std::collections::HashMap<String, SparklineSlot>> =
+        std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+/// Render a close-price sparkline into a small canvas
+///
+/// The first call for a canvas creates its WebGPU renderer; later calls reuse
+/// it and only resize it when `width` or `height` change.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub async fn render_sparkline(
+    canvas_id: String,
+    width: u32,
+    height: u32,
+    closes: Vec<f64>,
+) -> Result<(), JsValue> {
+    if !SPARKLINE_RENDERERS.with(|r| r.borrow().contains_key(&canvas_id)) {
+        let renderer =
+            crate::infrastructure::WebGpuRenderer::new(&canvas_id, width, height).await?;
+        // A call that overlapped the await may have stored one already; keep that one
+        SPARKLINE_RENDERERS.with(|r| {
+            r.borrow_mut()
+                .entry(canvas_id.clone())
+                .or_insert(SparklineSlot { size: (width, height), renderer });
+        });
+    }
+    // The renderer draws the series of the current interval
+    let interval = crate::app::current_interval().get_untracked();
+    let chart = sparkline_chart(&canvas_id, &closes, interval);
+    SPARKLINE_RENDERERS.with(|r| {
+        let mut renderers = r.borrow_mut();
+        let Some(slot) = renderers.get_mut(&canvas_id) else {
+            return Ok(());
+        };
+        if slot.size != (width, height) {
+            slot.renderer.resize(width, height);
+            slot.size = (width, height);
+        }
+        Ok(slot.renderer.render(&chart)?)
+    })
+}
+
 // Clean WASM exports only
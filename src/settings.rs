@@ -0,0 +1,188 @@
+//! Chart settings persisted in `localStorage` between sessions.
+//!
+//! Restored before the app mounts; every field falls back to its default
+//! when missing or malformed so a corrupt entry never blocks startup.
+
+use crate::domain::errors::ChartError;
+use crate::domain::logging::{LogComponent, get_logger};
+use crate::domain::market_data::{HistoryDepth, Symbol, TimeInterval};
+use crate::infrastructure::rendering::gpu_structures::ChartTheme;
+use crate::infrastructure::rendering::renderer::{LineVisibility, MinBodyHeight};
+use leptos::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// localStorage key holding the serialized `ChartSettings`
+pub const SETTINGS_STORAGE_KEY: &str = "price-chart.settings";
+
+/// Color scheme stored in the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    Colorblind,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 3] =
+        [ThemePreset::Dark, ThemePreset::Light, ThemePreset::Colorblind];
+
+    pub fn palette(self) -> ChartTheme {
+        match self {
+            ThemePreset::Dark => ChartTheme::dark(),
+            ThemePreset::Light => ChartTheme::light(),
+            ThemePreset::Colorblind => ChartTheme::colorblind(),
+        }
+    }
+
+    /// Stored name, as in the settings JSON
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+}
+
+/// ⚙️ User choices restored on the next visit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChartSettings {
+    pub symbol: Symbol,
+    pub interval: TimeInterval,
+    pub theme: ThemePreset,
+    pub line_visibility: LineVisibility,
+    pub min_body_height: MinBodyHeight,
+    pub history_depth: HistoryDepth,
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        Self {
+            symbol: Symbol::from("BTCUSDT"),
+            interval: TimeInterval::OneMinute,
+            theme: ThemePreset::default(),
+            line_visibility: LineVisibility::default(),
+            min_body_height: MinBodyHeight::default(),
+            history_depth: HistoryDepth::default(),
+        }
+    }
+}
+
+impl ChartSettings {
+    /// Settings from stored JSON, defaulting each missing or malformed field
+    ///
+    /// Fails only when `json` is not a JSON object at all.
+    pub fn from_json(json: &str) -> Result<Self, ChartError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if !value.is_object() {
+            return Err(ChartError::Parse("Settings are not a JSON object".to_string()));
+        }
+        let defaults = Self::default();
+        Ok(Self {
+            symbol: field::<Symbol>(&value, "symbol")
+                .filter(|s| !s.value().is_empty())
+                .unwrap_or(defaults.symbol),
+            interval: field(&value, "interval").unwrap_or(defaults.interval),
+            theme: field(&value, "theme").unwrap_or(defaults.theme),
+            line_visibility: field(&value, "line_visibility").unwrap_or(defaults.line_visibility),
+            min_body_height: field::<MinBodyHeight>(&value, "min_body_height")
+                .filter(|m| m.is_valid())
+                .unwrap_or(defaults.min_body_height),
+            history_depth: field(&value, "history_depth").unwrap_or(defaults.history_depth),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Settings from the stored value, or defaults when absent or corrupt
+    pub fn from_stored(stored: Option<&str>) -> Self {
+        let Some(json) = stored else {
+            return Self::default();
+        };
+        Self::from_json(json).unwrap_or_else(|e| {
+            get_logger().warn(
+                LogComponent::Presentation("Settings"),
+                &format!("⚠️ Ignoring stored settings: {e}"),
+            );
+            Self::default()
+        })
+    }
+}
+
+fn field<T: DeserializeOwned>(value: &serde_json::Value, key: &str) -> Option<T> {
+    serde_json::from_value(value.get(key)?.clone()).ok()
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Settings saved by a previous session, defaults otherwise
+pub fn load_settings() -> ChartSettings {
+    let stored = local_storage().and_then(|s| s.get_item(SETTINGS_STORAGE_KEY).ok().flatten());
+    ChartSettings::from_stored(stored.as_deref())
+}
+
+pub fn save_settings(settings: &ChartSettings) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(SETTINGS_STORAGE_KEY, &settings.to_json());
+    }
+}
+
+/// Settings as currently shown by the app
+pub fn current_settings() -> ChartSettings {
+    use crate::app::{
+        current_interval, current_symbol, global_line_visibility, history_depth, min_body_height,
+        theme_preset,
+    };
+    ChartSettings {
+        symbol: current_symbol().get_untracked(),
+        interval: current_interval().get_untracked(),
+        theme: theme_preset().get_untracked(),
+        line_visibility: global_line_visibility().get_untracked(),
+        min_body_height: min_body_height().get_untracked(),
+        history_depth: history_depth().get_untracked(),
+    }
+}
+
+/// ⚙️ Load the stored settings into the global signals and return them
+pub fn restore_settings() -> ChartSettings {
+    use crate::app::{
+        current_interval, current_symbol, global_line_visibility, history_depth, min_body_height,
+        theme_preset,
+    };
+    let settings = load_settings();
+    current_symbol().set(settings.symbol.clone());
+    current_interval().set(settings.interval);
+    theme_preset().set(settings.theme);
+    global_line_visibility().set(settings.line_visibility.clone());
+    min_body_height().set(settings.min_body_height);
+    history_depth().set(settings.history_depth);
+    settings
+}
+
+/// 💾 Save the settings whenever any of them changes
+pub fn persist_settings() {
+    use crate::app::{
+        current_interval, current_symbol, global_line_visibility, history_depth, min_body_height,
+        theme_preset,
+    };
+    create_effect(move |_| {
+        current_symbol().track();
+        current_interval().track();
+        theme_preset().track();
+        global_line_visibility().track();
+        min_body_height().track();
+        history_depth().track();
+        save_settings(&current_settings());
+    });
+}
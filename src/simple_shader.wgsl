@@ -12,6 +12,17 @@ struct ChartUniforms {
     ema12_color: vec4<f32>,       // EMA 12 color (purple)
     ema26_color: vec4<f32>,       // EMA 26 color (cyan)
     current_price_color: vec4<f32>, // 💰 current price color (bright yellow)
+    macd_color: vec4<f32>,        // MACD line color (blue)
+    macd_signal_color: vec4<f32>, // MACD signal line color (orange)
+    grid_color: vec4<f32>,        // grid line color (theme)
+    rsi_color: vec4<f32>,         // 📉 RSI line color
+    bollinger_color: vec4<f32>,   // Bollinger bands and their fill
+    price_line_color: vec4<f32>,  // 📈 line/area price line and its area
+    vwap_color: vec4<f32>,        // 📊 VWAP line color
+    stochastic_k_color: vec4<f32>, // 📉 Stochastic %K line color
+    stochastic_d_color: vec4<f32>, // 📉 Stochastic %D line color
+    atr_color: vec4<f32>,         // 📏 ATR line color
+    keltner_color: vec4<f32>,     // 📈 Keltner channel color
     render_params: vec4<f32>,     // candle_width, spacing, line_width, _padding
 }
 
@@ -54,7 +65,16 @@ fn vs_main(vertex: VertexInput) -> VertexOutput {
         out.color = uniforms.wick_color; // gray
     } else if (vertex.element_type < 2.5) {
         // Indicator lines with dedicated colors
-        if (vertex.color_type < 2.5) {
+        if (vertex.color_type < -0.5) {
+            // 📈 Moving averages carry their RGB packed into -(color_type + 1)
+            let rgb = u32(-vertex.color_type - 1.0);
+            out.color = vec4<f32>(
+                f32((rgb >> 16u) & 255u) / 255.0,
+                f32((rgb >> 8u) & 255u) / 255.0,
+                f32(rgb & 255u) / 255.0,
+                0.9,
+            );
+        } else if (vertex.color_type < 2.5) {
             out.color = uniforms.sma20_color;
         } else if (vertex.color_type < 3.5) {
             out.color = uniforms.sma50_color;
@@ -64,12 +84,32 @@ fn vs_main(vertex: VertexInput) -> VertexOutput {
             out.color = uniforms.ema12_color;
         } else if (vertex.color_type < 6.5) {
             out.color = uniforms.ema26_color;
+        } else if (vertex.color_type < 7.5) {
+            out.color = uniforms.rsi_color; // 📉 RSI
+        } else if (vertex.color_type < 8.5) {
+            out.color = uniforms.macd_color;
+        } else if (vertex.color_type < 9.5) {
+            out.color = uniforms.macd_signal_color;
+        } else if (vertex.color_type > 14.5 && vertex.color_type < 15.5) {
+            out.color = uniforms.bollinger_color; // Bollinger bands
+        } else if (vertex.color_type > 15.5 && vertex.color_type < 16.5) {
+            out.color = uniforms.price_line_color; // 📈 Line/area price line
+        } else if (vertex.color_type > 16.5 && vertex.color_type < 17.5) {
+            out.color = uniforms.vwap_color; // 📊 VWAP
+        } else if (vertex.color_type > 17.5 && vertex.color_type < 18.5) {
+            out.color = uniforms.stochastic_k_color; // 📉 Stochastic %K
+        } else if (vertex.color_type > 18.5 && vertex.color_type < 19.5) {
+            out.color = uniforms.stochastic_d_color; // 📉 Stochastic %D
+        } else if (vertex.color_type > 19.5 && vertex.color_type < 20.5) {
+            out.color = uniforms.atr_color; // 📏 ATR
+        } else if (vertex.color_type > 20.5 && vertex.color_type < 21.5) {
+            out.color = uniforms.keltner_color; // 📈 Keltner channel
         } else {
             out.color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
         }
     } else if (vertex.element_type < 3.5) {
         // Chart grid
-        out.color = vec4<f32>(0.3, 0.3, 0.3, 0.3); // semi-transparent gray
+        out.color = uniforms.grid_color;
     } else if (vertex.element_type < 4.5) {
         // 💰 Current price line
         out.color = uniforms.current_price_color; // bright yellow
@@ -82,6 +122,33 @@ fn vs_main(vertex: VertexInput) -> VertexOutput {
             // Bearish volume - red, slightly darker
             out.color = vec4<f32>(uniforms.bearish_color.rgb * 0.6, 0.8);
         }
+    } else if (vertex.element_type > 6.5 && vertex.element_type < 7.5) {
+        // 📏 Session high/low lines
+        out.color = vec4<f32>(0.6, 0.7, 0.9, 1.0); // light steel blue
+    } else if (vertex.element_type > 7.5 && vertex.element_type < 8.5) {
+        // 📌 Reference lines carry their RGB packed into color_type
+        let rgb = u32(vertex.color_type);
+        out.color = vec4<f32>(
+            f32((rgb >> 16u) & 255u) / 255.0,
+            f32((rgb >> 8u) & 255u) / 255.0,
+            f32(rgb & 255u) / 255.0,
+            1.0,
+        );
+    } else if (vertex.element_type > 8.5 && vertex.element_type < 9.5) {
+        if (vertex.color_type > 0.5) {
+            // 📈 Translucent area under the price line
+            out.color = vec4<f32>(uniforms.price_line_color.rgb, 0.18);
+        } else {
+            // 🌫️ Translucent fill between Bollinger bands
+            out.color = vec4<f32>(uniforms.bollinger_color.rgb, 0.12);
+        }
+    } else if (vertex.element_type > 9.5 && vertex.element_type < 10.5) {
+        // 📚 Order book depth: translucent bids and asks
+        if (vertex.color_type > 0.5) {
+            out.color = vec4<f32>(uniforms.bullish_color.rgb, 0.25);
+        } else {
+            out.color = vec4<f32>(uniforms.bearish_color.rgb, 0.25);
+        }
     } else if (vertex.element_type > 98.0) {
         // ULTRA-SIMPLE TEST - bright red
         out.color = vec4<f32>(1.0, 0.0, 0.0, 1.0); // red
@@ -99,4 +166,68 @@ fn vs_main(vertex: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Simple fragment shader - return color from vertex shader
     return vec4<f32>(in.color.rgb, 1.0);
-} 
\ No newline at end of file
+} 
+// 🕯️ Instanced candles: a unit quad template stretched by per-candle attributes
+const CORNER_RADIUS_RATIO: f32 = 0.15; // matches CandleGeometry::CORNER_RADIUS_RATIO
+const WICK_WIDTH_RATIO: f32 = 0.1;     // wick is thinner than the body
+
+struct CandleInput {
+    @location(0) corner: vec2<f32>,
+    @location(4) x: f32,
+    @location(5) width: f32,
+    @location(6) body_top: f32,
+    @location(7) body_bottom: f32,
+    @location(8) high: f32,
+    @location(9) low: f32,
+    @location(10) bullish: f32,
+};
+
+struct CandleOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) local: vec2<f32>,     // offset from the body center
+    @location(2) half_size: vec2<f32>, // half width and half height of the body
+};
+
+@vertex
+fn vs_candle_body(candle: CandleInput) -> CandleOutput {
+    var out: CandleOutput;
+    let half_size = vec2<f32>(candle.width * 0.5, (candle.body_top - candle.body_bottom) * 0.5);
+    let center = vec2<f32>(candle.x, (candle.body_top + candle.body_bottom) * 0.5);
+    let local = vec2<f32>(candle.corner.x * candle.width, (candle.corner.y - 0.5) * 2.0 * half_size.y);
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(center + local, 0.0, 1.0);
+    if (candle.bullish > 0.5) {
+        out.color = uniforms.bullish_color;
+    } else {
+        out.color = uniforms.bearish_color;
+    }
+    out.local = local;
+    out.half_size = half_size;
+    return out;
+}
+
+@vertex
+fn vs_candle_wick(candle: CandleInput) -> CandleOutput {
+    var out: CandleOutput;
+    let x = candle.x + candle.corner.x * candle.width * WICK_WIDTH_RATIO;
+    let y = mix(candle.low, candle.high, candle.corner.y);
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(x, y, 0.0, 1.0);
+    out.color = uniforms.wick_color;
+    // Wicks are never rounded
+    out.local = vec2<f32>(0.0, 0.0);
+    out.half_size = vec2<f32>(0.0, 0.0);
+    return out;
+}
+
+@fragment
+fn fs_candle(in: CandleOutput) -> @location(0) vec4<f32> {
+    // Rounded body corners, same radius rule as the old per-vertex arcs
+    let radius = min(in.half_size.x * 2.0 * CORNER_RADIUS_RATIO, in.half_size.y);
+    if (radius >= 0.001) {
+        let q = abs(in.local) - in.half_size + vec2<f32>(radius, radius);
+        if (length(max(q, vec2<f32>(0.0, 0.0))) > radius) {
+            discard;
+        }
+    }
+    return vec4<f32>(in.color.rgb, 1.0);
+}
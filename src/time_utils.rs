@@ -1,6 +1,15 @@
+use crate::domain::market_data::TimeInterval;
 use js_sys::Date;
 use wasm_bindgen::JsValue;
 
+/// Milliseconds in one UTC day
+pub const MS_PER_DAY: u64 = 86_400_000;
+
+/// Start of the UTC day containing `timestamp` (milliseconds)
+pub fn utc_day_start(timestamp: u64) -> u64 {
+    timestamp - timestamp % MS_PER_DAY
+}
+
 /// Format timestamp according to zoom level using UTC components.
 ///
 /// - `zoom >= 2.0` -> `HH:MM`
@@ -17,9 +26,63 @@ pub fn format_time_label(timestamp: u64, zoom: f64) -> String {
     }
 }
 
+/// Civil `(year, month, day)` of the day containing `timestamp` (milliseconds)
+pub fn civil_date(timestamp: i64) -> (i64, u32, u32) {
+    // Days-to-civil conversion from Howard Hinnant's date algorithms
+    let z = timestamp.div_euclid(MS_PER_DAY as i64) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Time-axis label for candles of `interval`
+///
+/// Intraday intervals show `HH:MM`, daily and longer ones `DD.MM.YYYY`.
+/// `offset_minutes` shifts the label from UTC to a configured time zone.
+pub fn format_axis_time(timestamp: u64, interval: TimeInterval, offset_minutes: i32) -> String {
+    let local = timestamp as i64 + offset_minutes as i64 * 60_000;
+    if interval.duration_ms() < MS_PER_DAY {
+        let minutes = local.rem_euclid(MS_PER_DAY as i64) / 60_000;
+        format!("{:02}:{:02}", minutes / 60, minutes % 60)
+    } else {
+        let (year, month, day) = civil_date(local);
+        format!("{day:02}.{month:02}.{year}")
+    }
+}
+
+/// UTC ISO-8601 timestamp `YYYY-MM-DDTHH:MM:SSZ` for `timestamp` (milliseconds)
+pub fn format_iso8601(timestamp: u64) -> String {
+    let (year, month, day) = civil_date(timestamp as i64);
+    let seconds = (timestamp % MS_PER_DAY) / 1000;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Zone suffix shown next to the time axis: `UTC` or `UTC±HH:MM`
+pub fn time_zone_suffix(offset_minutes: Option<i32>) -> String {
+    match offset_minutes {
+        None | Some(0) => "UTC".to_string(),
+        Some(offset) => {
+            let sign = if offset < 0 { '-' } else { '+' };
+            let abs = offset.unsigned_abs();
+            format!("UTC{sign}{:02}:{:02}", abs / 60, abs % 60)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::format_time_label;
+    use super::{MS_PER_DAY, format_time_label, utc_day_start};
     use js_sys::Date;
     use wasm_bindgen::JsValue;
 
@@ -40,4 +103,11 @@ mod tests {
             format!("{:02}.{}", date.get_utc_month() + 1, date.get_utc_full_year())
         );
     }
+
+    #[test]
+    fn day_start_truncates_to_midnight() {
+        assert_eq!(utc_day_start(0), 0);
+        assert_eq!(utc_day_start(MS_PER_DAY - 1), 0);
+        assert_eq!(utc_day_start(MS_PER_DAY * 3 + 5_000), MS_PER_DAY * 3);
+    }
 }
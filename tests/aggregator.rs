@@ -75,3 +75,55 @@ fn aggregates_one_minute_from_two_seconds() {
     assert!((aggregated.ohlcv.low.value() - 99.5).abs() < f64::EPSILON);
     assert!((aggregated.ohlcv.volume.value() - 30.0).abs() < f64::EPSILON);
 }
+
+#[wasm_bindgen_test]
+fn resamples_one_minute_series_into_five_minutes() {
+    // Twelve minutes: two full 5m buckets and a two-candle in-progress one
+    let candles: Vec<Candle> =
+        (0..12).map(|i| minute_candle(i * 60_000, 100.0 + i as f64)).collect();
+
+    let resampled = Aggregator::resample(&candles, TimeInterval::FiveMinutes);
+
+    assert_eq!(resampled.len(), 3);
+    let expected =
+        [(0, 100.0, 105.0, 109.0, 95.0, 5.0), (300_000, 105.0, 110.0, 114.0, 100.0, 5.0)];
+    for (candle, (ts, open, close, high, low, volume)) in resampled.iter().zip(expected) {
+        assert_eq!(candle.timestamp.value(), ts);
+        assert_eq!(candle.ohlcv.open.value(), open);
+        assert_eq!(candle.ohlcv.close.value(), close);
+        assert_eq!(candle.ohlcv.high.value(), high);
+        assert_eq!(candle.ohlcv.low.value(), low);
+        assert_eq!(candle.ohlcv.volume.value(), volume);
+    }
+
+    // The trailing bucket holds only minutes 10 and 11 so far
+    let partial = &resampled[2];
+    assert_eq!(partial.timestamp.value(), 600_000);
+    assert_eq!(partial.ohlcv.open.value(), 110.0);
+    assert_eq!(partial.ohlcv.close.value(), 112.0);
+    assert_eq!(partial.ohlcv.volume.value(), 2.0);
+}
+
+#[wasm_bindgen_test]
+fn resample_skips_empty_buckets_and_input() {
+    // Minutes 0 and 11: the 5m bucket at minute 5 has no candles
+    let candles = vec![minute_candle(0, 100.0), minute_candle(11 * 60_000, 200.0)];
+    let resampled = Aggregator::resample(&candles, TimeInterval::FiveMinutes);
+    let starts: Vec<u64> = resampled.iter().map(|c| c.timestamp.value()).collect();
+    assert_eq!(starts, vec![0, 600_000]);
+    assert!(Aggregator::resample(&[], TimeInterval::FiveMinutes).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn chart_resample_matches_the_aggregated_series() {
+    use price_chart_wasm::domain::chart::{Chart, value_objects::ChartType};
+
+    let mut chart = Chart::new("resample".into(), ChartType::Candlestick, 100);
+    chart.set_historical_data(
+        (0..12).map(|i| minute_candle(i * 60_000, 100.0 + i as f64)).collect(),
+    );
+
+    let resampled = chart.resample(TimeInterval::FiveMinutes);
+    let series = chart.get_series(TimeInterval::FiveMinutes).unwrap();
+    assert_eq!(resampled, series.get_candles().iter().cloned().collect::<Vec<_>>());
+}
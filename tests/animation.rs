@@ -0,0 +1,127 @@
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    AnimatedFrame, TRANSITION_MS, Transition, ease_out_cubic,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(i: u64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 60_000),
+        OHLCV::new(
+            Price::from(close - 0.5),
+            Price::from(close + 1.0),
+            Price::from(close - 1.0),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+/// Thirty rising candles, the newest closing at `last_close`
+fn chart_with(last_close: f64, extra: usize) -> Chart {
+    let mut candles: Vec<Candle> = (0..29).map(|i| candle(i, 100.0 + i as f64)).collect();
+    candles.push(candle(29, last_close));
+    candles.extend((30..30 + extra as u64).map(|i| candle(i, last_close)));
+    let mut chart = Chart::new("animated".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    chart
+}
+
+fn frame(close: f64, timestamp: u64) -> AnimatedFrame {
+    AnimatedFrame { min_price: 0.0, max_price: 10.0, last: [1.0, 4.0, 0.5, close], timestamp }
+}
+
+#[wasm_bindgen_test]
+fn easing_starts_fast_and_settles() {
+    assert_eq!(ease_out_cubic(0.0), 0.0);
+    assert_eq!(ease_out_cubic(1.0), 1.0);
+    assert_eq!(ease_out_cubic(2.0), 1.0);
+    assert!(ease_out_cubic(0.5) > 0.5);
+}
+
+#[wasm_bindgen_test]
+fn transition_eases_from_shown_to_target() {
+    let transition = Transition::new(frame(2.0, 1), frame(3.0, 1), 1_000.0);
+    assert_eq!(transition.frame_at(1_000.0).last[3], 2.0);
+    let half = transition.frame_at(1_000.0 + TRANSITION_MS / 2.0).last[3];
+    assert!((half - (2.0 + ease_out_cubic(0.5))).abs() < 1e-9);
+    assert_eq!(transition.frame_at(1_000.0 + TRANSITION_MS), frame(3.0, 1));
+    assert!(!transition.is_finished(1_000.0 + TRANSITION_MS - 1.0));
+    assert!(transition.is_finished(1_000.0 + TRANSITION_MS));
+}
+
+#[wasm_bindgen_test]
+fn new_candle_grows_out_of_its_open() {
+    let shown = AnimatedFrame { min_price: -5.0, ..frame(2.0, 1) };
+    let transition = Transition::new(shown, frame(3.0, 2), 0.0);
+    let start = transition.frame_at(0.0);
+    assert_eq!(start.last, [1.0; 4]);
+    assert_eq!(start.timestamp, 2);
+    // The price range still tweens from what was on screen
+    assert_eq!(start.min_price, -5.0);
+}
+
+#[wasm_bindgen_test]
+fn builder_tweens_the_newest_candle() {
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.set_animate_transitions(true);
+    builder.update_at(&chart_with(129.0, 0), Some(0.0));
+    let before = builder.shown_frame().unwrap();
+    assert!(!builder.is_animating());
+
+    let updated = chart_with(135.0, 0);
+    builder.update_at(&updated, Some(10.0));
+    assert!(builder.is_animating());
+    assert_eq!(builder.shown_frame().unwrap().last[3], 129.0);
+    assert_eq!(builder.cached_uniforms().viewport[3], before.max_price);
+
+    builder.update_at(&updated, Some(10.0 + TRANSITION_MS / 2.0));
+    let mid = builder.shown_frame().unwrap().last[3];
+    assert!(mid > 129.0 && mid < 135.0);
+
+    builder.update_at(&updated, Some(10.0 + TRANSITION_MS));
+    assert!(!builder.is_animating());
+    assert_eq!(builder.shown_frame().unwrap().last[3], 135.0);
+    assert!(builder.cached_uniforms().viewport[3] > before.max_price);
+}
+
+#[wasm_bindgen_test]
+fn update_mid_tween_restarts_from_the_shown_frame() {
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.set_animate_transitions(true);
+    builder.update_at(&chart_with(129.0, 0), Some(0.0));
+    builder.update_at(&chart_with(135.0, 0), Some(0.0));
+    builder.update_at(&chart_with(135.0, 0), Some(TRANSITION_MS / 2.0));
+    let mid = builder.shown_frame().unwrap().last[3];
+
+    builder.update_at(&chart_with(120.0, 0), Some(TRANSITION_MS / 2.0));
+    assert!(builder.is_animating());
+    assert_eq!(builder.shown_frame().unwrap().last[3], mid);
+    builder.update_at(&chart_with(120.0, 0), Some(TRANSITION_MS * 1.5));
+    assert_eq!(builder.shown_frame().unwrap().last[3], 120.0);
+}
+
+#[wasm_bindgen_test]
+fn disabled_or_untimed_updates_are_instant() {
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.update_at(&chart_with(129.0, 0), Some(0.0));
+    builder.update_at(&chart_with(135.0, 1), Some(10.0));
+    assert!(!builder.is_animating());
+    assert_eq!(builder.shown_frame().unwrap().last[3], 135.0);
+
+    builder.set_animate_transitions(true);
+    builder.update_at(&chart_with(140.0, 1), None);
+    assert!(!builder.is_animating());
+
+    builder.update_at(&chart_with(150.0, 1), Some(20.0));
+    assert!(builder.is_animating());
+    builder.set_animate_transitions(false);
+    assert!(!builder.is_animating());
+    builder.update_at(&chart_with(150.0, 1), Some(25.0));
+    assert_eq!(builder.shown_frame().unwrap().last[3], 150.0);
+}
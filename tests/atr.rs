@@ -0,0 +1,80 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    ATR_PERIOD, KELTNER_OFFSET, KELTNER_PERIOD, atr_series, keltner_bands, true_ranges,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(i: u64, high: f64, low: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 60_000),
+        OHLCV::new(
+            Price::from(close),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn true_range_covers_gaps_from_the_previous_close() {
+    let candles = [
+        candle(0, 12.0, 10.0, 11.0),
+        // Gap up: high − previous close is the widest
+        candle(1, 16.0, 15.0, 15.5),
+        // Gap down: previous close − low is the widest
+        candle(2, 13.0, 12.0, 12.5),
+        // Inside bar: high − low
+        candle(3, 14.0, 11.0, 13.0),
+    ];
+    // The first candle has no previous close
+    assert_eq!(true_ranges(&candles), vec![2.0, 5.0, 3.5, 3.0]);
+}
+
+#[wasm_bindgen_test]
+fn atr_uses_wilder_smoothing() {
+    let candles: Vec<Candle> = (0..20).map(|i| candle(i, 11.0, 10.0, 10.5)).collect();
+    let mut spiked = candles.clone();
+    spiked[ATR_PERIOD] = candle(ATR_PERIOD as u64, 15.0, 10.0, 10.5);
+
+    let flat = atr_series(&candles, ATR_PERIOD);
+    assert_eq!(flat.len(), candles.len() - ATR_PERIOD + 1);
+    assert!(flat.iter().all(|v| (v - 1.0).abs() < 1e-12));
+
+    // A range of 5 after a run of 1s moves the average by 4/14
+    let atr = atr_series(&spiked, ATR_PERIOD);
+    assert!((atr[1] - (1.0 + 4.0 / ATR_PERIOD as f64)).abs() < 1e-12);
+    assert!(atr[2] < atr[1]);
+}
+
+#[wasm_bindgen_test]
+fn atr_needs_a_full_period() {
+    let candles: Vec<Candle> =
+        (0..ATR_PERIOD as u64 - 1).map(|i| candle(i, 2.0, 1.0, 1.5)).collect();
+    assert!(atr_series(&candles, ATR_PERIOD).is_empty());
+    assert!(atr_series(&candles, 0).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn keltner_bands_wrap_the_ema_by_two_atrs() {
+    let candles: Vec<Candle> = (0..40)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            candle(i, close + 1.0, close - 1.0, close)
+        })
+        .collect();
+    let bands = keltner_bands(&candles, 2.0);
+    assert_eq!(KELTNER_OFFSET, KELTNER_PERIOD - 1);
+    assert_eq!(bands.len(), candles.len() - KELTNER_OFFSET);
+
+    let atr = atr_series(&candles, ATR_PERIOD);
+    for (k, (lower, middle, upper)) in bands.iter().enumerate() {
+        let width = 2.0 * atr[k + KELTNER_OFFSET + 1 - ATR_PERIOD];
+        assert!((upper - middle - width).abs() < 1e-9);
+        assert!((middle - lower - width).abs() < 1e-9);
+    }
+    // The first middle value is the SMA seed of the EMA: closes 100..=119
+    assert!((bands[0].1 - 109.5).abs() < 1e-9);
+}
@@ -0,0 +1,40 @@
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    FLAT_PRICE_SPAN_RATIO, PRICE_PADDING, ensure_price_span, fit_price_range,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn fit_pads_visible_range() {
+    let (min, max) = fit_price_range([110.0, 100.0, 105.0, 120.0]);
+    let pad = 20.0 * PRICE_PADDING;
+    assert!((min - (100.0 - pad)).abs() < 1e-4);
+    assert!((max - (120.0 + pad)).abs() < 1e-4);
+}
+
+#[wasm_bindgen_test]
+fn zoomed_in_slice_fills_the_scale() {
+    // Zooming into a calm stretch after a spike drops the spike from the scale
+    let all = [100.0, 200.0, 101.0, 102.0, 101.5];
+    let (_, full_max) = fit_price_range(all);
+    let (min, max) = fit_price_range(all[2..].iter().copied());
+    assert!(full_max > 200.0);
+    assert!(min > 100.0 && max < 103.0);
+}
+
+#[wasm_bindgen_test]
+fn flat_prices_get_a_centered_range() {
+    let (min, max) = fit_price_range([250.0; 4]);
+    let half = 250.0 * FLAT_PRICE_SPAN_RATIO;
+    assert!((min - (250.0 - half)).abs() < 1e-4);
+    assert!((max - (250.0 + half)).abs() < 1e-4);
+
+    // A lone price of zero still gets a drawable range
+    assert_eq!(ensure_price_span(0.0, 0.0), (-1.0, 1.0));
+}
+
+#[wasm_bindgen_test]
+fn narrow_but_drawable_ranges_are_kept() {
+    assert_eq!(ensure_price_span(0.00010, 0.00012), (0.00010, 0.00012));
+    assert_eq!(ensure_price_span(100.0, 100.5), (100.0, 100.5));
+}
@@ -5,3 +5,16 @@ fn detects_right_edge() {
     assert!(should_auto_scroll(100, 2.0, 0.0));
     assert!(!should_auto_scroll(100, 2.0, -1.0));
 }
+
+#[test]
+fn new_candles_keep_history_in_place() {
+    use price_chart_wasm::app::{pan_after_new_candles, visible_range};
+
+    let pan = pan_after_new_candles(-20.0, 1, false);
+    assert_eq!(pan, -21.0);
+    assert_eq!(visible_range(100, 2.0, -20.0).0, visible_range(101, 2.0, pan).0);
+
+    // Auto-scroll stays anchored to the latest candle
+    assert_eq!(pan_after_new_candles(0.0, 1, true), 0.0);
+    assert!(should_auto_scroll(101, 2.0, pan_after_new_candles(0.0, 1, true)));
+}
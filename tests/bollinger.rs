@@ -0,0 +1,46 @@
+use price_chart_wasm::infrastructure::rendering::gpu_structures::CandleGeometry;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    BOLLINGER_PERIOD, BOLLINGER_STD_DEV, bollinger_bands,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn bands_are_sma_plus_minus_two_std_dev() {
+    // Alternating closes: mean 101, population std dev 1
+    let closes: Vec<f64> =
+        (0..BOLLINGER_PERIOD).map(|i| if i % 2 == 0 { 100.0 } else { 102.0 }).collect();
+    let bands = bollinger_bands(&closes, BOLLINGER_PERIOD, BOLLINGER_STD_DEV);
+    assert_eq!(bands.len(), 1);
+    let (lower, middle, upper) = bands[0];
+    assert!((middle - 101.0).abs() < 1e-9);
+    assert!((upper - 103.0).abs() < 1e-9);
+    assert!((lower - 99.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn flat_prices_collapse_onto_sma() {
+    let closes = [50.0; 30];
+    let bands = bollinger_bands(&closes, BOLLINGER_PERIOD, BOLLINGER_STD_DEV);
+    assert_eq!(bands.len(), 30 - BOLLINGER_PERIOD + 1);
+    for (lower, middle, upper) in bands {
+        assert!(lower.is_finite() && upper.is_finite());
+        assert_eq!((lower, middle, upper), (50.0, 50.0, 50.0));
+    }
+}
+
+#[wasm_bindgen_test]
+fn too_few_closes_give_no_bands() {
+    assert!(bollinger_bands(&[1.0; 19], BOLLINGER_PERIOD, BOLLINGER_STD_DEV).is_empty());
+    assert!(bollinger_bands(&[1.0; 5], 0, BOLLINGER_STD_DEV).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn fill_covers_area_between_bands() {
+    let upper = [(-0.5, 0.5), (0.0, 0.6), (0.5, 0.4)];
+    let lower = [(-0.5, -0.5), (0.0, -0.4), (0.5, -0.6)];
+    let fill = CandleGeometry::create_band_fill(&upper, &lower);
+    assert_eq!(fill.len(), 2 * 6);
+    assert!(fill.iter().all(|v| v.element_type == 9.0));
+    assert!(fill.iter().all(|v| v.position_y >= -0.6 && v.position_y <= 0.6));
+}
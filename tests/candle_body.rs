@@ -0,0 +1,76 @@
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    DEFAULT_MIN_BODY_HEIGHT, MAX_MIN_BODY_HEIGHT, MinBodyHeight, candle_body,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const PIXEL: f32 = 2.0 / 600.0;
+
+#[wasm_bindgen_test]
+fn default_floor_stretches_short_bodies_upward() {
+    let floor = MinBodyHeight::default();
+    assert_eq!(floor, MinBodyHeight::Ndc(DEFAULT_MIN_BODY_HEIGHT));
+    assert_eq!(candle_body(0.2, 0.2, floor, PIXEL), (0.2 + DEFAULT_MIN_BODY_HEIGHT, 0.2));
+    assert_eq!(candle_body(0.3, 0.1, floor, PIXEL), (0.3, 0.1));
+    assert_eq!(candle_body(0.1, 0.3, MinBodyHeight::Ndc(0.5), PIXEL), (0.6, 0.1));
+}
+
+#[wasm_bindgen_test]
+fn floors_outside_the_range_are_invalid() {
+    assert!(MinBodyHeight::default().is_valid());
+    assert!(MinBodyHeight::Off.is_valid());
+    assert!(MinBodyHeight::Ndc(MAX_MIN_BODY_HEIGHT).is_valid());
+    for min in [-0.01, f32::NAN, MAX_MIN_BODY_HEIGHT * 2.0] {
+        assert!(!MinBodyHeight::Ndc(min).is_valid(), "{min}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn without_floor_dojis_are_one_pixel_slivers_at_their_price() {
+    let (top, bottom) = candle_body(0.2, 0.2, MinBodyHeight::Off, PIXEL);
+    assert!((top - bottom - PIXEL).abs() < 1e-6);
+    assert!(((top + bottom) / 2.0 - 0.2).abs() < 1e-6);
+
+    // Bodies taller than a pixel keep their true height
+    let tall = 0.2 + PIXEL * 1.5;
+    assert_eq!(candle_body(0.2, tall, MinBodyHeight::Off, PIXEL), (tall, 0.2));
+}
+
+#[wasm_bindgen_test]
+fn builder_applies_the_configured_floor() {
+    let candles = (0..20)
+        .map(|i| {
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(100.0),
+                    Price::from(102.0),
+                    Price::from(98.0),
+                    Price::from(100.0),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("doji".into(), ChartType::Candlestick, 100);
+    chart.set_historical_data(candles);
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    let heights = |b: &ChartGeometryBuilder| {
+        b.create_geometry(&chart).0.iter().map(|c| c.body_top - c.body_bottom).collect::<Vec<_>>()
+    };
+    assert!(heights(&builder).iter().all(|h| *h >= DEFAULT_MIN_BODY_HEIGHT - f32::EPSILON));
+
+    builder.set_min_body_height(MinBodyHeight::Off);
+    assert_eq!(builder.min_body_height(), MinBodyHeight::Off);
+    let thin = heights(&builder);
+    assert!(thin.iter().all(|h| *h > 0.0 && *h < DEFAULT_MIN_BODY_HEIGHT));
+    // Wicks still span the full range
+    let (instances, _, _) = builder.create_geometry(&chart);
+    assert!(instances.iter().all(|c| c.high - c.low > 0.1));
+}
@@ -0,0 +1,79 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    CandleGeometry, CandleVertex, ChartUniforms,
+};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    CandleColoring, candle_color, is_bullish_candle,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(ts: u64, open: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(ts),
+        OHLCV::new(
+            Price::from(open),
+            Price::from(open.max(close) + 1.0),
+            Price::from(open.min(close) - 1.0),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn doji_is_bullish_under_both_rules() {
+    let doji = candle(0, 100.0, 100.0);
+    let theme = ChartUniforms::new();
+
+    assert!(is_bullish_candle(&doji, None, CandleColoring::OpenClose));
+    assert!(is_bullish_candle(&doji, Some(&candle(0, 90.0, 100.0)), CandleColoring::PreviousClose));
+    assert_eq!(candle_color(&doji, None, CandleColoring::OpenClose, &theme), theme.bullish_color);
+}
+
+#[wasm_bindgen_test]
+fn gap_up_red_candle_depends_on_rule() {
+    // Previous close 100, gap up to open 110, close 105: down on the bar, up on the day
+    let prev = candle(0, 95.0, 100.0);
+    let gap = candle(60_000, 110.0, 105.0);
+    let theme = ChartUniforms::new();
+
+    assert_eq!(
+        candle_color(&gap, Some(&prev), CandleColoring::OpenClose, &theme),
+        theme.bearish_color
+    );
+    assert_eq!(
+        candle_color(&gap, Some(&prev), CandleColoring::PreviousClose, &theme),
+        theme.bullish_color
+    );
+}
+
+#[wasm_bindgen_test]
+fn gap_down_green_candle_depends_on_rule() {
+    let prev = candle(0, 100.0, 110.0);
+    let gap = candle(60_000, 100.0, 105.0);
+
+    assert!(is_bullish_candle(&gap, Some(&prev), CandleColoring::OpenClose));
+    assert!(!is_bullish_candle(&gap, Some(&prev), CandleColoring::PreviousClose));
+    // Without a previous candle the rule falls back to open/close
+    assert!(is_bullish_candle(&gap, None, CandleColoring::PreviousClose));
+}
+
+#[wasm_bindgen_test]
+fn body_and_volume_share_the_decision() {
+    let c = candle(0, 100.0, 100.0);
+    let bullish = is_bullish_candle(&c, None, CandleColoring::OpenClose);
+
+    let body =
+        CandleGeometry::create_colored_candle_vertices(0.0, 0.0, 0.1, -0.1, 0.0, 0.05, bullish);
+    let volume = CandleGeometry::create_volume_vertices(0.0, 0.05, 0.5, bullish);
+    let is_body = |v: &&CandleVertex| v.element_type == 0.0;
+    assert!(body.iter().filter(is_body).all(|v| v.color_type == 1.0));
+    assert!(volume.iter().all(|v| v.color_type == 1.0));
+
+    // The raw-price helper agrees for a doji too
+    let legacy = CandleGeometry::create_candle_vertices(
+        0.0, 100.0, 101.0, 99.0, 100.0, 0.0, 0.0, 0.1, -0.1, 0.0, 0.05,
+    );
+    assert!(legacy.iter().filter(is_body).all(|v| v.color_type == 1.0));
+}
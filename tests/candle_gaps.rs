@@ -0,0 +1,96 @@
+use price_chart_wasm::domain::{
+    chart::{Chart, missing_candles, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, TimeInterval, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    LineVisibility, candle_x_position, gap_indices,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+fn candle(minute: u64) -> Candle {
+    let close = 100.0 + minute as f64;
+    Candle::new(
+        Timestamp::from_millis(minute * MINUTE),
+        OHLCV::new(
+            Price::from(close - 0.5),
+            Price::from(close + 1.0),
+            Price::from(close - 1.0),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+/// Minutes 0..10, one skipped bar at 12, then an outage from 15 to 40
+fn gappy_candles() -> Vec<Candle> {
+    let minutes = (0..=11).chain(13..=14).chain(40..=50);
+    minutes.map(candle).collect()
+}
+
+fn chart_of(candles: Vec<Candle>) -> Chart {
+    let mut chart = Chart::new("gaps".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    chart
+}
+
+#[wasm_bindgen_test]
+fn missing_candles_counts_whole_intervals() {
+    let ts = |minute: u64| Timestamp::from_millis(minute * MINUTE);
+    let interval = TimeInterval::OneMinute;
+    assert_eq!(missing_candles(ts(0), ts(1), interval), 0);
+    assert_eq!(missing_candles(ts(0), ts(2), interval), 1);
+    assert_eq!(missing_candles(ts(5), ts(40), interval), 34);
+    assert_eq!(missing_candles(ts(5), ts(5), interval), 0);
+}
+
+#[wasm_bindgen_test]
+fn single_missing_bar_is_tolerated() {
+    let chart = chart_of(gappy_candles());
+    let ts = |minute: u64| Timestamp::from_millis(minute * MINUTE);
+
+    assert_eq!(chart.find_gaps(TimeInterval::OneMinute), vec![(ts(14), ts(40))]);
+    assert_eq!(
+        chart.find_gaps_with(TimeInterval::OneMinute, 1),
+        vec![(ts(11), ts(13)), (ts(14), ts(40))]
+    );
+}
+
+#[wasm_bindgen_test]
+fn continuous_series_has_no_gaps() {
+    let chart = chart_of((0..30).map(candle).collect());
+    assert!(chart.find_gaps(TimeInterval::OneMinute).is_empty());
+    assert!(chart.find_gaps_with(TimeInterval::OneMinute, 0).is_empty());
+}
+
+/// Vertex x positions of the dashed gap markers in the main panel
+fn marker_xs(builder: &ChartGeometryBuilder, chart: &Chart, len: usize) -> Vec<f32> {
+    let (_, verts, _) = builder.create_geometry(chart);
+    let index = gap_indices(&gappy_candles(), TimeInterval::OneMinute)[0];
+    let x = (candle_x_position(index - 1, len) + candle_x_position(index, len)) / 2.0;
+    verts
+        .iter()
+        .filter(|v| (v.element_type - 3.0).abs() < f32::EPSILON)
+        .map(|v| v.position_x)
+        .filter(|vx| (vx - x).abs() < 0.01)
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn gap_markers_follow_the_toggle() {
+    let candles = gappy_candles();
+    let len = candles.len();
+    assert_eq!(gap_indices(&candles, TimeInterval::OneMinute), vec![14]);
+    let chart = chart_of(candles);
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    assert!(LineVisibility::default().gaps);
+    assert!(!marker_xs(&builder, &chart, len).is_empty());
+
+    builder.toggle_line_visibility("gaps");
+    assert!(!builder.line_visibility().gaps);
+    assert!(marker_xs(&builder, &chart, len).is_empty());
+}
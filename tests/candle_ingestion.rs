@@ -0,0 +1,92 @@
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Symbol, TimeInterval};
+use price_chart_wasm::infrastructure::websocket::{
+    BinanceWebSocketClient, ingest_candle, parse_coinbase_candles, parse_historical_klines,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn kline_message(open: &str, high: &str, low: &str, close: &str, volume: &str) -> String {
+    format!(
+        r#"{{"k":{{"t":60000,"o":"{open}","h":"{high}","l":"{low}","c":"{close}","v":"{volume}"}}}}"#
+    )
+}
+
+fn client() -> BinanceWebSocketClient {
+    BinanceWebSocketClient::new(Symbol::from("BTCUSDT"), TimeInterval::OneMinute)
+}
+
+#[wasm_bindgen_test]
+fn high_below_low_is_repaired() {
+    let msg = kline_message("100", "90", "110", "105", "1");
+    let candle = client().parse_message(&msg).unwrap();
+    assert!(candle.ohlcv.is_valid());
+    assert_eq!(candle.ohlcv.high.value(), 110.0);
+    assert_eq!(candle.ohlcv.low.value(), 90.0);
+    assert_eq!(candle.ohlcv.open.value(), 100.0);
+    assert_eq!(candle.ohlcv.close.value(), 105.0);
+}
+
+#[wasm_bindgen_test]
+fn negative_volume_is_rejected() {
+    let msg = kline_message("100", "110", "90", "105", "-1");
+    assert!(matches!(client().parse_message(&msg), Err(ChartError::InvalidData(_))));
+}
+
+#[wasm_bindgen_test]
+fn non_finite_prices_are_rejected() {
+    for bad in ["NaN", "inf", "-5"] {
+        let msg = kline_message("100", bad, "90", "105", "1");
+        assert!(matches!(client().parse_message(&msg), Err(ChartError::InvalidData(_))), "{bad}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn corrupt_history_rows_are_dropped() {
+    let body = r#"[
+        [60000,"100","110","90","105","1",119999,"0",0,"0","0","0"],
+        [120000,"100","110","90","105","-3",179999,"0",0,"0","0","0"],
+        [180000,"NaN","110","90","105","1",239999,"0",0,"0","0","0"],
+        [240000,"abc","110","90","105","1",299999,"0",0,"0","0","0"],
+        [300000,"100","95","99","105","1",359999,"0",0,"0","0","0"]
+    ]"#;
+    let candles = parse_historical_klines(body).unwrap();
+    let ts: Vec<u64> = candles.iter().map(|c| c.timestamp.value()).collect();
+    assert_eq!(ts, vec![60_000, 300_000]);
+    assert!(candles.iter().all(|c| c.ohlcv.is_valid()));
+    assert_eq!(candles[1].ohlcv.high.value(), 105.0);
+    assert_eq!(candles[1].ohlcv.low.value(), 95.0);
+}
+
+#[wasm_bindgen_test]
+fn corrupt_coinbase_rows_are_dropped() {
+    // [time, low, high, open, close, volume]
+    let body = "[[120, 90, 110, 100, 105, 1], [60, 90, 110, 100, 105, -2]]";
+    let candles = parse_coinbase_candles(body).unwrap();
+    assert_eq!(candles.len(), 1);
+    assert_eq!(candles[0].timestamp.value(), 120_000);
+}
+
+#[wasm_bindgen_test]
+fn invalid_trades_are_rejected() {
+    let msg = r#"{"e":"trade","E":1,"s":"BTCUSDT","t":1,"p":"-1","q":"1","T":1,"m":true,"M":true}"#;
+    assert!(matches!(client().parse_trade(msg), Err(ChartError::InvalidData(_))));
+}
+
+#[wasm_bindgen_test]
+fn validated_ohlcv_and_repair() {
+    assert!(OHLCV::validated(1.0, 2.0, 0.5, 1.5, f64::INFINITY).is_err());
+    let ohlcv = OHLCV::validated(1.0, 0.8, 1.2, 1.1, 3.0).unwrap().repaired();
+    assert_eq!((ohlcv.high.value(), ohlcv.low.value()), (1.2, 0.8));
+    let candle = ingest_candle(0, 1.0, 2.0, 0.5, 1.5, 3.0).unwrap();
+    assert_eq!(candle.ohlcv.high.value(), 2.0);
+}
+
+#[wasm_bindgen_test]
+fn ingestion_agrees_with_the_validated_constructor() {
+    let candle = Candle::from_raw(60_000.0, 10.0, 12.0, 9.0, 11.0, 5.0).unwrap();
+    assert_eq!(ingest_candle(60_000, 10.0, 12.0, 9.0, 11.0, 5.0).unwrap(), candle);
+    assert_eq!(candle.ohlcv, OHLCV::validated(10.0, 12.0, 9.0, 11.0, 5.0).unwrap());
+    let repaired = ingest_candle(60_000, 10.0, 9.0, 12.0, 11.0, 5.0).unwrap();
+    assert_eq!(repaired, candle);
+}
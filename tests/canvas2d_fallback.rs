@@ -0,0 +1,88 @@
+use price_chart_wasm::app::set_theme_preset;
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    Canvas2dRenderer, Renderer, ndc_to_canvas_px, set_fallback_renderer, with_fallback_renderer,
+};
+use price_chart_wasm::settings::ThemePreset;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn chart(count: u64) -> Chart {
+    let candles = (0..count).map(|i| {
+        let p = 100.0 + i as f64;
+        Candle::new(
+            Timestamp::from_millis(i * 60_000),
+            OHLCV::new(
+                Price::from(p),
+                Price::from(p + 2.0),
+                Price::from(p - 2.0),
+                Price::from(p + 1.0),
+                Volume::from(1.0),
+            ),
+        )
+    });
+    let mut chart = Chart::new("canvas2d".into(), ChartType::Candlestick, 100);
+    chart.set_historical_data(candles.collect());
+    chart
+}
+
+#[wasm_bindgen_test]
+fn ndc_maps_to_canvas_pixels() {
+    assert_eq!(ndc_to_canvas_px(-1.0, 1.0, 800.0, 600.0), (0.0, 0.0));
+    assert_eq!(ndc_to_canvas_px(1.0, -1.0, 800.0, 600.0), (800.0, 600.0));
+    assert_eq!(ndc_to_canvas_px(0.0, 0.0, 800.0, 600.0), (400.0, 300.0));
+}
+
+#[wasm_bindgen_test]
+fn canvas2d_renderer_draws_without_webgpu() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document
+        .create_element("canvas")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .unwrap();
+    canvas.set_id("canvas2d-fallback");
+    document.body().unwrap().append_child(&canvas).unwrap();
+
+    let mut renderer = Canvas2dRenderer::new("canvas2d-fallback", 200, 100).unwrap();
+    let backend: &mut dyn Renderer = &mut renderer;
+    assert_eq!(backend.backend_name(), "Canvas 2D");
+
+    // Empty and populated charts both draw
+    backend.render(&Chart::new("empty".into(), ChartType::Candlestick, 10)).unwrap();
+    backend.render(&chart(20)).unwrap();
+    assert_eq!(renderer.geometry().cached_instances().len(), 20);
+
+    // A zero size is ignored instead of breaking the next frame
+    renderer.resize(0, 0);
+    renderer.render(&chart(5)).unwrap();
+    assert_eq!(renderer.geometry().cached_instances().len(), 5);
+}
+
+#[wasm_bindgen_test]
+fn theme_changes_reach_the_fallback_renderer() {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document
+        .create_element("canvas")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .unwrap();
+    canvas.set_id("canvas2d-theme");
+    document.body().unwrap().append_child(&canvas).unwrap();
+    let renderer = Canvas2dRenderer::new("canvas2d-theme", 200, 100).unwrap();
+    set_fallback_renderer(Rc::new(RefCell::new(renderer)));
+
+    set_theme_preset(ThemePreset::Light);
+    let theme = with_fallback_renderer(|r| r.geometry_mut().theme());
+    assert_eq!(theme, Some(ThemePreset::Light.palette()));
+}
+
+#[wasm_bindgen_test]
+fn missing_canvas_is_an_error() {
+    assert!(Canvas2dRenderer::new("no-such-canvas", 10, 10).is_err());
+}
@@ -0,0 +1,27 @@
+use price_chart_wasm::app::{CHART_HEIGHT, CHART_WIDTH, backing_size, fit_canvas_size};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn canvas_keeps_its_aspect_ratio() {
+    let aspect = CHART_WIDTH / CHART_HEIGHT;
+    for available in [100.0, 480.0, 640.0, 2_000.0] {
+        let (width, height) = fit_canvas_size(available);
+        assert!((width / height - aspect).abs() < 1e-9);
+    }
+}
+
+#[wasm_bindgen_test]
+fn canvas_width_is_clamped() {
+    assert_eq!(fit_canvas_size(2_000.0).0, CHART_WIDTH);
+    assert_eq!(fit_canvas_size(640.0).0, 640.0);
+    assert_eq!(fit_canvas_size(10.0).0, 320.0);
+}
+
+#[wasm_bindgen_test]
+fn backing_store_scales_with_device_pixel_ratio() {
+    assert_eq!(backing_size(800.0, 500.0, 1.0), (800, 500));
+    assert_eq!(backing_size(800.0, 500.0, 2.0), (1600, 1000));
+    assert_eq!(backing_size(641.0, 400.625, 1.5), (962, 601));
+    assert_eq!(backing_size(0.0, 0.0, 2.0), (1, 1));
+}
@@ -0,0 +1,78 @@
+use futures::executor::block_on;
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{Price, Symbol, TimeInterval, Volume};
+use price_chart_wasm::infrastructure::websocket::{
+    BinanceWebSocketClient, CoinbaseDataSource, ConnectionState, FetchError, MarketDataSource,
+    parse_coinbase_candles,
+};
+use std::time::Duration;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn display_prefixes_the_category() {
+    let err = ChartError::Gpu("no adapter".to_string());
+    assert_eq!(err.category(), "GPU");
+    assert_eq!(err.message(), "no adapter");
+    assert_eq!(err.to_string(), "GPU error: no adapter");
+}
+
+#[wasm_bindgen_test]
+fn fetch_errors_map_to_network_or_parse() {
+    let limited = FetchError::RateLimited { retry_after: Some(Duration::from_secs(5)) };
+    assert_eq!(
+        ChartError::from(limited),
+        ChartError::Network("Rate limited, retry after 5s".to_string())
+    );
+    assert_eq!(
+        ChartError::from(FetchError::Status(503)),
+        ChartError::Network("HTTP error: 503".to_string())
+    );
+    assert_eq!(
+        ChartError::from(FetchError::Parse("bad row".to_string())),
+        ChartError::Parse("bad row".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn library_errors_convert_to_parse() {
+    let json = serde_json::from_str::<u32>("x").unwrap_err();
+    assert!(matches!(ChartError::from(json), ChartError::Parse(_)));
+    let float = "x".parse::<f64>().unwrap_err();
+    assert!(matches!(ChartError::from(float), ChartError::Parse(_)));
+}
+
+#[wasm_bindgen_test]
+fn value_objects_reject_invalid_data() {
+    assert!(matches!(Price::validate(-1.0), Err(ChartError::InvalidData(_))));
+    assert!(matches!(Volume::validate(f64::NAN), Err(ChartError::InvalidData(_))));
+    assert!(matches!(Symbol::new(String::new()), Err(ChartError::InvalidData(_))));
+}
+
+#[wasm_bindgen_test]
+fn malformed_messages_are_parse_errors() {
+    let client = BinanceWebSocketClient::new(Symbol::from("BTCUSDT"), TimeInterval::OneMinute);
+    assert!(matches!(client.parse_message("{"), Err(ChartError::Parse(_))));
+    assert!(matches!(parse_coinbase_candles("[[\"x\"]]"), Err(ChartError::Parse(_))));
+}
+
+#[wasm_bindgen_test]
+fn unsupported_coinbase_interval_is_reported() {
+    let source = CoinbaseDataSource::new();
+    let result = block_on(source.stream(&Symbol::from("BTCUSDT"), TimeInterval::OneMinute, |_| {}));
+    assert!(matches!(result, Err(ChartError::Unsupported(_))));
+}
+
+#[wasm_bindgen_test]
+fn unsupported_coinbase_interval_reports_an_error_state() {
+    let source = CoinbaseDataSource::new();
+    let mut states = Vec::new();
+    let result = block_on(source.stream_with_status(
+        &Symbol::from("BTCUSDT"),
+        TimeInterval::OneMinute,
+        |_| {},
+        |state| states.push(state),
+    ));
+    assert!(result.is_err());
+    assert!(matches!(states.as_slice(), [ConnectionState::Error(_)]));
+}
@@ -1,6 +1,8 @@
 use price_chart_wasm::domain::chart::Chart;
 use price_chart_wasm::domain::chart::value_objects::ChartType;
 use price_chart_wasm::domain::chart::value_objects::Viewport;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
 use wasm_bindgen_test::*;
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
@@ -25,3 +27,64 @@ fn horizontal_pan_moves_viewport() {
     assert!((chart.viewport.start_time - 10.0).abs() < 1e-6);
     assert!((chart.viewport.end_time - 110.0).abs() < 1e-6);
 }
+
+/// Body and volume bar centers of every drawn candle
+fn drawn_layout(builder: &ChartGeometryBuilder, chart: &Chart) -> Vec<(f32, f32)> {
+    let (instances, vertices, _) = builder.create_geometry(chart);
+    let volume: Vec<_> = vertices.iter().filter(|v| v.element_type == 5.0).collect();
+    assert_eq!(volume.len(), instances.len() * 6);
+    let centers: Vec<_> = instances
+        .iter()
+        .zip(volume.chunks(6))
+        .map(|(body, bar)| {
+            let left = bar.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+            let right = bar.iter().map(|v| v.position_x).fold(f32::NEG_INFINITY, f32::max);
+            (body.x, (left + right) / 2.0)
+        })
+        .collect();
+    assert!(!centers.is_empty());
+    centers
+}
+
+#[wasm_bindgen_test]
+fn candles_and_volume_pan_together() {
+    let mut chart = Chart::new("pan".to_string(), ChartType::Candlestick, 200);
+    chart.set_historical_data(
+        (0..100u64)
+            .map(|i| {
+                let open = 100.0 + i as f64;
+                Candle::new(
+                    Timestamp::from_millis(i * 60_000),
+                    OHLCV::new(
+                        Price::from(open),
+                        Price::from(open + 2.0),
+                        Price::from(open - 1.0),
+                        Price::from(open + 1.0),
+                        Volume::from(1.0 + i as f64),
+                    ),
+                )
+            })
+            .collect(),
+    );
+    // Show the first 32 of 100 candles so there is room to pan
+    chart.viewport.end_time = chart.viewport.start_time + 31.0 * 60_000.0;
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let start = drawn_layout(&builder, &chart);
+    let first = chart.viewport.start_time;
+
+    // Past the latest candle, partway back, then past the first one
+    let mut starts = Vec::new();
+    for delta in [2.0, -0.5, -2.0] {
+        chart.pan(delta, 0.0);
+        let centers = drawn_layout(&builder, &chart);
+        assert_eq!(centers.len(), start.len());
+        for (i, (body_x, volume_x)) in centers.iter().enumerate() {
+            assert!((body_x - volume_x).abs() < 1e-6, "pan {delta}: candle {i} desynced");
+            assert!((body_x - start[i].0).abs() < 1e-6, "pan {delta}: slot {i} moved");
+        }
+        starts.push(chart.viewport.start_time);
+    }
+    // The view moved through the data and stayed clamped to it at both ends
+    assert!(starts[0] > first && starts[1] < starts[0]);
+    assert_eq!(starts[2], first);
+}
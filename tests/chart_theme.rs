@@ -0,0 +1,79 @@
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    ChartTheme, ChartUniforms, IndicatorType,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn dark_theme_is_default() {
+    assert_eq!(ChartTheme::default(), ChartTheme::dark());
+    assert_eq!(ChartTheme::dark().bullish, [0.455, 0.780, 0.529, 1.0]);
+    assert_eq!(ChartTheme::dark().bearish, [0.882, 0.424, 0.282, 1.0]);
+}
+
+#[wasm_bindgen_test]
+fn light_theme_keeps_buy_sell_colors() {
+    let (dark, light) = (ChartTheme::dark(), ChartTheme::light());
+    assert_eq!(light.bullish, dark.bullish);
+    assert_eq!(light.bearish, dark.bearish);
+    assert_ne!(light.background, dark.background);
+    assert_ne!(light.grid, dark.grid);
+}
+
+#[wasm_bindgen_test]
+fn uniforms_copy_theme_colors() {
+    let theme = ChartTheme::light();
+    let uniforms = ChartUniforms::from_theme(&theme);
+    assert_eq!(uniforms.bullish_color, theme.bullish);
+    assert_eq!(uniforms.wick_color, theme.wick);
+    assert_eq!(uniforms.grid_color, theme.grid);
+    assert_eq!(uniforms.current_price_color, theme.current_price);
+    assert_eq!(uniforms.macd_signal_color, theme.macd_signal);
+    assert_eq!(ChartUniforms::new().grid_color, ChartTheme::dark().grid);
+}
+
+#[wasm_bindgen_test]
+fn indicator_colors_come_from_the_theme() {
+    let theme = ChartTheme { vwap: [0.1, 0.2, 0.3, 1.0], ..ChartTheme::light() };
+    let uniforms = ChartUniforms::from_theme(&theme);
+    assert_eq!(uniforms.vwap_color, theme.vwap);
+    assert_eq!(uniforms.bollinger_color, theme.bollinger);
+    assert_eq!(uniforms.price_line_color, theme.price_line);
+    assert_eq!(uniforms.stochastic_d_color, theme.stochastic_d);
+    assert_eq!(uniforms.keltner_color, theme.keltner);
+    assert_eq!(IndicatorType::Vwap.color(&theme), theme.vwap);
+    assert_eq!(IndicatorType::Atr.color(&theme), theme.atr);
+    assert_eq!(IndicatorType::RSI.color(&theme), theme.rsi);
+}
+
+/// Relative luminance of an sRGB color
+fn luminance(c: [f32; 4]) -> f32 {
+    let linear = |v: f32| {
+        if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(c[0]) + 0.7152 * linear(c[1]) + 0.0722 * linear(c[2])
+}
+
+/// WCAG contrast ratio of two opaque colors
+fn contrast(a: [f32; 4], b: [f32; 4]) -> f32 {
+    let (hi, lo) = (luminance(a).max(luminance(b)), luminance(a).min(luminance(b)));
+    (hi + 0.05) / (lo + 0.05)
+}
+
+#[wasm_bindgen_test]
+fn colorblind_theme_avoids_red_green() {
+    let theme = ChartTheme::colorblind();
+    // Blue buy, orange sell: told apart by the blue channel rather than red vs green
+    assert!(theme.bullish[2] > theme.bullish[0] && theme.bullish[2] > theme.bullish[1]);
+    assert!(theme.bearish[0] > theme.bearish[2]);
+    assert_ne!(theme.bullish, ChartTheme::dark().bullish);
+}
+
+#[wasm_bindgen_test]
+fn colorblind_grid_and_price_line_stand_out() {
+    let (dark, theme) = (ChartTheme::dark(), ChartTheme::colorblind());
+    assert!(contrast(theme.grid, theme.background) > contrast(dark.grid, dark.background));
+    assert!(contrast(theme.current_price, theme.background) >= 4.5);
+    assert!(contrast(theme.bullish, theme.background) > 1.5);
+    assert!(contrast(theme.bearish, theme.background) >= 3.0);
+}
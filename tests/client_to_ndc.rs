@@ -0,0 +1,32 @@
+use price_chart_wasm::event_utils::{CanvasRect, client_to_canvas_px, client_to_ndc_in};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const RECT: CanvasRect = CanvasRect { left: 100.0, top: 50.0, width: 800.0, height: 500.0 };
+
+#[wasm_bindgen_test]
+fn backing_pixels_scale_with_dpr() {
+    assert_eq!(client_to_canvas_px(500.0, 300.0, RECT, 1.0), (400.0, 250.0));
+    assert_eq!(client_to_canvas_px(500.0, 300.0, RECT, 2.0), (800.0, 500.0));
+}
+
+#[wasm_bindgen_test]
+fn ndc_is_independent_of_dpr() {
+    for dpr in [1.0, 1.5, 2.0, 3.0] {
+        let (x, y) = client_to_ndc_in(500.0, 300.0, RECT, dpr);
+        assert!(x.abs() < 1e-6 && y.abs() < 1e-6, "center at dpr {dpr}: {x},{y}");
+
+        let (x, y) = client_to_ndc_in(100.0, 50.0, RECT, dpr);
+        assert!((x + 1.0).abs() < 1e-6 && (y - 1.0).abs() < 1e-6);
+
+        let (x, y) = client_to_ndc_in(900.0, 550.0, RECT, dpr);
+        assert!((x - 1.0).abs() < 1e-6 && (y + 1.0).abs() < 1e-6);
+    }
+}
+
+#[wasm_bindgen_test]
+fn scrolled_rect_shifts_origin() {
+    // After scrolling down by 200px the rect top moves up by the same amount
+    let scrolled = CanvasRect { top: RECT.top - 200.0, ..RECT };
+    assert_eq!(client_to_ndc_in(500.0, 100.0, scrolled, 2.0), (0.0, 0.0));
+}
@@ -0,0 +1,92 @@
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{Symbol, TimeInterval};
+use price_chart_wasm::infrastructure::websocket::{
+    BinanceStream, BinanceWebSocketClient, StreamEvent, Trade, stream_url,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const KLINE: &str = r#"{"e":"kline","E":1,"s":"BTCUSDT","k":{"t":60000,"o":"100","h":"110","l":"90","c":"105","v":"2"}}"#;
+const TRADE: &str =
+    r#"{"e":"trade","E":1,"s":"BTCUSDT","t":7,"p":"101.5","q":"0.25","T":61000,"m":true,"M":true}"#;
+const DEPTH: &str =
+    r#"{"e":"depthUpdate","E":1,"s":"BTCUSDT","U":10,"u":12,"b":[["100","1"]],"a":[["101","0"]]}"#;
+
+fn combined(stream: &str, data: &str) -> String {
+    format!(r#"{{"stream":"{stream}","data":{data}}}"#)
+}
+
+fn client(interval: TimeInterval) -> BinanceWebSocketClient {
+    BinanceWebSocketClient::new(Symbol::from("BTCUSDT"), interval)
+}
+
+#[wasm_bindgen_test]
+fn one_stream_uses_the_raw_endpoint() {
+    let client = client(TimeInterval::OneMinute);
+    assert_eq!(client.streams(), [BinanceStream::Kline]);
+    assert_eq!(
+        stream_url(&client.stream_names()),
+        "wss://stream.binance.com:9443/ws/btcusdt@kline_1m"
+    );
+    // Intervals without a kline read trades instead
+    let names = self::client(TimeInterval::TwoSeconds).stream_names();
+    assert_eq!(names, ["btcusdt@trade"]);
+}
+
+#[wasm_bindgen_test]
+fn several_streams_share_the_combined_endpoint() {
+    let mut client = client(TimeInterval::OneMinute);
+    client.subscribe(BinanceStream::Trade);
+    client.subscribe(BinanceStream::Depth);
+    client.subscribe(BinanceStream::Trade);
+    assert_eq!(client.streams().len(), 3);
+    assert_eq!(
+        stream_url(&client.stream_names()),
+        "wss://stream.binance.com:9443/stream?streams=btcusdt@kline_1m/btcusdt@trade/btcusdt@depth@100ms"
+    );
+}
+
+#[wasm_bindgen_test]
+fn stream_names_map_back_to_their_kind() {
+    for kind in [BinanceStream::Kline, BinanceStream::Trade, BinanceStream::Depth] {
+        let name = kind.name(&Symbol::from("ETHUSDT"), TimeInterval::FiveMinutes);
+        assert_eq!(BinanceStream::from_name(&name), Some(kind), "{name}");
+    }
+    assert_eq!(BinanceStream::from_name("btcusdt@bookTicker"), None);
+    assert_eq!(BinanceStream::from_name("garbage"), None);
+}
+
+#[wasm_bindgen_test]
+fn combined_messages_are_routed_by_stream_key() {
+    let mut client = client(TimeInterval::OneMinute);
+    client.subscribe(BinanceStream::Trade);
+    client.subscribe(BinanceStream::Depth);
+
+    let candle = client.parse_event(&combined("btcusdt@kline_1m", KLINE)).unwrap();
+    assert_eq!(candle, StreamEvent::Candle(client.parse_message(KLINE).unwrap()));
+
+    let trade = client.parse_event(&combined("btcusdt@trade", TRADE)).unwrap();
+    assert_eq!(
+        trade,
+        StreamEvent::Trade(Trade { price: 101.5, quantity: 0.25, timestamp: 61_000 })
+    );
+
+    let StreamEvent::Depth(update) =
+        client.parse_event(&combined("btcusdt@depth@100ms", DEPTH)).unwrap()
+    else {
+        panic!("expected a depth update");
+    };
+    assert_eq!((update.first_update_id, update.final_update_id), (10, 12));
+    assert_eq!(update.bids, vec![(100.0, 1.0)]);
+
+    let unknown = client.parse_event(&combined("btcusdt@bookTicker", "{}"));
+    assert!(matches!(unknown, Err(ChartError::Parse(_))));
+}
+
+#[wasm_bindgen_test]
+fn single_stream_messages_are_bare_events() {
+    let event = client(TimeInterval::OneMinute).parse_event(KLINE).unwrap();
+    assert!(matches!(event, StreamEvent::Candle(_)));
+    let event = client(TimeInterval::TwoSeconds).parse_event(TRADE).unwrap();
+    assert!(matches!(event, StreamEvent::Trade(_)));
+}
@@ -0,0 +1,58 @@
+use price_chart_wasm::domain::chart::{CSV_HEADER, Chart, ChartType, candles_from_csv};
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, TimeInterval, Timestamp, Volume,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn make_candle(i: u64) -> Candle {
+    let base = 100.0 + i as f64 * 0.37;
+    Candle::new(
+        Timestamp::new(1_700_000_000_000 + i * 2_000),
+        OHLCV::new(
+            Price::new(base),
+            Price::new(base + 1.25),
+            Price::new(base - 0.1),
+            Price::new(base + 0.5),
+            Volume::new(3.0 + i as f64 / 7.0),
+        ),
+    )
+}
+
+fn chart_with(n: u64) -> (Chart, Vec<Candle>) {
+    let candles: Vec<Candle> = (0..n).map(make_candle).collect();
+    let mut chart = Chart::new("csv".to_string(), ChartType::Candlestick, 100);
+    chart.set_historical_data(candles.clone());
+    (chart, candles)
+}
+
+#[wasm_bindgen_test]
+fn csv_round_trips_all_candles() {
+    let (chart, candles) = chart_with(10);
+    let csv = chart.to_csv(TimeInterval::TwoSeconds, None);
+    assert_eq!(candles_from_csv(&csv).unwrap(), candles);
+}
+
+#[wasm_bindgen_test]
+fn window_limits_the_rows() {
+    let (chart, candles) = chart_with(10);
+    let csv = chart.to_csv(TimeInterval::TwoSeconds, Some((3, 4)));
+    assert_eq!(candles_from_csv(&csv).unwrap(), candles[3..7].to_vec());
+}
+
+#[wasm_bindgen_test]
+fn rows_carry_epoch_and_iso_times() {
+    let (chart, _) = chart_with(1);
+    let csv = chart.to_csv(TimeInterval::TwoSeconds, None);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(CSV_HEADER));
+    assert_eq!(lines.next(), Some("1700000000000,2023-11-14T22:13:20Z,100,101.25,99.9,100.5,3"));
+    assert_eq!(lines.next(), None);
+}
+
+#[wasm_bindgen_test]
+fn malformed_csv_is_rejected() {
+    assert!(candles_from_csv("a,b\n").is_err());
+    assert!(candles_from_csv(&format!("{CSV_HEADER}\n1,t,1,2,0.5\n")).is_err());
+    assert!(candles_from_csv(&format!("{CSV_HEADER}\n1,t,x,2,0.5,1,1\n")).is_err());
+}
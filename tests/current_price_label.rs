@@ -0,0 +1,66 @@
+use leptos::*;
+use price_chart_wasm::app::{
+    CURRENT_PRICE_LABEL_HEIGHT_PX, css_hex, current_price_label_top, current_price_marker,
+    global_current_price, publish_frame_overlays,
+};
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::ChartTheme;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn label_is_centered_on_the_line() {
+    assert!((current_price_label_top(0.0, 600.0) - 300.0).abs() < 1e-9);
+    assert!((current_price_label_top(0.5, 600.0) - 150.0).abs() < 1e-9);
+    assert!((current_price_label_top(-0.5, 600.0) - 450.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn label_stays_inside_the_canvas() {
+    let half = CURRENT_PRICE_LABEL_HEIGHT_PX / 2.0;
+    assert_eq!(current_price_label_top(1.0, 600.0), half);
+    assert_eq!(current_price_label_top(-1.0, 600.0), 600.0 - half);
+    // Prices panned out of view pin the label to the nearest edge
+    assert_eq!(current_price_label_top(3.0, 600.0), half);
+    assert_eq!(current_price_label_top(-3.0, 600.0), 600.0 - half);
+    // A canvas shorter than the label never yields an inverted range
+    assert_eq!(current_price_label_top(0.0, 10.0), half);
+}
+
+#[wasm_bindgen_test]
+fn label_background_matches_the_line_color() {
+    assert_eq!(css_hex(ChartTheme::dark().current_price), "#ffff00");
+    assert_eq!(css_hex(ChartTheme::light().current_price), "#cc9900");
+    assert_eq!(css_hex([2.0, -1.0, 0.5, 1.0]), "#ff0080");
+}
+
+#[wasm_bindgen_test]
+fn built_frames_publish_the_label_position() {
+    let mut chart = Chart::new("label".into(), ChartType::Candlestick, 10);
+    chart.add_candle(Candle::new(
+        Timestamp::from_millis(0),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(2.0),
+            Price::from(0.5),
+            Price::from(1.5),
+            Volume::from(1.0),
+        ),
+    ));
+    global_current_price().set(1.5);
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.update(&chart);
+
+    publish_frame_overlays(&builder, &chart);
+    let y = builder.current_price_y_ndc().unwrap();
+    assert_eq!(current_price_marker().get_untracked(), Some((y, builder.theme().current_price)));
+
+    // A placeholder frame has no price line
+    builder.update_placeholder();
+    publish_frame_overlays(&builder, &chart);
+    assert_eq!(current_price_marker().get_untracked(), None);
+}
@@ -23,7 +23,7 @@ fn pan_reset_shows_latest_candle() {
     }
 
     let pan = 0.0;
-    chart.update_viewport_for_data();
+    chart.recompute_viewport();
 
     let len = chart.get_candle_count();
     let (start, visible) = visible_range(len, 1.0, pan);
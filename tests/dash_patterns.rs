@@ -0,0 +1,68 @@
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    CandleGeometry, CandleVertex, DashPattern, IndicatorType, LineStyles,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn dash_segment_count_for_length() {
+    let pattern = DashPattern { on: 0.2, off: 0.1 };
+    // Period 0.3 over length 2.0: six full dashes plus a clipped one at 1.8..2.0
+    let segments = pattern.segments(2.0, 0.0);
+    assert_eq!(segments.len(), 7);
+    assert!((segments[6].0 - 1.8).abs() < 1e-4);
+    assert!((segments[6].1 - 2.0).abs() < 1e-4);
+
+    assert_eq!(DashPattern::SOLID.segments(2.0, 0.3), vec![(0.0, 2.0)]);
+    assert!(pattern.segments(0.0, 0.0).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn dashes_stay_put_under_pan() {
+    let pattern = DashPattern { on: 0.2, off: 0.1 };
+    // Line starting 0.45 further along the pattern, as after panning by 0.45 NDC
+    let full = pattern.segments(3.0, 0.0);
+    let shifted = pattern.segments(2.55, 0.45);
+
+    for (a, b) in shifted {
+        let (a, b) = (a + 0.45, b + 0.45);
+        assert!(
+            full.iter().any(|(fa, fb)| a >= fa - 1e-4 && b <= fb + 1e-4),
+            "dash {a}..{b} moved"
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn dashed_lines_emit_one_quad_per_dash() {
+    let pattern = DashPattern { on: 0.2, off: 0.1 };
+    let verts = CandleGeometry::create_horizontal_line(
+        0.0,
+        0.01,
+        pattern,
+        CandleVertex::current_price_vertex,
+    );
+    assert_eq!(verts.len(), 7 * 6);
+
+    let points = [(-1.0, 0.0), (1.0, 0.0)];
+    let solid = CandleGeometry::create_indicator_line_vertices(&points, IndicatorType::SMA20, 0.1);
+    let dashed = CandleGeometry::create_dashed_indicator_line_vertices(
+        &points,
+        IndicatorType::SMA20,
+        0.1,
+        pattern,
+        0.0,
+    );
+    assert_eq!(solid.len(), 6);
+    assert_eq!(dashed.len(), 7 * 6);
+}
+
+#[wasm_bindgen_test]
+fn default_styles_keep_lines_solid() {
+    let styles = LineStyles::default();
+    assert!(styles.grid.is_solid());
+    assert!(styles.current_price.is_solid());
+    assert!(styles.moving_averages.is_solid());
+    assert!(styles.session_levels.is_solid());
+    assert!(!styles.alerts.is_solid());
+}
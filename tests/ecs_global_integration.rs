@@ -28,7 +28,7 @@ fn push_candle_updates_world() {
             Volume::from(1.0),
         ),
     );
-    push_realtime_candle(candle);
+    push_realtime_candle(&symbol, candle);
     let world_ref = ecs_world().lock().unwrap();
     let mut query = world_ref.world.query::<&ChartComponent>();
     let chart_comp = query.iter().next().expect("chart component").1;
@@ -1,8 +1,16 @@
 use price_chart_wasm::domain::chart::{Chart, value_objects::ChartType};
-use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::services::{MarketAnalysisService, MovingAveragesData};
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, Symbol, TimeInterval, Timestamp, Volume,
+};
 use price_chart_wasm::ecs::EcsWorld;
-use price_chart_wasm::ecs::components::CandleComponent;
 use price_chart_wasm::ecs::components::ChartComponent;
+use price_chart_wasm::ecs::components::PendingCandle;
+use price_chart_wasm::ecs::components::{IndicatorComponent, RenderTargetComponent};
+use price_chart_wasm::ecs::systems::{Schedule, render_charts};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::{MaCacheUpdate, Renderer};
 
 #[test]
 fn world_starts_empty() {
@@ -22,7 +30,7 @@ fn spawn_chart_entity() {
 #[test]
 fn candle_system_applies_candles() {
     let mut world = EcsWorld::new();
-    let chart = Chart::new("test".into(), ChartType::Candlestick, 10);
+    let chart = Chart::new("TEST".into(), ChartType::Candlestick, 10);
     world.spawn_chart(chart.clone());
 
     let candle = Candle::new(
@@ -35,7 +43,7 @@ fn candle_system_applies_candles() {
             Volume::from(1.0),
         ),
     );
-    world.world.spawn((CandleComponent(candle.clone()),));
+    world.world.spawn((PendingCandle { symbol: Symbol::from("TEST"), candle: candle.clone() },));
 
     world.run_candle_system();
 
@@ -44,3 +52,215 @@ fn candle_system_applies_candles() {
     assert_eq!(chart_comp.0.get_candle_count(), 1);
     assert_eq!(world.world.len(), 1);
 }
+
+fn rising_candles(n: u64) -> Vec<Candle> {
+    (0..n)
+        .map(|i| {
+            let p = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(p),
+                    Price::from(p + 1.0),
+                    Price::from(p - 1.0),
+                    Price::from(p),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn target() -> RenderTargetComponent {
+    RenderTargetComponent { interval: TimeInterval::OneMinute, zoom_level: 1.0, pan_offset: 0.0 }
+}
+
+#[test]
+fn schedule_runs_systems_in_order() {
+    fn mark_first(world: &mut hecs::World) {
+        world.spawn((1u8,));
+    }
+    fn mark_second(world: &mut hecs::World) {
+        let seen = world.query::<&u8>().iter().count();
+        world.spawn((seen as u16,));
+    }
+    let mut world = hecs::World::new();
+    Schedule::new().with_system(mark_first).with_system(mark_second).run(&mut world);
+    let mut query = world.query::<&u16>();
+    assert_eq!(query.iter().next().map(|(_, v)| *v), Some(1));
+}
+
+#[test]
+fn indicator_system_only_computes_render_targets() {
+    let mut world = EcsWorld::new();
+    let mut shown = Chart::new("shown".into(), ChartType::Candlestick, 300);
+    shown.set_historical_data(rising_candles(30));
+    let mut hidden = Chart::new("hidden".into(), ChartType::Candlestick, 300);
+    hidden.set_historical_data(rising_candles(30));
+    let shown_entity = world.spawn_chart(shown);
+    let hidden_entity = world.spawn_chart(hidden);
+
+    assert!(world.set_render_target("shown", target()));
+    world.run_frame();
+
+    let indicators =
+        world.world.get::<&IndicatorComponent>(shown_entity).expect("indicators computed");
+    assert_eq!(indicators.source.len, 30);
+    assert_eq!(indicators.moving_averages.sma_20.len(), 11);
+    assert!(world.world.get::<&IndicatorComponent>(hidden_entity).is_err());
+}
+
+#[test]
+fn indicator_system_refreshes_on_forming_candle_update() {
+    let mut world = EcsWorld::new();
+    let mut chart = Chart::new("LIVE".into(), ChartType::Candlestick, 300);
+    let candles = rising_candles(20);
+    chart.set_historical_data(candles.clone());
+    let entity = world.spawn_chart(chart);
+    world.set_render_target("LIVE", target());
+    world.run_frame();
+    let before = world.world.get::<&IndicatorComponent>(entity).unwrap().source;
+
+    let mut forming = candles.last().unwrap().clone();
+    forming.ohlcv.close = Price::from(500.0);
+    world.world.spawn((PendingCandle { symbol: Symbol::from("LIVE"), candle: forming },));
+    world.run_frame();
+
+    let after = world.world.get::<&IndicatorComponent>(entity).unwrap();
+    assert_eq!(after.source.len, before.len);
+    assert_ne!(after.source, before);
+    assert!(after.moving_averages.sma_20[0].value() > 100.0 + 19.0 / 2.0);
+}
+
+#[test]
+fn indicator_system_updates_live_ticks_incrementally() {
+    let mut world = EcsWorld::new();
+    let mut chart = Chart::new("LIVE".into(), ChartType::Candlestick, 300);
+    let mut candles = rising_candles(60);
+    chart.set_historical_data(candles.clone());
+    let entity = world.spawn_chart(chart);
+    world.set_render_target("LIVE", target());
+    world.run_frame();
+    assert_eq!(
+        world.world.get::<&IndicatorComponent>(entity).unwrap().last_update,
+        MaCacheUpdate::Rebuild
+    );
+
+    let mut forming = candles.last().unwrap().clone();
+    forming.ohlcv.close = Price::from(42.0);
+    *candles.last_mut().unwrap() = forming.clone();
+    world.world.spawn((PendingCandle { symbol: Symbol::from("LIVE"), candle: forming },));
+    world.run_frame();
+
+    let after = world.world.get::<&IndicatorComponent>(entity).unwrap();
+    assert_eq!(after.last_update, MaCacheUpdate::Incremental { from: 59 });
+    let scratch = MarketAnalysisService::new().calculate_multiple_mas(&candles);
+    for (kind, period) in MovingAveragesData::LINES {
+        assert_eq!(after.moving_averages.get(kind, period), scratch.get(kind, period));
+    }
+}
+
+#[test]
+fn render_target_moves_between_charts() {
+    let mut world = EcsWorld::new();
+    let first = world.spawn_chart(Chart::new("a".into(), ChartType::Candlestick, 10));
+    let second = world.spawn_chart(Chart::new("b".into(), ChartType::Candlestick, 10));
+
+    assert!(world.set_render_target("a", target()));
+    assert!(world.set_render_target("b", target()));
+    assert!(world.world.get::<&RenderTargetComponent>(first).is_err());
+    assert!(world.world.get::<&RenderTargetComponent>(second).is_ok());
+    assert!(!world.set_render_target("missing", target()));
+}
+
+/// Renderer remembering the candle count of every chart it was asked to draw
+struct RecordingRenderer {
+    geometry: ChartGeometryBuilder,
+    rendered: Vec<usize>,
+}
+
+impl Renderer for RecordingRenderer {
+    fn render(&mut self, chart: &Chart) -> Result<(), ChartError> {
+        self.rendered.push(chart.get_candle_count());
+        Ok(())
+    }
+
+    fn resize(&mut self, _: u32, _: u32) {}
+
+    fn geometry_mut(&mut self) -> &mut ChartGeometryBuilder {
+        &mut self.geometry
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "recording"
+    }
+}
+
+#[test]
+fn empty_charts_still_reach_the_renderer() {
+    let mut world = EcsWorld::new();
+    world.spawn_chart(Chart::new("EMPTY".into(), ChartType::Candlestick, 10));
+    world.set_render_target("EMPTY", target());
+    world.run_frame();
+
+    let mut renderer =
+        RecordingRenderer { geometry: ChartGeometryBuilder::new(800, 600), rendered: Vec::new() };
+    render_charts(&world.world, &mut renderer).unwrap();
+    assert_eq!(renderer.rendered, vec![0]);
+}
+
+fn pending(symbol: &str, candle: Candle) -> (PendingCandle,) {
+    (PendingCandle { symbol: Symbol::from(symbol), candle },)
+}
+
+fn base_closes(world: &EcsWorld, id: &str) -> Vec<f64> {
+    let mut query = world.world.query::<&ChartComponent>();
+    let (_, chart) = query.iter().find(|(_, c)| c.0.id == id).expect("chart");
+    chart
+        .0
+        .get_series(TimeInterval::TwoSeconds)
+        .expect("base series")
+        .get_candles()
+        .iter()
+        .map(|c| c.ohlcv.close.value())
+        .collect()
+}
+
+#[test]
+fn pending_candles_append_and_update_matching_chart() {
+    let mut world = EcsWorld::new();
+    world.spawn_chart(Chart::new("BTCUSDT".into(), ChartType::Candlestick, 10));
+    world.spawn_chart(Chart::new("ETHUSDT".into(), ChartType::Candlestick, 10));
+
+    let candles = rising_candles(3);
+    let mut updated = candles[2].clone();
+    updated.ohlcv.close = Price::from(250.0);
+    for candle in candles.iter().cloned().chain([updated]) {
+        world.world.spawn(pending("BTCUSDT", candle));
+    }
+    world.world.spawn(pending("ETHUSDT", candles[0].clone()));
+    world.world.spawn(pending("SOLUSDT", candles[0].clone()));
+
+    world.run_candle_system();
+
+    assert_eq!(base_closes(&world, "BTCUSDT"), vec![100.0, 101.0, 250.0]);
+    assert_eq!(base_closes(&world, "ETHUSDT"), vec![100.0]);
+    assert_eq!(world.world.query::<&PendingCandle>().iter().count(), 0);
+    assert_eq!(world.world.len(), 2);
+}
+
+#[test]
+fn pending_candles_respect_chart_capacity() {
+    let mut world = EcsWorld::new();
+    world.spawn_chart(Chart::new("BTCUSDT".into(), ChartType::Candlestick, 300));
+
+    for candle in rising_candles(305) {
+        world.world.spawn(pending("BTCUSDT", candle));
+    }
+    world.run_candle_system();
+
+    let closes = base_closes(&world, "BTCUSDT");
+    assert_eq!(closes.len(), 300);
+    assert_eq!(closes.first().copied(), Some(105.0));
+    assert_eq!(closes.last().copied(), Some(404.0));
+}
@@ -0,0 +1,61 @@
+use price_chart_wasm::app::empty_chart_state;
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::EmptyChartState;
+use price_chart_wasm::infrastructure::websocket::ConnectionState;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn connection_state_decides_between_loading_and_no_data() {
+    use EmptyChartState::{Loading, NoData};
+    assert_eq!(empty_chart_state(&ConnectionState::Offline, false), Loading);
+    assert_eq!(empty_chart_state(&ConnectionState::Connecting, false), Loading);
+    assert_eq!(empty_chart_state(&ConnectionState::Reconnecting, false), Loading);
+    assert_eq!(empty_chart_state(&ConnectionState::Live, false), NoData);
+    assert_eq!(empty_chart_state(&ConnectionState::Paused, false), NoData);
+    assert_eq!(empty_chart_state(&ConnectionState::Error("down".into()), false), NoData);
+    // A failed history load is final whatever the stream does
+    assert_eq!(empty_chart_state(&ConnectionState::Connecting, true), NoData);
+}
+
+#[wasm_bindgen_test]
+fn placeholders_differ_and_carry_no_price_range() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (loading, uniforms) = builder.placeholder_geometry(EmptyChartState::Loading);
+    let (no_data, _) = builder.placeholder_geometry(EmptyChartState::NoData);
+
+    assert!(!loading.is_empty() && !no_data.is_empty());
+    assert!(loading.len() > no_data.len());
+    assert!(loading.iter().chain(&no_data).all(|v| v.draws_under_candles()));
+    assert_eq!(uniforms.viewport[2], uniforms.viewport[3]);
+}
+
+#[wasm_bindgen_test]
+fn placeholder_is_cached_until_candles_arrive() {
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    assert_eq!(builder.empty_state(), EmptyChartState::Loading);
+    assert!(builder.update_placeholder());
+    assert!(!builder.update_placeholder());
+    assert!(builder.cached_instances().is_empty());
+
+    builder.set_empty_state(EmptyChartState::NoData);
+    assert!(builder.update_placeholder());
+
+    let mut chart = Chart::new("placeholder".into(), ChartType::Candlestick, 10);
+    chart.add_candle(Candle::new(
+        Timestamp::from_millis(0),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(2.0),
+            Price::from(0.5),
+            Price::from(1.5),
+            Volume::from(1.0),
+        ),
+    ));
+    assert_eq!(builder.update(&chart), Some(true));
+    assert_eq!(builder.cached_instances().len(), 1);
+}
@@ -0,0 +1,47 @@
+use price_chart_wasm::app::{FORMING_CANDLE_THROTTLE_MS, FormingCandleThrottle};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn forming_candle_updates_are_rate_limited() {
+    let mut throttle = FormingCandleThrottle::new(FORMING_CANDLE_THROTTLE_MS);
+
+    assert!(throttle.should_render(60_000, 0.0));
+    assert!(!throttle.should_render(60_000, 40.0));
+    assert!(!throttle.should_render(60_000, 99.0));
+    assert!(throttle.should_render(60_000, 100.0));
+}
+
+#[wasm_bindgen_test]
+fn new_candle_always_renders() {
+    let mut throttle = FormingCandleThrottle::new(FORMING_CANDLE_THROTTLE_MS);
+
+    assert!(throttle.should_render(60_000, 0.0));
+    assert!(throttle.should_render(120_000, 10.0));
+    assert!(!throttle.should_render(120_000, 20.0));
+}
+
+#[wasm_bindgen_test]
+fn zero_interval_disables_throttling() {
+    let mut throttle = FormingCandleThrottle::new(0.0);
+
+    assert!(throttle.should_render(60_000, 0.0));
+    assert!(throttle.should_render(60_000, 0.0));
+}
+
+#[wasm_bindgen_test]
+fn skipped_updates_get_one_trailing_render() {
+    let mut throttle = FormingCandleThrottle::new(FORMING_CANDLE_THROTTLE_MS);
+
+    assert_eq!(throttle.trailing_render_delay(0.0), None);
+    assert!(throttle.should_render(60_000, 0.0));
+    assert!(!throttle.should_render(60_000, 40.0));
+    assert_eq!(throttle.trailing_render_delay(40.0), Some(60.0));
+    assert!(!throttle.should_render(60_000, 70.0));
+    assert_eq!(throttle.trailing_render_delay(70.0), None);
+
+    // The next interval schedules its own trailing render
+    assert!(throttle.should_render(60_000, 120.0));
+    assert!(!throttle.should_render(60_000, 150.0));
+    assert_eq!(throttle.trailing_render_delay(150.0), Some(70.0));
+}
@@ -0,0 +1,15 @@
+use price_chart_wasm::app::format_frame_timing;
+use price_chart_wasm::infrastructure::rendering::renderer::FrameTiming;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn readout_is_zero_before_first_frame() {
+    assert_eq!(format_frame_timing(FrameTiming::default()), "0 fps • 0.0 ms");
+}
+
+#[wasm_bindgen_test]
+fn readout_rounds_fps_and_frame_time() {
+    let timing = FrameTiming { fps: 59.7, frame_ms: 16.75 };
+    assert_eq!(format_frame_timing(timing), "60 fps • 16.8 ms");
+}
@@ -0,0 +1,53 @@
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, TimeInterval, Timestamp, Volume,
+};
+use price_chart_wasm::infrastructure::websocket::binance_client::{
+    backfill_limit, backfill_seam, has_gap,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+fn candle(ts: u64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(ts),
+        OHLCV::new(
+            Price::from(close),
+            Price::from(close),
+            Price::from(close),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn gap_detected_beyond_one_interval() {
+    let interval = TimeInterval::OneMinute;
+    assert!(!has_gap(0, 0, interval));
+    assert!(!has_gap(0, MINUTE, interval));
+    assert!(has_gap(0, 3 * MINUTE, interval));
+}
+
+#[wasm_bindgen_test]
+fn backfill_limit_covers_range() {
+    let interval = TimeInterval::OneMinute;
+    assert_eq!(backfill_limit(0, 5 * MINUTE, interval), 6);
+    assert_eq!(backfill_limit(0, 100_000 * MINUTE, interval), 1000);
+}
+
+#[wasm_bindgen_test]
+fn seam_is_ordered_and_deduplicated() {
+    let fetched = vec![
+        candle(4 * MINUTE, 4.0),
+        candle(MINUTE, 0.5),
+        candle(2 * MINUTE, 2.0),
+        candle(3 * MINUTE, 3.0),
+        candle(2 * MINUTE, 2.0),
+        candle(5 * MINUTE, 5.0),
+    ];
+    let seam = backfill_seam(2 * MINUTE, 5 * MINUTE, fetched);
+    let stamps: Vec<u64> = seam.iter().map(|c| c.timestamp.value()).collect();
+    assert_eq!(stamps, vec![2 * MINUTE, 3 * MINUTE, 4 * MINUTE]);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "bench")]
+
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    BENCH_CANDLE_COUNTS, BENCH_ITERATIONS, geometry_bench, moving_average_bench,
+    run_geometry_bench, run_moving_average_bench,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn bench_covers_every_size_with_and_without_indicators() {
+    let results = geometry_bench();
+    assert_eq!(results.len(), BENCH_CANDLE_COUNTS.len() * 2);
+    for pair in results.chunks(2) {
+        let (off, on) = (&pair[0], &pair[1]);
+        assert_eq!(off.candles, on.candles);
+        assert!(!off.indicators && on.indicators);
+        assert_eq!(off.iterations, BENCH_ITERATIONS);
+        assert!(off.instances > 0 && off.instances == on.instances);
+        assert!(on.vertices > off.vertices);
+        assert!(off.mean_ms >= 0.0 && on.mean_ms >= 0.0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn bench_reports_json() {
+    let json: serde_json::Value = serde_json::from_str(&run_geometry_bench()).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), BENCH_CANDLE_COUNTS.len() * 2);
+    for key in ["candles", "indicators", "iterations", "mean_ms", "instances", "vertices"] {
+        assert!(entries[0].get(key).is_some(), "missing {key}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn moving_average_bench_times_both_paths() {
+    let results = moving_average_bench();
+    assert_eq!(results.len(), BENCH_CANDLE_COUNTS.len());
+    for result in &results {
+        assert_eq!(result.iterations, BENCH_ITERATIONS);
+        assert!(result.full_ms >= 0.0 && result.incremental_ms >= 0.0 && result.speedup > 0.0);
+    }
+    let json: serde_json::Value = serde_json::from_str(&run_moving_average_bench()).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), BENCH_CANDLE_COUNTS.len());
+}
@@ -0,0 +1,25 @@
+use price_chart_wasm::app::{ease_toward_latest, should_auto_scroll};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn glide_moves_towards_latest() {
+    let next = ease_toward_latest(-100.0, 16.0);
+    assert!(next > -100.0 && next < 0.0);
+
+    // Longer frames cover more distance
+    assert!(ease_toward_latest(-100.0, 32.0) > next);
+}
+
+#[wasm_bindgen_test]
+fn glide_reaches_right_edge() {
+    let mut pan = -250.0;
+    let mut frames = 0;
+    while pan != 0.0 {
+        pan = ease_toward_latest(pan, 16.0);
+        frames += 1;
+        assert!(frames < 100);
+    }
+    assert!(should_auto_scroll(300, 1.0, pan));
+    assert_eq!(ease_toward_latest(-0.4, 16.0), 0.0);
+}
@@ -0,0 +1,233 @@
+use leptos::SignalGetUntracked;
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume, services::MarketAnalysisService},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    CandleVertex, ChartTheme, packed_line_color,
+};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    FLAT_PRICE_SPAN_RATIO, LineVisibility, XAxis, candle_x_position, fit_price_range,
+    moving_average_points, time_grid_indices,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// Rising closes with volume growing by one per candle
+fn fixture(count: u64) -> Vec<Candle> {
+    (0..count)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(close - 0.5),
+                    Price::from(close + 1.0),
+                    Price::from(close - 1.0),
+                    Price::from(close),
+                    Volume::from(1.0 + i as f64),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn chart_of(candles: Vec<Candle>) -> Chart {
+    let mut chart = Chart::new("headless".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    chart
+}
+
+fn only_sma20() -> LineVisibility {
+    LineVisibility {
+        sma_20: true,
+        sma_50: false,
+        sma_200: false,
+        ema_12: false,
+        ema_26: false,
+        ..LineVisibility::default()
+    }
+}
+
+fn is_element(v: &CandleVertex, element_type: f32) -> bool {
+    (v.element_type - element_type).abs() < f32::EPSILON
+}
+
+#[wasm_bindgen_test]
+fn candle_x_position_steps_evenly() {
+    let len = 30;
+    let step = 2.0 / len as f32;
+    for i in 1..len {
+        let delta = candle_x_position(i, len) - candle_x_position(i - 1, len);
+        assert!((delta - step).abs() < 1e-6);
+    }
+    assert!(candle_x_position(0, len) > -1.0);
+    assert!(candle_x_position(len - 1, len) < 1.0);
+}
+
+#[wasm_bindgen_test]
+fn sma20_has_len_minus_19_points() {
+    let candles = fixture(30);
+    let sma = MarketAnalysisService::new().calculate_sma(&candles, 20);
+    let points = moving_average_points(&sma, 20, 0, &XAxis::ordinal(candles.len()), |p| p as f32);
+
+    assert_eq!(points.len(), candles.len() - 19);
+    assert!((points[0].0 - candle_x_position(19, candles.len())).abs() < f32::EPSILON);
+    // Average of closes 100..=119
+    assert!((points[0].1 - 109.5).abs() < 1e-4);
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.set_line_visibility(only_sma20());
+    let (_, verts, _) = builder.create_geometry(&chart_of(candles));
+    let sma20 = packed_line_color(ChartTheme::default().sma20);
+    let sma_vertices = verts.iter().filter(|v| is_element(v, 2.0) && v.color_type == sma20).count();
+    // Solid line: one quad (two triangles) per segment
+    assert_eq!(sma_vertices, (points.len() - 1) * 6);
+}
+
+#[wasm_bindgen_test]
+fn ema12_starts_at_index_11() {
+    let candles = fixture(30);
+    let ema = MarketAnalysisService::new().calculate_ema(&candles, 12);
+    let points = moving_average_points(&ema, 12, 0, &XAxis::ordinal(candles.len()), |p| p as f32);
+
+    assert_eq!(points.len(), candles.len() - 11);
+    assert!((points[0].0 - candle_x_position(11, candles.len())).abs() < f32::EPSILON);
+    // Seeded with the SMA of closes 100..=111
+    assert!((points[0].1 - 105.5).abs() < 1e-4);
+}
+
+#[wasm_bindgen_test]
+fn moving_average_points_follow_the_visible_window() {
+    let candles = fixture(60);
+    let sma = MarketAnalysisService::new().calculate_sma(&candles, 20);
+    let points = moving_average_points(&sma, 20, 40, &XAxis::ordinal(20), |p| p as f32);
+
+    assert_eq!(points.len(), 20);
+    assert!((points[0].0 - candle_x_position(0, 20)).abs() < f32::EPSILON);
+    // Candle 40 averages closes 121..=140
+    assert!((points[0].1 - 130.5).abs() < 1e-4);
+}
+
+#[wasm_bindgen_test]
+fn volume_bars_scale_with_the_largest_volume() {
+    let candles = fixture(10);
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (_, verts, _) = builder.create_geometry(&chart_of(candles));
+
+    let bars: Vec<(f32, f32)> = verts
+        .iter()
+        .filter(|v| is_element(v, 5.0))
+        .collect::<Vec<_>>()
+        .chunks(6)
+        .map(|bar| {
+            let bottom = bar.iter().map(|v| v.position_y).fold(f32::INFINITY, f32::min);
+            let top = bar.iter().map(|v| v.position_y).fold(f32::NEG_INFINITY, f32::max);
+            (bottom, top - bottom)
+        })
+        .collect();
+    assert_eq!(bars.len(), 10);
+
+    let tallest = bars.last().unwrap().1;
+    for (i, (bottom, height)) in bars.iter().enumerate() {
+        assert!((bottom - bars[0].0).abs() < f32::EPSILON);
+        // Volume of candle `i` is `i + 1`, the largest is 10
+        assert!((height / tallest - (i + 1) as f32 / 10.0).abs() < 1e-4);
+    }
+}
+
+#[wasm_bindgen_test]
+fn time_grid_lines_are_evenly_spaced() {
+    // 29 candles split into four equal gaps of seven
+    assert_eq!(time_grid_indices(29), vec![0, 7, 14, 21, 28]);
+
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (_, verts, _) = builder.create_geometry(&chart_of(fixture(29)));
+
+    let mut centers: Vec<f32> = verts
+        .iter()
+        .filter(|v| is_element(v, 3.0))
+        .collect::<Vec<_>>()
+        .chunks(6)
+        .filter_map(|quad| {
+            let min_x = quad.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+            let max_x = quad.iter().map(|v| v.position_x).fold(f32::NEG_INFINITY, f32::max);
+            // Vertical lines are only a pixel wide
+            (max_x - min_x < 0.01).then_some((min_x + max_x) / 2.0)
+        })
+        .collect();
+    centers.sort_by(f32::total_cmp);
+    centers.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+
+    let expected: Vec<f32> = [0, 7, 14, 21, 28].iter().map(|&i| candle_x_position(i, 29)).collect();
+    assert_eq!(centers.len(), expected.len());
+    for (got, want) in centers.iter().zip(&expected) {
+        assert!((got - want).abs() < 1e-5);
+    }
+}
+
+/// `count` candles that all traded once at 100 with no volume
+fn flat_candles(count: u64) -> Vec<Candle> {
+    (0..count)
+        .map(|i| {
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(100.0),
+                    Price::from(100.0),
+                    Price::from(100.0),
+                    Price::from(100.0),
+                    Volume::from(0.0),
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Flat candles are drawn at the middle of the main panel, not blanked
+fn assert_centered_flat_chart(candles: Vec<Candle>) {
+    let count = candles.len();
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (instances, verts, uniforms) = builder.create_geometry(&chart_of(candles));
+
+    assert_eq!(instances.len(), count);
+    assert!(!verts.is_empty());
+    let [_, _, min, max] = uniforms.viewport;
+    let half = 100.0 * FLAT_PRICE_SPAN_RATIO;
+    assert!((min - (100.0 - half)).abs() < 1e-4 && (max - (100.0 + half)).abs() < 1e-4);
+
+    let band = price_chart_wasm::app::panel_layout().get_untracked().main_band();
+    let center = (band.bottom + band.top) / 2.0;
+    for candle in &instances {
+        assert!((candle.high - center).abs() < 1e-5 && (candle.low - center).abs() < 1e-5);
+        assert!((candle.body_bottom - center).abs() < 1e-5 && candle.body_top > center);
+    }
+}
+
+#[wasm_bindgen_test]
+fn single_candle_renders_centered() {
+    assert_centered_flat_chart(flat_candles(1));
+}
+
+#[wasm_bindgen_test]
+fn identical_candles_render_centered() {
+    assert_centered_flat_chart(flat_candles(30));
+}
+
+#[wasm_bindgen_test]
+fn flat_prices_produce_no_nans() {
+    let candles = flat_candles(30);
+
+    let (min, max) = fit_price_range(candles.iter().map(|c| c.ohlcv.close.value() as f32));
+    assert!(min.is_finite() && max.is_finite() && min <= max);
+
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (instances, verts, uniforms) = builder.create_geometry(&chart_of(candles));
+
+    assert!(verts.iter().all(|v| v.position_x.is_finite() && v.position_y.is_finite()));
+    assert!(instances.iter().all(|c| {
+        [c.x, c.width, c.body_top, c.body_bottom, c.high, c.low].iter().all(|v| v.is_finite())
+    }));
+    assert!(uniforms.viewport.iter().all(|v| v.is_finite()));
+}
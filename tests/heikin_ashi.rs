@@ -0,0 +1,56 @@
+use price_chart_wasm::domain::chart::value_objects::ChartType;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::heikin_ashi;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(ts: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(ts),
+        OHLCV::new(
+            Price::from(open),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Volume::from(ts as f64),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn first_candle_is_seeded_from_raw_open_close() {
+    let ha = heikin_ashi(&[candle(1, 10.0, 14.0, 8.0, 12.0)]);
+    assert_eq!(ha[0].ohlcv.open.value(), 11.0);
+    assert_eq!(ha[0].ohlcv.close.value(), 11.0);
+    assert_eq!(ha[0].ohlcv.high.value(), 14.0);
+    assert_eq!(ha[0].ohlcv.low.value(), 8.0);
+}
+
+#[wasm_bindgen_test]
+fn later_candles_average_previous_body() {
+    let raw = [candle(1, 10.0, 14.0, 8.0, 12.0), candle(2, 12.0, 20.0, 11.0, 19.0)];
+    let ha = heikin_ashi(&raw);
+
+    assert_eq!(ha[1].ohlcv.open.value(), 11.0);
+    assert_eq!(ha[1].ohlcv.close.value(), 15.5);
+    assert_eq!(ha[1].ohlcv.high.value(), 20.0);
+    assert_eq!(ha[1].ohlcv.low.value(), 11.0);
+    assert_eq!(ha[1].timestamp.value(), 2);
+    assert_eq!(ha[1].ohlcv.volume.value(), 2.0);
+}
+
+#[wasm_bindgen_test]
+fn wicks_cover_the_smoothed_body() {
+    // A gap down leaves the averaged open above the raw high
+    let raw = [candle(1, 100.0, 101.0, 99.0, 100.0), candle(2, 50.0, 52.0, 48.0, 50.0)];
+    let ha = heikin_ashi(&raw);
+    assert_eq!(ha[1].ohlcv.open.value(), 100.0);
+    assert_eq!(ha[1].ohlcv.high.value(), 100.0);
+    assert!(ha[1].ohlcv.low.value() <= ha[1].ohlcv.close.value());
+}
+
+#[wasm_bindgen_test]
+fn empty_input_and_mode_name() {
+    assert!(heikin_ashi(&[]).is_empty());
+    assert_eq!("heikin-ashi".parse::<ChartType>().unwrap(), ChartType::HeikinAshi);
+}
@@ -0,0 +1,151 @@
+use futures::executor::block_on;
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{
+    Candle, HistoryDepth, MAX_HISTORY_CANDLES, OHLCV, Price, Symbol, TimeInterval, Timestamp,
+    Volume,
+};
+use price_chart_wasm::infrastructure::websocket::{ConnectionState, MarketDataSource};
+use std::cell::Cell;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+/// Minute candles from 0 up to `newest`, served `page` at a time
+struct PagedSource {
+    newest: u64,
+    page: u32,
+    fail_before: bool,
+    requests: Cell<u32>,
+}
+
+impl PagedSource {
+    fn new(newest: u64, page: u32) -> Self {
+        Self { newest, page, fail_before: false, requests: Cell::new(0) }
+    }
+
+    fn candles_until(&self, end: u64, limit: u32) -> Vec<Candle> {
+        self.requests.set(self.requests.get() + 1);
+        let last = end.min(self.newest) / MINUTE;
+        let limit = u64::from(limit.min(self.page));
+        (last.saturating_sub(limit - 1)..=last)
+            .map(|i| {
+                let p = Price::from(1.0);
+                Candle::new(
+                    Timestamp::from_millis(i * MINUTE),
+                    OHLCV::new(p, p, p, p, Volume::from(1.0)),
+                )
+            })
+            .collect()
+    }
+}
+
+impl MarketDataSource for PagedSource {
+    async fn fetch_historical(
+        &self,
+        _: &Symbol,
+        _: TimeInterval,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        Ok(self.candles_until(self.newest, limit))
+    }
+
+    async fn fetch_historical_before(
+        &self,
+        _: &Symbol,
+        _: TimeInterval,
+        end_time: u64,
+        limit: u32,
+    ) -> Result<Vec<Candle>, ChartError> {
+        if self.fail_before {
+            return Err(ChartError::Network("down".into()));
+        }
+        Ok(self.candles_until(end_time, limit))
+    }
+
+    fn history_page_limit(&self) -> u32 {
+        self.page
+    }
+
+    async fn fetch_tick_size(&self, _: &Symbol) -> Result<f64, ChartError> {
+        Ok(0.01)
+    }
+
+    async fn stream_with_status<F, S>(
+        &self,
+        _: &Symbol,
+        _: TimeInterval,
+        _: F,
+        _: S,
+    ) -> Result<(), ChartError>
+    where
+        F: FnMut(Candle),
+        S: FnMut(ConnectionState),
+    {
+        Ok(())
+    }
+}
+
+fn timestamps(candles: &[Candle]) -> Vec<u64> {
+    candles.iter().map(|c| c.timestamp.value()).collect()
+}
+
+#[wasm_bindgen_test]
+fn intraday_intervals_load_more_bars_by_default() {
+    let m1 = TimeInterval::OneMinute.default_history_candles();
+    let h1 = TimeInterval::OneHour.default_history_candles();
+    let d1 = TimeInterval::OneDay.default_history_candles();
+    assert!(m1 > h1 && h1 > d1);
+    assert_eq!(HistoryDepth::Auto.candle_count(TimeInterval::OneDay), d1);
+}
+
+#[wasm_bindgen_test]
+fn lookback_converts_to_candles_of_the_interval() {
+    let week = HistoryDepth::days(7);
+    assert_eq!(week.candle_count(TimeInterval::OneDay), 7);
+    assert_eq!(week.candle_count(TimeInterval::OneHour), 7 * 24);
+    assert_eq!(week.candle_count(TimeInterval::FourHours), 42);
+    // Partial candles round up
+    assert_eq!(HistoryDepth::Lookback(90 * MINUTE).candle_count(TimeInterval::OneHour), 2);
+}
+
+#[wasm_bindgen_test]
+fn absurd_limits_are_capped_or_replaced_by_the_default() {
+    let interval = TimeInterval::FiveMinutes;
+    let default = interval.default_history_candles();
+    assert_eq!(HistoryDepth::Candles(0).candle_count(interval), default);
+    assert_eq!(HistoryDepth::Lookback(0).candle_count(interval), default);
+    assert_eq!(HistoryDepth::Candles(u32::MAX).candle_count(interval), MAX_HISTORY_CANDLES);
+    assert_eq!(HistoryDepth::Lookback(u64::MAX).candle_count(interval), MAX_HISTORY_CANDLES);
+    assert_eq!(HistoryDepth::Candles(250).candle_count(interval), 250);
+}
+
+#[wasm_bindgen_test]
+fn history_pages_back_past_one_request() {
+    let source = PagedSource::new(10_000 * MINUTE, 1000);
+    let symbol = Symbol::from("BTCUSDT");
+    let candles = block_on(source.fetch_history(&symbol, TimeInterval::OneMinute, 2_500)).unwrap();
+
+    assert_eq!(candles.len(), 2_500);
+    assert_eq!(source.requests.get(), 3);
+    let ts = timestamps(&candles);
+    assert!(ts.windows(2).all(|w| w[1] == w[0] + MINUTE));
+    assert_eq!(ts.last(), Some(&(10_000 * MINUTE)));
+}
+
+#[wasm_bindgen_test]
+fn history_stops_where_the_venue_runs_out() {
+    let source = PagedSource::new(1_200 * MINUTE, 1000);
+    let symbol = Symbol::from("BTCUSDT");
+    let candles = block_on(source.fetch_history(&symbol, TimeInterval::OneMinute, 5_000)).unwrap();
+    assert_eq!(candles.len(), 1_201);
+    assert_eq!(timestamps(&candles)[0], 0);
+}
+
+#[wasm_bindgen_test]
+fn failed_older_page_keeps_the_latest_candles() {
+    let source = PagedSource { fail_before: true, ..PagedSource::new(10_000 * MINUTE, 300) };
+    let symbol = Symbol::from("BTCUSDT");
+    let candles = block_on(source.fetch_history(&symbol, TimeInterval::OneMinute, 1_000)).unwrap();
+    assert_eq!(candles.len(), 300);
+}
@@ -0,0 +1,65 @@
+use price_chart_wasm::app::{visible_range, visible_range_by_time};
+use price_chart_wasm::domain::chart::{Chart, value_objects::ChartType};
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, TimeInterval, Timestamp, Volume,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn make_candle(i: u64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 2_000),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(1.0),
+            Price::from(1.0),
+            Price::from(1.0),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+fn oldest(chart: &Chart) -> u64 {
+    let series = chart.get_series(TimeInterval::TwoSeconds).unwrap();
+    series.get_candles().front().unwrap().timestamp.value()
+}
+
+#[wasm_bindgen_test]
+fn raised_limit_keeps_older_candles() {
+    let mut chart = Chart::new("limit".to_string(), ChartType::Candlestick, 1000);
+    chart.set_max_candles(5_000);
+    assert_eq!(chart.max_candles(), 5_000);
+    for i in 0..6_000 {
+        chart.add_candle(make_candle(i));
+    }
+    assert_eq!(chart.get_candle_count(), 5_000);
+    assert_eq!(oldest(&chart), 1_000 * 2_000);
+}
+
+#[wasm_bindgen_test]
+fn lowered_limit_drops_the_oldest_candles() {
+    let mut chart = Chart::new("limit".to_string(), ChartType::Candlestick, 1000);
+    for i in 0..1_000 {
+        chart.add_candle(make_candle(i));
+    }
+    chart.set_max_candles(300);
+    assert_eq!(chart.get_candle_count(), 300);
+    assert_eq!(oldest(&chart), 700 * 2_000);
+    assert!(chart.series.values().all(|s| s.count() <= 300 && s.capacity() == 300));
+}
+
+#[wasm_bindgen_test]
+fn visible_window_is_independent_of_retained_history() {
+    let (_, small) = visible_range(300, 1.0, 0.0);
+    let (start, large) = visible_range(5_000, 1.0, 0.0);
+    assert_eq!(small, large);
+    assert_eq!(start, 5_000 - large);
+
+    let mut chart = Chart::new("limit".to_string(), ChartType::Candlestick, 5_000);
+    chart.set_historical_data((0..5_000).map(make_candle).collect());
+    let candles: Vec<Candle> =
+        chart.get_series(TimeInterval::TwoSeconds).unwrap().get_candles().iter().cloned().collect();
+    let (start, visible) = visible_range_by_time(&candles, &chart.viewport, 1.0);
+    assert_eq!(visible, large);
+    assert!(start + visible <= candles.len());
+}
@@ -0,0 +1,95 @@
+use futures::executor::block_on;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::websocket::{FetchError, paginate_range, stitch_pages};
+use std::cell::Cell;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+fn make_candle(timestamp: u64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(timestamp),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(1.0),
+            Price::from(1.0),
+            Price::from(1.0),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+/// Up to `limit` minute candles ending at `end`, like the klines endpoint
+fn page(end: u64, limit: u32) -> Vec<Candle> {
+    let last = end - end % MINUTE;
+    (0..limit as u64).filter_map(|i| last.checked_sub(i * MINUTE)).rev().map(make_candle).collect()
+}
+
+fn timestamps(candles: &[Candle]) -> Vec<u64> {
+    candles.iter().map(|c| c.timestamp.value()).collect()
+}
+
+#[wasm_bindgen_test]
+fn range_spanning_several_pages_is_stitched_ascending() {
+    let start = 100 * MINUTE;
+    let end = 2_600 * MINUTE;
+    let requests = Cell::new(0);
+    let candles = block_on(paginate_range(start, end, |end, limit| {
+        requests.set(requests.get() + 1);
+        async move { Ok(page(end, limit)) }
+    }))
+    .unwrap();
+
+    assert_eq!(requests.get(), 3);
+    let expected: Vec<u64> = (100..=2_600).map(|i| i * MINUTE).collect();
+    assert_eq!(timestamps(&candles), expected);
+}
+
+#[wasm_bindgen_test]
+fn overlapping_pages_are_deduplicated() {
+    // Each page repeats the newest candle of the previous one
+    let candles = block_on(paginate_range(0, 1_500 * MINUTE, |end, limit| async move {
+        Ok(page(end + MINUTE, limit))
+    }))
+    .unwrap();
+
+    let ts = timestamps(&candles);
+    assert_eq!(ts.len(), 1_501);
+    assert!(ts.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[wasm_bindgen_test]
+fn failure_mid_pagination_returns_loaded_candles() {
+    let calls = Cell::new(0);
+    let err = block_on(paginate_range(0, 2_500 * MINUTE, |end, limit| {
+        calls.set(calls.get() + 1);
+        let first = calls.get() == 1;
+        async move { if first { Ok(page(end, limit)) } else { Err(FetchError::Status(500)) } }
+    }))
+    .unwrap_err();
+
+    assert_eq!(err.error, FetchError::Status(500));
+    let expected: Vec<u64> = (1_501..=2_500).map(|i| i * MINUTE).collect();
+    assert_eq!(timestamps(&err.candles), expected);
+}
+
+#[wasm_bindgen_test]
+fn empty_page_ends_pagination() {
+    let calls = Cell::new(0);
+    let candles = block_on(paginate_range(0, 10 * MINUTE, |_, _| {
+        calls.set(calls.get() + 1);
+        async { Ok(Vec::new()) }
+    }))
+    .unwrap();
+
+    assert!(candles.is_empty());
+    assert_eq!(calls.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn stitching_drops_candles_outside_the_range() {
+    let pages =
+        vec![make_candle(5), make_candle(1), make_candle(3), make_candle(3), make_candle(9)];
+    assert_eq!(timestamps(&stitch_pages(pages, 1, 5)), vec![1, 3, 5]);
+}
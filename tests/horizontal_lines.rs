@@ -0,0 +1,95 @@
+use leptos::*;
+use price_chart_wasm::app::{add_horizontal_line, horizontal_lines, remove_horizontal_line};
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    CandleGeometry, CandleVertex, DashPattern, pack_rgb,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn lines_are_added_and_removed_by_id() {
+    let liquidation = add_horizontal_line(25_000.0, [1.0, 0.0, 0.0, 1.0], "Liq");
+    let entry = add_horizontal_line(30_000.0, [0.0, 1.0, 0.0, 1.0], "Entry");
+    assert_ne!(liquidation, entry);
+
+    let ids: Vec<u32> = horizontal_lines().with_untracked(|l| l.iter().map(|l| l.id).collect());
+    assert!(ids.contains(&liquidation) && ids.contains(&entry));
+
+    assert!(remove_horizontal_line(liquidation));
+    assert!(!remove_horizontal_line(liquidation));
+    horizontal_lines().with_untracked(|lines| {
+        assert!(lines.iter().all(|l| l.id != liquidation));
+        let entry_line = lines.iter().find(|l| l.id == entry).unwrap();
+        assert_eq!(entry_line.label, "Entry");
+        assert_eq!(entry_line.price, 30_000.0);
+    });
+    assert!(remove_horizontal_line(entry));
+}
+
+#[wasm_bindgen_test]
+fn reference_line_vertices_carry_color() {
+    let color = [1.0, 0.5, 0.0, 1.0];
+    let verts = CandleGeometry::create_horizontal_line(0.25, 0.01, DashPattern::SOLID, |x, y| {
+        CandleVertex::reference_line_vertex(x, y, color)
+    });
+    assert_eq!(verts.len(), 6);
+    assert!(verts.iter().all(|v| v.element_type == 8.0));
+    assert_eq!(verts[0].color_type, pack_rgb(color));
+    assert_eq!(pack_rgb(color), ((255 << 16) | (128 << 8)) as f32);
+}
+
+#[wasm_bindgen_test]
+fn adding_a_line_rebuilds_a_static_chart() {
+    let mut chart = Chart::new("static-lines".into(), ChartType::Candlestick, 10);
+    chart.add_candle(Candle::new(
+        Timestamp::from_millis(0),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(2.0),
+            Price::from(0.5),
+            Price::from(1.5),
+            Volume::from(1.0),
+        ),
+    ));
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    assert_eq!(builder.update(&chart), Some(true));
+    assert_eq!(builder.update(&chart), Some(false));
+
+    let id = add_horizontal_line(1.2, [1.0, 0.0, 0.0, 1.0], "Static");
+    assert_eq!(builder.update(&chart), Some(true));
+    assert!(remove_horizontal_line(id));
+    assert_eq!(builder.update(&chart), Some(true));
+}
+
+#[wasm_bindgen_test]
+fn alert_lines_use_the_alert_dash_pattern() {
+    let mut chart = Chart::new("alert-lines".into(), ChartType::Candlestick, 10);
+    chart.add_candle(Candle::new(
+        Timestamp::from_millis(0),
+        OHLCV::new(
+            Price::from(1.0),
+            Price::from(2.0),
+            Price::from(0.5),
+            Price::from(1.5),
+            Volume::from(1.0),
+        ),
+    ));
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    let id = add_horizontal_line(1.2, [1.0, 0.0, 0.0, 1.0], "Alert");
+    let reference_vertices = |builder: &ChartGeometryBuilder| {
+        builder.cached_vertices().iter().filter(|v| v.element_type == 8.0).count()
+    };
+
+    builder.update(&chart);
+    assert_eq!(reference_vertices(&builder), 6);
+    horizontal_lines().update(|lines| lines.iter_mut().for_each(|l| l.alert = l.id == id));
+    assert_eq!(builder.update(&chart), Some(true));
+    let dashes = builder.line_styles().alerts.segments(2.0, 0.0).len();
+    assert_eq!(reference_vertices(&builder), dashes * 6);
+    assert!(remove_horizontal_line(id));
+}
@@ -0,0 +1,25 @@
+use price_chart_wasm::app::candle_index_at_ndc;
+use price_chart_wasm::infrastructure::rendering::renderer::candle_x_position;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn hover_hits_candle_centers() {
+    for &len in &[1usize, 7, 50, 300] {
+        for idx in 0..len {
+            let x = candle_x_position(idx, len) as f64;
+            assert_eq!(candle_index_at_ndc(x, len), Some(idx));
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn hover_clamps_at_edges() {
+    assert_eq!(candle_index_at_ndc(-1.5, 20), Some(0));
+    assert_eq!(candle_index_at_ndc(1.5, 20), Some(19));
+}
+
+#[wasm_bindgen_test]
+fn hover_without_candles() {
+    assert_eq!(candle_index_at_ndc(0.0, 0), None);
+}
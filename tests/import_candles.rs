@@ -0,0 +1,77 @@
+use price_chart_wasm::domain::market_data::{RowError, import_candles};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn closes(text: &str) -> Vec<f64> {
+    let report = import_candles(text).unwrap();
+    report.candles.iter().map(|c| c.ohlcv.close.value()).collect()
+}
+
+#[wasm_bindgen_test]
+fn binance_klines_json() {
+    let json = r#"[
+        [60000, "2.0", "4.0", "1.0", "3.0", "10.5", 119999, "31.5", 7, "5", "15", "0"],
+        [0, 1.0, 2.5, 0.5, 2.0, 8]
+    ]"#;
+    let report = import_candles(json).unwrap();
+    assert!(report.errors.is_empty());
+    assert_eq!(report.candles.len(), 2);
+    // Rows come back in time order
+    assert_eq!(report.candles[0].timestamp.value(), 0);
+    assert_eq!(report.candles[1].ohlcv.volume.value(), 10.5);
+}
+
+#[wasm_bindgen_test]
+fn candle_objects_json() {
+    let json = r#"[
+        {"timestamp": 0, "open": 1, "high": 2, "low": 0.5, "close": 1.5, "volume": 3},
+        {"timestamp": 60000, "open": 1.5, "high": 2, "low": 1, "close": 1.8}
+    ]"#;
+    assert_eq!(closes(json), vec![1.5, 1.8]);
+}
+
+#[wasm_bindgen_test]
+fn csv_with_and_without_header() {
+    let headed = "time,Open,High,Low,Close,Volume\n0,1,2,0.5,1.5,3\n\n60000,1.5,2,1,1.8,4\n";
+    assert_eq!(closes(headed), vec![1.5, 1.8]);
+
+    let reordered = "close,open,high,low,timestamp\n1.5,1,2,0.5,0\n";
+    assert_eq!(closes(reordered), vec![1.5]);
+
+    let bare = "0,1,2,0.5,1.5,3\n60000,1.5,2,1,1.8\n";
+    assert_eq!(closes(bare), vec![1.5, 1.8]);
+}
+
+#[wasm_bindgen_test]
+fn invalid_rows_are_skipped_with_their_row_number() {
+    let json = r#"[
+        [0, "1", "2", "0.5", "1.5", "3"],
+        [60000, "oops", "2", "0.5", "1.5", "3"],
+        {"timestamp": 120000, "open": 1, "high": 0.5, "low": 2, "close": 1},
+        "not a candle"
+    ]"#;
+    let report = import_candles(json).unwrap();
+    assert_eq!(report.candles.len(), 1);
+    let rows: Vec<usize> = report.errors.iter().map(|e: &RowError| e.row).collect();
+    assert_eq!(rows, vec![2, 3, 4]);
+    assert!(report.errors[0].message.contains("open price"));
+
+    let csv = "timestamp,open,high,low,close\n0,1,2,0.5,1.5\n60000,1,2\n-5,1,2,0.5,1.5\n";
+    let report = import_candles(csv).unwrap();
+    assert_eq!(report.candles.len(), 1);
+    let rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+    assert_eq!(rows, vec![3, 4]);
+}
+
+#[wasm_bindgen_test]
+fn unreadable_files_are_rejected() {
+    assert!(import_candles("[1, 2").is_err());
+    assert!(import_candles("date,price\n0,1\n").is_err());
+    assert!(import_candles("").unwrap().candles.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn import_without_valid_candles_keeps_the_chart() {
+    let status = price_chart_wasm::app::import_file("bad.csv", "0,x,2,0.5,1.5\n");
+    assert!(status.starts_with("❌"));
+}
@@ -0,0 +1,106 @@
+use price_chart_wasm::domain::market_data::services::{MaKind, MarketAnalysisService};
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    MaCacheUpdate, MaUpdateTiming, MovingAverageCache,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const LINES: [(MaKind, usize); 5] =
+    [(MaKind::Sma, 3), (MaKind::Sma, 20), (MaKind::Ema, 5), (MaKind::Ema, 26), (MaKind::Sma, 0)];
+
+fn candle(i: u64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 60_000),
+        OHLCV::new(
+            Price::from(close),
+            Price::from(close + 1.0),
+            Price::from(close - 1.0),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+fn walk(count: u64) -> Vec<Candle> {
+    (0..count).map(|i| candle(i, 100.0 + ((i * 37) % 23) as f64 * 0.37)).collect()
+}
+
+/// Every cached line equals its from-scratch computation
+fn assert_matches_scratch(cache: &MovingAverageCache, candles: &[Candle]) {
+    let analysis = MarketAnalysisService::new();
+    for (kind, period) in LINES {
+        let scratch = analysis.calculate_ma(candles, kind, period);
+        assert_eq!(cache.get(kind, period).unwrap(), scratch.as_slice(), "{kind:?} {period}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn live_updates_match_full_recomputation() {
+    let mut candles = walk(2);
+    let mut cache = MovingAverageCache::default();
+    assert_eq!(cache.update(&candles, LINES), MaCacheUpdate::Rebuild);
+    assert_matches_scratch(&cache, &candles);
+
+    for i in 2..80 {
+        // The forming candle ticks a few times, then the next one opens
+        for tick in 0..3 {
+            let last = candles.len() - 1;
+            candles[last].ohlcv.close = (101.0 + tick as f64 * 0.25 + i as f64 * 0.1).into();
+            assert_eq!(cache.update(&candles, LINES), MaCacheUpdate::Incremental { from: last });
+            assert_matches_scratch(&cache, &candles);
+        }
+        candles.push(candle(i, 99.0 + (i % 7) as f64));
+        let from = candles.len() - 1;
+        assert_eq!(cache.update(&candles, LINES), MaCacheUpdate::Incremental { from });
+        assert_matches_scratch(&cache, &candles);
+    }
+
+    assert_eq!(cache.update(&candles, LINES), MaCacheUpdate::Unchanged);
+    assert_matches_scratch(&cache, &candles);
+}
+
+#[wasm_bindgen_test]
+fn non_append_changes_rebuild() {
+    let mut candles = walk(60);
+    let mut cache = MovingAverageCache::default();
+    cache.update(&candles, LINES);
+
+    // Older history loaded in front shifts every value
+    let mut older = walk(10);
+    older.append(&mut candles);
+    assert_eq!(cache.update(&older, LINES), MaCacheUpdate::Rebuild);
+    assert_matches_scratch(&cache, &older);
+
+    // An edit in the middle and a shorter series too
+    older[30].ohlcv.close = 1.0.into();
+    assert_eq!(cache.update(&older, LINES), MaCacheUpdate::Rebuild);
+    assert_matches_scratch(&cache, &older);
+    assert_eq!(cache.update(&older[..40], LINES), MaCacheUpdate::Rebuild);
+    assert_matches_scratch(&cache, &older[..40]);
+}
+
+#[wasm_bindgen_test]
+fn lines_follow_the_requested_set() {
+    let candles = walk(40);
+    let mut cache = MovingAverageCache::default();
+    cache.update(&candles, [(MaKind::Sma, 3)]);
+    assert!(cache.get(MaKind::Ema, 5).is_none());
+
+    // A newly requested line is computed in full even on an unchanged series
+    assert_eq!(cache.update(&candles, [(MaKind::Ema, 5)]), MaCacheUpdate::Unchanged);
+    assert!(cache.get(MaKind::Sma, 3).is_none());
+    let scratch = MarketAnalysisService::new().calculate_ema(&candles, 5);
+    assert_eq!(cache.get(MaKind::Ema, 5).unwrap(), scratch.as_slice());
+}
+
+#[wasm_bindgen_test]
+fn timing_reports_incremental_against_rebuild() {
+    let mut timing = MaUpdateTiming::default();
+    let incremental = MaCacheUpdate::Incremental { from: 0 };
+    assert_eq!(timing.record(MaCacheUpdate::Rebuild, 8.0, 2), None);
+    assert_eq!(timing.record(MaCacheUpdate::Unchanged, 5.0, 2), None);
+    assert_eq!(timing.record(incremental, 1.0, 2), None);
+    assert_eq!(timing.record(incremental, 3.0, 2), Some((2.0, 8.0)));
+    assert_eq!(timing.record(incremental, 1.0, 2), None);
+}
@@ -0,0 +1,116 @@
+use leptos::*;
+use price_chart_wasm::app::{
+    format_indicator_value, indicator_legend_text, indicator_values, publish_frame_overlays,
+};
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::ChartTheme;
+use price_chart_wasm::infrastructure::rendering::renderer::{IndicatorValue, LineVisibility};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// `count` candles closing at 100, 101, 102, ...
+fn chart_of(count: u64) -> Chart {
+    let candles = (0..count)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(close - 0.5),
+                    Price::from(close + 1.0),
+                    Price::from(close - 1.0),
+                    Price::from(close),
+                    Volume::from(2.0),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("legend".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    chart
+}
+
+#[wasm_bindgen_test]
+fn values_are_grouped_by_thousands() {
+    assert_eq!(format_indicator_value(43_210.5), "43,210.50");
+    assert_eq!(format_indicator_value(-1_234_567.891), "-1,234,567.89");
+    assert_eq!(format_indicator_value(999.999), "1,000.00");
+    assert_eq!(format_indicator_value(0.123456), "0.12346");
+    assert_eq!(format_indicator_value(-0.5), "-0.50000");
+}
+
+#[wasm_bindgen_test]
+fn legend_text_lists_every_line() {
+    let value = IndicatorValue {
+        toggle: Some("bollinger"),
+        label: "BB 20".to_string(),
+        values: vec![1_010.0, 1_000.0, 990.0],
+        color: [1.0; 4],
+    };
+    assert_eq!(indicator_legend_text(&value), "BB 20: 1,010.00 / 1,000.00 / 990.00");
+}
+
+#[wasm_bindgen_test]
+fn moving_averages_report_their_latest_value() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let values = builder.compute_indicator_values(&chart_of(30));
+
+    // SMA 50 and SMA 200 need more history than is loaded
+    let labels: Vec<&str> = values.iter().map(|v| v.label.as_str()).collect();
+    assert_eq!(labels, vec!["SMA 20", "EMA 12", "EMA 26"]);
+
+    let sma20 = &values[0];
+    assert_eq!(sma20.toggle, Some("sma20"));
+    assert_eq!(sma20.color, ChartTheme::default().sma20);
+    // Average of closes 110..=129
+    assert!((sma20.values[0] - 119.5).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn hidden_indicators_are_left_out() {
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.set_line_visibility(LineVisibility {
+        sma_20: false,
+        sma_50: false,
+        sma_200: false,
+        ema_12: false,
+        ema_26: false,
+        rsi_14: true,
+        macd: true,
+        bollinger: true,
+        vwap: true,
+        stochastic: true,
+        atr: true,
+        keltner: true,
+        volume_ma: true,
+        ..LineVisibility::default()
+    });
+    let values = builder.compute_indicator_values(&chart_of(60));
+
+    let toggles: Vec<&str> = values.iter().filter_map(|v| v.toggle).collect();
+    assert_eq!(
+        toggles,
+        vec!["bollinger", "keltner", "vwap", "rsi", "macd", "stochastic", "atr", "volume_ma"]
+    );
+    let value = |toggle: &str| &values.iter().find(|v| v.toggle == Some(toggle)).unwrap().values;
+    assert_eq!(value("bollinger").len(), 3);
+    assert_eq!(value("macd").len(), 2);
+    // Closes only ever rise
+    assert!((value("rsi")[0] - 100.0).abs() < 1e-9);
+    assert!((value("volume_ma")[0] - 2.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn built_frames_publish_the_legend() {
+    let chart = chart_of(30);
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.update(&chart);
+
+    publish_frame_overlays(&builder, &chart);
+    assert_eq!(indicator_values().get_untracked(), builder.compute_indicator_values(&chart));
+    assert!(!indicator_values().with_untracked(Vec::is_empty));
+}
@@ -0,0 +1,69 @@
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{CandleVertex, QuadVertex};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    ChartGeometryBuilder, candle_upload_sizes,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn chart_with(count: u64) -> Chart {
+    let mut chart = Chart::new("instanced".into(), ChartType::Candlestick, 1_000);
+    let candles = (0..count)
+        .map(|i| {
+            let open = 100.0 + (i % 7) as f64;
+            let close = open + if i % 2 == 0 { 3.0 } else { -3.0 };
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(open),
+                    Price::from(open.max(close) + 2.0),
+                    Price::from(open.min(close) - 2.0),
+                    Price::from(close),
+                    Volume::from(1.0 + i as f64),
+                ),
+            )
+        })
+        .collect();
+    chart.set_historical_data(candles);
+    chart
+}
+
+#[wasm_bindgen_test]
+fn unit_quad_is_two_ccw_triangles() {
+    assert_eq!(QuadVertex::INDICES.len(), 6);
+    for tri in QuadVertex::INDICES.chunks(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| QuadVertex::UNIT[i as usize].corner);
+        let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        assert!(cross > 0.0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn candles_become_instances_not_vertices() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (instances, vertices, _) = builder.create_geometry(&chart_with(300));
+
+    assert_eq!(instances.len(), 300);
+    assert!(!vertices.iter().any(|v| v.element_type == 0.0 || v.element_type == 1.0));
+    // Volume bars stay in the vertex buffer under the candles
+    assert!(vertices.iter().any(CandleVertex::draws_under_candles));
+}
+
+#[wasm_bindgen_test]
+fn instancing_cuts_upload_size() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (instances, _, _) = builder.create_geometry(&chart_with(300));
+
+    let (legacy, instanced) = candle_upload_sizes(&instances);
+    console_log!(
+        "300 candles: {} bytes as vertices, {} bytes as instances ({:.1}x smaller)",
+        legacy,
+        instanced,
+        legacy as f64 / instanced as f64
+    );
+    assert_eq!(instanced, 300 * 32);
+    assert!(legacy > instanced * 5);
+}
@@ -0,0 +1,38 @@
+use price_chart_wasm::domain::market_data::TimeInterval;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    MAX_ELEMENT_WIDTH, MIN_ELEMENT_WIDTH, candle_width_for,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn higher_intervals_render_wider() {
+    let visible = 100;
+    let two_sec = candle_width_for(visible, Some(TimeInterval::TwoSeconds));
+    let minute = candle_width_for(visible, Some(TimeInterval::OneMinute));
+    let hour = candle_width_for(visible, Some(TimeInterval::OneHour));
+    let day = candle_width_for(visible, Some(TimeInterval::OneDay));
+
+    assert!(two_sec < minute);
+    assert!(minute < hour);
+    assert!(hour < day);
+}
+
+#[wasm_bindgen_test]
+fn disabled_scaling_matches_base_width() {
+    let visible = 100;
+    let base = candle_width_for(visible, None);
+    let same = candle_width_for(visible, Some(TimeInterval::FiveMinutes));
+    assert!((base - same).abs() < f32::EPSILON);
+}
+
+#[wasm_bindgen_test]
+fn scaled_width_stays_within_bounds() {
+    for visible in [1usize, 2, 10, 100, 1000, 5000] {
+        for interval in [TimeInterval::TwoSeconds, TimeInterval::OneMonth] {
+            let width = candle_width_for(visible, Some(interval));
+            assert!((MIN_ELEMENT_WIDTH..=MAX_ELEMENT_WIDTH).contains(&width));
+            assert!(width <= (2.0 / visible as f32).max(MIN_ELEMENT_WIDTH));
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use price_chart_wasm::app::stream_interval;
+use price_chart_wasm::domain::market_data::TimeInterval;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+#[wasm_bindgen_test]
+fn two_seconds_is_aggregated_client_side() {
+    assert!(!TimeInterval::TwoSeconds.is_binance_kline());
+    assert_eq!(stream_interval(TimeInterval::TwoSeconds), TimeInterval::OneMinute);
+}
+
+#[wasm_bindgen_test]
+fn kline_intervals_stream_directly() {
+    for interval in [
+        TimeInterval::OneMinute,
+        TimeInterval::FiveMinutes,
+        TimeInterval::FifteenMinutes,
+        TimeInterval::OneHour,
+    ] {
+        assert!(interval.is_binance_kline());
+        assert_eq!(stream_interval(interval), interval);
+    }
+}
+
+#[wasm_bindgen_test]
+fn spacing_validated_against_duration() {
+    let five = TimeInterval::FiveMinutes;
+    assert!(five.matches_spacing(&[0, 5 * MINUTE, 10 * MINUTE, 20 * MINUTE]));
+    assert!(!five.matches_spacing(&[0, MINUTE, 2 * MINUTE]));
+    assert!(!five.matches_spacing(&[5 * MINUTE, 5 * MINUTE]));
+    assert!(five.matches_spacing(&[]));
+}
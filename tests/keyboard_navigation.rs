@@ -0,0 +1,45 @@
+use price_chart_wasm::app::{KEYBOARD_PAN_PX, KeyboardShortcut, keyboard_shortcut};
+use price_chart_wasm::infrastructure::rendering::renderer::LineVisibility;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn arrows_pan_and_shift_arrows_inspect() {
+    assert_eq!(keyboard_shortcut("ArrowLeft", false), Some(KeyboardShortcut::Pan(KEYBOARD_PAN_PX)));
+    assert_eq!(
+        keyboard_shortcut("ArrowRight", false),
+        Some(KeyboardShortcut::Pan(-KEYBOARD_PAN_PX))
+    );
+    assert_eq!(keyboard_shortcut("ArrowLeft", true), Some(KeyboardShortcut::Inspect(-1)));
+    assert_eq!(keyboard_shortcut("ArrowRight", true), Some(KeyboardShortcut::Inspect(1)));
+}
+
+#[wasm_bindgen_test]
+fn zoom_and_home_keys() {
+    assert_eq!(keyboard_shortcut("+", false), Some(KeyboardShortcut::Zoom(1.2)));
+    assert_eq!(keyboard_shortcut("=", false), Some(KeyboardShortcut::Zoom(1.2)));
+    assert_eq!(keyboard_shortcut("-", false), Some(KeyboardShortcut::Zoom(0.8)));
+    assert_eq!(keyboard_shortcut("Home", false), Some(KeyboardShortcut::JumpToLatest));
+    assert_eq!(keyboard_shortcut("Escape", false), Some(KeyboardShortcut::ClearSelection));
+    assert_eq!(keyboard_shortcut("a", false), None);
+}
+
+#[wasm_bindgen_test]
+fn digits_toggle_indicator_lines() {
+    assert_eq!(keyboard_shortcut("1", false), Some(KeyboardShortcut::ToggleLine("sma20")));
+    assert_eq!(keyboard_shortcut("0", false), Some(KeyboardShortcut::ToggleLine("session")));
+    assert_eq!(LineVisibility::line_for_key("7"), Some("vwap"));
+    assert_eq!(LineVisibility::line_for_key("x"), None);
+}
+
+#[wasm_bindgen_test]
+fn hotkeys_are_unique() {
+    let mut keys: Vec<_> = LineVisibility::HOTKEYS.iter().map(|(k, _)| *k).collect();
+    let mut lines: Vec<_> = LineVisibility::HOTKEYS.iter().map(|(_, l)| *l).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    lines.sort_unstable();
+    lines.dedup();
+    assert_eq!(keys.len(), LineVisibility::HOTKEYS.len());
+    assert_eq!(lines.len(), LineVisibility::HOTKEYS.len());
+}
@@ -0,0 +1,72 @@
+use price_chart_wasm::app::{candle_screen_position, step_selection};
+use price_chart_wasm::domain::chart::value_objects::Viewport;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{XAxis, candle_x_position};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(ts: u64, low: f64, high: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::new(ts),
+        OHLCV::new(
+            Price::new(close),
+            Price::new(high),
+            Price::new(low),
+            Price::new(close),
+            Volume::new(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn selection_starts_at_latest_and_clamps() {
+    assert_eq!(step_selection(None, -1, 5), Some(4));
+    assert_eq!(step_selection(Some(4), -1, 5), Some(3));
+    assert_eq!(step_selection(Some(4), 1, 5), Some(4));
+    assert_eq!(step_selection(Some(0), -1, 5), Some(0));
+    // Selection shrinks with the visible range
+    assert_eq!(step_selection(Some(9), -1, 5), Some(3));
+    assert_eq!(step_selection(Some(1), 1, 0), None);
+}
+
+fn viewport(min_price: f32, max_price: f32) -> Viewport {
+    Viewport { start_time: 0.0, end_time: 100.0, min_price, max_price, width: 800, height: 500 }
+}
+
+#[wasm_bindgen_test]
+fn screen_position_follows_candle() {
+    let visible = vec![candle(0, 100.0, 200.0, 150.0), candle(60_000, 100.0, 200.0, 200.0)];
+    let x_axis = XAxis::ordinal(visible.len());
+    let shown = viewport(100.0, 200.0);
+
+    let (x0, y0) = candle_screen_position(0, &visible, &x_axis, &shown, (800, 500), 1.0);
+    let (x1, y1) = candle_screen_position(1, &visible, &x_axis, &shown, (800, 500), 1.0);
+
+    let expected_x1 = (candle_x_position(1, 2) as f64 + 1.0) / 2.0 * 800.0;
+    assert!((x1 - expected_x1).abs() < 1e-3);
+    assert!(x0 < x1);
+    // Mid-range close sits at the vertical center, the top of the range at the top edge
+    assert!((y0 - 250.0).abs() < 1e-6);
+    assert!(y1.abs() < 1e-6);
+}
+
+#[wasm_bindgen_test]
+fn screen_position_uses_the_rendered_range_and_density() {
+    let visible = vec![candle(0, 100.0, 200.0, 150.0)];
+    let x_axis = XAxis::ordinal(visible.len());
+
+    // A zoomed price range moves the close even though the candles did not change
+    let (_, y) =
+        candle_screen_position(0, &visible, &x_axis, &viewport(140.0, 160.0), (800, 500), 1.0);
+    assert!((y - 250.0).abs() < 1e-6);
+    let (_, y) =
+        candle_screen_position(0, &visible, &x_axis, &viewport(150.0, 250.0), (800, 500), 1.0);
+    assert!((y - 500.0).abs() < 1e-6);
+
+    // The same backing store at twice the density covers half the CSS pixels
+    let hi_dpi =
+        candle_screen_position(0, &visible, &x_axis, &viewport(100.0, 200.0), (1600, 1000), 2.0);
+    let lo_dpi =
+        candle_screen_position(0, &visible, &x_axis, &viewport(100.0, 200.0), (800, 500), 1.0);
+    assert!((hi_dpi.0 - lo_dpi.0).abs() < 1e-6 && (hi_dpi.1 - lo_dpi.1).abs() < 1e-6);
+}
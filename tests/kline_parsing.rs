@@ -0,0 +1,55 @@
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, Symbol, TimeInterval, Timestamp, Volume,
+};
+use price_chart_wasm::infrastructure::websocket::{
+    BinanceKlineData, BinanceWebSocketClient, kline_to_candle, parse_historical_klines,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// `@kline_1m` stream event as Binance sends it
+const STREAM_EVENT: &str = r#"{"e":"kline","E":1700000065123,"s":"BTCUSDT","k":{"t":1700000040000,"T":1700000099999,"s":"BTCUSDT","i":"1m","f":3270113432,"L":3270114101,"o":"37318.54000000","c":"37325.01000000","h":"37330.00000000","l":"37311.20000000","v":"12.48713000","n":670,"x":false,"q":"466054.53811270","V":"6.61543000","Q":"246909.86352230","B":"0"}}"#;
+
+/// The same candle as a `/api/v3/klines` row
+const REST_ROWS: &str = r#"[[1700000040000,"37318.54000000","37330.00000000","37311.20000000","37325.01000000","12.48713000",1700000099999,"466054.53811270",670,"6.61543000","246909.86352230","0"]]"#;
+
+fn expected() -> Candle {
+    Candle::new(
+        Timestamp::from_millis(1_700_000_040_000),
+        OHLCV::new(
+            Price::from(37318.54),
+            Price::from(37330.0),
+            Price::from(37311.2),
+            Price::from(37325.01),
+            Volume::from(12.48713),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn every_kline_path_yields_the_same_candle() {
+    let client = BinanceWebSocketClient::new(Symbol::from("BTCUSDT"), TimeInterval::OneMinute);
+    let stream = client.parse_message(STREAM_EVENT).unwrap();
+    let rest = parse_historical_klines(REST_ROWS).unwrap();
+    let event: BinanceKlineData = serde_json::from_str(STREAM_EVENT).unwrap();
+    let dto = event.kline.to_domain_candle().unwrap();
+
+    assert_eq!(stream, expected());
+    assert_eq!(rest, vec![expected()]);
+    assert_eq!(dto, expected());
+}
+
+#[wasm_bindgen_test]
+fn unparsable_fields_are_parse_errors() {
+    let err = kline_to_candle(0, "1", "abc", "0.5", "1.5", "3").unwrap_err();
+    assert_eq!(err, ChartError::Parse("Invalid high price".to_string()));
+    assert!(matches!(
+        kline_to_candle(0, "1", "2", "0.5", "1.5", "-3"),
+        Err(ChartError::InvalidData(_))
+    ));
+
+    // REST rows that fail are dropped instead of failing the page
+    let rows = REST_ROWS.replace("\"12.48713000\"", "\"x\"");
+    assert!(parse_historical_klines(&rows).unwrap().is_empty());
+}
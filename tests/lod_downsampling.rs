@@ -0,0 +1,80 @@
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::CandleInstance;
+use price_chart_wasm::infrastructure::rendering::renderer::{downsample_candles, lod_bucket_size};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candles(count: u64) -> Vec<Candle> {
+    (0..count)
+        .map(|i| {
+            let base = 100.0 + ((i * 37) % 23) as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(base),
+                    Price::from(base + 1.0 + (i % 5) as f64),
+                    Price::from(base - 1.0 - (i % 3) as f64),
+                    Price::from(base + 0.5),
+                    Volume::from(1.0 + i as f64),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn extremes(candles: &[Candle]) -> (f64, f64) {
+    candles.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), c| {
+        (lo.min(c.ohlcv.low.value()), hi.max(c.ohlcv.high.value()))
+    })
+}
+
+#[wasm_bindgen_test]
+fn bucket_size_keeps_one_candle_per_pixel() {
+    assert_eq!(lod_bucket_size(300, 800), 1);
+    assert_eq!(lod_bucket_size(800, 800), 1);
+    assert_eq!(lod_bucket_size(801, 800), 2);
+    assert_eq!(lod_bucket_size(3000, 100), 30);
+    assert_eq!(lod_bucket_size(10, 0), 10);
+}
+
+#[wasm_bindgen_test]
+fn aggregation_preserves_high_low_extremes() {
+    let raw = candles(100);
+    let merged = downsample_candles(&raw, 7);
+    assert_eq!(merged.len(), 15);
+    assert_eq!(extremes(&merged), extremes(&raw));
+
+    for (bucket, chunk) in merged.iter().zip(raw.chunks(7)) {
+        assert_eq!(bucket.timestamp, chunk[0].timestamp);
+        assert_eq!(bucket.ohlcv.open, chunk[0].ohlcv.open);
+        assert_eq!(bucket.ohlcv.close, chunk[chunk.len() - 1].ohlcv.close);
+        assert_eq!(extremes(std::slice::from_ref(bucket)), extremes(chunk));
+        let volume: f64 = chunk.iter().map(|c| c.ohlcv.volume.value()).sum();
+        assert!((bucket.ohlcv.volume.value() - volume).abs() < 1e-9);
+    }
+    assert_eq!(downsample_candles(&raw, 1), raw);
+}
+
+#[wasm_bindgen_test]
+fn narrow_canvas_draws_fewer_candles_with_the_same_range() {
+    let mut chart = Chart::new("lod".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles(400));
+
+    let mut wide = ChartGeometryBuilder::new(800, 600);
+    let mut narrow = ChartGeometryBuilder::new(100, 600);
+    wide.set_zoom_params(0.0, 0.0);
+    narrow.set_zoom_params(0.0, 0.0);
+    let (full, _, _) = wide.create_geometry(&chart);
+    let (merged, _, _) = narrow.create_geometry(&chart);
+
+    assert!(full.len() > 100);
+    assert!(merged.len() <= 100);
+    let top = |inst: &[CandleInstance]| inst.iter().fold(f32::MIN, |m, c| m.max(c.high));
+    let bottom = |inst: &[CandleInstance]| inst.iter().fold(f32::MAX, |m, c| m.min(c.low));
+    assert_eq!(top(&full), top(&merged));
+    assert_eq!(bottom(&full), bottom(&merged));
+}
@@ -0,0 +1,39 @@
+use price_chart_wasm::domain::logging::{
+    MAX_LOG_LINES, filter_logs, logs_revision, recent_logs, record_log_line,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn lines(raw: &[&str]) -> Vec<String> {
+    raw.iter().map(|s| s.to_string()).collect()
+}
+
+#[wasm_bindgen_test]
+fn filter_is_case_insensitive_substring() {
+    let logs = lines(&["[10:00] ERROR INF:WebSocket | closed", "[10:01]  INFO APP:App | ready"]);
+    assert_eq!(filter_logs(&logs, "websocket"), lines(&["[10:00] ERROR INF:WebSocket | closed"]));
+    assert_eq!(filter_logs(&logs, "READY"), lines(&["[10:01]  INFO APP:App | ready"]));
+    assert!(filter_logs(&logs, "missing").is_empty());
+}
+
+#[wasm_bindgen_test]
+fn empty_filter_keeps_everything() {
+    let logs = lines(&["a", "b"]);
+    assert_eq!(filter_logs(&logs, ""), logs);
+    assert_eq!(filter_logs(&logs, "  "), logs);
+}
+
+#[wasm_bindgen_test]
+fn recorded_lines_are_bounded() {
+    let before = logs_revision();
+    for i in 0..MAX_LOG_LINES + 5 {
+        record_log_line(format!("line {i}"));
+    }
+    let logs = recent_logs();
+    assert_eq!(logs.len(), MAX_LOG_LINES);
+    assert_eq!(
+        logs.last().map(String::as_str),
+        Some(format!("line {}", MAX_LOG_LINES + 4).as_str())
+    );
+    assert_eq!(logs_revision() - before, (MAX_LOG_LINES + 5) as u64);
+}
@@ -0,0 +1,102 @@
+use price_chart_wasm::domain::market_data::services::MarketAnalysisService;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    MACD_PANEL_BAND, MACD_SIGNAL, MACD_SLOW, MacdSeries, XAxis, candle_x_position, create_macd,
+    macd_series,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candles(closes: impl Iterator<Item = f64>) -> Vec<Candle> {
+    closes
+        .enumerate()
+        .map(|(i, close)| {
+            Candle::new(
+                Timestamp::from_millis(i as u64 * 60_000),
+                OHLCV::new(
+                    Price::from(close),
+                    Price::from(close + 1.0),
+                    Price::from(close - 1.0),
+                    Price::from(close),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn wave(n: usize) -> Vec<Candle> {
+    candles((0..n).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0))
+}
+
+#[wasm_bindgen_test]
+fn macd_is_fast_minus_slow_ema() {
+    let data = wave(60);
+    let series = macd_series(&data);
+    let svc = MarketAnalysisService::new();
+    let ema12 = svc.calculate_ema(&data, 12);
+    let ema26 = svc.calculate_ema(&data, 26);
+
+    assert_eq!(series.macd.len(), 60 - MACD_SLOW + 1);
+    assert_eq!(series.signal.len(), series.macd.len() - MACD_SIGNAL + 1);
+    assert_eq!(series.histogram.len(), series.signal.len());
+
+    // Last values all belong to the last candle
+    let expected = ema12.last().unwrap().value() - ema26.last().unwrap().value();
+    assert!((series.macd.last().unwrap() - expected).abs() < 1e-9);
+    let hist = series.macd.last().unwrap() - series.signal.last().unwrap();
+    assert!((series.histogram.last().unwrap() - hist).abs() < 1e-9);
+    assert_eq!(MacdSeries::SIGNAL_OFFSET + series.signal.len(), 60);
+}
+
+#[wasm_bindgen_test]
+fn too_few_candles_give_empty_geometry() {
+    let data = wave(MACD_SLOW - 1);
+    assert_eq!(macd_series(&data), MacdSeries::default());
+    assert!(
+        create_macd(&data, &XAxis::ordinal(data.len()), MACD_PANEL_BAND, 0.01, 0.02).is_empty()
+    );
+
+    // Enough for MACD but not for the signal line
+    let data = wave(MACD_SLOW);
+    let series = macd_series(&data);
+    assert_eq!(series.macd.len(), 1);
+    assert!(series.signal.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn histogram_bars_align_with_candles() {
+    let data = candles((0..50).map(|i| 100.0 + i as f64));
+    let bar_width = 0.02;
+    let verts = create_macd(&data, &XAxis::ordinal(data.len()), MACD_PANEL_BAND, 0.01, bar_width);
+    let bars: Vec<_> = verts.iter().filter(|v| v.element_type == 0.0).collect();
+    let series = macd_series(&data);
+    assert_eq!(bars.len(), series.histogram.len() * 6);
+
+    for (k, quad) in bars.chunks(6).enumerate() {
+        let x = candle_x_position(k + MacdSeries::SIGNAL_OFFSET, data.len());
+        let center = (quad[0].position_x + quad[1].position_x) / 2.0;
+        assert!((center - x).abs() < 1e-6);
+    }
+    assert!(verts.iter().all(|v| {
+        v.position_y >= MACD_PANEL_BAND.bottom - 0.01 && v.position_y <= MACD_PANEL_BAND.top + 0.01
+    }));
+}
+
+#[wasm_bindgen_test]
+fn histogram_colors_follow_direction() {
+    // Accelerating rally then reversal: histogram rises then falls
+    let closes = (0..40)
+        .map(|i| 100.0 + (i * i) as f64 * 0.1)
+        .chain((0..20).map(|i| 260.0 - i as f64 * 8.0));
+    let data = candles(closes);
+    let series = macd_series(&data);
+    let verts = create_macd(&data, &XAxis::ordinal(data.len()), MACD_PANEL_BAND, 0.01, 0.02);
+    let bars: Vec<_> = verts.iter().filter(|v| v.element_type == 0.0).collect();
+
+    for (k, quad) in bars.chunks(6).enumerate().skip(1) {
+        let rising = series.histogram[k] >= series.histogram[k - 1];
+        assert_eq!(quad[0].color_type == 1.0, rising, "bar {k}");
+    }
+    assert!(bars.chunks(6).any(|q| q[0].color_type == 0.0));
+}
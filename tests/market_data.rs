@@ -74,3 +74,31 @@ fn candle_series_add_and_price_range() {
     assert_eq!(series.count(), 3);
     assert_eq!(series.get_candles().front().unwrap().timestamp.value(), 1);
 }
+
+#[wasm_bindgen_test]
+fn price_arithmetic() {
+    let a = Price::from(100.0);
+    let b = Price::from(110.0);
+    assert_eq!((a + b).value(), 210.0);
+    assert_eq!((b - a).value(), 10.0);
+    // Prices never go negative; spreads use abs_diff
+    assert_eq!((a - b).value(), 0.0);
+    assert_eq!(a.abs_diff(b).value(), 10.0);
+    assert_eq!((a * 1.5).value(), 150.0);
+    assert_eq!((b / 2.0).value(), 55.0);
+    assert_eq!(Price::midpoint(a, b).value(), 105.0);
+    assert!((a.pct_change(b) - 10.0).abs() < 1e-12);
+    assert_eq!(Price::from(0.0).pct_change(b), 0.0);
+    assert_eq!([a, b, a].into_iter().sum::<Price>().value(), 310.0);
+}
+
+#[wasm_bindgen_test]
+fn volume_arithmetic() {
+    let a = Volume::from(2.0);
+    let b = Volume::from(3.0);
+    assert_eq!((a + b).value(), 5.0);
+    assert_eq!((a - b).value(), 0.0);
+    assert_eq!((b * 2.0).value(), 6.0);
+    assert_eq!((b / 3.0).value(), 1.0);
+    assert_eq!([a, b].into_iter().sum::<Volume>().value(), 5.0);
+}
@@ -0,0 +1,109 @@
+use price_chart_wasm::domain::market_data::{Symbol, TimeInterval};
+use price_chart_wasm::infrastructure::websocket::{
+    BinanceWebSocketClient, CoinbaseDataSource, DataSource, Exchange, MarketDataSource,
+    coinbase_candles_url, coinbase_granularity, coinbase_product_id, coinbase_streams,
+    parse_coinbase_candles, parse_coinbase_message,
+};
+use price_chart_wasm::time_utils::format_iso8601;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn assert_source<T: MarketDataSource>() {}
+
+#[wasm_bindgen_test]
+fn every_client_is_a_data_source() {
+    assert_source::<BinanceWebSocketClient>();
+    assert_source::<CoinbaseDataSource>();
+    assert_source::<DataSource>();
+}
+
+#[wasm_bindgen_test]
+fn exchange_selects_matching_source() {
+    assert_eq!(Exchange::default(), Exchange::Binance);
+    assert_eq!("coinbase".parse::<Exchange>().unwrap(), Exchange::Coinbase);
+    assert!("kraken".parse::<Exchange>().is_err());
+
+    let symbol = Symbol::from("BTCUSDT");
+    let interval = TimeInterval::OneMinute;
+    assert!(matches!(Exchange::Binance.data_source(&symbol, interval), DataSource::Binance(_)));
+    assert!(matches!(Exchange::Coinbase.data_source(&symbol, interval), DataSource::Coinbase(_)));
+}
+
+#[wasm_bindgen_test]
+fn binance_symbols_map_to_coinbase_products() {
+    assert_eq!(coinbase_product_id(&Symbol::from("BTCUSDT")), "BTC-USDT");
+    assert_eq!(coinbase_product_id(&Symbol::from("ethusd")), "ETH-USD");
+    assert_eq!(coinbase_product_id(&Symbol::from("ETHBTC")), "ETH-BTC");
+    assert_eq!(coinbase_product_id(&Symbol::from("sol-eur")), "SOL-EUR");
+}
+
+#[wasm_bindgen_test]
+fn only_supported_granularities_are_requested() {
+    assert_eq!(coinbase_granularity(TimeInterval::OneMinute), Some(60));
+    assert_eq!(coinbase_granularity(TimeInterval::OneDay), Some(86_400));
+    assert_eq!(coinbase_granularity(TimeInterval::TwoSeconds), None);
+    assert_eq!(coinbase_granularity(TimeInterval::FourHours), None);
+}
+
+#[wasm_bindgen_test]
+fn coinbase_offers_only_intervals_built_from_its_stream() {
+    use TimeInterval::*;
+    let streamed: Vec<TimeInterval> =
+        [TwoSeconds, OneMinute, FiveMinutes, FifteenMinutes, OneHour, FourHours, OneDay]
+            .into_iter()
+            .filter(|i| coinbase_streams(*i))
+            .collect();
+    assert_eq!(streamed, vec![FiveMinutes, FifteenMinutes, OneHour, OneDay]);
+
+    assert!(Exchange::Binance.supports_interval(OneMinute));
+    assert!(!Exchange::Coinbase.supports_interval(OneMinute));
+    assert_eq!(Exchange::Coinbase.supported_interval(OneMinute), FiveMinutes);
+    assert_eq!(Exchange::Coinbase.supported_interval(OneHour), OneHour);
+    assert_eq!(Exchange::Binance.supported_interval(TwoSeconds), TwoSeconds);
+}
+
+#[wasm_bindgen_test]
+fn candles_url_carries_iso_range() {
+    assert_eq!(format_iso8601(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_iso8601(1_700_000_000_000), "2023-11-14T22:13:20Z");
+    assert_eq!(
+        coinbase_candles_url("BTC-USD", 60, Some((0, 60_000))),
+        "https://api.exchange.coinbase.com/products/BTC-USD/candles?granularity=60\
+         &start=1970-01-01T00:00:00Z&end=1970-01-01T00:01:00Z"
+    );
+    assert!(coinbase_candles_url("BTC-USD", 60, None).ends_with("granularity=60"));
+}
+
+#[wasm_bindgen_test]
+fn rest_rows_become_ascending_candles() {
+    let body =
+        "[[1700000060, 9.0, 12.0, 10.0, 11.0, 5.5], [1700000000, 8.0, 11.0, 9.5, 10.0, 2.0]]";
+    let candles = parse_coinbase_candles(body).unwrap();
+
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].timestamp.value(), 1_700_000_000_000);
+    let last = &candles[1];
+    assert_eq!(last.timestamp.value(), 1_700_000_060_000);
+    assert_eq!(last.ohlcv.open.value(), 10.0);
+    assert_eq!(last.ohlcv.high.value(), 12.0);
+    assert_eq!(last.ohlcv.low.value(), 9.0);
+    assert_eq!(last.ohlcv.close.value(), 11.0);
+    assert_eq!(last.ohlcv.volume.value(), 5.5);
+    assert!(parse_coinbase_candles("{\"message\":\"NotFound\"}").is_err());
+}
+
+#[wasm_bindgen_test]
+fn candles_channel_messages_become_candles() {
+    let msg = r#"{"channel":"candles","timestamp":"2023-06-09T20:19:35Z","sequence_num":0,
+        "events":[{"type":"update","candles":[{"start":"1688998200","high":"1867.72",
+        "low":"1865.63","open":"1867.38","close":"1866.81","volume":"0.20269406",
+        "product_id":"ETH-USD"}]}]}"#;
+    let candles = parse_coinbase_message(msg).unwrap();
+
+    assert_eq!(candles.len(), 1);
+    assert_eq!(candles[0].timestamp.value(), 1_688_998_200_000);
+    assert_eq!(candles[0].ohlcv.close.value(), 1866.81);
+
+    let heartbeat = r#"{"channel":"subscriptions","events":[{"subscriptions":{}}]}"#;
+    assert!(parse_coinbase_message(heartbeat).unwrap().is_empty());
+}
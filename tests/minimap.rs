@@ -0,0 +1,38 @@
+use price_chart_wasm::app::{
+    PAN_SENSITIVITY_BASE, minimap_drag_delta, minimap_window, minimap_zoom_for_width, visible_range,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn window_maps_visible_range() {
+    // 32 of 320 candles at the latest position
+    let (left, width) = minimap_window(320, 1.0, 0.0);
+    assert!((width - 0.1).abs() < 1e-9);
+    assert!((left - 0.9).abs() < 1e-9);
+
+    // Panned fully into history
+    let (left, _) = minimap_window(320, 1.0, -1000.0);
+    assert_eq!(left, 0.0);
+
+    assert_eq!(minimap_window(0, 1.0, 0.0), (0.0, 1.0));
+}
+
+#[wasm_bindgen_test]
+fn dragging_window_pans_by_matching_candles() {
+    let zoom = 2.0;
+    // A tenth of an 800 px strip over 320 candles is 32 candles towards newer data
+    let delta_x = minimap_drag_delta(80.0, 800.0, 320, zoom);
+    let pan_change = -delta_x * PAN_SENSITIVITY_BASE / zoom;
+    assert!((pan_change - 32.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn resizing_window_sets_zoom() {
+    let zoom = minimap_zoom_for_width(0.2, 320);
+    let (_, visible) = visible_range(320, zoom, 0.0);
+    assert_eq!(visible, 64);
+
+    // Wider than the zoom limit allows is clamped to the minimum zoom
+    assert_eq!(minimap_zoom_for_width(1.0, 10_000), minimap_zoom_for_width(1.0, 20_000));
+}
@@ -1,5 +1,6 @@
 use price_chart_wasm::domain::market_data::{
-    Candle, OHLCV, Price, Timestamp, Volume, services::MarketAnalysisService,
+    Candle, OHLCV, Price, Timestamp, Volume,
+    services::{MaKind, MarketAnalysisService},
 };
 use wasm_bindgen_test::*;
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
@@ -62,3 +63,39 @@ fn moving_average_short_input() {
     assert!(svc.calculate_sma(&candles, 5).is_empty());
     assert!(svc.calculate_ema(&candles, 5).is_empty());
 }
+
+/// StockCharts' 10-day EMA worked example (Intel closes), rounded to cents
+#[wasm_bindgen_test]
+fn ema_seeded_with_sma_matches_reference() {
+    let closes = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39, 22.38,
+        22.61, 23.36, 24.05, 23.75, 23.83, 23.95, 23.63, 23.82, 23.87, 23.65, 23.19, 23.10, 23.33,
+        22.68, 23.10, 22.40, 22.17,
+    ];
+    let reference = [
+        22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34, 23.43, 23.51,
+        23.53, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92,
+    ];
+    let candles: Vec<Candle> =
+        closes.iter().enumerate().map(|(i, &p)| create_candle(p, i as u64)).collect();
+
+    let ema = MarketAnalysisService::new().calculate_ma(&candles, MaKind::Ema, 10);
+    // First value belongs to candle `period - 1`
+    assert_eq!(ema.len(), closes.len() - 9);
+    for (calc, exp) in ema.iter().zip(reference.iter()) {
+        assert!((calc.value() - exp).abs() < 0.005 + 1e-9, "{} vs {exp}", calc.value());
+    }
+}
+
+#[wasm_bindgen_test]
+fn ema26_starts_from_the_sma_of_26_closes() {
+    let candles: Vec<Candle> = (0..40).map(|i| create_candle(100.0 + i as f64, i)).collect();
+    let svc = MarketAnalysisService::new();
+    let ema = svc.calculate_ma(&candles, MaKind::Ema, 26);
+    let sma = svc.calculate_ma(&candles, MaKind::Sma, 26);
+
+    assert_eq!(ema.len(), 40 - 25);
+    assert!((ema[0].value() - sma[0].value()).abs() < 1e-9);
+    // Seed is the mean of closes 100..=125
+    assert!((ema[0].value() - 112.5).abs() < 1e-9);
+}
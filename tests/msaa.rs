@@ -1,4 +1,6 @@
-use price_chart_wasm::infrastructure::rendering::renderer::MSAA_SAMPLE_COUNT;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    MSAA_SAMPLE_COUNT, MSAA_SAMPLE_OPTIONS, nearest_sample_count,
+};
 use wasm_bindgen_test::*;
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
@@ -6,3 +8,26 @@ wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 fn msaa_sample_count_is_four() {
     assert_eq!(MSAA_SAMPLE_COUNT, 4);
 }
+
+#[wasm_bindgen_test]
+fn supported_counts_are_kept() {
+    for count in [1, 2, 4, 8] {
+        assert_eq!(nearest_sample_count(count, &[1, 2, 4, 8]), count);
+    }
+}
+
+#[wasm_bindgen_test]
+fn unsupported_counts_fall_back_to_the_next_lower_one() {
+    assert_eq!(nearest_sample_count(8, &[1, 4]), 4);
+    assert_eq!(nearest_sample_count(2, &[1, 4]), 1);
+    assert_eq!(nearest_sample_count(16, &[1, 2, 4, 8]), 8);
+    // Single sampling is always valid, even with nothing reported
+    assert_eq!(nearest_sample_count(4, &[]), 1);
+    assert_eq!(nearest_sample_count(0, &[1, 4]), 1);
+}
+
+#[wasm_bindgen_test]
+fn options_include_off_and_the_default() {
+    assert_eq!(MSAA_SAMPLE_OPTIONS[0], 1);
+    assert!(MSAA_SAMPLE_OPTIONS.contains(&MSAA_SAMPLE_COUNT));
+}
@@ -0,0 +1,120 @@
+use price_chart_wasm::domain::chart::value_objects::ChartType;
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    CandleColoring, OHLC_TICK_RATIO, XAxis, candle_x_position, create_ohlc_bars, is_bullish_candle,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(ts: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(ts),
+        OHLCV::new(
+            Price::from(open),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+fn identity(price: f64) -> f32 {
+    price as f32
+}
+
+#[wasm_bindgen_test]
+fn bar_has_stem_and_two_ticks() {
+    let vertices = create_ohlc_bars(
+        &[candle(1, 2.0, 4.0, 1.0, 3.0)],
+        None,
+        CandleColoring::OpenClose,
+        &XAxis::ordinal(1),
+        0.2,
+        0.01,
+        identity,
+    );
+    assert_eq!(vertices.len(), 18);
+
+    let x = candle_x_position(0, 1);
+    let stem = &vertices[0..6];
+    let ys: Vec<f32> = stem.iter().map(|v| v.position_y).collect();
+    assert_eq!(ys.iter().cloned().fold(f32::MAX, f32::min), 1.0);
+    assert_eq!(ys.iter().cloned().fold(f32::MIN, f32::max), 4.0);
+
+    // Open tick sits left of the stem, close tick right of it
+    let open_tick = &vertices[6..12];
+    assert!(open_tick.iter().all(|v| v.position_x <= x));
+    assert!(open_tick.iter().all(|v| (v.position_y - 2.0).abs() <= 0.005 + f32::EPSILON));
+    let close_tick = &vertices[12..18];
+    assert!(close_tick.iter().all(|v| v.position_x >= x));
+    assert!(close_tick.iter().all(|v| (v.position_y - 3.0).abs() <= 0.005 + f32::EPSILON));
+}
+
+#[wasm_bindgen_test]
+fn ticks_scale_with_candle_width() {
+    let c = [candle(1, 2.0, 4.0, 1.0, 3.0)];
+    let x = candle_x_position(0, 1);
+    for width in [0.05f32, 0.4] {
+        let vertices = create_ohlc_bars(
+            &c,
+            None,
+            CandleColoring::OpenClose,
+            &XAxis::ordinal(c.len()),
+            width,
+            0.01,
+            identity,
+        );
+        let left = vertices[6..12].iter().map(|v| v.position_x).fold(f32::MAX, f32::min);
+        let right = vertices[12..18].iter().map(|v| v.position_x).fold(f32::MIN, f32::max);
+        assert!((x - left - width * OHLC_TICK_RATIO).abs() < 1e-6);
+        assert!((right - x - width * OHLC_TICK_RATIO).abs() < 1e-6);
+    }
+}
+
+#[wasm_bindgen_test]
+fn color_follows_close_vs_open() {
+    let bars = [candle(1, 2.0, 4.0, 1.0, 3.0), candle(2, 3.0, 4.0, 1.0, 2.0)];
+    let vertices = create_ohlc_bars(
+        &bars,
+        None,
+        CandleColoring::OpenClose,
+        &XAxis::ordinal(bars.len()),
+        0.2,
+        0.01,
+        identity,
+    );
+    assert!(vertices[..18].iter().all(|v| v.element_type == 0.0 && v.color_type == 1.0));
+    assert!(vertices[18..].iter().all(|v| v.color_type == 0.0));
+}
+
+#[wasm_bindgen_test]
+fn previous_close_coloring_matches_the_volume_bars() {
+    // A gap up that closes below its own open, then a gap down that closes above it
+    let prev = candle(0, 1.0, 2.0, 1.0, 2.0);
+    let bars = [candle(1, 4.0, 4.0, 2.5, 3.0), candle(2, 1.0, 2.5, 1.0, 2.0)];
+    let vertices = create_ohlc_bars(
+        &bars,
+        Some(&prev),
+        CandleColoring::PreviousClose,
+        &XAxis::ordinal(bars.len()),
+        0.2,
+        0.01,
+        identity,
+    );
+    assert!(vertices[..18].iter().all(|v| v.color_type == 1.0));
+    assert!(vertices[18..].iter().all(|v| v.color_type == 0.0));
+
+    // Same decision as the volume bar drawn under each candle
+    let prevs = [Some(&prev), Some(&bars[0])];
+    for ((bar, p), chunk) in bars.iter().zip(prevs).zip(vertices.chunks(18)) {
+        let bullish = is_bullish_candle(bar, p, CandleColoring::PreviousClose);
+        assert_eq!(chunk[0].color_type == 1.0, bullish);
+    }
+}
+
+#[wasm_bindgen_test]
+fn mode_name_round_trips() {
+    assert_eq!("ohlc".parse::<ChartType>().unwrap(), ChartType::OhlcBar);
+    assert_eq!(ChartType::OhlcBar.to_string(), "OHLC");
+}
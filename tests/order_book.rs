@@ -0,0 +1,97 @@
+use price_chart_wasm::domain::market_data::{
+    DepthSync, DepthUpdate, OrderBook, Symbol, TimeInterval,
+};
+use price_chart_wasm::infrastructure::rendering::PanelBand;
+use price_chart_wasm::infrastructure::rendering::renderer::{DEPTH_PANEL_WIDTH, create_depth_bars};
+use price_chart_wasm::infrastructure::websocket::{BinanceWebSocketClient, parse_depth_snapshot};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn update(first: u64, last: u64, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> DepthUpdate {
+    DepthUpdate {
+        first_update_id: first,
+        final_update_id: last,
+        bids: bids.to_vec(),
+        asks: asks.to_vec(),
+    }
+}
+
+fn book() -> OrderBook {
+    OrderBook::from_snapshot(100, &[(99.0, 1.0), (98.0, 2.0)], &[(101.0, 1.5), (102.0, 3.0)], 10)
+}
+
+#[wasm_bindgen_test]
+fn levels_are_sorted_from_the_spread() {
+    let book = book();
+    assert_eq!(book.best_bid(), Some(99.0));
+    assert_eq!(book.best_ask(), Some(101.0));
+    assert_eq!(book.bids().collect::<Vec<_>>(), vec![(99.0, 1.0), (98.0, 2.0)]);
+    assert_eq!(book.cumulative_asks(), vec![(101.0, 1.5), (102.0, 4.5)]);
+}
+
+#[wasm_bindgen_test]
+fn diffs_follow_the_update_sequence() {
+    let mut book = book();
+    // Fully covered by the snapshot
+    assert_eq!(book.apply(&update(90, 100, &[(99.0, 9.0)], &[])), DepthSync::Stale);
+    // Straddles the snapshot id: accepted
+    assert_eq!(book.apply(&update(95, 105, &[(99.5, 4.0)], &[(101.0, 0.0)])), DepthSync::Applied);
+    assert_eq!(book.last_update_id(), 105);
+    assert_eq!(book.best_bid(), Some(99.5));
+    assert_eq!(book.best_ask(), Some(102.0));
+    // Missing 106..=107
+    assert_eq!(book.apply(&update(108, 110, &[], &[])), DepthSync::Gap);
+    assert_eq!(book.apply(&update(106, 107, &[(99.5, 0.0)], &[])), DepthSync::Applied);
+    assert_eq!(book.best_bid(), Some(99.0));
+}
+
+#[wasm_bindgen_test]
+fn book_keeps_only_top_levels() {
+    let bids: Vec<(f64, f64)> = (0..10).map(|i| (90.0 + i as f64, 1.0)).collect();
+    let asks: Vec<(f64, f64)> = (0..10).map(|i| (110.0 - i as f64, 1.0)).collect();
+    let book = OrderBook::from_snapshot(1, &bids, &asks, 3);
+    assert_eq!(book.bids().map(|b| b.0).collect::<Vec<_>>(), vec![99.0, 98.0, 97.0]);
+    assert_eq!(book.asks().map(|a| a.0).collect::<Vec<_>>(), vec![101.0, 102.0, 103.0]);
+}
+
+#[wasm_bindgen_test]
+fn parses_binance_depth_messages() {
+    let snapshot = r#"{"lastUpdateId":1027024,"bids":[["4.00000000","431.00000000"]],"asks":[["4.00000200","12.00000000"]]}"#;
+    let book = parse_depth_snapshot(snapshot, 100).unwrap();
+    assert_eq!(book.last_update_id(), 1_027_024);
+    assert_eq!(book.best_bid(), Some(4.0));
+
+    let client = BinanceWebSocketClient::new(Symbol::from("BNBBTC"), TimeInterval::OneMinute);
+    let diff = r#"{"e":"depthUpdate","E":123456789,"s":"BNBBTC","U":157,"u":160,"b":[["0.0024","10"]],"a":[["0.0026","100"],["0.0027","0"]]}"#;
+    let parsed = client.parse_depth_update(diff).unwrap();
+    assert_eq!(parsed, update(157, 160, &[(0.0024, 10.0)], &[(0.0026, 100.0), (0.0027, 0.0)]));
+    assert!(client.parse_depth_update(r#"{"U":1,"u":2,"b":[["x","1"]],"a":[]}"#).is_err());
+}
+
+#[wasm_bindgen_test]
+fn depth_bars_grow_with_cumulative_volume() {
+    let book = book();
+    let band = PanelBand { bottom: -1.0, top: 1.0 };
+    let vertices = create_depth_bars(&book, band, |p| ((p - 100.0) / 10.0) as f32);
+
+    // One step per side between its two levels
+    assert_eq!(vertices.len(), 12);
+    let (bids, asks) = vertices.split_at(6);
+    assert!(bids.iter().all(|v| v.element_type == 10.0 && v.color_type == 1.0));
+    assert!(asks.iter().all(|v| v.color_type == 0.0));
+    assert!(vertices.iter().all(|v| v.position_x <= 1.0));
+
+    // Largest cumulative side (asks, 1.5 near the spread of 4.5 total) sets the scale
+    let ask_left = asks.iter().map(|v| v.position_x).fold(f32::MAX, f32::min);
+    assert!((ask_left - (1.0 - DEPTH_PANEL_WIDTH * 1.5 / 4.5)).abs() < 1e-6);
+    let bid_top = bids.iter().map(|v| v.position_y).fold(f32::MIN, f32::max);
+    assert!((bid_top - (-0.1)).abs() < 1e-6);
+}
+
+#[wasm_bindgen_test]
+fn depth_bars_are_clipped_to_the_band() {
+    let band = PanelBand { bottom: -0.05, top: 0.05 };
+    let vertices = create_depth_bars(&book(), band, |p| ((p - 100.0) / 10.0) as f32);
+    assert!(vertices.iter().all(|v| (-0.05..=0.05).contains(&v.position_y)));
+    assert!(create_depth_bars(&OrderBook::new(5), band, |p| p as f32).is_empty());
+}
@@ -0,0 +1,64 @@
+use price_chart_wasm::domain::market_data::services::MovingAveragesData;
+use price_chart_wasm::infrastructure::rendering::renderer::MaConfig;
+use price_chart_wasm::infrastructure::rendering::{ChartTheme, palette};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// Chart background `#253242`
+const BACKGROUND: [f32; 3] = [0.145, 0.196, 0.259];
+
+fn luminance([r, g, b]: [f32; 3]) -> f32 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+fn distance(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt()
+}
+
+#[wasm_bindgen_test]
+fn palette_colors_are_unique_and_in_range() {
+    for n in [1, 2, 5, 12, 32] {
+        let colors = palette(n);
+        assert_eq!(colors.len(), n);
+
+        for (i, c) in colors.iter().enumerate() {
+            assert!(c.iter().all(|v| (0.0..=1.0).contains(v)), "{c:?} out of range");
+            assert_eq!(c[3], 1.0);
+            for other in &colors[i + 1..] {
+                assert!(distance(c, other) > 1e-3, "duplicate color {c:?}");
+            }
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn palette_is_deterministic_and_distinct_from_background() {
+    assert_eq!(palette(8), palette(8));
+    assert!(palette(0).is_empty());
+
+    let bg = luminance(BACKGROUND);
+    for c in palette(16) {
+        assert!(luminance([c[0], c[1], c[2]]) > bg + 0.15, "{c:?} too close to background");
+    }
+}
+
+#[wasm_bindgen_test]
+fn adjacent_colors_are_distinguishable() {
+    for n in [3, 8, 24] {
+        let colors = palette(n);
+        for pair in colors.windows(2) {
+            assert!(distance(&pair[0], &pair[1]) > 0.1, "{:?} vs {:?}", pair[0], pair[1]);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn default_moving_averages_use_the_palette() {
+    let lines = MovingAveragesData::LINES;
+    let defaults = MaConfig::defaults(&ChartTheme::dark());
+    assert_eq!(defaults, MaConfig::from_palette(&lines));
+    assert_eq!(defaults.iter().map(|c| (c.kind, c.period)).collect::<Vec<_>>(), lines);
+    for (i, c) in defaults.iter().enumerate() {
+        assert!(defaults[i + 1..].iter().all(|o| o.color != c.color), "{c:?} repeated");
+    }
+}
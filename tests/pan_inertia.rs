@@ -0,0 +1,56 @@
+use price_chart_wasm::app::{
+    PAN_INERTIA_MIN_VELOCITY, PAN_VELOCITY_WINDOW_MS, PanVelocityTracker, inertia_step,
+    pan_at_boundary,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn release_velocity_uses_recent_samples() {
+    let mut tracker = PanVelocityTracker::default();
+    tracker.record(0.0, 0.0);
+    tracker.record(500.0, 10.0);
+    tracker.record(500.0, 200.0);
+    tracker.record(520.0, 210.0);
+    tracker.record(540.0, 220.0);
+
+    let velocity = tracker.release_velocity(225.0);
+    assert!((velocity - 2.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn pause_before_release_cancels_flick() {
+    let mut tracker = PanVelocityTracker::default();
+    tracker.record(0.0, 0.0);
+    tracker.record(100.0, 10.0);
+
+    assert_eq!(tracker.release_velocity(10.0 + PAN_VELOCITY_WINDOW_MS + 1.0), 0.0);
+    tracker.reset();
+    assert_eq!(tracker.release_velocity(0.0), 0.0);
+}
+
+#[wasm_bindgen_test]
+fn inertia_decays_until_it_stops() {
+    let (delta, next) = inertia_step(2.0, 16.0);
+    assert!((delta - 32.0).abs() < 1e-9);
+    assert!(next > 0.0 && next < 2.0);
+
+    let mut velocity = -2.0;
+    let mut frames = 0;
+    while velocity != 0.0 {
+        velocity = inertia_step(velocity, 16.0).1;
+        frames += 1;
+        assert!(frames < 1000);
+    }
+    assert_eq!(inertia_step(PAN_INERTIA_MIN_VELOCITY / 2.0, 16.0), (0.0, 0.0));
+}
+
+#[wasm_bindgen_test]
+fn inertia_stops_at_data_boundary() {
+    // Latest candles visible: panning towards newer data is blocked
+    assert!(pan_at_boundary(100, 1.0, 0.0, -10.0));
+    assert!(!pan_at_boundary(100, 1.0, 0.0, 10.0));
+    // Oldest candles visible: panning towards older data is blocked
+    assert!(pan_at_boundary(100, 1.0, -100.0, 10.0));
+    assert!(!pan_at_boundary(100, 1.0, -100.0, -10.0));
+}
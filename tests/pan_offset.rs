@@ -1,4 +1,6 @@
-use price_chart_wasm::app::{HISTORY_FETCH_THRESHOLD, PAN_SENSITIVITY_BASE, should_fetch_history};
+use price_chart_wasm::app::{
+    CHART_WIDTH, HISTORY_FETCH_THRESHOLD, PAN_SENSITIVITY_BASE, pan_pointer_x, should_fetch_history,
+};
 
 #[test]
 fn pan_direction_and_history_activation() {
@@ -18,3 +20,12 @@ fn pan_direction_and_history_activation() {
     offset_big -= delta_x_big * pan_sensitivity;
     assert!(should_fetch_history(offset_big));
 }
+
+#[test]
+fn ndc_drag_spans_the_nominal_chart_width() {
+    assert_eq!(pan_pointer_x(-1.0), 0.0);
+    assert_eq!(pan_pointer_x(1.0), CHART_WIDTH);
+    // Dragging across the whole canvas moves the same candles at any size
+    let candles = (pan_pointer_x(1.0) - pan_pointer_x(-1.0)) * PAN_SENSITIVITY_BASE;
+    assert!((candles - CHART_WIDTH * PAN_SENSITIVITY_BASE).abs() < 1e-9);
+}
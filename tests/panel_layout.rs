@@ -0,0 +1,117 @@
+use price_chart_wasm::infrastructure::rendering::panel_layout::PANEL_GAP;
+use price_chart_wasm::infrastructure::rendering::{MAIN_PANEL, PanelBand, PanelLayout};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn approx(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+#[wasm_bindgen_test]
+fn default_layout_fills_canvas() {
+    let layout = PanelLayout::default();
+    assert_eq!(layout.main_band(), PanelBand::FULL);
+    assert_eq!(layout.band_for("rsi"), PanelBand::FULL);
+}
+
+#[wasm_bindgen_test]
+fn bands_follow_relative_heights() {
+    let mut layout = PanelLayout::default();
+    let rsi = layout.add_panel(0.5);
+    let macd = layout.add_panel(0.5);
+
+    let bands = layout.bands();
+    assert_eq!(bands.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![MAIN_PANEL, rsi, macd]);
+
+    let main = layout.main_band();
+    let rsi_band = layout.band(rsi).unwrap();
+    let macd_band = layout.band(macd).unwrap();
+    assert!(approx(main.top, 1.0));
+    assert!(approx(macd_band.bottom, -1.0));
+    assert!(approx(main.height(), 2.0 * rsi_band.height()));
+    assert!(approx(rsi_band.height(), macd_band.height()));
+    assert!(approx(main.bottom - rsi_band.top, PANEL_GAP));
+}
+
+#[wasm_bindgen_test]
+fn indicators_normalize_into_their_panel() {
+    let mut layout = PanelLayout::default();
+    let panel = layout.add_panel(0.25);
+    assert!(layout.assign("rsi", panel));
+    assert!(!layout.assign("macd", 42));
+
+    let band = layout.band_for("rsi");
+    assert_eq!(band, layout.band(panel).unwrap());
+    assert!(approx(band.normalize(0.0, 0.0, 100.0), band.bottom));
+    assert!(approx(band.normalize(100.0, 0.0, 100.0), band.top));
+    assert!(approx(band.map_ndc(0.0), (band.top + band.bottom) / 2.0));
+    assert_eq!(layout.band_for("sma20"), layout.main_band());
+}
+
+#[wasm_bindgen_test]
+fn removing_panel_returns_indicators_to_main() {
+    let mut layout = PanelLayout::default();
+    let panel = layout.add_panel(0.3);
+    layout.assign("rsi", panel);
+
+    assert!(!layout.remove_panel(MAIN_PANEL));
+    assert!(layout.remove_panel(panel));
+    assert!(!layout.remove_panel(panel));
+    assert_eq!(layout.panel_of("rsi"), MAIN_PANEL);
+    assert_eq!(layout.main_band(), PanelBand::FULL);
+}
+
+#[wasm_bindgen_test]
+fn weights_are_adjustable() {
+    let mut layout = PanelLayout::default();
+    let panel = layout.add_panel(0.5);
+    let before = layout.band(panel).unwrap().height();
+    assert!(layout.set_weight(panel, 1.0));
+    assert!(layout.band(panel).unwrap().height() > before);
+    assert!(!layout.set_weight(99, 1.0));
+}
+
+#[wasm_bindgen_test]
+fn layout_round_trips_through_json() {
+    let mut layout = PanelLayout::default();
+    let panel = layout.add_panel(0.4);
+    layout.assign("rsi", panel);
+
+    let json = serde_json::to_string(&layout).unwrap();
+    let restored: PanelLayout = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, layout);
+}
+
+#[wasm_bindgen_test]
+fn labels_map_into_main_panel() {
+    let band = PanelBand { bottom: 0.0, top: 1.0 };
+    assert!((band.css_percent(0.0) - 0.0).abs() < 1e-9);
+    assert!((band.css_percent(100.0) - 50.0).abs() < 1e-9);
+    assert!((PanelBand::FULL.css_percent(40.0) - 40.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn stored_layouts_are_validated() {
+    let json = r#"{"panels":[{"id":0,"weight":1.0},{"id":1,"weight":0.0},{"id":2,"weight":-3.0}],
+        "assignments":{"rsi":1,"macd":9},"next_id":0}"#;
+    let mut layout = serde_json::from_str::<PanelLayout>(json).unwrap().validated();
+    assert!(layout.panels().iter().all(|p| p.weight > 0.0));
+    assert!(layout.bands().iter().all(|(_, band)| band.height() > 0.0));
+    assert_eq!(layout.panel_of("rsi"), 1);
+    assert_eq!(layout.panel_of("macd"), MAIN_PANEL);
+    assert_eq!(layout.add_panel(0.5), 3);
+
+    // Without the main panel there is nothing sensible to keep
+    let json = r#"{"panels":[{"id":4,"weight":1.0}],"assignments":{},"next_id":5}"#;
+    let layout = serde_json::from_str::<PanelLayout>(json).unwrap().validated();
+    assert_eq!(layout, PanelLayout::default());
+}
+
+#[wasm_bindgen_test]
+fn non_finite_weights_get_an_even_share() {
+    let mut layout = PanelLayout::default();
+    let rsi = layout.add_panel(f32::INFINITY);
+    assert!(layout.set_weight(MAIN_PANEL, f32::NAN));
+    assert!(layout.panels().iter().all(|p| p.weight == 1.0));
+    assert!(approx(layout.band(rsi).unwrap().height(), layout.main_band().height()));
+}
@@ -0,0 +1,85 @@
+use leptos::*;
+use price_chart_wasm::app::{
+    PAUSED_CANDLE_LIMIT, PausedCandles, connection_state, pause_streaming, report_connection_state,
+    resume_streaming, stream_paused,
+};
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Symbol, Timestamp, Volume};
+use price_chart_wasm::infrastructure::websocket::ConnectionState;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(open_time: u64, close: f64) -> Candle {
+    let price = Price::from(close);
+    Candle::new(
+        Timestamp::from_millis(open_time),
+        OHLCV::new(price, price, price, price, Volume::from(1.0)),
+    )
+}
+
+fn closes(candles: &[Candle]) -> Vec<f64> {
+    candles.iter().map(|c| c.ohlcv.close.value()).collect()
+}
+
+#[wasm_bindgen_test]
+fn forming_updates_collapse_into_the_latest() {
+    let btc = Symbol::from("BTCUSDT");
+    let mut buffer = PausedCandles::default();
+    buffer.push(&btc, candle(0, 1.0));
+    buffer.push(&btc, candle(0, 2.0));
+    buffer.push(&btc, candle(60_000, 3.0));
+    buffer.push(&btc, candle(60_000, 4.0));
+    assert_eq!(buffer.len(), 2);
+    assert_eq!(closes(&buffer.drain(&btc)), vec![2.0, 4.0]);
+    assert!(buffer.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn candles_of_another_symbol_are_dropped() {
+    let (btc, eth) = (Symbol::from("BTCUSDT"), Symbol::from("ETHUSDT"));
+    let mut buffer = PausedCandles::default();
+    buffer.push(&btc, candle(0, 1.0));
+    buffer.push(&eth, candle(0, 2.0));
+    assert_eq!(closes(&buffer.drain(&btc)), Vec::<f64>::new());
+
+    buffer.push(&eth, candle(0, 3.0));
+    assert_eq!(closes(&buffer.drain(&eth)), vec![3.0]);
+}
+
+#[wasm_bindgen_test]
+fn buffer_keeps_the_newest_candles() {
+    let btc = Symbol::from("BTCUSDT");
+    let mut buffer = PausedCandles::default();
+    for i in 0..PAUSED_CANDLE_LIMIT as u64 + 5 {
+        buffer.push(&btc, candle(i * 1_000, i as f64));
+    }
+    let drained = buffer.drain(&btc);
+    assert_eq!(drained.len(), PAUSED_CANDLE_LIMIT);
+    assert_eq!(drained[0].ohlcv.close.value(), 5.0);
+}
+
+#[wasm_bindgen_test]
+fn pausing_marks_the_stream_as_not_live() {
+    report_connection_state(ConnectionState::Offline);
+    connection_state().set(ConnectionState::Live);
+    pause_streaming();
+    assert!(stream_paused().get_untracked());
+    assert_eq!(connection_state().get_untracked(), ConnectionState::Paused);
+
+    // No stream is running here, so resuming does not claim to be live
+    resume_streaming();
+    assert!(!stream_paused().get_untracked());
+    assert_eq!(connection_state().get_untracked(), ConnectionState::Offline);
+}
+
+#[wasm_bindgen_test]
+fn stream_reports_while_paused_show_after_resuming() {
+    report_connection_state(ConnectionState::Reconnecting);
+    assert_eq!(connection_state().get_untracked().label(), "🟡 Reconnecting…");
+
+    pause_streaming();
+    report_connection_state(ConnectionState::Live);
+    assert_eq!(connection_state().get_untracked(), ConnectionState::Paused);
+
+    resume_streaming();
+    assert!(connection_state().get_untracked().is_live());
+}
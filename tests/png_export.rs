@@ -0,0 +1,41 @@
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    padded_bytes_per_row, to_straight_rgba, unpad_rows,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rows_are_padded_to_256_bytes() {
+    assert_eq!(padded_bytes_per_row(1), 256);
+    assert_eq!(padded_bytes_per_row(64), 256);
+    assert_eq!(padded_bytes_per_row(65), 512);
+    assert_eq!(padded_bytes_per_row(800), 3328);
+}
+
+#[wasm_bindgen_test]
+fn padding_is_stripped_from_every_row() {
+    // 2x3 image: each 8-byte row padded to 256 bytes
+    let mut data = vec![0xEE; 256 * 3];
+    for y in 0..3 {
+        for x in 0..8 {
+            data[y * 256 + x] = (y * 8 + x) as u8;
+        }
+    }
+    let pixels = unpad_rows(&data, 2, 3);
+    assert_eq!(pixels, (0..24).collect::<Vec<u8>>());
+}
+
+#[wasm_bindgen_test]
+fn bgra_is_swizzled_to_rgba() {
+    let mut px = vec![10, 20, 30, 255];
+    to_straight_rgba(&mut px, true, false);
+    assert_eq!(px, vec![30, 20, 10, 255]);
+}
+
+#[wasm_bindgen_test]
+fn premultiplied_alpha_is_undone() {
+    // 50% alpha red premultiplied to 128
+    let mut px = vec![128, 0, 0, 128, 0, 0, 0, 0, 40, 60, 80, 255];
+    to_straight_rgba(&mut px, false, true);
+    assert_eq!(px, vec![255, 0, 0, 128, 0, 0, 0, 0, 40, 60, 80, 255]);
+}
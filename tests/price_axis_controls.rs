@@ -0,0 +1,33 @@
+use leptos::{SignalGetUntracked, SignalWithUntracked};
+use price_chart_wasm::app::{
+    current_symbol, global_charts, pan_price, price_zoom_factor, reset_price_view, zoom_price,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn wheel_up_zooms_the_price_scale_in() {
+    assert!(price_zoom_factor(-100.0) > 1.0);
+    assert!(price_zoom_factor(100.0) < 1.0);
+    assert_eq!(price_zoom_factor(0.0), 1.0);
+    // Opposite scrolls cancel out
+    assert!((price_zoom_factor(-50.0) * price_zoom_factor(50.0) - 1.0).abs() < 1e-12);
+}
+
+#[wasm_bindgen_test]
+fn price_controls_are_noops_before_renderer_init() {
+    let symbol = current_symbol().get_untracked();
+    let viewport = || {
+        global_charts()
+            .get_untracked()
+            .get(&symbol)
+            .map(|c| c.with_untracked(|c| (c.viewport.min_price, c.viewport.max_price)))
+    };
+    let before = viewport();
+
+    pan_price(40.0);
+    zoom_price(2.0, 0.5);
+    reset_price_view();
+
+    assert_eq!(viewport(), before);
+}
@@ -0,0 +1,70 @@
+use price_chart_wasm::app::{HEADER_CHANGE_WINDOW, format_price_change};
+use price_chart_wasm::domain::chart::{Chart, PriceChange, value_objects::ChartType};
+use price_chart_wasm::domain::market_data::{
+    Candle, OHLCV, Price, TimeInterval, Timestamp, Volume,
+};
+use std::time::Duration;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+const START_MS: u64 = 1_700_000_000_000;
+
+/// Hourly candles opening at 100, 101, ... and closing one above the open
+fn hourly_chart(count: u64) -> Chart {
+    let candles = (0..count)
+        .map(|i| {
+            let open = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(START_MS + i * HOUR_MS),
+                OHLCV::new(
+                    Price::from(open),
+                    Price::from(open + 1.0),
+                    Price::from(open),
+                    Price::from(open + 1.0),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("change".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    chart
+}
+
+#[wasm_bindgen_test]
+fn change_spans_the_last_window() {
+    let chart = hourly_chart(48);
+    let change = chart.price_change_pct(TimeInterval::OneHour, HEADER_CHANGE_WINDOW).unwrap();
+    assert!(change.full_window);
+    // The 24h window opens at candle 24 (open 124) and ends at close 148
+    assert!((change.pct - (148.0 - 124.0) / 124.0 * 100.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn short_history_falls_back_to_the_oldest_candle() {
+    let chart = hourly_chart(6);
+    let change = chart.price_change_pct(TimeInterval::OneHour, HEADER_CHANGE_WINDOW).unwrap();
+    assert!(!change.full_window);
+    assert!((change.pct - 6.0).abs() < 1e-9);
+
+    // Exactly enough history still covers the window
+    let exact = hourly_chart(24);
+    let change = exact.price_change_pct(TimeInterval::OneHour, Duration::from_secs(24 * 3600));
+    assert!(change.unwrap().full_window);
+}
+
+#[wasm_bindgen_test]
+fn empty_chart_has_no_change() {
+    let chart = Chart::new("empty".into(), ChartType::Candlestick, 10);
+    assert_eq!(chart.price_change_pct(TimeInterval::OneHour, HEADER_CHANGE_WINDOW), None);
+    assert_eq!(format_price_change(None).0, "—");
+}
+
+#[wasm_bindgen_test]
+fn header_text_is_signed_and_labelled() {
+    let up = format_price_change(Some(PriceChange { pct: 1.234, full_window: true }));
+    assert_eq!(up, ("+1.23%".to_string(), "24h Change"));
+    let down = format_price_change(Some(PriceChange { pct: -0.5, full_window: false }));
+    assert_eq!(down, ("-0.50%".to_string(), "Change (loaded history)"));
+}
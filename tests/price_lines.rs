@@ -0,0 +1,121 @@
+use leptos::*;
+use price_chart_wasm::app::{
+    ALERT_REARM_RATIO, KeyboardShortcut, PriceAlertMonitor, add_price_line, horizontal_lines,
+    keyboard_shortcut, price_at_ndc, price_line_near, remove_price_line,
+};
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::{
+    ChartGeometryBuilder, panel_layout::PanelBand, renderer::HorizontalLine,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn alert(id: u32, price: f64) -> HorizontalLine {
+    HorizontalLine { alert: true, ..line(id, price) }
+}
+
+fn line(id: u32, price: f64) -> HorizontalLine {
+    HorizontalLine { id, price, color: [1.0; 4], label: String::new(), alert: false }
+}
+
+#[wasm_bindgen_test]
+fn pointer_height_maps_to_band_prices() {
+    let band = PanelBand { bottom: -0.5, top: 1.0 };
+    assert_eq!(price_at_ndc(-0.5, band, 100.0, 200.0), 100.0);
+    assert_eq!(price_at_ndc(1.0, band, 100.0, 200.0), 200.0);
+    assert!((price_at_ndc(0.25, band, 100.0, 200.0) - 150.0).abs() < 1e-4);
+}
+
+#[wasm_bindgen_test]
+fn clicks_select_the_closest_line_within_tolerance() {
+    let lines = [line(1, 100.0), line(2, 103.0), line(3, 110.0)];
+    assert_eq!(price_line_near(&lines, 102.0, 2.5), Some(1));
+    assert_eq!(price_line_near(&lines, 100.5, 2.5), Some(0));
+    assert_eq!(price_line_near(&lines, 106.5, 2.5), None);
+    assert_eq!(price_line_near(&[], 100.0, 2.5), None);
+}
+
+#[wasm_bindgen_test]
+fn price_lines_are_removed_by_index() {
+    horizontal_lines().set(Vec::new());
+    let first = add_price_line(101.0);
+    let second = add_price_line(202.0);
+
+    assert!(remove_price_line(0));
+    assert!(!remove_price_line(5));
+    horizontal_lines().with_untracked(|lines| {
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].id, second);
+        assert_eq!(lines[0].price, 202.0);
+        assert!(lines.iter().all(|l| l.id != first));
+    });
+    assert!(remove_price_line(0));
+}
+
+#[wasm_bindgen_test]
+fn alerts_fire_once_per_crossing() {
+    let lines = [alert(1, 100.0), line(2, 100.0)];
+    let mut monitor = PriceAlertMonitor::new();
+
+    // The first live price only arms the alert, even on the far side of the level
+    assert!(monitor.check(&lines, 105.0).is_empty());
+    let fired = monitor.check(&lines, 99.0);
+    assert_eq!(fired.iter().map(|l| l.id).collect::<Vec<_>>(), vec![1]);
+
+    // Hovering around the level stays silent until the price moves away
+    assert!(monitor.check(&lines, 100.02).is_empty());
+    assert!(monitor.check(&lines, 99.98).is_empty());
+    let away = 100.0 * (1.0 + 2.0 * ALERT_REARM_RATIO);
+    assert!(monitor.check(&lines, away).is_empty());
+    assert_eq!(monitor.check(&lines, 99.0).len(), 1);
+}
+
+#[wasm_bindgen_test]
+fn alerts_fire_from_below_and_on_touch() {
+    let lines = [alert(7, 50.0)];
+    let mut monitor = PriceAlertMonitor::new();
+    assert!(monitor.check(&lines, 49.0).is_empty());
+    assert_eq!(monitor.check(&lines, 50.0).len(), 1);
+
+    // Disabling the alert forgets its state
+    assert!(monitor.check(&[line(7, 50.0)], 60.0).is_empty());
+    assert!(monitor.check(&lines, 40.0).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn selected_line_keys() {
+    assert_eq!(keyboard_shortcut("Delete", false), Some(KeyboardShortcut::DeletePriceLine));
+    assert_eq!(keyboard_shortcut("n", false), Some(KeyboardShortcut::TogglePriceAlert));
+}
+
+#[wasm_bindgen_test]
+fn price_line_edits_rebuild_a_static_chart() {
+    horizontal_lines().set(Vec::new());
+    let mut chart = Chart::new("static-price-lines".into(), ChartType::Candlestick, 10);
+    chart.add_candle(Candle::new(
+        Timestamp::from_millis(0),
+        OHLCV::new(
+            Price::from(100.0),
+            Price::from(110.0),
+            Price::from(90.0),
+            Price::from(105.0),
+            Volume::from(1.0),
+        ),
+    ));
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.update(&chart);
+
+    add_price_line(101.0);
+    assert_eq!(builder.update(&chart), Some(true));
+    // Delete key on the selected line
+    assert!(remove_price_line(0));
+    assert_eq!(builder.update(&chart), Some(true));
+    assert_eq!(builder.update(&chart), Some(false));
+    // Switching symbols swaps in that symbol's stored lines
+    horizontal_lines().set(vec![line(1, 95.0), line(2, 108.0)]);
+    assert_eq!(builder.update(&chart), Some(true));
+    horizontal_lines().set(Vec::new());
+}
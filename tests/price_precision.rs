@@ -0,0 +1,54 @@
+use price_chart_wasm::app::effective_price_tick;
+use price_chart_wasm::domain::market_data::{OHLCV, Price, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{round_ohlc_to_tick, round_to_tick};
+use price_chart_wasm::infrastructure::websocket::BinanceExchangeInfo;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn prices_snap_to_tick() {
+    assert!((round_to_tick(100.0149, 0.01) - 100.01).abs() < 1e-9);
+    assert!((round_to_tick(100.0151, 0.01) - 100.02).abs() < 1e-9);
+    assert!((round_to_tick(0.123456, 0.0001) - 0.1235).abs() < 1e-12);
+    // Invalid ticks leave prices untouched
+    assert_eq!(round_to_tick(1.2345, 0.0), 1.2345);
+    assert_eq!(round_to_tick(1.2345, -1.0), 1.2345);
+}
+
+#[wasm_bindgen_test]
+fn ohlc_rounding_keeps_volume() {
+    let mut ohlcv = OHLCV::new(
+        Price::new(10.004),
+        Price::new(10.016),
+        Price::new(9.994),
+        Price::new(10.006),
+        Volume::new(1.2345),
+    );
+    round_ohlc_to_tick(&mut ohlcv, 0.01);
+
+    assert!((ohlcv.open.value() - 10.0).abs() < 1e-9);
+    assert!((ohlcv.high.value() - 10.02).abs() < 1e-9);
+    assert!((ohlcv.low.value() - 9.99).abs() < 1e-9);
+    assert!((ohlcv.close.value() - 10.01).abs() < 1e-9);
+    assert_eq!(ohlcv.volume.value(), 1.2345);
+}
+
+#[wasm_bindgen_test]
+fn rounding_setting_gates_tick() {
+    assert_eq!(effective_price_tick(false, Some(0.01)), None);
+    assert_eq!(effective_price_tick(true, Some(0.01)), Some(0.01));
+    assert_eq!(effective_price_tick(true, None), None);
+}
+
+#[wasm_bindgen_test]
+fn tick_size_from_exchange_info() {
+    let json = r#"{"symbols":[{"symbol":"BTCUSDT","filters":[
+        {"filterType":"PRICE_FILTER","minPrice":"0.01","maxPrice":"1000000.00","tickSize":"0.01000000"},
+        {"filterType":"LOT_SIZE","minQty":"0.00001","maxQty":"9000.0","stepSize":"0.00001"}
+    ]}]}"#;
+    let info: BinanceExchangeInfo = serde_json::from_str(json).unwrap();
+
+    assert_eq!(info.tick_size("BTCUSDT"), Some(0.01));
+    assert_eq!(info.tick_size("btcusdt"), Some(0.01));
+    assert_eq!(info.tick_size("ETHUSDT"), None);
+}
@@ -0,0 +1,147 @@
+use leptos::SignalWithUntracked;
+use price_chart_wasm::app::{
+    displayed_price_viewport, price_level_decimals, price_level_percent, price_levels,
+    publish_frame_overlays,
+};
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType, value_objects::Viewport},
+    market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::gpu_structures::CandleVertex;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    PRICE_GRID_LINES, nice_price_step, nice_price_ticks, price_step_decimals,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn viewport(min_price: f32, max_price: f32) -> Viewport {
+    Viewport { start_time: 0.0, end_time: 100.0, min_price, max_price, width: 800, height: 600 }
+}
+
+#[wasm_bindgen_test]
+fn steps_are_one_two_or_five_times_a_power_of_ten() {
+    assert_eq!(nice_price_step(0.0, 100.0, 10), 10.0);
+    assert_eq!(nice_price_step(0.0, 100.0, 8), 10.0);
+    assert_eq!(nice_price_step(25.0, 75.0, 8), 5.0);
+    assert_eq!(nice_price_step(0.0, 30.0, 8), 5.0);
+    assert_eq!(nice_price_step(0.0, 16.0, 8), 2.0);
+    assert_eq!(nice_price_step(5.0, 5.0, 8), 0.0);
+}
+
+#[wasm_bindgen_test]
+fn ticks_land_on_round_prices_inside_the_range() {
+    assert_eq!(
+        nice_price_ticks(97.3, 131.8, 8),
+        vec![100.0, 105.0, 110.0, 115.0, 120.0, 125.0, 130.0]
+    );
+    assert!(nice_price_ticks(1.0, 1.0, 8).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn sub_dollar_ranges_use_small_steps() {
+    let ticks = nice_price_ticks(0.12341, 0.12387, PRICE_GRID_LINES);
+    assert_eq!(
+        ticks,
+        vec![0.12345, 0.1235, 0.12355, 0.1236, 0.12365, 0.1237, 0.12375, 0.1238, 0.12385]
+    );
+    assert_eq!(price_step_decimals(0.00005), 5);
+    assert_eq!(price_step_decimals(5.0), 2);
+}
+
+#[wasm_bindgen_test]
+fn bitcoin_ranges_use_large_steps() {
+    let ticks = nice_price_ticks(61_234.5, 68_910.2, PRICE_GRID_LINES);
+    assert_eq!(ticks.first(), Some(&62_000.0));
+    assert_eq!(ticks.last(), Some(&68_000.0));
+    assert!(ticks.windows(2).all(|w| w[1] - w[0] == 1_000.0));
+}
+
+#[wasm_bindgen_test]
+fn axis_labels_match_the_grid() {
+    let vp = viewport(0.12341, 0.12387);
+    let levels = price_levels(&vp);
+    assert_eq!(levels.first(), Some(&0.12385));
+    assert_eq!(price_level_decimals(&levels), 5);
+    assert!((price_level_percent(&vp, vp.max_price as f64)).abs() < 1e-3);
+    assert!((price_level_percent(&vp, vp.min_price as f64) - 100.0).abs() < 1e-3);
+}
+
+#[wasm_bindgen_test]
+fn builder_draws_a_grid_line_per_tick() {
+    let candles: Vec<Candle> = (0..30u64)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(close - 0.5),
+                    Price::from(close + 1.0),
+                    Price::from(close - 1.0),
+                    Price::from(close),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("ticks".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (_, verts, uniforms) = builder.create_geometry(&chart);
+    let [_, _, min, max] = uniforms.viewport;
+
+    let is_grid = |v: &&CandleVertex| (v.element_type - 3.0).abs() < f32::EPSILON;
+    let full_width_lines = verts
+        .iter()
+        .filter(is_grid)
+        .collect::<Vec<_>>()
+        .chunks(6)
+        .filter(|quad| {
+            let min_x = quad.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+            let max_x = quad.iter().map(|v| v.position_x).fold(f32::NEG_INFINITY, f32::max);
+            max_x - min_x > 1.9
+        })
+        .count();
+    let ticks = nice_price_ticks(min as f64, max as f64, PRICE_GRID_LINES).len();
+    assert!(ticks >= 4);
+    // Panel separators are full-width too
+    let separators =
+        price_chart_wasm::app::panel_layout().with_untracked(|l| l.bands().len().saturating_sub(1));
+    assert_eq!(full_width_lines, ticks + separators);
+}
+
+#[wasm_bindgen_test]
+fn labels_follow_the_auto_fitted_range() {
+    let candles: Vec<Candle> = (0..400u64)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            Candle::new(
+                Timestamp::from_millis(i * 60_000),
+                OHLCV::new(
+                    Price::from(close - 0.5),
+                    Price::from(close + 1.0),
+                    Price::from(close - 1.0),
+                    Price::from(close),
+                    Volume::from(1.0),
+                ),
+            )
+        })
+        .collect();
+    let mut chart = Chart::new("fitted".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles);
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    builder.set_zoom_params(4.0, 0.0);
+    builder.update(&chart);
+    publish_frame_overlays(&builder, &chart);
+
+    // Only the newest candles are on screen, so auto-fit narrows the range
+    let [_, _, min, max] = builder.cached_uniforms().viewport;
+    assert!(max - min < chart.viewport.max_price - chart.viewport.min_price);
+
+    let shown = displayed_price_viewport(&chart.viewport);
+    let mut grid = nice_price_ticks(min as f64, max as f64, PRICE_GRID_LINES);
+    grid.reverse();
+    assert_eq!(price_levels(&shown), grid);
+    assert_ne!(price_levels(&chart.viewport), grid);
+    assert!(price_level_percent(&shown, max as f64).abs() < 1e-3);
+}
@@ -0,0 +1,77 @@
+use leptos::*;
+use price_chart_wasm::app::{
+    current_interval, current_symbol, global_current_price, push_candle, set_candles,
+};
+use price_chart_wasm::domain::errors::ChartError;
+use price_chart_wasm::domain::market_data::{Candle, RawCandle, Symbol, TimeInterval, Timestamp};
+use price_chart_wasm::global_state::ensure_chart;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn timestamps_are_whole_unix_milliseconds() {
+    assert_eq!(Timestamp::validate(1_700_000_000_000.0).unwrap().value(), 1_700_000_000_000);
+    for bad in [-1.0, 1.5, f64::NAN, f64::INFINITY] {
+        assert!(matches!(Timestamp::validate(bad), Err(ChartError::InvalidData(_))));
+    }
+}
+
+#[wasm_bindgen_test]
+fn raw_candles_are_validated() {
+    let candle = Candle::from_raw(60_000.0, 10.0, 12.0, 9.0, 11.0, 5.0).unwrap();
+    assert_eq!(candle.timestamp.value(), 60_000);
+    assert_eq!(candle.ohlcv.close.value(), 11.0);
+
+    // Negative price, NaN volume, and a high below the close
+    assert!(Candle::from_raw(0.0, -1.0, 12.0, 9.0, 11.0, 5.0).is_err());
+    assert!(Candle::from_raw(0.0, 10.0, 12.0, 9.0, 11.0, f64::NAN).is_err());
+    assert!(Candle::from_raw(0.0, 10.0, 10.5, 9.0, 11.0, 5.0).is_err());
+}
+
+#[wasm_bindgen_test]
+fn json_lists_parse_or_name_the_bad_candle() {
+    let json = r#"[
+        {"timestamp": 0, "open": 1, "high": 2, "low": 0.5, "close": 1.5, "volume": 3},
+        {"timestamp": 60000, "open": 1.5, "high": 1.5, "low": 1, "close": 1}
+    ]"#;
+    let candles = RawCandle::parse_list(json).unwrap();
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[1].ohlcv.volume.value(), 0.0);
+
+    let bad = r#"[{"timestamp": 0, "open": 1, "high": 2, "low": 0.5, "close": 1.5},
+                  {"timestamp": 1, "open": 1, "high": 0, "low": 0.5, "close": 1.5}]"#;
+    let err = RawCandle::parse_list(bad).unwrap_err();
+    assert!(err.message().starts_with("Candle 1:"));
+    assert!(matches!(RawCandle::parse_list("{}"), Err(ChartError::Parse(_))));
+}
+
+#[wasm_bindgen_test]
+fn embedder_candles_drive_the_chart() {
+    let symbol = Symbol::from("EMBEDDED");
+    current_symbol().set(symbol.clone());
+    current_interval().set(TimeInterval::OneMinute);
+
+    let history = RawCandle::parse_list(
+        r#"[{"timestamp": 0, "open": 1, "high": 2, "low": 0.5, "close": 1.5, "volume": 3},
+            {"timestamp": 60000, "open": 1.5, "high": 3, "low": 1, "close": 2.5, "volume": 1}]"#,
+    )
+    .unwrap();
+    set_candles(history);
+    let chart = ensure_chart(&symbol);
+    let count = move || chart.with_untracked(|c| c.get_candle_count());
+    assert_eq!(count(), 2);
+    assert_eq!(global_current_price().get_untracked(), 2.5);
+
+    // Same open time replaces the forming candle, a later one appends
+    push_candle(Candle::from_raw(60_000.0, 1.5, 3.5, 1.0, 3.0, 2.0).unwrap());
+    assert_eq!(count(), 2);
+    push_candle(Candle::from_raw(120_000.0, 3.0, 4.0, 2.0, 3.5, 1.0).unwrap());
+    assert_eq!(count(), 3);
+    assert_eq!(global_current_price().get_untracked(), 3.5);
+
+    let closes: Vec<f64> = chart.with_untracked(|c| {
+        let series = c.get_series(TimeInterval::OneMinute).unwrap();
+        series.get_candles().iter().map(|c| c.ohlcv.close.value()).collect()
+    });
+    assert_eq!(closes, vec![1.5, 3.0, 3.5]);
+}
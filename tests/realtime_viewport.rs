@@ -77,3 +77,37 @@ fn pan_reveals_latest_realtime_candle() {
     assert_eq!(chart.viewport.start_time, 60_000.0);
     assert_eq!(chart.viewport.end_time, 120_000.0);
 }
+
+#[wasm_bindgen_test]
+fn first_candle_fits_an_empty_viewport() {
+    let candle = |ms: u64, price: f64| {
+        Candle::new(
+            Timestamp::from_millis(ms),
+            OHLCV::new(
+                Price::from(price),
+                Price::from(price + 1.0),
+                Price::from(price - 1.0),
+                Price::from(price),
+                Volume::from(1.0),
+            ),
+        )
+    };
+    let mut chart = Chart::new("test".into(), ChartType::Candlestick, 10);
+    chart.viewport.min_price = 0.0;
+    chart.viewport.max_price = 0.0;
+    assert!(chart.viewport.is_empty());
+
+    chart.add_candle(candle(1_000, 50.0));
+    assert!(!chart.viewport.is_empty());
+    assert!(chart.viewport.min_price < 49.0 && chart.viewport.max_price > 51.0);
+    assert_eq!(chart.viewport.start_time, 1_000.0);
+
+    // Later candles leave a fitted viewport alone
+    let fitted = chart.viewport.clone();
+    chart.add_candle(candle(3_000, 80.0));
+    assert_eq!(chart.viewport, fitted);
+
+    chart.recompute_viewport();
+    assert!(chart.viewport.max_price > 81.0);
+    assert_eq!(chart.viewport.end_time, 3_000.0);
+}
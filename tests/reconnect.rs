@@ -17,3 +17,21 @@ async fn reconnect_called_on_failure() {
     let _ = select(Box::pin(fut), Box::pin(sleep(Duration::from_millis(10)))).await;
     assert!(*called.borrow() > 0);
 }
+
+#[wasm_bindgen_test]
+fn reconnect_backoff_doubles_up_to_cap() {
+    use price_chart_wasm::infrastructure::websocket::binance_client::reconnect_delay;
+    let secs: Vec<u64> = (0..7).map(|a| reconnect_delay(a).as_secs()).collect();
+    assert_eq!(secs, vec![1, 2, 4, 8, 16, 30, 30]);
+    assert_eq!(reconnect_delay(u32::MAX).as_secs(), 30);
+}
+
+#[wasm_bindgen_test]
+fn retry_limit_configurable() {
+    let symbol = Symbol::from("BTCUSDT");
+    let client = BinanceWebSocketClient::new(symbol.clone(), TimeInterval::OneMinute);
+    assert_eq!(client.max_retries(), None);
+    let limited =
+        BinanceWebSocketClient::with_max_retries(symbol, TimeInterval::OneMinute, Some(5));
+    assert_eq!(limited.max_retries(), Some(5));
+}
@@ -2,7 +2,7 @@ use price_chart_wasm::domain::{
     chart::{Chart, value_objects::ChartType},
     market_data::{Candle, OHLCV, Price, Timestamp, Volume},
 };
-use price_chart_wasm::infrastructure::rendering::renderer::dummy_renderer;
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
 use wasm_bindgen_test::*;
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
@@ -35,15 +35,15 @@ fn sample_chart() -> Chart {
 #[wasm_bindgen_test]
 fn body_positions_within_bounds() {
     let chart = sample_chart();
-    let renderer = dummy_renderer();
-    let (_instances, vertices, _uniforms) = renderer.create_geometry_for_test(&chart);
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let (instances, vertices, _uniforms) = builder.create_geometry(&chart);
 
-    let body: Vec<_> = vertices.iter().filter(|v| v.element_type == 0.0).collect();
+    // Bodies are instanced, one record per candle
+    assert_eq!(instances.len(), 2);
+    assert!(!vertices.iter().any(|v| v.element_type == 0.0));
 
-    assert!(body.len() >= 36); // two candles * 18 vertices each
-
-    let first_body_x = body[0].position_x;
-    let second_body_x = body[18].position_x;
+    let first_body_x = instances[0].x;
+    let second_body_x = instances[1].x;
 
     assert_ne!(first_body_x, second_body_x);
     for &x in &[first_body_x, second_body_x] {
@@ -0,0 +1,58 @@
+use price_chart_wasm::infrastructure::websocket::rest::{
+    FetchError, TokenBucket, is_rate_limited, parse_retry_after, retry_delay,
+};
+use std::time::Duration;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn bucket_grants_until_empty_then_reports_wait() {
+    // 60 tokens per minute = one per second
+    let mut bucket = TokenBucket::new(60.0, Duration::from_secs(60));
+    assert_eq!(bucket.try_take(50.0, 0.0), Ok(()));
+    assert_eq!(bucket.try_take(10.0, 0.0), Ok(()));
+    assert_eq!(bucket.try_take(2.0, 0.0), Err(Duration::from_secs(2)));
+}
+
+#[wasm_bindgen_test]
+fn bucket_refills_over_time_up_to_capacity() {
+    let mut bucket = TokenBucket::new(60.0, Duration::from_secs(60));
+    assert_eq!(bucket.try_take(60.0, 0.0), Ok(()));
+    assert_eq!(bucket.try_take(5.0, 5_000.0), Ok(()));
+    assert!(bucket.available().abs() < 1e-9);
+
+    assert_eq!(bucket.try_take(0.0, 1_000_000.0), Ok(()));
+    assert_eq!(bucket.available(), 60.0);
+}
+
+#[wasm_bindgen_test]
+fn oversized_weight_waits_for_a_full_bucket() {
+    let mut bucket = TokenBucket::new(10.0, Duration::from_secs(10));
+    assert_eq!(bucket.try_take(5.0, 0.0), Ok(()));
+    assert_eq!(bucket.try_take(50.0, 0.0), Err(Duration::from_secs(5)));
+    assert_eq!(bucket.try_take(50.0, 5_000.0), Ok(()));
+}
+
+#[wasm_bindgen_test]
+fn retry_after_header_overrides_backoff() {
+    assert_eq!(parse_retry_after(Some(" 7 ")), Some(Duration::from_secs(7)));
+    assert_eq!(parse_retry_after(Some("Wed, 21 Oct 2015 07:28:00 GMT")), None);
+    assert_eq!(parse_retry_after(None), None);
+
+    assert_eq!(retry_delay(Some(Duration::from_secs(7)), 3), Duration::from_secs(7));
+    assert_eq!(retry_delay(None, 0), Duration::from_secs(1));
+    assert_eq!(retry_delay(None, 2), Duration::from_secs(4));
+}
+
+#[wasm_bindgen_test]
+fn rate_limit_statuses_and_error_messages() {
+    assert!(is_rate_limited(429));
+    assert!(is_rate_limited(418));
+    assert!(!is_rate_limited(500));
+
+    let limited = FetchError::RateLimited { retry_after: Some(Duration::from_secs(3)) };
+    assert_eq!(limited.to_string(), "Rate limited, retry after 3s");
+    assert_eq!(FetchError::Status(503).to_string(), "HTTP error: 503");
+    assert_eq!(FetchError::Parse("bad".into()).to_string(), "Parse error: bad");
+    assert_eq!(FetchError::Network("down".into()).to_string(), "Network error: down");
+}
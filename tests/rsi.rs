@@ -0,0 +1,37 @@
+use price_chart_wasm::infrastructure::rendering::renderer::{DEFAULT_RSI_PERIOD, wilder_rsi};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// Wilder's reference closes
+const CLOSES: [f64; 16] = [
+    44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61,
+    46.28, 46.28, 46.00,
+];
+
+#[wasm_bindgen_test]
+fn rsi_uses_wilder_smoothing() {
+    let rsi = wilder_rsi(&CLOSES, DEFAULT_RSI_PERIOD);
+    assert_eq!(rsi.len(), 2);
+    assert!((rsi[0] - 70.464).abs() < 0.01);
+    assert!((rsi[1] - 66.250).abs() < 0.01);
+}
+
+#[wasm_bindgen_test]
+fn rsi_needs_period_plus_one_closes() {
+    assert!(wilder_rsi(&CLOSES[..14], 14).is_empty());
+    assert_eq!(wilder_rsi(&CLOSES[..15], 14).len(), 1);
+    assert!(wilder_rsi(&[], 14).is_empty());
+    assert!(wilder_rsi(&CLOSES, 0).is_empty());
+}
+
+#[wasm_bindgen_test]
+fn rsi_extremes() {
+    let rising: Vec<f64> = (0..20).map(f64::from).collect();
+    assert!(wilder_rsi(&rising, 14).iter().all(|v| *v == 100.0));
+
+    let flat = [1.0; 20];
+    assert!(wilder_rsi(&flat, 14).iter().all(|v| *v == 50.0));
+
+    let falling: Vec<f64> = (0..20).rev().map(f64::from).collect();
+    assert!(wilder_rsi(&falling, 14).iter().all(|v| *v == 0.0));
+}
@@ -0,0 +1,52 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{LineVisibility, session_high_low};
+use price_chart_wasm::time_utils::MS_PER_DAY;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(ts: u64, low: f64, high: f64) -> Candle {
+    Candle::new(
+        Timestamp::new(ts),
+        OHLCV::new(
+            Price::new(low),
+            Price::new(high),
+            Price::new(low),
+            Price::new(high),
+            Volume::new(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn session_uses_only_current_day() {
+    let day = MS_PER_DAY * 10;
+    let candles = vec![
+        // Previous day extremes must be ignored
+        candle(day - 60_000, 10.0, 500.0),
+        candle(day, 100.0, 120.0),
+        candle(day + 60_000, 95.0, 110.0),
+        candle(day + 120_000, 101.0, 130.0),
+    ];
+
+    assert_eq!(session_high_low(&candles), Some((95.0, 130.0)));
+}
+
+#[wasm_bindgen_test]
+fn session_updates_with_new_extremes() {
+    let day = MS_PER_DAY * 3;
+    let mut candles = vec![candle(day, 100.0, 110.0)];
+    assert_eq!(session_high_low(&candles), Some((100.0, 110.0)));
+
+    candles.push(candle(day + 60_000, 90.0, 140.0));
+    assert_eq!(session_high_low(&candles), Some((90.0, 140.0)));
+
+    // A new day resets the session
+    candles.push(candle(day + MS_PER_DAY, 120.0, 125.0));
+    assert_eq!(session_high_low(&candles), Some((120.0, 125.0)));
+}
+
+#[wasm_bindgen_test]
+fn session_empty_and_hidden_by_default() {
+    assert_eq!(session_high_low(&[]), None);
+    assert!(!LineVisibility::default().session_levels);
+}
@@ -0,0 +1,83 @@
+use price_chart_wasm::domain::market_data::{HistoryDepth, Symbol, TimeInterval};
+use price_chart_wasm::infrastructure::rendering::renderer::{LineVisibility, MinBodyHeight};
+use price_chart_wasm::settings::{ChartSettings, ThemePreset};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn custom() -> ChartSettings {
+    ChartSettings {
+        symbol: Symbol::from("ETHUSDT"),
+        interval: TimeInterval::FifteenMinutes,
+        theme: ThemePreset::Light,
+        line_visibility: LineVisibility { rsi_14: true, sma_200: false, ..Default::default() },
+        min_body_height: MinBodyHeight::Off,
+        history_depth: HistoryDepth::days(7),
+    }
+}
+
+#[wasm_bindgen_test]
+fn settings_round_trip_through_json() {
+    let settings = custom();
+    assert_eq!(ChartSettings::from_json(&settings.to_json()).unwrap(), settings);
+}
+
+#[wasm_bindgen_test]
+fn absent_or_corrupt_storage_falls_back_to_defaults() {
+    assert_eq!(ChartSettings::from_stored(None), ChartSettings::default());
+    assert_eq!(ChartSettings::from_stored(Some("{not json")), ChartSettings::default());
+    assert_eq!(ChartSettings::from_stored(Some("[1,2]")), ChartSettings::default());
+    assert!(ChartSettings::from_json("42").is_err());
+}
+
+#[wasm_bindgen_test]
+fn malformed_fields_fall_back_individually() {
+    let json = r#"{"symbol":"","interval":"3h","theme":"light","line_visibility":{"macd":true}}"#;
+    let settings = ChartSettings::from_json(json).unwrap();
+    assert_eq!(settings.symbol, Symbol::from("BTCUSDT"));
+    assert_eq!(settings.interval, TimeInterval::OneMinute);
+    assert_eq!(settings.theme, ThemePreset::Light);
+    assert!(settings.line_visibility.macd);
+    assert_eq!(settings.line_visibility.sma_20, LineVisibility::default().sma_20);
+    assert_eq!(settings.min_body_height, MinBodyHeight::default());
+}
+
+#[wasm_bindgen_test]
+fn min_body_height_is_stored() {
+    let settings = custom();
+    assert!(settings.to_json().contains("\"min_body_height\":\"off\""));
+    let json = r#"{"min_body_height":{"ndc":0.01}}"#;
+    assert_eq!(ChartSettings::from_json(json).unwrap().min_body_height, MinBodyHeight::Ndc(0.01));
+    let json = r#"{"min_body_height":"tall"}"#;
+    assert_eq!(ChartSettings::from_json(json).unwrap().min_body_height, MinBodyHeight::default());
+    for json in [r#"{"min_body_height":{"ndc":-0.01}}"#, r#"{"min_body_height":{"ndc":5}}"#] {
+        let settings = ChartSettings::from_json(json).unwrap();
+        assert_eq!(settings.min_body_height, MinBodyHeight::default());
+    }
+}
+
+#[wasm_bindgen_test]
+fn history_depth_is_stored() {
+    let json = r#"{"history_depth":{"candles":300}}"#;
+    assert_eq!(ChartSettings::from_json(json).unwrap().history_depth, HistoryDepth::Candles(300));
+    let json = r#"{"history_depth":{"lookback":-1}}"#;
+    assert_eq!(ChartSettings::from_json(json).unwrap().history_depth, HistoryDepth::Auto);
+}
+
+#[wasm_bindgen_test]
+fn theme_presets_map_to_palettes() {
+    use price_chart_wasm::infrastructure::rendering::gpu_structures::ChartTheme;
+    assert_eq!(ThemePreset::Dark.palette(), ChartTheme::dark());
+    assert_eq!(ThemePreset::Light.palette(), ChartTheme::light());
+    assert_eq!(ThemePreset::Colorblind.palette(), ChartTheme::colorblind());
+}
+
+#[wasm_bindgen_test]
+fn colorblind_preset_is_stored_by_name() {
+    let settings = ChartSettings { theme: ThemePreset::Colorblind, ..custom() };
+    assert!(settings.to_json().contains("\"theme\":\"colorblind\""));
+    assert_eq!(ChartSettings::from_json(&settings.to_json()).unwrap(), settings);
+    for preset in ThemePreset::ALL {
+        assert_eq!(ThemePreset::from_name(preset.name()), Some(preset));
+    }
+    assert_eq!(ThemePreset::from_name("sepia"), None);
+}
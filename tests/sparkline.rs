@@ -0,0 +1,52 @@
+use price_chart_wasm::domain::market_data::TimeInterval;
+use price_chart_wasm::infrastructure::rendering::renderer::sparkline_vertices;
+use price_chart_wasm::sparkline_chart;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn sparkline_fills_full_height() {
+    let closes = [10.0, 20.0, 15.0, 30.0];
+    let width = 0.02;
+    let verts = sparkline_vertices(&closes, width);
+    assert_eq!(verts.len(), (closes.len() - 1) * 6);
+
+    let min_y = verts.iter().map(|v| v.position_y).fold(f32::INFINITY, f32::min);
+    let max_y = verts.iter().map(|v| v.position_y).fold(f32::NEG_INFINITY, f32::max);
+    let min_x = verts.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+    let max_x = verts.iter().map(|v| v.position_x).fold(f32::NEG_INFINITY, f32::max);
+
+    // Extremes touch the edges without the stroke leaving the canvas
+    assert!((max_y - 1.0).abs() < width);
+    assert!((min_y + 1.0).abs() < width);
+    assert!(max_y <= 1.0 + 1e-6 && min_y >= -1.0 - 1e-6);
+    assert!((min_x + 1.0).abs() < width && (max_x - 1.0).abs() < width);
+}
+
+#[wasm_bindgen_test]
+fn sparkline_color_follows_trend() {
+    let up = sparkline_vertices(&[1.0, 2.0], 0.02);
+    let down = sparkline_vertices(&[2.0, 1.0], 0.02);
+    assert!(up.iter().all(|v| v.color_type > 0.5));
+    assert!(down.iter().all(|v| v.color_type < 0.5));
+}
+
+#[wasm_bindgen_test]
+fn sparkline_handles_flat_and_short_input() {
+    assert!(sparkline_vertices(&[], 0.02).is_empty());
+    assert!(sparkline_vertices(&[5.0], 0.02).is_empty());
+
+    let flat = sparkline_vertices(&[5.0, 5.0, 5.0], 0.02);
+    assert!(flat.iter().all(|v| v.position_y.abs() <= 0.01 + 1e-6));
+}
+
+#[wasm_bindgen_test]
+fn sparkline_chart_spaces_closes_by_the_given_interval() {
+    let closes = [10.0, f64::NAN, 12.0, 11.0];
+    let chart = sparkline_chart("spark", &closes, TimeInterval::FiveMinutes);
+    let series = chart.get_series(TimeInterval::FiveMinutes).unwrap();
+    let candles: Vec<_> = series.get_candles().iter().collect();
+    assert_eq!(candles.len(), 3);
+    assert_eq!(candles[1].timestamp.value(), 2 * TimeInterval::FiveMinutes.duration_ms());
+    assert_eq!(candles[2].ohlcv.close.value(), 11.0);
+}
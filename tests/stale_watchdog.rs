@@ -0,0 +1,28 @@
+use price_chart_wasm::domain::market_data::TimeInterval;
+use price_chart_wasm::infrastructure::websocket::{BinanceStream, stale_timeout};
+use std::time::Duration;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn kline_streams_wait_twice_the_interval_within_bounds() {
+    let kline = |interval| stale_timeout(&[BinanceStream::Kline], interval);
+    assert_eq!(kline(TimeInterval::OneMinute), Duration::from_secs(120));
+    // Short intervals never drop below 30s, long ones never exceed 120s
+    assert_eq!(kline(TimeInterval::TwoSeconds), Duration::from_secs(30));
+    assert_eq!(kline(TimeInterval::OneDay), Duration::from_secs(120));
+}
+
+#[wasm_bindgen_test]
+fn trade_and_depth_streams_allow_thirty_seconds() {
+    for stream in [BinanceStream::Trade, BinanceStream::Depth] {
+        assert_eq!(stale_timeout(&[stream], TimeInterval::OneDay), Duration::from_secs(30));
+    }
+}
+
+#[wasm_bindgen_test]
+fn combined_streams_use_the_shortest_timeout() {
+    let streams = [BinanceStream::Kline, BinanceStream::Depth];
+    assert_eq!(stale_timeout(&streams, TimeInterval::OneHour), Duration::from_secs(30));
+    assert_eq!(stale_timeout(&[], TimeInterval::OneHour), Duration::from_secs(30));
+}
@@ -0,0 +1,70 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    DEFAULT_STOCHASTIC_PERIOD, STOCHASTIC_SMOOTHING, stochastic_series,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn candle(i: u64, high: f64, low: f64, close: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 60_000),
+        OHLCV::new(
+            Price::from(close),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn k_uses_highs_and_lows_not_just_closes() {
+    // Closes sit at the middle of a 10-wide high/low range
+    let candles: Vec<Candle> =
+        (0..5).map(|i| candle(i, 110.0 + i as f64, 100.0 + i as f64, 105.0 + i as f64)).collect();
+    let series = stochastic_series(&candles, 3);
+    assert_eq!(series.k.len(), 3);
+    // Window 0..3: low 100, high 112, close 107
+    assert!((series.k[0] - 100.0 * 7.0 / 12.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn d_is_a_three_period_average_of_k() {
+    let candles: Vec<Candle> = (0..30)
+        .map(|i| {
+            let close = 100.0 + (i as f64 * 0.7).sin() * 5.0;
+            candle(i, close + 1.0, close - 1.0, close)
+        })
+        .collect();
+    let series = stochastic_series(&candles, DEFAULT_STOCHASTIC_PERIOD);
+    assert_eq!(series.k.len(), candles.len() - DEFAULT_STOCHASTIC_PERIOD + 1);
+    assert_eq!(series.d.len(), series.k.len() - STOCHASTIC_SMOOTHING + 1);
+    for (i, d) in series.d.iter().enumerate() {
+        let mean = series.k[i..i + STOCHASTIC_SMOOTHING].iter().sum::<f64>() / 3.0;
+        assert!((d - mean).abs() < 1e-9);
+    }
+    assert!(series.k.iter().all(|k| (0.0..=100.0).contains(k)));
+}
+
+#[wasm_bindgen_test]
+fn flat_window_holds_the_previous_k() {
+    let mut candles: Vec<Candle> = (0..3).map(|i| candle(i, 110.0, 100.0, 110.0)).collect();
+    // Once the rising candles leave the window, high equals low
+    candles.extend((3..6).map(|i| candle(i, 108.0, 108.0, 108.0)));
+    let series = stochastic_series(&candles, 3);
+    assert_eq!(series.k[0], 100.0);
+    assert!((series.k[2] - 80.0).abs() < 1e-9);
+    assert_eq!(series.k[3], series.k[2]);
+    assert!(series.k.iter().all(|k| k.is_finite()));
+
+    let flat: Vec<Candle> = (0..5).map(|i| candle(i, 1.0, 1.0, 1.0)).collect();
+    assert!(stochastic_series(&flat, 3).k.iter().all(|k| *k == 50.0));
+}
+
+#[wasm_bindgen_test]
+fn too_few_candles_yield_nothing() {
+    let candles: Vec<Candle> = (0..13).map(|i| candle(i, 2.0, 1.0, 1.5)).collect();
+    assert!(stochastic_series(&candles, DEFAULT_STOCHASTIC_PERIOD).k.is_empty());
+    assert!(stochastic_series(&candles, 0).k.is_empty());
+}
@@ -0,0 +1,26 @@
+use futures::FutureExt;
+use leptos::*;
+use price_chart_wasm::app::{
+    abort_other_streams, current_symbol, start_websocket_stream, stream_abort_handles,
+};
+use price_chart_wasm::domain::market_data::Symbol;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn loading_stream_can_be_aborted() {
+    let symbol = Symbol::from("BTCUSDT");
+    current_symbol().set(symbol.clone());
+    let (_, set_status) = create_signal(String::new());
+
+    // Poll once: the load now waits for history
+    let mut load = Box::pin(start_websocket_stream(set_status));
+    assert!(load.as_mut().now_or_never().is_none());
+    let handle = stream_abort_handles().with(|m| m.get(&symbol).cloned());
+    let handle = handle.expect("handle registered before the history request");
+
+    abort_other_streams(&Symbol::from("ETHUSDT"));
+    assert!(handle.is_aborted());
+    assert!(stream_abort_handles().with(|m| !m.contains_key(&symbol)));
+}
@@ -0,0 +1,61 @@
+use price_chart_wasm::domain::market_data::TimeInterval;
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    TIME_GRID_LINES, VisibleSpan, time_grid_indices,
+};
+use price_chart_wasm::time_utils::{civil_date, format_axis_time, time_zone_suffix};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+// 2024-03-10 14:35:00 UTC
+const TS: u64 = 1_710_081_300_000;
+
+#[wasm_bindgen_test]
+fn intraday_labels_show_utc_clock() {
+    assert_eq!(format_axis_time(TS, TimeInterval::OneMinute, 0), "14:35");
+    assert_eq!(format_axis_time(TS, TimeInterval::OneHour, 0), "14:35");
+    assert_eq!(format_axis_time(0, TimeInterval::FiveMinutes, 0), "00:00");
+}
+
+#[wasm_bindgen_test]
+fn daily_labels_show_date() {
+    assert_eq!(format_axis_time(TS, TimeInterval::OneDay, 0), "10.03.2024");
+    assert_eq!(format_axis_time(0, TimeInterval::OneWeek, 0), "01.01.1970");
+    assert_eq!(civil_date(951_782_400_000), (2000, 2, 29));
+}
+
+#[wasm_bindgen_test]
+fn configured_offset_shifts_labels() {
+    assert_eq!(format_axis_time(TS, TimeInterval::OneMinute, 180), "17:35");
+    assert_eq!(format_axis_time(TS, TimeInterval::OneMinute, -15 * 60), "23:35");
+    // Crossing midnight moves the date too
+    assert_eq!(format_axis_time(TS, TimeInterval::OneDay, 10 * 60), "11.03.2024");
+}
+
+#[wasm_bindgen_test]
+fn zone_suffix_is_explicit() {
+    assert_eq!(time_zone_suffix(None), "UTC");
+    assert_eq!(time_zone_suffix(Some(0)), "UTC");
+    assert_eq!(time_zone_suffix(Some(330)), "UTC+05:30");
+    assert_eq!(time_zone_suffix(Some(-240)), "UTC-04:00");
+}
+
+#[wasm_bindgen_test]
+fn grid_indices_span_visible_candles() {
+    assert!(time_grid_indices(0).is_empty());
+    assert_eq!(time_grid_indices(1), vec![0]);
+    assert_eq!(time_grid_indices(3), vec![0, 1, 2]);
+    let indices = time_grid_indices(101);
+    assert_eq!(indices.len(), TIME_GRID_LINES);
+    assert_eq!(indices, vec![0, 25, 50, 75, 100]);
+}
+
+#[wasm_bindgen_test]
+fn span_interpolates_timestamps() {
+    let span = VisibleSpan { first: 0, last: 600_000, count: 11 };
+    assert_eq!(span.timestamp_at(0), 0);
+    assert_eq!(span.timestamp_at(5), 300_000);
+    assert_eq!(span.timestamp_at(50), 600_000);
+
+    let single = VisibleSpan { first: 42, last: 42, count: 1 };
+    assert_eq!(single.timestamp_at(3), 42);
+}
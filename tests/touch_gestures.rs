@@ -0,0 +1,62 @@
+use price_chart_wasm::app::{
+    TAP_SLOP_PX, TouchAction, TouchGesture, pinch_zoom_delta, zoom_at_cursor,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn one_finger_drag_pans_by_the_finger_movement() {
+    let mut gesture = TouchGesture::default();
+    gesture.begin(&[(100.0, 50.0)]);
+    assert_eq!(gesture.update(&[(130.0, 52.0)]), Some(TouchAction::Pan(30.0)));
+    assert_eq!(gesture.update(&[(110.0, 52.0)]), Some(TouchAction::Pan(-20.0)));
+    // Moved past the slop, so lifting the finger is no tap
+    assert_eq!(gesture.end(&[]), None);
+    assert_eq!(gesture, TouchGesture::Idle);
+}
+
+#[wasm_bindgen_test]
+fn short_touch_is_a_tap_at_its_start() {
+    let mut gesture = TouchGesture::default();
+    gesture.begin(&[(100.0, 50.0)]);
+    gesture.update(&[(100.0 + TAP_SLOP_PX / 2.0, 50.0)]);
+    assert_eq!(gesture.end(&[]), Some(TouchAction::Tap { x: 100.0, y: 50.0 }));
+}
+
+#[wasm_bindgen_test]
+fn pinch_zooms_around_the_centroid() {
+    let mut gesture = TouchGesture::default();
+    gesture.begin(&[(100.0, 0.0)]);
+    gesture.begin(&[(100.0, 0.0), (200.0, 0.0)]);
+    let Some(TouchAction::Zoom { delta, center_x }) = gesture.update(&[(50.0, 0.0), (250.0, 0.0)])
+    else {
+        panic!("expected a zoom");
+    };
+    assert_eq!(center_x, 150.0);
+    // Spreading the fingers zooms in like scrolling up, by the same factor
+    assert!(delta < 0.0);
+    let (zoom, _) = zoom_at_cursor(1.0, 0.0, delta, 0.0);
+    assert!((zoom - 2.0).abs() < 1e-9);
+
+    assert_eq!(pinch_zoom_delta(100.0, 100.0), 0.0);
+    assert!(pinch_zoom_delta(200.0, 100.0) > 0.0);
+    assert_eq!(pinch_zoom_delta(0.0, 100.0), 0.0);
+}
+
+#[wasm_bindgen_test]
+fn lifting_a_pinch_finger_continues_without_a_jump() {
+    let mut gesture = TouchGesture::default();
+    gesture.begin(&[(100.0, 0.0), (200.0, 0.0)]);
+    gesture.update(&[(90.0, 0.0), (210.0, 0.0)]);
+
+    // The remaining finger re-anchors, so its next move pans from there
+    assert_eq!(gesture.end(&[(210.0, 0.0)]), None);
+    assert_eq!(gesture.update(&[(215.0, 0.0)]), Some(TouchAction::Pan(5.0)));
+    // A finger left over from a pinch never taps
+    assert_eq!(gesture.end(&[]), None);
+
+    // A finger count change without a start event also re-anchors
+    gesture.begin(&[(0.0, 0.0)]);
+    assert_eq!(gesture.update(&[(0.0, 0.0), (100.0, 0.0)]), None);
+    assert!(matches!(gesture.update(&[(0.0, 0.0), (100.0, 0.0)]), Some(TouchAction::Zoom { .. })));
+}
@@ -0,0 +1,86 @@
+use price_chart_wasm::domain::market_data::{Candle, Symbol, TimeInterval};
+use price_chart_wasm::infrastructure::websocket::{BinanceWebSocketClient, Trade, TradeAggregator};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn trade(timestamp: u64, price: f64, quantity: f64) -> Trade {
+    Trade { price, quantity, timestamp }
+}
+
+fn ohlcv(candle: &Candle) -> (f64, f64, f64, f64, f64) {
+    let o = &candle.ohlcv;
+    (o.open.value(), o.high.value(), o.low.value(), o.close.value(), o.volume.value())
+}
+
+#[wasm_bindgen_test]
+fn parses_trade_event() {
+    let client = BinanceWebSocketClient::new(Symbol::from("BTCUSDT"), TimeInterval::TwoSeconds);
+    let msg = r#"{"e":"trade","E":1700000000100,"s":"BTCUSDT","t":1,"p":"37000.5","q":"0.25","T":1700000000050,"m":true,"M":true}"#;
+    let parsed = client.parse_trade(msg).unwrap();
+    assert_eq!(parsed, trade(1_700_000_000_050, 37000.5, 0.25));
+    assert!(client.parse_trade(r#"{"p":"x","q":"1","T":1}"#).is_err());
+}
+
+#[wasm_bindgen_test]
+fn trades_in_one_bucket_update_the_forming_candle() {
+    let mut agg = TradeAggregator::new(TimeInterval::TwoSeconds);
+    assert_eq!(agg.bucket_start(3_999), 2_000);
+
+    let first = agg.push(trade(2_100, 10.0, 1.0));
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].timestamp.value(), 2_000);
+
+    agg.push(trade(2_500, 12.0, 2.0));
+    let forming = agg.push(trade(3_900, 9.0, 0.5));
+    assert_eq!(forming.len(), 1);
+    assert_eq!(ohlcv(&forming[0]), (10.0, 12.0, 9.0, 9.0, 3.5));
+}
+
+#[wasm_bindgen_test]
+fn out_of_order_trades_keep_open_and_close_by_time() {
+    let mut agg = TradeAggregator::new(TimeInterval::TwoSeconds);
+    agg.push(trade(2_500, 10.0, 1.0));
+    agg.push(trade(3_000, 11.0, 1.0));
+    // Earlier than both: becomes the open, not the close
+    let candles = agg.push(trade(2_100, 8.0, 1.0));
+    assert_eq!(ohlcv(&candles[0]), (8.0, 11.0, 8.0, 11.0, 3.0));
+}
+
+#[wasm_bindgen_test]
+fn next_bucket_finalizes_previous_one() {
+    let mut agg = TradeAggregator::new(TimeInterval::TwoSeconds);
+    agg.push(trade(0, 10.0, 1.0));
+    agg.push(trade(1_000, 11.0, 1.0));
+
+    let candles = agg.push(trade(2_000, 12.0, 1.0));
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].timestamp.value(), 0);
+    assert_eq!(ohlcv(&candles[0]), (10.0, 11.0, 10.0, 11.0, 2.0));
+    assert_eq!(candles[1].timestamp.value(), 2_000);
+    assert_eq!(agg.current().unwrap().timestamp.value(), 2_000);
+}
+
+#[wasm_bindgen_test]
+fn empty_buckets_carry_the_prior_close_flat() {
+    let mut agg = TradeAggregator::new(TimeInterval::TwoSeconds);
+    agg.push(trade(0, 10.0, 1.0));
+    agg.push(trade(500, 11.0, 1.0));
+
+    let candles = agg.push(trade(6_100, 15.0, 1.0));
+    let times: Vec<u64> = candles.iter().map(|c| c.timestamp.value()).collect();
+    assert_eq!(times, vec![0, 2_000, 4_000, 6_000]);
+    for flat in &candles[1..3] {
+        assert_eq!(ohlcv(flat), (11.0, 11.0, 11.0, 11.0, 0.0));
+    }
+    assert_eq!(ohlcv(&candles[3]), (15.0, 15.0, 15.0, 15.0, 1.0));
+}
+
+#[wasm_bindgen_test]
+fn late_trades_for_closed_buckets_are_dropped() {
+    let mut agg = TradeAggregator::new(TimeInterval::TwoSeconds);
+    agg.push(trade(0, 10.0, 1.0));
+    agg.push(trade(2_000, 12.0, 1.0));
+
+    assert!(agg.push(trade(1_500, 99.0, 1.0)).is_empty());
+    assert_eq!(ohlcv(&agg.current().unwrap()), (12.0, 12.0, 12.0, 12.0, 1.0));
+}
@@ -0,0 +1,36 @@
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    INITIAL_VERTEX_CAPACITY, VERTEX_BUFFER_GROWTH, VertexBufferStats,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn no_realloc_within_capacity() {
+    let mut stats = VertexBufferStats::default();
+    assert!(!stats.record(1_000));
+    assert!(!stats.record(500));
+    assert_eq!(stats.capacity, INITIAL_VERTEX_CAPACITY);
+    assert_eq!(stats.realloc_count, 0);
+    assert_eq!(stats.last_vertex_count, 500);
+    assert_eq!(stats.peak_vertex_count, 1_000);
+}
+
+#[wasm_bindgen_test]
+fn capacity_grows_by_half() {
+    let mut stats = VertexBufferStats::default();
+    assert!(stats.record(INITIAL_VERTEX_CAPACITY + 1));
+    let grown = (INITIAL_VERTEX_CAPACITY as f64 * VERTEX_BUFFER_GROWTH).ceil() as usize;
+    assert_eq!(stats.capacity, grown);
+    assert_eq!(stats.realloc_count, 1);
+
+    // Staying below the grown capacity does not trigger another reallocation
+    assert!(!stats.record(stats.capacity - 1));
+    assert_eq!(stats.realloc_count, 1);
+
+    // A jump past 1.5x grows straight to the requested size
+    let jump = stats.capacity * 3;
+    assert!(stats.record(jump));
+    assert_eq!(stats.capacity, jump);
+    assert_eq!(stats.realloc_count, 2);
+    assert_eq!(stats.peak_vertex_count, stats.last_vertex_count);
+}
@@ -0,0 +1,18 @@
+use leptos::SignalGetUntracked;
+use price_chart_wasm::app::{DEFAULT_ZOOM_LEVEL, pan, reset_view, set_zoom};
+use price_chart_wasm::global_state::globals;
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn controls_are_noops_before_renderer_init() {
+    let zoom = globals().zoom_level.get_untracked();
+    let pan_offset = globals().pan_offset.get_untracked();
+
+    set_zoom(DEFAULT_ZOOM_LEVEL * 4.0);
+    pan(120.0);
+    reset_view();
+
+    assert_eq!(globals().zoom_level.get_untracked(), zoom);
+    assert_eq!(globals().pan_offset.get_untracked(), pan_offset);
+}
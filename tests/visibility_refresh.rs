@@ -2,7 +2,7 @@ use price_chart_wasm::domain::{
     chart::{Chart, value_objects::ChartType},
     market_data::{Candle, OHLCV, Price, Timestamp, Volume},
 };
-use price_chart_wasm::infrastructure::rendering::renderer::dummy_renderer;
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
 use wasm_bindgen_test::*;
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
@@ -26,12 +26,12 @@ fn sample_chart() -> Chart {
 #[wasm_bindgen_test]
 fn visibility_refreshes_cached_geometry() {
     let chart = sample_chart();
-    let mut renderer = dummy_renderer();
-    renderer.cache_geometry_for_test(&chart);
-    let initial = renderer.cached_hash_for_test();
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    assert_eq!(builder.update(&chart), Some(true));
+    let initial = builder.cached_hash();
 
-    renderer.toggle_line_visibility("sma20");
-    let _ = renderer.render(&chart);
+    builder.toggle_line_visibility("sma20");
+    assert_eq!(builder.update(&chart), Some(true));
 
-    assert_ne!(renderer.cached_hash_for_test(), initial);
+    assert_ne!(builder.cached_hash(), initial);
 }
@@ -1,4 +1,6 @@
+use price_chart_wasm::domain::chart::{Chart, value_objects::ChartType};
 use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
 use price_chart_wasm::infrastructure::rendering::renderer::{
     EDGE_GAP, MAX_ELEMENT_WIDTH, MIN_ELEMENT_WIDTH, SPACING_RATIO, candle_x_position,
     spacing_ratio_for,
@@ -156,3 +158,30 @@ fn debug_positioning_logic() {
         );
     }
 }
+
+#[wasm_bindgen_test]
+fn body_center_matches_volume_bar_center() {
+    let mut chart = Chart::new("sync".into(), ChartType::Candlestick, 100);
+    chart.set_historical_data(create_test_candles(60));
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    for (zoom, interval_aware) in [(1.0, false), (4.0, false), (4.0, true), (0.5, true)] {
+        builder.set_zoom_params(zoom, 0.0);
+        builder.set_interval_aware_width(interval_aware);
+        let (instances, vertices, _) = builder.create_geometry(&chart);
+
+        let volume: Vec<_> = vertices.iter().filter(|v| v.element_type == 5.0).collect();
+        assert_eq!(volume.len(), instances.len() * 6);
+        for (i, (body, bar)) in instances.iter().zip(volume.chunks(6)).enumerate() {
+            let left = bar.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+            let right = bar.iter().map(|v| v.position_x).fold(f32::NEG_INFINITY, f32::max);
+            assert!(
+                ((left + right) / 2.0 - body.x).abs() < 1e-6,
+                "zoom {zoom}: volume bar {i} center {:.6} != body center {:.6}",
+                (left + right) / 2.0,
+                body.x
+            );
+            assert!((right - left - body.width).abs() < 1e-6, "zoom {zoom}: width of {i}");
+        }
+    }
+}
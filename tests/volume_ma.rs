@@ -0,0 +1,78 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::gpu_structures::{
+    CandleGeometry, ChartTheme, packed_line_color,
+};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    LineVisibility, VOLUME_MA_PERIOD, candle_x_position,
+};
+use price_chart_wasm::infrastructure::rendering::{ChartGeometryBuilder, PanelBand};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const FULL: PanelBand = PanelBand { bottom: -1.0, top: 1.0 };
+
+fn candle(i: u64, volume: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(i * 60_000),
+        OHLCV::new(
+            Price::from(100.0),
+            Price::from(101.0),
+            Price::from(99.0),
+            Price::from(100.0),
+            Volume::from(volume),
+        ),
+    )
+}
+
+/// Number of points on the drawn volume average
+fn point_count(
+    builder: &ChartGeometryBuilder,
+    candles: &[Candle],
+    start: usize,
+    len: usize,
+) -> usize {
+    let verts = builder.create_volume_ma(candles, start, len, 10.0, FULL);
+    assert!(
+        verts.iter().all(|v| v.color_type == packed_line_color(ChartTheme::default().volume_ma))
+    );
+    // Solid line: one quad per segment
+    verts.len() / 6 + 1
+}
+
+#[wasm_bindgen_test]
+fn warmup_candles_have_no_points() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let candles: Vec<Candle> = (0..30).map(|i| candle(i, 5.0)).collect();
+    assert_eq!(point_count(&builder, &candles, 0, 30), 30 - VOLUME_MA_PERIOD + 1);
+
+    let short: Vec<Candle> = (0..VOLUME_MA_PERIOD as u64 - 1).map(|i| candle(i, 5.0)).collect();
+    assert!(builder.create_volume_ma(&short, 0, short.len(), 10.0, FULL).is_empty());
+
+    // History before the window fills the warmup of visible candles
+    assert_eq!(point_count(&builder, &candles, 20, 10), 10);
+}
+
+#[wasm_bindgen_test]
+fn average_sits_in_the_volume_panel() {
+    let builder = ChartGeometryBuilder::new(800, 600);
+    let candles: Vec<Candle> = (0..VOLUME_MA_PERIOD as u64).map(|i| candle(i, 5.0)).collect();
+    let mut with_next = candles.clone();
+    with_next.push(candle(VOLUME_MA_PERIOD as u64, 5.0));
+
+    let verts = builder.create_volume_ma(&with_next, 0, with_next.len(), 10.0, FULL);
+    // Average 5 of a tallest bar 10 reaches half the volume height
+    let mid = -1.0 + 0.5 * CandleGeometry::VOLUME_HEIGHT;
+    let ys: Vec<f32> = verts.iter().map(|v| v.position_y).collect();
+    let center = (ys.iter().cloned().fold(f32::INFINITY, f32::min)
+        + ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max))
+        / 2.0;
+    assert!((center - mid).abs() < 1e-4);
+    // The line starts at the first candle with a full lookback
+    let first_x = verts.iter().map(|v| v.position_x).fold(f32::INFINITY, f32::min);
+    assert!((first_x - candle_x_position(VOLUME_MA_PERIOD - 1, with_next.len())).abs() < 0.01);
+}
+
+#[wasm_bindgen_test]
+fn volume_ma_is_off_by_default() {
+    assert!(!LineVisibility::default().volume_ma);
+}
@@ -0,0 +1,65 @@
+use price_chart_wasm::domain::market_data::{Candle, OHLCV, Price, Timestamp, Volume};
+use price_chart_wasm::infrastructure::rendering::renderer::{
+    VWAP_SESSION_MS, vwap_series, vwap_session,
+};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const HOUR: u64 = 3_600_000;
+
+fn candle(ts: u64, high: f64, low: f64, close: f64, volume: f64) -> Candle {
+    Candle::new(
+        Timestamp::from_millis(ts),
+        OHLCV::new(
+            Price::from(close),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Volume::from(volume),
+        ),
+    )
+}
+
+#[wasm_bindgen_test]
+fn weights_typical_price_by_volume() {
+    let candles = [candle(0, 12.0, 6.0, 9.0, 1.0), candle(HOUR, 24.0, 18.0, 21.0, 3.0)];
+    let values = vwap_series(&candles, None);
+    assert_eq!(values[0], Some(9.0));
+    // (9 * 1 + 21 * 3) / 4
+    assert_eq!(values[1], Some(18.0));
+}
+
+#[wasm_bindgen_test]
+fn zero_volume_is_skipped() {
+    let candles = [
+        candle(0, 10.0, 10.0, 10.0, 0.0),
+        candle(HOUR, 20.0, 20.0, 20.0, 2.0),
+        candle(2 * HOUR, 30.0, 30.0, 30.0, 0.0),
+    ];
+    assert_eq!(vwap_series(&candles, None), vec![None, Some(20.0), Some(20.0)]);
+}
+
+#[wasm_bindgen_test]
+fn anchor_resets_at_each_utc_day() {
+    let day = VWAP_SESSION_MS as u64;
+    let candles = [
+        candle(day - HOUR, 10.0, 10.0, 10.0, 1.0),
+        candle(day, 40.0, 40.0, 40.0, 1.0),
+        candle(day + HOUR, 20.0, 20.0, 20.0, 1.0),
+    ];
+
+    // Unanchored sums run across midnight
+    assert_eq!(vwap_series(&candles, None)[1], Some(25.0));
+
+    let anchored = vwap_series(&candles, Some(0));
+    assert_eq!(anchored, vec![Some(10.0), Some(40.0), Some(30.0)]);
+}
+
+#[wasm_bindgen_test]
+fn session_index_counts_days_from_anchor() {
+    assert_eq!(vwap_session(123, None), 0);
+    assert_eq!(vwap_session(VWAP_SESSION_MS as u64 - 1, Some(0)), 0);
+    assert_eq!(vwap_session(VWAP_SESSION_MS as u64, Some(0)), 1);
+    // Candles before the anchor fall into earlier sessions
+    assert_eq!(vwap_session(0, Some(HOUR)), -1);
+}
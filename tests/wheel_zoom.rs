@@ -0,0 +1,84 @@
+use price_chart_wasm::app::{clamp_zoom, visible_range, zoom_at_cursor};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// Fractional candle index under `cursor_ndc_x` for an unclamped window
+fn candle_under_cursor(len: usize, zoom: f64, pan: f64, cursor_ndc_x: f32) -> f64 {
+    let (_, visible) = visible_range(usize::MAX / 2, zoom, 0.0);
+    let right_share = (1.0 - cursor_ndc_x as f64) / 2.0;
+    len as f64 + pan - visible as f64 * right_share
+}
+
+#[wasm_bindgen_test]
+fn wheel_zoom_is_multiplicative() {
+    let (in_once, _) = zoom_at_cursor(1.0, 0.0, -100.0, 0.0);
+    let (in_twice, _) = zoom_at_cursor(in_once, 0.0, -100.0, 0.0);
+    assert!(in_once > 1.0);
+    assert!((in_twice / in_once - in_once).abs() < 1e-9);
+
+    let (out, _) = zoom_at_cursor(1.0, 0.0, 100.0, 0.0);
+    assert!((out * in_once - 1.0).abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn wheel_zoom_respects_limits() {
+    let (max, _) = zoom_at_cursor(1.0, 0.0, -1e6, 0.0);
+    let (min, _) = zoom_at_cursor(1.0, 0.0, 1e6, 0.0);
+    assert!(max.is_finite() && min > 0.0);
+    assert_eq!(zoom_at_cursor(max, 0.0, -100.0, 0.0).0, max);
+    assert_eq!(zoom_at_cursor(min, 0.0, 100.0, 0.0).0, min);
+    assert_eq!((clamp_zoom(1e6), clamp_zoom(0.0)), (max, min));
+    assert_eq!(clamp_zoom(1.0), 1.0);
+}
+
+#[wasm_bindgen_test]
+fn candle_under_cursor_stays_fixed() {
+    let len = 1000;
+    for &cursor in &[-0.8f32, 0.0, 0.6] {
+        let (zoom, pan) = (0.5, -40.0);
+        let before = candle_under_cursor(len, zoom, pan, cursor);
+        let (new_zoom, new_pan) = zoom_at_cursor(zoom, pan, -120.0, cursor);
+        let after = candle_under_cursor(len, new_zoom, new_pan, cursor);
+        assert!((before - after).abs() < 1.0, "cursor {cursor}: {before} vs {after}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn zoom_at_right_edge_keeps_latest_pinned() {
+    let (_, pan) = zoom_at_cursor(1.0, 0.0, -200.0, 1.0);
+    assert!(pan.abs() < 1e-9);
+}
+
+#[wasm_bindgen_test]
+fn renderer_zoom_uses_the_same_limits() {
+    use price_chart_wasm::domain::{
+        chart::{Chart, value_objects::ChartType},
+        market_data::{Candle, OHLCV, Price, Timestamp, Volume},
+    };
+    use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+
+    let candles = (0..400u64).map(|i| {
+        let p = 100.0 + (i % 7) as f64;
+        Candle::new(
+            Timestamp::from_millis(i * 60_000),
+            OHLCV::new(
+                Price::from(p),
+                Price::from(p + 1.0),
+                Price::from(p - 1.0),
+                Price::from(p + 0.5),
+                Volume::from(1.0),
+            ),
+        )
+    });
+    let mut chart = Chart::new("zoom".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles.collect());
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    let mut visible_at = |zoom: f64| {
+        builder.set_zoom_params(zoom, 0.0);
+        builder.create_geometry(&chart).0.len()
+    };
+    assert_eq!(visible_at(1e6), visible_at(clamp_zoom(1e6)));
+    assert_eq!(visible_at(1e-6), visible_at(clamp_zoom(1e-6)));
+    assert!(visible_at(1e-6) < 400);
+}
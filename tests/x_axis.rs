@@ -0,0 +1,107 @@
+use leptos::SignalSet;
+use price_chart_wasm::app::current_interval;
+use price_chart_wasm::domain::{
+    chart::{Chart, value_objects::ChartType},
+    market_data::{Candle, OHLCV, Price, TimeInterval, Timestamp, Volume},
+};
+use price_chart_wasm::infrastructure::rendering::ChartGeometryBuilder;
+use price_chart_wasm::infrastructure::rendering::renderer::{XAxis, XAxisMode, candle_x_position};
+use wasm_bindgen_test::*;
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const MINUTE: u64 = 60_000;
+
+fn candle(minute: u64) -> Candle {
+    let close = 100.0 + minute as f64;
+    Candle::new(
+        Timestamp::from_millis(minute * MINUTE),
+        OHLCV::new(
+            Price::from(close - 0.5),
+            Price::from(close + 1.0),
+            Price::from(close - 1.0),
+            Price::from(close),
+            Volume::from(1.0),
+        ),
+    )
+}
+
+/// Minutes 0..=4, an outage, then minutes 15..=19
+fn gappy_candles() -> Vec<Candle> {
+    (0..=4).chain(15..=19).map(candle).collect()
+}
+
+#[wasm_bindgen_test]
+fn ordinal_axis_matches_candle_x_position() {
+    let candles = gappy_candles();
+    let axis = XAxis::new(XAxisMode::Ordinal, &candles, TimeInterval::OneMinute);
+    assert_eq!(axis, XAxis::ordinal(candles.len()));
+    assert_eq!(axis.slot_count(), candles.len());
+    for i in 0..candles.len() {
+        assert_eq!(axis.x(i), candle_x_position(i, candles.len()));
+    }
+}
+
+#[wasm_bindgen_test]
+fn time_axis_leaves_room_for_missing_candles() {
+    let candles = gappy_candles();
+    let axis = XAxis::new(XAxisMode::Time, &candles, TimeInterval::OneMinute);
+    assert_eq!(axis.len(), 10);
+    assert_eq!(axis.slot_count(), 20);
+    assert_eq!(axis.slot(5), 15);
+    assert_eq!(axis.x(5), candle_x_position(15, 20));
+
+    // The jump over the outage is ten regular steps wide
+    let step = axis.x(1) - axis.x(0);
+    assert!((axis.x(5) - axis.x(4) - 11.0 * step).abs() < 1e-5);
+    assert_eq!(axis.x(9), candle_x_position(19, 20));
+}
+
+#[wasm_bindgen_test]
+fn duplicate_buckets_keep_distinct_slots() {
+    let mut candles = vec![candle(0), candle(1)];
+    candles.push(Candle::new(Timestamp::from_millis(MINUTE + 1), candles[1].ohlcv));
+    let axis = XAxis::new(XAxisMode::Time, &candles, TimeInterval::OneMinute);
+    assert_eq!((0..3).map(|i| axis.slot(i)).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[wasm_bindgen_test]
+fn index_at_inverts_x_and_picks_nearest_across_gaps() {
+    let candles = gappy_candles();
+    for mode in [XAxisMode::Ordinal, XAxisMode::Time] {
+        let axis = XAxis::new(mode, &candles, TimeInterval::OneMinute);
+        for i in 0..axis.len() {
+            assert_eq!(axis.index_at(axis.x(i) as f64), Some(i));
+        }
+        assert_eq!(axis.index_at(-1.5), Some(0));
+        assert_eq!(axis.index_at(1.5), Some(axis.len() - 1));
+    }
+
+    let axis = XAxis::new(XAxisMode::Time, &candles, TimeInterval::OneMinute);
+    // Slots 6 and 13 lie inside the outage, closer to minute 4 and 15
+    assert_eq!(axis.index_at(candle_x_position(6, 20) as f64), Some(4));
+    assert_eq!(axis.index_at(candle_x_position(13, 20) as f64), Some(5));
+    assert_eq!(XAxis::ordinal(0).index_at(0.0), None);
+}
+
+#[wasm_bindgen_test]
+fn builder_places_candles_by_time_in_time_mode() {
+    current_interval().set(TimeInterval::OneMinute);
+    let candles = gappy_candles();
+    let mut chart = Chart::new("x-axis".into(), ChartType::Candlestick, 1_000);
+    chart.set_historical_data(candles.clone());
+
+    let mut builder = ChartGeometryBuilder::new(800, 600);
+    assert_eq!(builder.x_axis_mode(), XAxisMode::Ordinal);
+    assert_eq!(builder.visible_time_span(&chart).unwrap().count, 10);
+
+    builder.set_x_axis_mode(XAxisMode::Time);
+    assert_eq!(builder.x_axis_for(&candles).slot(5), 15);
+    let span = builder.visible_time_span(&chart).unwrap();
+    assert_eq!(span.count, 20);
+    // Labels interpolate one interval per slot
+    assert_eq!(span.timestamp_at(15), 15 * MINUTE);
+
+    let (instances, _, _) = builder.create_geometry(&chart);
+    assert_eq!(instances.len(), candles.len());
+    assert!((instances[5].x - candle_x_position(15, 20)).abs() < 1e-6);
+}